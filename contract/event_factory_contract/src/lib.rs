@@ -4,10 +4,12 @@
 mod test;
 
 mod storage_types;
+pub use storage_types::{EventSalesStats, OrganizerStats};
 use storage_types::DataKey;
 
 use soroban_sdk::{
-    contract, contractimpl, symbol_short, vec, Address, BytesN, Env, IntoVal, String, Vec,
+    contract, contractimpl, symbol_short, vec, Address, BytesN, Env, IntoVal, Map, String, Symbol,
+    Val, Vec,
 };
 
 #[contract]
@@ -87,18 +89,29 @@ impl EventFactoryContract {
 
         // Max length for symbol_short is 9, initialize is 10.
         // Use soroban_sdk::Symbol::new(&e, "initialize")
+        // The ticket contract takes a single `InitConfig` struct. Contract structs
+        // are encoded as field-name maps, so it is built here without depending on
+        // the ticket crate; metadata, pricing, roles and the payout schedule keep
+        // their defaults.
+        let mut init_config: Map<Symbol, Val> = Map::new(&e);
+        init_config.set(Symbol::new(&e, "admin"), organizer.to_val());
+        init_config.set(Symbol::new(&e, "name"), name.into_val(&e));
+        init_config.set(Symbol::new(&e, "symbol"), symbol.into_val(&e));
+        init_config.set(Symbol::new(&e, "uri"), uri.into_val(&e));
+        init_config.set(Symbol::new(&e, "start_time"), start_time.into_val(&e));
+        init_config.set(
+            Symbol::new(&e, "refund_cutoff_time"),
+            refund_cutoff_time.into_val(&e),
+        );
+        init_config.set(Symbol::new(&e, "metadata"), ().into_val(&e));
+        init_config.set(Symbol::new(&e, "pricing"), ().into_val(&e));
+        init_config.set(Symbol::new(&e, "roles"), ().into_val(&e));
+        init_config.set(Symbol::new(&e, "payout_schedule"), ().into_val(&e));
+
         e.invoke_contract::<()>(
             &event_contract_id,
             &soroban_sdk::Symbol::new(&e, "initialize"),
-            vec![
-                &e,
-                organizer.to_val(),
-                name.into_val(&e),
-                symbol.into_val(&e),
-                uri.into_val(&e),
-                start_time.into_val(&e),
-                refund_cutoff_time.into_val(&e),
-            ],
+            vec![&e, init_config.into_val(&e)],
         );
 
         let key = DataKey::OrganizerEvents(organizer.clone());
@@ -110,6 +123,14 @@ impl EventFactoryContract {
 
         events.push_back(event_contract_id.clone());
         e.storage().persistent().set(&key, &events);
+        e.storage().persistent().set(
+            &DataKey::EventOrganizer(event_contract_id.clone()),
+            &organizer,
+        );
+
+        let mut stats = Self::get_organizer_stats(e.clone(), organizer.clone());
+        stats.events_run += 1;
+        Self::set_organizer_stats(&e, &organizer, stats);
 
         // Emit an event (using soroban events)
         #[allow(deprecated)]
@@ -161,8 +182,106 @@ impl EventFactoryContract {
         to_events.push_back(event.clone());
         e.storage().persistent().set(&to_key, &to_events);
 
+        // Reputation earned so far stays with `from`; later syncs credit `to`
+        e.storage()
+            .persistent()
+            .set(&DataKey::EventOrganizer(event.clone()), &to);
+
         #[allow(deprecated)]
         e.events()
             .publish((symbol_short!("transfer"), event), (from, to));
     }
+
+    pub fn get_organizer_stats(e: Env, organizer: Address) -> OrganizerStats {
+        e.storage()
+            .persistent()
+            .get(&DataKey::OrganizerStats(organizer))
+            .unwrap_or_default()
+    }
+
+    /// Pull the latest sales counters from an event created by this factory and
+    /// fold the change since the last sync into its organizer's record.
+    /// Anyone may call it.
+    pub fn sync_event_stats(e: Env, event: Address) -> OrganizerStats {
+        let organizer = Self::event_organizer(&e, &event);
+
+        let latest: EventSalesStats = e.invoke_contract(
+            &event,
+            &Symbol::new(&e, "get_sales_stats"),
+            Vec::new(&e),
+        );
+        let sales_key = DataKey::EventSales(event.clone());
+        let previous: EventSalesStats = e
+            .storage()
+            .persistent()
+            .get(&sales_key)
+            .unwrap_or_default();
+        e.storage().persistent().set(&sales_key, &latest);
+
+        let mut stats = Self::get_organizer_stats(e.clone(), organizer.clone());
+        stats.tickets_sold = (stats.tickets_sold + latest.tickets_sold)
+            .saturating_sub(previous.tickets_sold);
+        stats.tickets_refunded = (stats.tickets_refunded + latest.tickets_refunded)
+            .saturating_sub(previous.tickets_refunded);
+        Self::set_organizer_stats(&e, &organizer, stats.clone());
+        stats
+    }
+
+    /// Record that an event was cancelled. Admin only; counted once per event.
+    pub fn record_cancellation(e: Env, event: Address) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        let organizer = Self::event_organizer(&e, &event);
+
+        let cancelled_key = DataKey::EventCancelled(event.clone());
+        if e.storage().persistent().has(&cancelled_key) {
+            panic!("event already cancelled");
+        }
+        e.storage().persistent().set(&cancelled_key, &true);
+
+        let mut stats = Self::get_organizer_stats(e.clone(), organizer.clone());
+        stats.cancellations += 1;
+        Self::set_organizer_stats(&e, &organizer, stats);
+
+        #[allow(deprecated)]
+        e.events()
+            .publish((symbol_short!("cancelled"), organizer), event);
+    }
+
+    /// Record the outcome of a buyer dispute against an event. Admin only.
+    pub fn record_dispute(e: Env, event: Address, upheld: bool) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        let organizer = Self::event_organizer(&e, &event);
+
+        let mut stats = Self::get_organizer_stats(e.clone(), organizer.clone());
+        if upheld {
+            stats.disputes_lost += 1;
+        } else {
+            stats.disputes_won += 1;
+        }
+        Self::set_organizer_stats(&e, &organizer, stats);
+
+        #[allow(deprecated)]
+        e.events()
+            .publish((symbol_short!("dispute"), organizer), (event, upheld));
+    }
+
+    fn event_organizer(e: &Env, event: &Address) -> Address {
+        e.storage()
+            .persistent()
+            .get(&DataKey::EventOrganizer(event.clone()))
+            .unwrap_or_else(|| panic!("event not registered"))
+    }
+
+    fn set_organizer_stats(e: &Env, organizer: &Address, mut stats: OrganizerStats) {
+        stats.refund_rate_bps = if stats.tickets_sold == 0 {
+            0
+        } else {
+            (stats.tickets_refunded as u64 * 10_000 / stats.tickets_sold as u64).min(10_000) as u32
+        };
+        e.storage()
+            .persistent()
+            .set(&DataKey::OrganizerStats(organizer.clone()), &stats);
+    }
 }
@@ -7,4 +7,33 @@ pub enum DataKey {
     EventWasmHash,            // BytesN<32> of the event contract WASM
     Paused,                   // bool indicating if new events can be created
     OrganizerEvents(Address), // Mapping from an organizer Address to Vec<Address> of event contracts
+    EventOrganizer(Address),  // Mapping from an event contract to its current organizer
+    OrganizerStats(Address),  // OrganizerStats accumulated by an organizer
+    EventSales(Address),      // EventSalesStats last synced from an event contract
+    EventCancelled(Address),  // bool set once an event is recorded as cancelled
+}
+
+/// Reputation record of an organizer across every event created through the factory
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct OrganizerStats {
+    pub events_run: u32,
+    pub tickets_sold: u32,
+    pub tickets_refunded: u32,
+    /// tickets_refunded / tickets_sold in basis points
+    pub refund_rate_bps: u32,
+    pub cancellations: u32,
+    /// Disputes resolved in the organizer's favour
+    pub disputes_won: u32,
+    /// Disputes resolved in the buyer's favour
+    pub disputes_lost: u32,
+}
+
+/// Mirror of the ticket contract's `SalesStats`; contract structs are encoded
+/// by field name, so this decodes the event's `get_sales_stats` result
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct EventSalesStats {
+    pub tickets_sold: u32,
+    pub tickets_refunded: u32,
 }
@@ -124,3 +124,71 @@ fn test_transfer_ownership() {
     assert_eq!(to_events_after.len(), 1);
     assert_eq!(to_events_after.get(0).unwrap(), event_id);
 }
+
+#[contract]
+pub struct MockEvent;
+
+#[contractimpl]
+impl MockEvent {
+    pub fn set_sales(e: Env, tickets_sold: u32, tickets_refunded: u32) {
+        e.storage().instance().set(
+            &symbol_short!("sales"),
+            &EventSalesStats {
+                tickets_sold,
+                tickets_refunded,
+            },
+        );
+    }
+
+    pub fn get_sales_stats(e: Env) -> EventSalesStats {
+        e.storage()
+            .instance()
+            .get(&symbol_short!("sales"))
+            .unwrap_or_default()
+    }
+}
+
+#[test]
+fn test_organizer_stats() {
+    let (e, admin, organizer, wasm_hash) = setup_test();
+    let factory_id = e.register(EventFactoryContract, ());
+    let factory = EventFactoryContractClient::new(&e, &factory_id);
+    factory.initialize(&admin, &wasm_hash);
+
+    let name = String::from_str(&e, "Test Event");
+    let symbol = String::from_str(&e, "TST");
+    let uri = String::from_str(&e, "https://example.com");
+    factory.create_event(&organizer, &name, &symbol, &uri, &1000, &500);
+    assert_eq!(factory.get_organizer_stats(&organizer).events_run, 1);
+
+    // Stand-in for a deployed event that reports its sales
+    let event_id = e.register(MockEvent, ());
+    let event = MockEventClient::new(&e, &event_id);
+    e.as_contract(&factory_id, || {
+        e.storage()
+            .persistent()
+            .set(&DataKey::EventOrganizer(event_id.clone()), &organizer);
+    });
+
+    event.set_sales(&100, &5);
+    factory.sync_event_stats(&event_id);
+    event.set_sales(&120, &6);
+    let stats = factory.sync_event_stats(&event_id);
+    assert_eq!(stats.tickets_sold, 120);
+    assert_eq!(stats.tickets_refunded, 6);
+    assert_eq!(stats.refund_rate_bps, 500);
+
+    factory.record_dispute(&event_id, &false);
+    factory.record_dispute(&event_id, &true);
+    factory.record_cancellation(&event_id);
+    assert!(factory.try_record_cancellation(&event_id).is_err());
+
+    let stats = factory.get_organizer_stats(&organizer);
+    assert_eq!(stats.events_run, 1);
+    assert_eq!(stats.cancellations, 1);
+    assert_eq!(stats.disputes_won, 1);
+    assert_eq!(stats.disputes_lost, 1);
+
+    let unknown = e.register(MockEvent, ());
+    assert!(factory.try_sync_event_stats(&unknown).is_err());
+}
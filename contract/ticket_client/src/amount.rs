@@ -0,0 +1,25 @@
+/// Conversions between on-chain token units and `Decimal`
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+
+use crate::error::ClientError;
+
+/// Stellar asset contracts use 7 decimal places
+pub const STELLAR_ASSET_DECIMALS: u32 = 7;
+
+/// Token units to a decimal amount, e.g. 15_000_000 with 7 decimals is 1.5
+pub fn to_decimal(amount: i128, decimals: u32) -> Result<Decimal, ClientError> {
+    Decimal::try_from_i128_with_scale(amount, decimals).map_err(|_| ClientError::AmountOutOfRange)
+}
+
+/// Decimal amount to token units. Fails rather than rounding.
+pub fn from_decimal(value: Decimal, decimals: u32) -> Result<i128, ClientError> {
+    let unit = Decimal::from_i128_with_scale(10i128.pow(decimals), 0);
+    let scaled = value
+        .checked_mul(unit)
+        .ok_or(ClientError::AmountOutOfRange)?;
+    if !scaled.fract().is_zero() {
+        return Err(ClientError::PrecisionLoss);
+    }
+    scaled.to_i128().ok_or(ClientError::AmountOutOfRange)
+}
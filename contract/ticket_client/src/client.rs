@@ -0,0 +1,153 @@
+/// High-level client flows
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use soroban_sdk::{Address, Env, Symbol};
+use ticket_contract::{AllocationProof, SoulboundTicketContractClient};
+
+use crate::amount::to_decimal;
+use crate::error::ClientError;
+use crate::time::to_datetime;
+
+/// Purchase preview in decimal amounts
+#[derive(Clone, Debug, PartialEq)]
+pub struct Quote {
+    pub unit_price: Decimal,
+    pub total_cost: Decimal,
+    pub fees: Decimal,
+    pub discount: Decimal,
+    pub first_token_id: u32,
+    pub last_token_id: u32,
+}
+
+/// Where a tier's lottery stands
+#[derive(Clone, Debug, PartialEq)]
+pub enum LotteryStatus {
+    /// Entries open or draw not yet executed
+    Pending,
+    /// Draw sealed; winners in allocation order
+    Finalized {
+        winners: Vec<Address>,
+        proof: AllocationProof,
+    },
+}
+
+/// Typed wrapper around the generated contract client
+pub struct TicketClient<'a> {
+    inner: SoulboundTicketContractClient<'a>,
+    /// Decimal places of the payment token
+    decimals: u32,
+}
+
+impl<'a> TicketClient<'a> {
+    pub fn new(env: &Env, contract_id: &Address, decimals: u32) -> Self {
+        Self {
+            inner: SoulboundTicketContractClient::new(env, contract_id),
+            decimals,
+        }
+    }
+
+    /// The generated client, for calls this wrapper does not cover
+    pub fn inner(&self) -> &SoulboundTicketContractClient<'a> {
+        &self.inner
+    }
+
+    pub fn ticket_price(&self, tier: &Symbol) -> Result<Decimal, ClientError> {
+        to_decimal(self.inner.get_ticket_price(tier), self.decimals)
+    }
+
+    pub fn event_start(&self) -> Result<DateTime<Utc>, ClientError> {
+        to_datetime(self.inner.get_event_info().start_time)
+    }
+
+    pub fn refund_cutoff(&self) -> Result<DateTime<Utc>, ClientError> {
+        to_datetime(self.inner.get_event_info().refund_cutoff_time)
+    }
+
+    /// Price `quantity` tickets for `buyer`, failing with the contract's reason
+    /// if the purchase would be rejected
+    pub fn quote(
+        &self,
+        buyer: &Address,
+        tier: &Symbol,
+        quantity: u32,
+    ) -> Result<Quote, ClientError> {
+        let preview = self.inner.simulate_purchase(buyer, tier, &quantity);
+        if let Some(reason) = preview.error {
+            return Err(ClientError::PurchaseUnavailable(reason));
+        }
+
+        Ok(Quote {
+            unit_price: to_decimal(preview.unit_price, self.decimals)?,
+            total_cost: to_decimal(preview.total_cost, self.decimals)?,
+            fees: to_decimal(preview.fees, self.decimals)?,
+            discount: to_decimal(preview.discount, self.decimals)?,
+            first_token_id: preview.first_token_id,
+            last_token_id: preview.last_token_id,
+        })
+    }
+
+    /// Quote, then purchase only if the unit price is at most `max_price`.
+    ///
+    /// The limit is checked client-side only: the contract's `purchase` does
+    /// not see `max_price`, so a price change between the quote and the ledger
+    /// that runs the purchase is not caught. Callers that need the limit
+    /// enforced on-chain should use the contract's `purchase_with_allowance`.
+    pub fn purchase_with_slippage(
+        &self,
+        buyer: &Address,
+        payment_token: &Address,
+        tier: &Symbol,
+        max_price: Decimal,
+    ) -> Result<u32, ClientError> {
+        let quote = self.quote(buyer, tier, 1)?;
+        if quote.unit_price > max_price {
+            return Err(ClientError::PriceAboveLimit {
+                quoted: quote.unit_price,
+                limit: max_price,
+            });
+        }
+
+        match self.inner.try_purchase(buyer, payment_token, tier) {
+            Ok(Ok(token_id)) => Ok(token_id),
+            _ => Err(ClientError::InvocationFailed),
+        }
+    }
+
+    /// Status of the tier's current lottery round
+    pub fn lottery_status(&self, tier: &Symbol) -> LotteryStatus {
+        let round = self.inner.get_lottery_round(tier);
+        match self.inner.try_export_allocation_proof(tier, &round) {
+            Ok(Ok(proof)) => LotteryStatus::Finalized {
+                winners: self
+                    .inner
+                    .get_round_winners(tier, &round)
+                    .iter()
+                    .map(|result| result.winner)
+                    .collect(),
+                proof,
+            },
+            _ => LotteryStatus::Pending,
+        }
+    }
+
+    /// Check the lottery up to `max_attempts` times, calling `wait` between
+    /// attempts (e.g. sleep or advance a test ledger)
+    pub fn poll_lottery(
+        &self,
+        tier: &Symbol,
+        max_attempts: u32,
+        mut wait: impl FnMut(),
+    ) -> Result<LotteryStatus, ClientError> {
+        for attempt in 0..max_attempts {
+            let status = self.lottery_status(tier);
+            if status != LotteryStatus::Pending {
+                return Ok(status);
+            }
+            if attempt + 1 < max_attempts {
+                wait();
+            }
+        }
+
+        Err(ClientError::LotteryNotFinalized)
+    }
+}
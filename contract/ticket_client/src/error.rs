@@ -0,0 +1,43 @@
+/// Client error type
+use core::fmt;
+
+use rust_decimal::Decimal;
+use ticket_contract::PurchaseError;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ClientError {
+    /// Amount does not fit the target representation
+    AmountOutOfRange,
+    /// Decimal has more fractional digits than the token supports
+    PrecisionLoss,
+    /// Timestamp is outside the range chrono or the ledger can represent
+    InvalidTimestamp,
+    /// Quoted unit price is above the caller's limit
+    PriceAboveLimit { quoted: Decimal, limit: Decimal },
+    /// The contract would reject the purchase
+    PurchaseUnavailable(PurchaseError),
+    /// The contract invocation failed
+    InvocationFailed,
+    /// Polling gave up before the lottery was finalized
+    LotteryNotFinalized,
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::AmountOutOfRange => write!(f, "amount out of range"),
+            ClientError::PrecisionLoss => write!(f, "amount has too many decimal places"),
+            ClientError::InvalidTimestamp => write!(f, "timestamp out of range"),
+            ClientError::PriceAboveLimit { quoted, limit } => {
+                write!(f, "quoted price {quoted} is above limit {limit}")
+            }
+            ClientError::PurchaseUnavailable(reason) => {
+                write!(f, "purchase unavailable: {reason:?}")
+            }
+            ClientError::InvocationFailed => write!(f, "contract invocation failed"),
+            ClientError::LotteryNotFinalized => write!(f, "lottery not finalized"),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
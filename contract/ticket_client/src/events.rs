@@ -0,0 +1,63 @@
+/// Decoding of contract events
+use soroban_sdk::{Address, Env, Symbol, TryFromVal, Val, Vec};
+
+/// Contract events a backend typically indexes
+#[derive(Clone, Debug, PartialEq)]
+pub enum TicketEvent {
+    Purchase {
+        buyer: Address,
+        token_id: u32,
+        tier: Symbol,
+        price: i128,
+    },
+    BatchMint {
+        to: Address,
+        tier: Symbol,
+        token_ids: std::vec::Vec<u32>,
+    },
+    InvitationClaimed {
+        recipient: Address,
+        token_id: u32,
+        tier: Symbol,
+    },
+    Paused {
+        member: Address,
+    },
+}
+
+impl TicketEvent {
+    /// Decode an event from its topics and data. Returns `None` for events this
+    /// client does not model or that do not match the expected shape.
+    pub fn decode(env: &Env, topics: &Vec<Val>, data: &Val) -> Option<Self> {
+        let name = Symbol::try_from_val(env, &topics.get(0)?).ok()?;
+        let subject = Address::try_from_val(env, &topics.get(1)?).ok()?;
+
+        if name == Symbol::new(env, "purchase") {
+            let (token_id, tier, price) = <(u32, Symbol, i128)>::try_from_val(env, data).ok()?;
+            Some(TicketEvent::Purchase {
+                buyer: subject,
+                token_id,
+                tier,
+                price,
+            })
+        } else if name == Symbol::new(env, "batch_mint") {
+            let (tier, token_ids) = <(Symbol, Vec<u32>)>::try_from_val(env, data).ok()?;
+            Some(TicketEvent::BatchMint {
+                to: subject,
+                tier,
+                token_ids: token_ids.iter().collect(),
+            })
+        } else if name == Symbol::new(env, "invitation_claimed") {
+            let (token_id, tier) = <(u32, Symbol)>::try_from_val(env, data).ok()?;
+            Some(TicketEvent::InvitationClaimed {
+                recipient: subject,
+                token_id,
+                tier,
+            })
+        } else if name == Symbol::new(env, "paused") {
+            Some(TicketEvent::Paused { member: subject })
+        } else {
+            None
+        }
+    }
+}
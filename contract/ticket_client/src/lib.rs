@@ -0,0 +1,24 @@
+/// Typed client for the soulbound ticket contract
+///
+/// Wraps the generated `SoulboundTicketContractClient` for backend services:
+///   - prices as `Decimal` instead of raw token units
+///   - ledger timestamps as `chrono::DateTime<Utc>`
+///   - decoding of contract events into `TicketEvent`
+///   - higher-level flows: quote → purchase with a client-side price limit,
+///     and lottery status polling
+mod amount;
+mod client;
+mod error;
+mod events;
+mod time;
+
+#[cfg(test)]
+mod test;
+
+pub use amount::{from_decimal, to_decimal, STELLAR_ASSET_DECIMALS};
+pub use client::{LotteryStatus, Quote, TicketClient};
+pub use error::ClientError;
+pub use events::TicketEvent;
+pub use time::{from_datetime, to_datetime};
+
+pub use ticket_contract::{SoulboundTicketContract, SoulboundTicketContractClient};
@@ -0,0 +1,121 @@
+#![cfg(test)]
+
+use super::*;
+use rust_decimal::Decimal;
+use soroban_sdk::{testutils::Address as _, vec, Address, Env, IntoVal, Symbol, Val, Vec};
+use ticket_contract::testutils::TicketHarness;
+use ticket_contract::PurchaseError;
+
+#[test]
+fn test_amount_and_time_conversions() {
+    assert_eq!(
+        to_decimal(15_000_000, STELLAR_ASSET_DECIMALS).unwrap(),
+        Decimal::new(15, 1)
+    );
+    assert_eq!(
+        from_decimal(Decimal::new(15, 1), STELLAR_ASSET_DECIMALS).unwrap(),
+        15_000_000
+    );
+    assert_eq!(
+        from_decimal(Decimal::new(1, 8), STELLAR_ASSET_DECIMALS),
+        Err(ClientError::PrecisionLoss)
+    );
+
+    let datetime = to_datetime(1_700_000_000).unwrap();
+    assert_eq!(from_datetime(&datetime).unwrap(), 1_700_000_000);
+    assert_eq!(to_datetime(u64::MAX), Err(ClientError::InvalidTimestamp));
+}
+
+#[test]
+fn test_purchase_with_slippage_limit() {
+    let e = Env::default();
+    let harness = TicketHarness::setup(&e);
+    let tier = harness.add_tier("GEN", 20_000_000, 10);
+    let token = harness.create_payment_token(&harness.admin, 0);
+    let buyer = harness.funded_buyer(&token, 100_000_000);
+
+    let client = TicketClient::new(&e, &harness.client.address, STELLAR_ASSET_DECIMALS);
+    assert_eq!(client.ticket_price(&tier).unwrap(), Decimal::new(2, 0));
+
+    let quote = client.quote(&buyer, &tier, 2).unwrap();
+    assert_eq!(quote.total_cost, Decimal::new(4, 0));
+    assert_eq!(
+        client.quote(&buyer, &tier, 11),
+        Err(ClientError::PurchaseUnavailable(
+            PurchaseError::InsufficientSupply
+        ))
+    );
+
+    assert_eq!(
+        client.purchase_with_slippage(&buyer, &token, &tier, Decimal::new(19, 1)),
+        Err(ClientError::PriceAboveLimit {
+            quoted: Decimal::new(2, 0),
+            limit: Decimal::new(19, 1),
+        })
+    );
+    let token_id = client
+        .purchase_with_slippage(&buyer, &token, &tier, Decimal::new(2, 0))
+        .unwrap();
+    assert_eq!(client.inner().owner_of(&token_id), buyer);
+}
+
+#[test]
+fn test_decode_events() {
+    let e = Env::default();
+    let buyer = Address::generate(&e);
+    let tier = Symbol::new(&e, "GEN");
+
+    let topics: Vec<Val> = (Symbol::new(&e, "purchase"), buyer.clone()).into_val(&e);
+    let data: Val = (7u32, tier.clone(), 100i128).into_val(&e);
+    assert_eq!(
+        TicketEvent::decode(&e, &topics, &data),
+        Some(TicketEvent::Purchase {
+            buyer: buyer.clone(),
+            token_id: 7,
+            tier: tier.clone(),
+            price: 100,
+        })
+    );
+
+    let topics: Vec<Val> = (Symbol::new(&e, "batch_mint"), buyer.clone()).into_val(&e);
+    let data: Val = (tier.clone(), vec![&e, 1u32, 2u32]).into_val(&e);
+    assert_eq!(
+        TicketEvent::decode(&e, &topics, &data),
+        Some(TicketEvent::BatchMint {
+            to: buyer.clone(),
+            tier,
+            token_ids: std::vec![1, 2],
+        })
+    );
+
+    let topics: Vec<Val> = (Symbol::new(&e, "unknown"), buyer).into_val(&e);
+    assert_eq!(TicketEvent::decode(&e, &topics, &data), None);
+}
+
+#[test]
+fn test_poll_lottery_until_finalized() {
+    let e = Env::default();
+    let harness = TicketHarness::setup(&e);
+    let tier = harness.add_tier("LOT", 100, 10);
+    let client = TicketClient::new(&e, &harness.client.address, STELLAR_ASSET_DECIMALS);
+
+    assert_eq!(client.lottery_status(&tier), LotteryStatus::Pending);
+    assert_eq!(
+        client.poll_lottery(&tier, 2, || harness.advance_ledgers(1)),
+        Err(ClientError::LotteryNotFinalized)
+    );
+
+    let entries = harness.lottery_entries(3);
+    let mut sealed = None;
+    let status = client
+        .poll_lottery(&tier, 3, || {
+            sealed
+                .get_or_insert_with(|| harness.seal_lottery(&tier, &entries, &vec![&e, 5u128], 1));
+        })
+        .unwrap();
+
+    match status {
+        LotteryStatus::Finalized { proof, .. } => assert_eq!(Some(proof), sealed),
+        LotteryStatus::Pending => panic!("lottery should be finalized"),
+    }
+}
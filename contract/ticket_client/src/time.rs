@@ -0,0 +1,17 @@
+/// Conversions between ledger timestamps and `chrono` datetimes
+use chrono::{DateTime, TimeZone, Utc};
+
+use crate::error::ClientError;
+
+/// Ledger timestamp (UNIX seconds) to a UTC datetime
+pub fn to_datetime(timestamp: u64) -> Result<DateTime<Utc>, ClientError> {
+    let seconds = i64::try_from(timestamp).map_err(|_| ClientError::InvalidTimestamp)?;
+    Utc.timestamp_opt(seconds, 0)
+        .single()
+        .ok_or(ClientError::InvalidTimestamp)
+}
+
+/// UTC datetime to a ledger timestamp; sub-second precision is dropped
+pub fn from_datetime(datetime: &DateTime<Utc>) -> Result<u64, ClientError> {
+    u64::try_from(datetime.timestamp()).map_err(|_| ClientError::InvalidTimestamp)
+}
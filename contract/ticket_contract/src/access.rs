@@ -0,0 +1,62 @@
+/// Ticket-gated access for other contracts
+/// The organizer maps access symbols (e.g. `chat`, `merch`) to the tiers that
+/// grant them. Other Soroban contracts call `has_access(address, access)` to
+/// gate their own features on holding a valid ticket of one of those tiers.
+
+use soroban_sdk::{Address, Env, Symbol, Vec};
+
+use crate::storage_types::{DataKey, Ticket, TicketKey, TicketStatus};
+
+/// Most tiers one access symbol may map to
+pub const MAX_ACCESS_TIERS: u32 = 16;
+
+/// Access mappings and holder checks
+pub struct AccessManager;
+
+impl AccessManager {
+    pub fn get_tiers(e: &Env, access: &Symbol) -> Vec<Symbol> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Tickets(TicketKey::AccessTiers(access.clone())))
+            .unwrap_or_else(|| Vec::new(e))
+    }
+
+    /// An empty list removes the mapping
+    pub fn set_tiers(e: &Env, access: &Symbol, tiers: &Vec<Symbol>) {
+        let key = DataKey::Tickets(TicketKey::AccessTiers(access.clone()));
+        if tiers.is_empty() {
+            e.storage().persistent().remove(&key);
+            return;
+        }
+        if tiers.len() > MAX_ACCESS_TIERS {
+            panic!("Too many access tiers");
+        }
+        e.storage().persistent().set(&key, tiers);
+    }
+
+    /// Whether `holder` owns a valid, unrefunded ticket of a tier mapped to `access`
+    pub fn has_access(e: &Env, holder: &Address, access: &Symbol) -> bool {
+        let tiers = Self::get_tiers(e, access);
+        if tiers.is_empty() {
+            return false;
+        }
+
+        // Tickets are soulbound, so the minting history is the holder's inventory
+        let token_ids: Vec<u32> = e
+            .storage()
+            .persistent()
+            .get(&DataKey::Tickets(TicketKey::OwnerTickets(holder.clone())))
+            .unwrap_or_else(|| Vec::new(e));
+        token_ids.iter().any(|token_id| {
+            e.storage()
+                .persistent()
+                .get::<_, Ticket>(&DataKey::Tickets(TicketKey::Ticket(token_id)))
+                .map(|ticket| {
+                    ticket.is_valid
+                        && matches!(ticket.status, TicketStatus::Active | TicketStatus::CheckedIn)
+                        && tiers.contains(&ticket.tier_symbol)
+                })
+                .unwrap_or(false)
+        })
+    }
+}
@@ -0,0 +1,213 @@
+/// Reference-unit accounting
+/// Sales in several payment tokens can't be summed directly. Each token may
+/// have a reference feed (an oracle pair such as `XLM/USD` and the token's
+/// decimals); every ticket sold in it records the feed's rate at purchase
+/// time, so revenue and refunds can be totalled in the reference unit
+/// (8 decimals, as reported by the oracle) next to per-token raw subtotals.
+/// The feed is read whatever the pricing mode. Sales in a token without a
+/// feed, or while the oracle and DEX are both unavailable, count towards the
+/// token's raw subtotal only.
+
+use soroban_sdk::{contracttype, Address, Env, String, Vec};
+
+use crate::oracle::fetch_price_with_fallback;
+use crate::storage_types::{DataKey, PricingConfig, PricingKey, TicketKey, TreasuryKey};
+
+/// Most decimals a reference feed may declare
+pub const MAX_FEED_DECIMALS: u32 = 18;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReferenceFeed {
+    /// Oracle pair pricing one whole token in the reference unit
+    pub pair: String,
+    /// Decimals of the payment token
+    pub decimals: u32,
+}
+
+/// Price of one whole token in the reference unit, as read at a sale
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReferenceRate {
+    pub price: i128,
+    pub decimals: u32,
+}
+
+impl ReferenceRate {
+    /// Reference value of `amount` of the token
+    pub fn value_of(&self, amount: i128) -> i128 {
+        amount
+            .checked_mul(self.price)
+            .map(|value| value / 10i128.pow(self.decimals))
+            .unwrap_or(i128::MAX)
+    }
+}
+
+/// Raw and reference-unit totals for one payment token
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenRevenue {
+    pub token: Address,
+    pub gross: i128,
+    pub refunded: i128,
+    pub gross_reference: i128,
+    pub refunded_reference: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RevenueReport {
+    pub gross_reference: i128,
+    pub refunded_reference: i128,
+    pub net_reference: i128,
+    pub by_token: Vec<TokenRevenue>,
+}
+
+/// Reference feeds, per-ticket rates and revenue totals
+pub struct AccountingManager;
+
+impl AccountingManager {
+    pub fn get_feed(e: &Env, token: &Address) -> Option<ReferenceFeed> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Pricing(PricingKey::ReferenceFeed(token.clone())))
+    }
+
+    /// `None` removes the feed; later sales in the token are left unpriced
+    pub fn set_feed(e: &Env, token: &Address, feed: Option<ReferenceFeed>) {
+        let key = DataKey::Pricing(PricingKey::ReferenceFeed(token.clone()));
+        match feed {
+            Some(feed) => {
+                if feed.decimals > MAX_FEED_DECIMALS {
+                    panic!("Invalid feed decimals");
+                }
+                e.storage().persistent().set(&key, &feed)
+            }
+            None => e.storage().persistent().remove(&key),
+        }
+    }
+
+    /// Rate of `token` right now, if it can be priced
+    pub fn current_rate(e: &Env, token: &Address) -> Option<ReferenceRate> {
+        let feed = Self::get_feed(e, token)?;
+        let config: PricingConfig = e
+            .storage()
+            .instance()
+            .get(&DataKey::Pricing(PricingKey::Config))
+            .unwrap();
+        fetch_price_with_fallback(
+            e,
+            &config.oracle_address,
+            &config.dex_pool_address,
+            feed.pair,
+            config.max_oracle_age_seconds,
+        )
+        .filter(|result| result.price > 0)
+        .map(|result| ReferenceRate {
+            price: result.price,
+            decimals: feed.decimals,
+        })
+    }
+
+    /// Rate recorded when `token_id` was sold, if it was priced
+    pub fn ticket_rate(e: &Env, token_id: u32) -> Option<ReferenceRate> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Tickets(TicketKey::ReferenceRate(token_id)))
+    }
+
+    /// Give a ticket split off `token_id` the same purchase-time rate
+    pub fn copy_rate(e: &Env, token_id: u32, to_token_id: u32) {
+        if let Some(rate) = Self::ticket_rate(e, token_id) {
+            e.storage()
+                .persistent()
+                .set(&DataKey::Tickets(TicketKey::ReferenceRate(to_token_id)), &rate);
+        }
+    }
+
+    /// Count the sale of `token_id` for `amount` of `token`, priced at `rate`
+    pub fn record_sale(
+        e: &Env,
+        token_id: u32,
+        token: &Address,
+        amount: i128,
+        rate: Option<&ReferenceRate>,
+    ) {
+        let mut revenue = Self::token_revenue(e, token);
+        revenue.gross = revenue.gross.saturating_add(amount);
+        if let Some(rate) = rate {
+            e.storage()
+                .persistent()
+                .set(&DataKey::Tickets(TicketKey::ReferenceRate(token_id)), rate);
+            revenue.gross_reference = revenue
+                .gross_reference
+                .saturating_add(rate.value_of(amount));
+        }
+        Self::set_token_revenue(e, &revenue);
+    }
+
+    /// Count `amount` of `token` refunded for `token_id`, at its purchase-time rate
+    pub fn record_refund(e: &Env, token_id: u32, token: &Address, amount: i128) {
+        let mut revenue = Self::token_revenue(e, token);
+        revenue.refunded = revenue.refunded.saturating_add(amount);
+        if let Some(rate) = Self::ticket_rate(e, token_id) {
+            revenue.refunded_reference = revenue
+                .refunded_reference
+                .saturating_add(rate.value_of(amount));
+        }
+        Self::set_token_revenue(e, &revenue);
+    }
+
+    pub fn report(e: &Env) -> RevenueReport {
+        let mut report = RevenueReport {
+            gross_reference: 0,
+            refunded_reference: 0,
+            net_reference: 0,
+            by_token: Vec::new(e),
+        };
+        for token in Self::tokens(e).iter() {
+            let revenue = Self::token_revenue(e, &token);
+            report.gross_reference = report
+                .gross_reference
+                .saturating_add(revenue.gross_reference);
+            report.refunded_reference = report
+                .refunded_reference
+                .saturating_add(revenue.refunded_reference);
+            report.by_token.push_back(revenue);
+        }
+        report.net_reference = report.gross_reference - report.refunded_reference;
+        report
+    }
+
+    fn tokens(e: &Env) -> Vec<Address> {
+        e.storage()
+            .instance()
+            .get(&DataKey::Treasury(TreasuryKey::RevenueTokens))
+            .unwrap_or_else(|| Vec::new(e))
+    }
+
+    pub fn token_revenue(e: &Env, token: &Address) -> TokenRevenue {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Treasury(TreasuryKey::TokenRevenue(token.clone())))
+            .unwrap_or(TokenRevenue {
+                token: token.clone(),
+                gross: 0,
+                refunded: 0,
+                gross_reference: 0,
+                refunded_reference: 0,
+            })
+    }
+
+    fn set_token_revenue(e: &Env, revenue: &TokenRevenue) {
+        let key = DataKey::Treasury(TreasuryKey::TokenRevenue(revenue.token.clone()));
+        if !e.storage().persistent().has(&key) {
+            let mut tokens = Self::tokens(e);
+            tokens.push_back(revenue.token.clone());
+            e.storage()
+                .instance()
+                .set(&DataKey::Treasury(TreasuryKey::RevenueTokens), &tokens);
+        }
+        e.storage().persistent().set(&key, revenue);
+    }
+}
@@ -0,0 +1,83 @@
+/// Admin action log
+/// Every privileged call appends an entry naming the function, the caller, a
+/// hash of its key parameters and the ledger it ran in, so attendees can audit
+/// what the organizer changed and when without running an indexer. Entries
+/// are never edited; only the most recent `MAX_ADMIN_LOG` are kept. Door
+/// check-ins are left out so they cannot push configuration changes out.
+
+use soroban_sdk::{contracttype, xdr::ToXdr, Address, BytesN, Env, IntoVal, Symbol, Val, Vec};
+
+use crate::recovery::RecoveryManager;
+use crate::storage_types::DataKey;
+
+/// Size of the admin log ring buffer
+pub const MAX_ADMIN_LOG: u32 = 500;
+
+/// Most entries returned by one `page` call
+pub const MAX_ADMIN_LOG_PAGE: u32 = 50;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminAction {
+    /// Sequential id, counting every action ever logged
+    pub id: u32,
+    pub function: Symbol,
+    pub caller: Address,
+    /// sha256 of the XDR of the call's key parameters
+    pub params_hash: BytesN<32>,
+    pub ledger: u32,
+}
+
+/// Admin log ring buffer
+pub struct AdminLogManager;
+
+impl AdminLogManager {
+    /// Number of actions ever logged
+    pub fn count(e: &Env) -> u32 {
+        e.storage()
+            .instance()
+            .get(&DataKey::NextAdminLogId)
+            .unwrap_or(0)
+    }
+
+    /// Append an action by `caller` to the log
+    pub fn record(e: &Env, caller: &Address, function: &str, params: impl IntoVal<Env, Val>) {
+        let id = Self::count(e);
+        let params: Val = params.into_val(e);
+        let action = AdminAction {
+            id,
+            function: Symbol::new(e, function),
+            caller: caller.clone(),
+            params_hash: e.crypto().sha256(&params.to_xdr(e)).to_bytes(),
+            ledger: e.ledger().sequence(),
+        };
+
+        e.storage()
+            .persistent()
+            .set(&DataKey::AdminLog(id % MAX_ADMIN_LOG), &action);
+        e.storage()
+            .instance()
+            .set(&DataKey::NextAdminLogId, &(id + 1));
+        RecoveryManager::record_activity(e);
+    }
+
+    /// Up to `limit` retained actions with ids from `start`, oldest first.
+    /// Ids that have been overwritten are skipped.
+    pub fn page(e: &Env, start: u32, limit: u32) -> Vec<AdminAction> {
+        let next_id = Self::count(e);
+        let first = start.max(next_id.saturating_sub(MAX_ADMIN_LOG));
+        let end = next_id.min(first.saturating_add(limit.min(MAX_ADMIN_LOG_PAGE)));
+
+        let mut actions = Vec::new(e);
+        for id in first..end {
+            if let Some(action) = e
+                .storage()
+                .persistent()
+                .get(&DataKey::AdminLog(id % MAX_ADMIN_LOG))
+            {
+                actions.push_back(action);
+            }
+        }
+        actions
+    }
+}
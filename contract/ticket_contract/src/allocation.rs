@@ -143,6 +143,50 @@ impl AllocationEngine {
         results
     }
 
+    /// Allocate tickets using lottery strategy with per-entry weights
+    /// Like `allocate_lottery`, each entry wins at most once; an entry's chance
+    /// in each round is its weight over the weight of entries not yet selected
+    pub fn allocate_weighted_lottery(
+        e: &Env,
+        entries: &Vec<LotteryEntry>,
+        weights: &Vec<u32>,
+        randomness_values: &Vec<u128>,
+        quantity: u32,
+    ) -> Vec<AllocationResult> {
+        let mut results = Vec::new(e);
+        let mut remaining = weights.clone();
+        let mut total_weight = 0u32;
+        for weight in weights.iter() {
+            total_weight = total_weight.saturating_add(weight);
+        }
+
+        for i in 0..quantity.min(randomness_values.len()) {
+            if total_weight == 0 {
+                break;
+            }
+            let randomness = randomness_values.get(i).unwrap();
+            let selection_value = (randomness % (total_weight as u128)) as u32;
+            let mut cumulative = 0u32;
+            for j in 0..remaining.len() {
+                let weight = remaining.get(j).unwrap();
+                cumulative = cumulative.saturating_add(weight);
+                if selection_value < cumulative {
+                    results.push_back(AllocationResult {
+                        winner: entries.get(j).unwrap().participant,
+                        allocation_index: i,
+                        randomness_value: randomness,
+                        weight_applied: weight,
+                    });
+                    remaining.set(j, 0);
+                    total_weight -= weight;
+                    break;
+                }
+            }
+        }
+
+        results
+    }
+
     /// Allocate tickets using whitelist strategy
     /// Whitelisted addresses get priority allocations
     pub fn allocate_whitelist(
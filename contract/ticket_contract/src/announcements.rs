@@ -0,0 +1,80 @@
+/// Organizer announcements
+/// A short on-chain log of schedule changes and notices for ticket holders.
+/// Only the most recent `MAX_ANNOUNCEMENTS` are kept (older slots are
+/// overwritten); every post is also emitted as an `announcement` event so
+/// wallets watching the contract can surface it without a separate backend.
+
+use soroban_sdk::{contracttype, BytesN, Env, String, Symbol, Vec};
+
+use crate::storage_types::{DataKey, TicketKey};
+
+/// Size of the announcement ring buffer
+pub const MAX_ANNOUNCEMENTS: u32 = 20;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Announcement {
+    /// Sequential id, counting every announcement ever posted
+    pub id: u32,
+    /// sha256 of the title, so clients can check the off-chain copy
+    pub title_hash: BytesN<32>,
+    /// Where the full announcement is published
+    pub uri: String,
+    pub posted_at: u64,
+}
+
+/// Announcement ring buffer
+pub struct AnnouncementManager;
+
+impl AnnouncementManager {
+    fn next_id(e: &Env) -> u32 {
+        e.storage()
+            .instance()
+            .get(&DataKey::Tickets(TicketKey::NextAnnouncementId))
+            .unwrap_or(0)
+    }
+
+    /// Store an announcement in the next slot and emit it. Returns its id.
+    pub fn post(e: &Env, title_hash: BytesN<32>, uri: String) -> u32 {
+        let id = Self::next_id(e);
+        let announcement = Announcement {
+            id,
+            title_hash,
+            uri,
+            posted_at: e.ledger().timestamp(),
+        };
+
+        e.storage().persistent().set(
+            &DataKey::Tickets(TicketKey::Announcement(id % MAX_ANNOUNCEMENTS)),
+            &announcement,
+        );
+        e.storage()
+            .instance()
+            .set(&DataKey::Tickets(TicketKey::NextAnnouncementId), &(id + 1));
+
+        #[allow(deprecated)]
+        e.events().publish(
+            (Symbol::new(e, "announcement"), id),
+            (announcement.title_hash, announcement.uri),
+        );
+        id
+    }
+
+    /// Retained announcements, oldest first
+    pub fn list(e: &Env) -> Vec<Announcement> {
+        let next_id = Self::next_id(e);
+        let mut announcements = Vec::new(e);
+        for id in next_id.saturating_sub(MAX_ANNOUNCEMENTS)..next_id {
+            if let Some(announcement) = e
+                .storage()
+                .persistent()
+                .get(&DataKey::Tickets(TicketKey::Announcement(
+                    id % MAX_ANNOUNCEMENTS,
+                )))
+            {
+                announcements.push_back(announcement);
+            }
+        }
+        announcements
+    }
+}
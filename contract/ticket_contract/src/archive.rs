@@ -0,0 +1,328 @@
+/// Lottery allocation archive
+/// Seals a compact, hash-chained summary of each finished draw so third
+/// parties can check it against off-chain archives of entries and winners,
+/// and lets the stored entries be pruned once the draw is sealed. The VRF
+/// outputs a draw was run with are kept too, so winners can be re-derived.
+
+use soroban_sdk::{contracttype, xdr::ToXdr, Bytes, BytesN, Env, Symbol, Vec};
+
+use crate::allocation::{AllocationResult, LotteryEntry};
+use crate::storage_types::{DataKey, LotteryKey};
+use crate::vrf::RandomnessOutput;
+
+/// Most randomness outputs returned by one `randomness_page` call
+pub const MAX_RANDOMNESS_PAGE: u32 = 50;
+
+/// Verifiable summary of a completed lottery
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AllocationProof {
+    pub tier_symbol: Symbol,
+    /// Lottery round of the tier the draw belongs to
+    pub round: u32,
+    pub entry_count: u32,
+    /// Merkle root over sha256(xdr(entry)) in entry order
+    pub entries_root: BytesN<32>,
+    /// sha256(xdr(randomness values)) used for the draw
+    pub randomness_hash: BytesN<32>,
+    pub winner_count: u32,
+    /// Merkle root over sha256(xdr(result)) in allocation order
+    pub winners_root: BytesN<32>,
+    /// `proof_hash` of the draw sealed before this one (zero for the first)
+    pub previous_hash: BytesN<32>,
+    /// sha256(previous_hash || entries_root || randomness_hash || winners_root)
+    pub proof_hash: BytesN<32>,
+    pub sealed_at: u64,
+}
+
+/// Allocation archive manager
+pub struct ArchiveManager;
+
+impl ArchiveManager {
+    /// Build and store the proof for a finished draw, chaining it to the last one
+    pub fn seal(
+        e: &Env,
+        tier_symbol: &Symbol,
+        round: u32,
+        entries: &Vec<LotteryEntry>,
+        randomness_values: &Vec<u128>,
+        results: &Vec<AllocationResult>,
+    ) -> AllocationProof {
+        let proof = Self::chain(e, tier_symbol, round, entries, randomness_values, results);
+        e.storage().persistent().set(
+            &DataKey::Lottery(LotteryKey::AllocationProof(tier_symbol.clone(), round)),
+            &proof,
+        );
+        proof
+    }
+
+    /// Build and store the proof for a backfill draw among `entries`, the
+    /// round's remaining entrants, chaining it like any other draw
+    pub fn seal_backfill(
+        e: &Env,
+        tier_symbol: &Symbol,
+        round: u32,
+        entries: &Vec<LotteryEntry>,
+        randomness_values: &Vec<u128>,
+        results: &Vec<AllocationResult>,
+    ) -> AllocationProof {
+        let proof = Self::chain(e, tier_symbol, round, entries, randomness_values, results);
+        let mut proofs = Self::backfill_proofs(e, tier_symbol, round);
+        proofs.push_back(proof.clone());
+        e.storage().persistent().set(
+            &DataKey::Lottery(LotteryKey::BackfillProofs(tier_symbol.clone(), round)),
+            &proofs,
+        );
+        proof
+    }
+
+    pub fn backfill_proofs(e: &Env, tier_symbol: &Symbol, round: u32) -> Vec<AllocationProof> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Lottery(LotteryKey::BackfillProofs(
+                tier_symbol.clone(),
+                round,
+            )))
+            .unwrap_or_else(|| Vec::new(e))
+    }
+
+    /// Proof of a draw, chained to the last sealed one, which it then becomes
+    fn chain(
+        e: &Env,
+        tier_symbol: &Symbol,
+        round: u32,
+        entries: &Vec<LotteryEntry>,
+        randomness_values: &Vec<u128>,
+        results: &Vec<AllocationResult>,
+    ) -> AllocationProof {
+        let mut entry_leaves = Vec::new(e);
+        for entry in entries.iter() {
+            entry_leaves.push_back(Self::entry_leaf(e, &entry));
+        }
+        let mut winner_leaves = Vec::new(e);
+        for result in results.iter() {
+            winner_leaves.push_back(Self::sha256(e, result.to_xdr(e)));
+        }
+
+        let entries_root = Self::merkle_root(e, &entry_leaves);
+        let randomness_hash = Self::sha256(e, randomness_values.clone().to_xdr(e));
+        let winners_root = Self::merkle_root(e, &winner_leaves);
+
+        let previous_hash: BytesN<32> = e
+            .storage()
+            .persistent()
+            .get(&DataKey::Lottery(LotteryKey::LastProofHash))
+            .unwrap_or_else(|| BytesN::from_array(e, &[0u8; 32]));
+
+        let mut chained = Bytes::from(previous_hash.clone());
+        chained.append(&Bytes::from(entries_root.clone()));
+        chained.append(&Bytes::from(randomness_hash.clone()));
+        chained.append(&Bytes::from(winners_root.clone()));
+        let proof_hash = Self::sha256(e, chained);
+
+        let proof = AllocationProof {
+            tier_symbol: tier_symbol.clone(),
+            round,
+            entry_count: entries.len(),
+            entries_root,
+            randomness_hash,
+            winner_count: results.len(),
+            winners_root,
+            previous_hash,
+            proof_hash: proof_hash.clone(),
+            sealed_at: e.ledger().timestamp(),
+        };
+
+        e.storage()
+            .persistent()
+            .set(&DataKey::Lottery(LotteryKey::LastProofHash), &proof_hash);
+
+        proof
+    }
+
+    pub fn get_proof(e: &Env, tier_symbol: &Symbol, round: u32) -> Option<AllocationProof> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Lottery(LotteryKey::AllocationProof(
+                tier_symbol.clone(),
+                round,
+            )))
+    }
+
+    /// Draws of the tier's `round` whose outputs are archived
+    pub fn randomness_draws(e: &Env, tier_symbol: &Symbol, round: u32) -> u32 {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Lottery(LotteryKey::RandomnessDraws(
+                tier_symbol.clone(),
+                round,
+            )))
+            .unwrap_or(0)
+    }
+
+    /// Archive the outputs of the next draw of the tier's `round` and return
+    /// its draw index. Archived batches are never overwritten.
+    pub fn store_randomness(
+        e: &Env,
+        tier_symbol: &Symbol,
+        round: u32,
+        outputs: &Vec<RandomnessOutput>,
+    ) -> u32 {
+        let draw = Self::randomness_draws(e, tier_symbol, round);
+        let key = DataKey::Lottery(LotteryKey::RandomnessBatch(
+            tier_symbol.clone(),
+            round,
+            draw,
+        ));
+        if e.storage().persistent().has(&key) {
+            panic!("Randomness batch already stored");
+        }
+        e.storage().persistent().set(&key, outputs);
+        e.storage().persistent().set(
+            &DataKey::Lottery(LotteryKey::RandomnessDraws(tier_symbol.clone(), round)),
+            &(draw + 1),
+        );
+        draw
+    }
+
+    /// All archived outputs of one draw of the tier's `round`
+    pub fn randomness(
+        e: &Env,
+        tier_symbol: &Symbol,
+        round: u32,
+        draw: u32,
+    ) -> Vec<RandomnessOutput> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Lottery(LotteryKey::RandomnessBatch(
+                tier_symbol.clone(),
+                round,
+                draw,
+            )))
+            .unwrap_or_else(|| Vec::new(e))
+    }
+
+    /// Up to `limit` archived outputs of one draw from index `start`
+    pub fn randomness_page(
+        e: &Env,
+        tier_symbol: &Symbol,
+        round: u32,
+        draw: u32,
+        start: u32,
+        limit: u32,
+    ) -> Vec<RandomnessOutput> {
+        let outputs = Self::randomness(e, tier_symbol, round, draw);
+        let start = start.min(outputs.len());
+        let end = outputs
+            .len()
+            .min(start.saturating_add(limit.min(MAX_RANDOMNESS_PAGE)));
+        outputs.slice(start..end)
+    }
+
+    /// Delete up to `max_count` stored entries of a sealed draw, oldest first.
+    /// Returns the number of entries deleted by this call.
+    pub fn prune_entries(
+        e: &Env,
+        tier_symbol: &Symbol,
+        round: u32,
+        entry_count: u32,
+        max_count: u32,
+    ) -> u32 {
+        let pruned_key = DataKey::Lottery(LotteryKey::PrunedCount(tier_symbol.clone(), round));
+        let pruned: u32 = e.storage().persistent().get(&pruned_key).unwrap_or(0);
+        let end = entry_count.min(pruned.saturating_add(max_count));
+
+        for i in pruned..end {
+            e.storage()
+                .persistent()
+                .remove(&DataKey::Lottery(LotteryKey::Entry(
+                    tier_symbol.clone(),
+                    round,
+                    i,
+                )));
+        }
+        e.storage().persistent().set(&pruned_key, &end);
+
+        end - pruned
+    }
+
+    /// Check a merkle inclusion proof for the leaf at `index` of a tree built by
+    /// `merkle_root` over `leaf_count` leaves. `proof` lists the sibling hashes
+    /// from the bottom up, skipping levels where the node was carried up.
+    pub fn verify_inclusion(
+        e: &Env,
+        leaf: BytesN<32>,
+        index: u32,
+        leaf_count: u32,
+        proof: &Vec<BytesN<32>>,
+        root: &BytesN<32>,
+    ) -> bool {
+        if index >= leaf_count {
+            return false;
+        }
+
+        let mut hash = leaf;
+        let mut index = index;
+        let mut len = leaf_count;
+        let mut used = 0;
+        while len > 1 {
+            if index % 2 == 1 || index + 1 < len {
+                let sibling = match proof.get(used) {
+                    Some(sibling) => sibling,
+                    None => return false,
+                };
+                used += 1;
+                hash = if index % 2 == 1 {
+                    Self::hash_pair(e, &sibling, &hash)
+                } else {
+                    Self::hash_pair(e, &hash, &sibling)
+                };
+            }
+            index /= 2;
+            len = (len + 1) / 2;
+        }
+
+        used == proof.len() && hash == *root
+    }
+
+    /// Leaf hash of a lottery entry, as used for `entries_root`
+    pub fn entry_leaf(e: &Env, entry: &LotteryEntry) -> BytesN<32> {
+        Self::sha256(e, entry.clone().to_xdr(e))
+    }
+
+    /// Binary merkle root: parents are sha256(left || right) and an odd node is
+    /// carried up unchanged. The root of no leaves is all zeroes.
+    pub fn merkle_root(e: &Env, leaves: &Vec<BytesN<32>>) -> BytesN<32> {
+        if leaves.is_empty() {
+            return BytesN::from_array(e, &[0u8; 32]);
+        }
+
+        let mut level = leaves.clone();
+        while level.len() > 1 {
+            let mut next = Vec::new(e);
+            let mut i = 0;
+            while i < level.len() {
+                let left = level.get_unchecked(i);
+                if i + 1 < level.len() {
+                    next.push_back(Self::hash_pair(e, &left, &level.get_unchecked(i + 1)));
+                } else {
+                    next.push_back(left);
+                }
+                i += 2;
+            }
+            level = next;
+        }
+
+        level.get_unchecked(0)
+    }
+
+    fn hash_pair(e: &Env, left: &BytesN<32>, right: &BytesN<32>) -> BytesN<32> {
+        let mut combined = Bytes::from(left.clone());
+        combined.append(&Bytes::from(right.clone()));
+        Self::sha256(e, combined)
+    }
+
+    fn sha256(e: &Env, data: Bytes) -> BytesN<32> {
+        e.crypto().sha256(&data).to_bytes()
+    }
+}
@@ -0,0 +1,201 @@
+/// Unclaimed winner expiry and backfill
+/// A tier can give lottery winners a claim window. Each allocation result of
+/// a draw gets a deadline when the draw runs; results still unclaimed after
+/// it can be expired by anyone, freeing the allocation for a backfill draw
+/// among entrants who have not won. A winner's claims are matched to their
+/// results in allocation order, so the results expired are always the last
+/// ones they hold. Expired results stay in the round's results, marked
+/// forfeited, so the sealed transcript still matches.
+
+use soroban_sdk::{Address, Env, Map, Symbol, Vec};
+
+use crate::allocation::{AllocationResult, LotteryEntry};
+use crate::storage_types::{DataKey, LotteryKey};
+
+/// Claim deadlines, forfeits and backfill pools
+pub struct BackfillManager;
+
+impl BackfillManager {
+    /// Seconds winners of the tier's later draws have to claim, 0 when unlimited
+    pub fn claim_window(e: &Env, tier_symbol: &Symbol) -> u64 {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Lottery(LotteryKey::ClaimWindow(
+                tier_symbol.clone(),
+            )))
+            .unwrap_or(0)
+    }
+
+    pub fn set_claim_window(e: &Env, tier_symbol: &Symbol, seconds: u64) {
+        let key = DataKey::Lottery(LotteryKey::ClaimWindow(tier_symbol.clone()));
+        if seconds == 0 {
+            e.storage().persistent().remove(&key);
+        } else {
+            e.storage().persistent().set(&key, &seconds);
+        }
+    }
+
+    /// Give each of `results` a deadline one claim window from now
+    pub fn stamp_deadlines(
+        e: &Env,
+        tier_symbol: &Symbol,
+        round: u32,
+        results: &Vec<AllocationResult>,
+    ) {
+        let window = Self::claim_window(e, tier_symbol);
+        if window == 0 {
+            return;
+        }
+        let deadline = e.ledger().timestamp().saturating_add(window);
+        for result in results.iter() {
+            e.storage().persistent().set(
+                &DataKey::Lottery(LotteryKey::ClaimDeadline(
+                    tier_symbol.clone(),
+                    round,
+                    result.allocation_index,
+                )),
+                &deadline,
+            );
+        }
+    }
+
+    /// Last timestamp allocation `index` of the round can be claimed at
+    pub fn deadline(e: &Env, tier_symbol: &Symbol, round: u32, index: u32) -> Option<u64> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Lottery(LotteryKey::ClaimDeadline(
+                tier_symbol.clone(),
+                round,
+                index,
+            )))
+    }
+
+    pub fn is_forfeited(e: &Env, tier_symbol: &Symbol, round: u32, index: u32) -> bool {
+        e.storage()
+            .persistent()
+            .has(&DataKey::Lottery(LotteryKey::Forfeited(
+                tier_symbol.clone(),
+                round,
+                index,
+            )))
+    }
+
+    pub fn claimed(e: &Env, tier_symbol: &Symbol, round: u32, winner: &Address) -> u32 {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Lottery(LotteryKey::Claimed(
+                tier_symbol.clone(),
+                round,
+                winner.clone(),
+            )))
+            .unwrap_or(0)
+    }
+
+    /// `winner`'s first result not yet claimed or forfeited
+    pub fn next_unclaimed(
+        e: &Env,
+        tier_symbol: &Symbol,
+        round: u32,
+        results: &Vec<AllocationResult>,
+        winner: &Address,
+    ) -> Option<AllocationResult> {
+        let mut skip = Self::claimed(e, tier_symbol, round, winner);
+        for result in results.iter() {
+            if result.winner != *winner
+                || Self::is_forfeited(e, tier_symbol, round, result.allocation_index)
+            {
+                continue;
+            }
+            if skip == 0 {
+                return Some(result);
+            }
+            skip -= 1;
+        }
+        None
+    }
+
+    /// Results neither claimed nor forfeited
+    fn unclaimed(
+        e: &Env,
+        tier_symbol: &Symbol,
+        round: u32,
+        results: &Vec<AllocationResult>,
+    ) -> Vec<AllocationResult> {
+        let mut seen: Map<Address, u32> = Map::new(e);
+        let mut unclaimed = Vec::new(e);
+        for result in results.iter() {
+            if Self::is_forfeited(e, tier_symbol, round, result.allocation_index) {
+                continue;
+            }
+            let held = seen.get(result.winner.clone()).unwrap_or(0);
+            seen.set(result.winner.clone(), held + 1);
+            if held < Self::claimed(e, tier_symbol, round, &result.winner) {
+                continue;
+            }
+            unclaimed.push_back(result);
+        }
+        unclaimed
+    }
+
+    /// Unclaimed results that can still be claimed: with no deadline, or
+    /// before it
+    pub fn open_claims(
+        e: &Env,
+        tier_symbol: &Symbol,
+        round: u32,
+        results: &Vec<AllocationResult>,
+    ) -> u32 {
+        let now = e.ledger().timestamp();
+        let mut open = 0;
+        for result in Self::unclaimed(e, tier_symbol, round, results).iter() {
+            let index = result.allocation_index;
+            if Self::deadline(e, tier_symbol, round, index).map_or(true, |d| now <= d) {
+                open += 1;
+            }
+        }
+        open
+    }
+
+    /// Forfeit every unclaimed result past its deadline. Returns the number
+    /// forfeited.
+    pub fn expire(
+        e: &Env,
+        tier_symbol: &Symbol,
+        round: u32,
+        results: &Vec<AllocationResult>,
+    ) -> u32 {
+        let now = e.ledger().timestamp();
+        let mut expired = 0;
+        for result in Self::unclaimed(e, tier_symbol, round, results).iter() {
+            let index = result.allocation_index;
+            if Self::deadline(e, tier_symbol, round, index).is_some_and(|d| now > d) {
+                e.storage().persistent().set(
+                    &DataKey::Lottery(LotteryKey::Forfeited(tier_symbol.clone(), round, index)),
+                    &true,
+                );
+                expired += 1;
+            }
+        }
+        expired
+    }
+
+    /// Entries from participants who have no result in the round, forfeited
+    /// or not
+    pub fn remaining_entrants(
+        e: &Env,
+        entries: &Vec<LotteryEntry>,
+        results: &Vec<AllocationResult>,
+    ) -> Vec<LotteryEntry> {
+        let mut winners: Map<Address, bool> = Map::new(e);
+        for result in results.iter() {
+            winners.set(result.winner, true);
+        }
+        let mut pool = Vec::new(e);
+        for entry in entries.iter() {
+            if !winners.contains_key(entry.participant.clone()) {
+                pool.push_back(entry);
+            }
+        }
+        pool
+    }
+}
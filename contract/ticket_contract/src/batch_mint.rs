@@ -0,0 +1,50 @@
+/// Resumable organizer mints
+/// A single `batch_mint` invocation runs out of resources long before a large
+/// comp run (thousands of sponsor tickets) completes. Chunked mints persist
+/// how many tickets of a job have been minted so each call picks up where the
+/// last one stopped, minting at most `MAX_MINT_CHUNK` tickets per call.
+
+use soroban_sdk::{contracttype, Address, Env, Symbol};
+
+use crate::storage_types::{DataKey, TicketKey};
+
+/// Most tickets minted by one `batch_mint_chunk` call
+pub const MAX_MINT_CHUNK: u32 = 50;
+
+/// Progress of a chunked mint of `amount` tickets of a tier to one recipient
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MintJob {
+    pub amount: u32,
+    /// Tickets minted so far; the cursor the next chunk must resume from
+    pub minted: u32,
+}
+
+/// Mint job storage
+pub struct BatchMintManager;
+
+impl BatchMintManager {
+    pub fn get(e: &Env, to: &Address, tier_symbol: &Symbol) -> Option<MintJob> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Tickets(TicketKey::MintJob(
+                to.clone(),
+                tier_symbol.clone(),
+            )))
+    }
+
+    /// Store the job's progress, dropping it once every ticket is minted
+    pub fn save(e: &Env, to: &Address, tier_symbol: &Symbol, job: &MintJob) {
+        let key = DataKey::Tickets(TicketKey::MintJob(to.clone(), tier_symbol.clone()));
+        if job.minted >= job.amount {
+            e.storage().persistent().remove(&key);
+        } else {
+            e.storage().persistent().set(&key, job);
+        }
+    }
+
+    /// Tickets the next chunk of `job` should mint
+    pub fn next_chunk(job: &MintJob) -> u32 {
+        (job.amount - job.minted).min(MAX_MINT_CHUNK)
+    }
+}
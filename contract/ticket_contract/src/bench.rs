@@ -0,0 +1,80 @@
+/// Benchmark entry points
+/// Run the heavy contract paths on synthetic data so their cost can be measured
+/// against Soroban's per-transaction limits. Only built with the `bench` feature;
+/// never enable it for a deployed contract.
+
+use soroban_sdk::{contractimpl, panic_with_error, Address, Env, Symbol, Vec};
+
+use crate::allocation::{AllocationEngine, LotteryEntry};
+use crate::archive::ArchiveManager;
+use crate::rounds::RoundManager;
+use crate::storage_types::{DataKey, PricingConfig, PricingKey, TicketKey, Tier};
+use crate::{SoulboundTicketContract, SoulboundTicketContractArgs, SoulboundTicketContractClient};
+
+/// Soroban per-transaction CPU instruction limit
+pub const MAX_CPU_INSTRUCTIONS: u64 = 100_000_000;
+/// Soroban per-transaction memory limit (40 MiB)
+pub const MAX_MEMORY_BYTES: u64 = 41_943_040;
+
+#[contractimpl]
+impl SoulboundTicketContract {
+    /// Draw `winners` from `entry_count` in-memory entries and seal the result.
+    /// Entries are not written to storage; only the proof is. Returns the winner count.
+    pub fn bench_lottery_allocation(
+        e: &Env,
+        tier_symbol: Symbol,
+        entry_count: u32,
+        winners: u32,
+    ) -> u32 {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        let participant = e.current_contract_address();
+        let mut entries = Vec::new(e);
+        for nonce in 0..entry_count {
+            entries.push_back(LotteryEntry {
+                participant: participant.clone(),
+                entry_time: e.ledger().timestamp(),
+                nonce,
+                commitment_hash: None,
+            });
+        }
+
+        let mut randomness = Vec::new(e);
+        for _ in 0..winners {
+            randomness.push_back(e.prng().gen::<u64>() as u128);
+        }
+
+        let results = AllocationEngine::allocate_lottery(e, &entries, &randomness, winners);
+        let round = RoundManager::current(e, &tier_symbol);
+        ArchiveManager::seal(e, &tier_symbol, round, &entries, &randomness, &results);
+        results.len()
+    }
+
+    /// Price `tier_symbol` `iterations` times, re-querying the oracle on every
+    /// iteration instead of using the cache. Returns the last price.
+    pub fn bench_pricing(e: &Env, tier_symbol: Symbol, iterations: u32) -> i128 {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        let config: PricingConfig = e
+            .storage()
+            .instance()
+            .get(&DataKey::Pricing(PricingKey::Config))
+            .unwrap();
+        let tier: Tier = e
+            .storage()
+            .persistent()
+            .get(&DataKey::Tickets(TicketKey::Tier(tier_symbol)))
+            .unwrap_or_else(|| panic!("Tier not found"));
+
+        let mut price = 0;
+        for _ in 0..iterations {
+            let multiplier = Self::refresh_oracle_multiplier(e, &config);
+            price = Self::compute_discounted_price(e, &config, &tier, multiplier, None, None)
+                .unwrap_or_else(|err| panic_with_error!(e, err))
+                .price;
+        }
+        price
+    }
+}
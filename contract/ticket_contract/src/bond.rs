@@ -0,0 +1,142 @@
+/// Organizer bond
+/// Before the event the organizer may post a bond, held by the contract apart
+/// from ticket revenue: withdrawals never touch it. If the event is cancelled,
+/// recovery mode is triggered or a refund finds the treasury short, the bond
+/// is slashed and what is left of it tops up refunds in its token; a slashed
+/// bond is never returned. Otherwise the organizer reclaims it once the event
+/// is complete.
+///
+/// Posted -> Released, or Posted -> Slashed
+
+use soroban_sdk::{contracttype, Address, Env};
+
+use crate::payout_schedule::PayoutScheduleManager;
+use crate::storage_types::{DataKey, EventInfo, TreasuryKey};
+use crate::treasury::TreasuryManager;
+
+/// How long after the event starts the bond is held when there is no payout
+/// schedule to say when the event completes (7 days)
+pub const DEFAULT_BOND_HOLD_SECONDS: u64 = 7 * 24 * 60 * 60;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BondState {
+    /// Held until the event completes
+    Posted,
+    /// Drawn on for refunds; what remains stays reserved for them
+    Slashed,
+    /// Returned to the organizer
+    Released,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OrganizerBond {
+    pub token: Address,
+    /// Amount originally posted
+    pub amount: i128,
+    /// Amount not yet drawn on or returned
+    pub remaining: i128,
+    pub state: BondState,
+}
+
+/// Bond sub-account of the treasury
+pub struct BondManager;
+
+impl BondManager {
+    pub fn get(e: &Env) -> Option<OrganizerBond> {
+        e.storage()
+            .instance()
+            .get(&DataKey::Treasury(TreasuryKey::Bond))
+    }
+
+    fn set(e: &Env, bond: &OrganizerBond) {
+        e.storage()
+            .instance()
+            .set(&DataKey::Treasury(TreasuryKey::Bond), bond);
+    }
+
+    /// Record a bond of `amount` of `token`; one per event
+    pub fn post(e: &Env, token: &Address, amount: i128) {
+        if Self::get(e).is_some() {
+            panic!("Bond already posted");
+        }
+        if amount <= 0 {
+            panic!("Invalid bond amount");
+        }
+        Self::set(
+            e,
+            &OrganizerBond {
+                token: token.clone(),
+                amount,
+                remaining: amount,
+                state: BondState::Posted,
+            },
+        );
+    }
+
+    /// Balance of `token` held for the bond and unavailable to withdrawals
+    pub fn locked(e: &Env, token: &Address) -> i128 {
+        match Self::get(e) {
+            Some(bond) if bond.token == *token && bond.state != BondState::Released => {
+                bond.remaining
+            }
+            _ => 0,
+        }
+    }
+
+    /// Mark a posted bond as forfeit to refunds
+    pub fn slash(e: &Env) {
+        if let Some(mut bond) = Self::get(e) {
+            if bond.state == BondState::Posted {
+                bond.state = BondState::Slashed;
+                Self::set(e, &bond);
+            }
+        }
+    }
+
+    /// Before paying a refund of `payout` in `token`, draw whatever revenue
+    /// can't cover from the bond, slashing it
+    pub fn cover_shortfall(e: &Env, token: &Address, payout: i128) {
+        let Some(mut bond) = Self::get(e) else {
+            return;
+        };
+        if bond.token != *token || bond.state == BondState::Released {
+            return;
+        }
+        let free = TreasuryManager::balance(e, token) - bond.remaining;
+        if free >= payout {
+            return;
+        }
+        bond.remaining -= (payout - free).min(bond.remaining);
+        bond.state = BondState::Slashed;
+        Self::set(e, &bond);
+    }
+
+    /// When the event counts as complete and a posted bond can be reclaimed
+    pub fn release_time(e: &Env) -> u64 {
+        match PayoutScheduleManager::get(e) {
+            Some(schedule) => schedule.completion_time,
+            None => {
+                let event_info: EventInfo =
+                    e.storage().instance().get(&DataKey::EventInfo).unwrap();
+                event_info.start_time.saturating_add(DEFAULT_BOND_HOLD_SECONDS)
+            }
+        }
+    }
+
+    /// Mark a posted bond returned, once the event is complete. Returns the
+    /// bond so its remaining amount can be paid back.
+    pub fn release(e: &Env) -> OrganizerBond {
+        let mut bond = Self::get(e).unwrap_or_else(|| panic!("No bond posted"));
+        if bond.state != BondState::Posted {
+            panic!("Bond not releasable");
+        }
+        if e.ledger().timestamp() < Self::release_time(e) {
+            panic!("Event not complete");
+        }
+        bond.state = BondState::Released;
+        Self::set(e, &bond);
+        bond
+    }
+}
@@ -0,0 +1,77 @@
+/// Cross-tier bundles
+/// A bundle combines units from several tiers (e.g. general admission plus
+/// camping) sold together at a discount. Every constituent ticket records the
+/// bundle id and its discounted share of the bundle price, so each one can be
+/// refunded on its own for what was actually paid for it.
+
+use soroban_sdk::{contracttype, Env, Symbol, Vec};
+
+use crate::storage_types::{DataKey, TicketKey};
+
+/// Most tickets a single bundle may mint
+pub const MAX_BUNDLE_TICKETS: u32 = 10;
+
+/// Units of one tier included in a bundle
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BundleItem {
+    pub tier_symbol: Symbol,
+    pub quantity: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Bundle {
+    pub items: Vec<BundleItem>,
+    /// Taken off the price of every constituent ticket
+    pub discount_bps: u32,
+    pub active: bool,
+}
+
+/// Bundle definitions
+pub struct BundleManager;
+
+impl BundleManager {
+    pub fn get(e: &Env, bundle_id: u32) -> Option<Bundle> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Tickets(TicketKey::Bundle(bundle_id)))
+    }
+
+    pub fn set(e: &Env, bundle_id: u32, bundle: &Bundle) {
+        e.storage()
+            .persistent()
+            .set(&DataKey::Tickets(TicketKey::Bundle(bundle_id)), bundle);
+    }
+
+    /// Validate and store a new bundle. Returns its id.
+    pub fn create(e: &Env, bundle: &Bundle) -> u32 {
+        if bundle.items.is_empty() {
+            panic!("Empty bundle");
+        }
+        if bundle.discount_bps > 10_000 {
+            panic!("Discount above 100%");
+        }
+        let mut tickets: u32 = 0;
+        for item in bundle.items.iter() {
+            if item.quantity == 0 {
+                panic!("Invalid bundle quantity");
+            }
+            tickets = tickets.saturating_add(item.quantity);
+        }
+        if tickets > MAX_BUNDLE_TICKETS {
+            panic!("Too many tickets in bundle");
+        }
+
+        let id: u32 = e
+            .storage()
+            .instance()
+            .get(&DataKey::Tickets(TicketKey::NextBundleId))
+            .unwrap_or(0);
+        Self::set(e, id, bundle);
+        e.storage()
+            .instance()
+            .set(&DataKey::Tickets(TicketKey::NextBundleId), &(id + 1));
+        id
+    }
+}
@@ -0,0 +1,25 @@
+/// Event cancellation
+/// Cancelling is final. Sales stop, refunds stay open past the cutoff and pay
+/// back the full price whatever the refund mode, and the treasury holds back
+/// the whole outstanding liability so nothing can be withdrawn ahead of them.
+
+use soroban_sdk::Env;
+
+use crate::storage_types::{DataKey, GovernanceKey};
+
+/// Cancellation flag
+pub struct CancellationManager;
+
+impl CancellationManager {
+    pub fn is_cancelled(e: &Env) -> bool {
+        e.storage()
+            .instance()
+            .has(&DataKey::Governance(GovernanceKey::Cancelled))
+    }
+
+    pub fn cancel(e: &Env) {
+        e.storage()
+            .instance()
+            .set(&DataKey::Governance(GovernanceKey::Cancelled), &e.ledger().timestamp());
+    }
+}
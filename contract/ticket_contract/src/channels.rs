@@ -0,0 +1,188 @@
+/// Sales channel inventory partitioning
+/// Lets organizers hold back part of a tier's supply for presale, lottery or
+/// box office; whatever is not reserved is sold through the general channel
+
+use soroban_sdk::{contracttype, Env, Map, Symbol};
+
+use crate::storage_types::{DataKey, TicketKey, Tier};
+
+/// Channel a ticket is sold through
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SalesChannel {
+    Presale,
+    Lottery,
+    General,
+    BoxOffice,
+    Invitation,
+    /// Held back for named VIP allocations
+    Comp,
+    /// Held for promotional offers awaiting acceptance
+    Offer,
+}
+
+/// Reserved inventory for one channel of a tier
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChannelAllocation {
+    /// Tickets held back for this channel
+    pub reserved: u32,
+    /// Tickets already sold out of the reservation
+    pub sold: u32,
+}
+
+/// Channel allocation bookkeeping
+pub struct ChannelManager;
+
+impl ChannelManager {
+    pub fn get_allocations(e: &Env, tier_symbol: &Symbol) -> Map<SalesChannel, ChannelAllocation> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Tickets(TicketKey::ChannelAllocations(
+                tier_symbol.clone(),
+            )))
+            .unwrap_or_else(|| Map::new(e))
+    }
+
+    fn save_allocations(
+        e: &Env,
+        tier_symbol: &Symbol,
+        allocations: &Map<SalesChannel, ChannelAllocation>,
+    ) {
+        e.storage().persistent().set(
+            &DataKey::Tickets(TicketKey::ChannelAllocations(tier_symbol.clone())),
+            allocations,
+        );
+    }
+
+    /// Reserved tickets not yet sold, across all channels
+    fn outstanding(allocations: &Map<SalesChannel, ChannelAllocation>) -> u32 {
+        let mut total = 0u32;
+        for (_, allocation) in allocations.iter() {
+            total += allocation.reserved - allocation.sold;
+        }
+        total
+    }
+
+    /// Remaining supply that no channel has reserved
+    pub fn unreserved_remaining(e: &Env, tier_symbol: &Symbol, tier: &Tier) -> u32 {
+        let allocations = Self::get_allocations(e, tier_symbol);
+        tier.max_supply
+            .saturating_sub(tier.minted)
+            .saturating_sub(Self::outstanding(&allocations))
+    }
+
+    /// Tickets left in `channel`'s own reservation
+    pub fn reserved_remaining(e: &Env, tier_symbol: &Symbol, channel: SalesChannel) -> u32 {
+        Self::get_allocations(e, tier_symbol)
+            .get(channel)
+            .map(|allocation| allocation.reserved - allocation.sold)
+            .unwrap_or(0)
+    }
+
+    /// Tickets `channel` can still sell: its own reservation plus the unreserved pool
+    pub fn available(e: &Env, tier_symbol: &Symbol, tier: &Tier, channel: SalesChannel) -> u32 {
+        Self::reserved_remaining(e, tier_symbol, channel)
+            + Self::unreserved_remaining(e, tier_symbol, tier)
+    }
+
+    /// Record `quantity` sales through `channel`, drawing on its reservation first.
+    /// Must be called before `tier.minted` is updated.
+    pub fn record_sale(
+        e: &Env,
+        tier_symbol: &Symbol,
+        tier: &Tier,
+        channel: SalesChannel,
+        quantity: u32,
+    ) {
+        if Self::available(e, tier_symbol, tier, channel) < quantity {
+            panic!("Channel allocation exhausted");
+        }
+
+        let mut allocations = Self::get_allocations(e, tier_symbol);
+        if let Some(mut allocation) = allocations.get(channel) {
+            let from_reservation = (allocation.reserved - allocation.sold).min(quantity);
+            if from_reservation > 0 {
+                allocation.sold += from_reservation;
+                allocations.set(channel, allocation);
+                Self::save_allocations(e, tier_symbol, &allocations);
+            }
+        }
+    }
+
+    /// Reserve `quantity` tickets of the tier for `channel`
+    pub fn set_allocation(
+        e: &Env,
+        tier_symbol: &Symbol,
+        tier: &Tier,
+        channel: SalesChannel,
+        quantity: u32,
+    ) {
+        if channel == SalesChannel::General {
+            panic!("General sale uses the unreserved pool");
+        }
+
+        let mut allocations = Self::get_allocations(e, tier_symbol);
+        let current = allocations.get(channel).unwrap_or(ChannelAllocation {
+            reserved: 0,
+            sold: 0,
+        });
+        if quantity < current.sold {
+            panic!("Allocation below tickets already sold");
+        }
+
+        let outstanding_elsewhere =
+            Self::outstanding(&allocations) - (current.reserved - current.sold);
+        let remaining = tier.max_supply.saturating_sub(tier.minted);
+        if outstanding_elsewhere + (quantity - current.sold) > remaining {
+            panic!("Allocation exceeds remaining supply");
+        }
+
+        allocations.set(
+            channel,
+            ChannelAllocation {
+                reserved: quantity,
+                sold: current.sold,
+            },
+        );
+        Self::save_allocations(e, tier_symbol, &allocations);
+    }
+
+    /// Hold `quantity` more tickets for `channel` on top of its current reservation
+    pub fn reserve(
+        e: &Env,
+        tier_symbol: &Symbol,
+        tier: &Tier,
+        channel: SalesChannel,
+        quantity: u32,
+    ) {
+        let reserved = Self::get_allocations(e, tier_symbol)
+            .get(channel)
+            .map(|allocation| allocation.reserved)
+            .unwrap_or(0);
+        Self::set_allocation(e, tier_symbol, tier, channel, reserved + quantity);
+    }
+
+    /// Return up to `quantity` unsold tickets of a channel's reservation to general sale
+    pub fn unreserve(e: &Env, tier_symbol: &Symbol, channel: SalesChannel, quantity: u32) {
+        let mut allocations = Self::get_allocations(e, tier_symbol);
+        if let Some(mut allocation) = allocations.get(channel) {
+            allocation.reserved = allocation
+                .reserved
+                .saturating_sub(quantity)
+                .max(allocation.sold);
+            allocations.set(channel, allocation);
+            Self::save_allocations(e, tier_symbol, &allocations);
+        }
+    }
+
+    /// Roll a channel's unsold reservation back into general sale
+    pub fn release(e: &Env, tier_symbol: &Symbol, channel: SalesChannel) {
+        let mut allocations = Self::get_allocations(e, tier_symbol);
+        if let Some(mut allocation) = allocations.get(channel) {
+            allocation.reserved = allocation.sold;
+            allocations.set(channel, allocation);
+            Self::save_allocations(e, tier_symbol, &allocations);
+        }
+    }
+}
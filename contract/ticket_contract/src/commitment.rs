@@ -1,8 +1,13 @@
 /// Commitment Scheme Implementation
 /// Implements commit-reveal pattern for additional fairness verification
-/// Ensures that lottery random numbers cannot be manipulated after participation
+/// Ensures that lottery random numbers cannot be manipulated after participation.
+/// Commitments are stored per tier and committer: made before the tier's reveal
+/// window opens, revealed inside it, expired once it closes unrevealed, and
+/// pruned after it closes.
 
-use soroban_sdk::{contracttype, Address, Bytes, Env, Vec};
+use soroban_sdk::{contracttype, Address, Bytes, Env, Symbol, Vec};
+
+use crate::storage_types::{DataKey, LotteryKey};
 
 /// Hash commitment for commit-reveal scheme
 #[contracttype]
@@ -146,6 +151,101 @@ impl CommitmentScheme {
     }
 }
 
+/// Stored commitments per tier
+pub struct CommitmentManager;
+
+impl CommitmentManager {
+    pub fn get(e: &Env, tier_symbol: &Symbol, committer: &Address) -> Option<Commitment> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Lottery(LotteryKey::Commitment(
+                tier_symbol.clone(),
+                committer.clone(),
+            )))
+    }
+
+    fn set(e: &Env, tier_symbol: &Symbol, commitment: &Commitment) {
+        e.storage().persistent().set(
+            &DataKey::Lottery(LotteryKey::Commitment(
+                tier_symbol.clone(),
+                commitment.committer.clone(),
+            )),
+            commitment,
+        );
+    }
+
+    /// Committers with a stored commitment for the tier, oldest first
+    pub fn committers(e: &Env, tier_symbol: &Symbol) -> Vec<Address> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Lottery(LotteryKey::Committers(
+                tier_symbol.clone(),
+            )))
+            .unwrap_or_else(|| Vec::new(e))
+    }
+
+    /// Store `hash` as `committer`'s commitment; one per committer and tier
+    pub fn create(e: &Env, tier_symbol: &Symbol, committer: &Address, hash: Bytes) -> Commitment {
+        if Self::get(e, tier_symbol, committer).is_some() {
+            panic!("Commitment already exists");
+        }
+        let commitment = Commitment {
+            hash,
+            committed_at: e.ledger().timestamp(),
+            revealed: false,
+            committer: committer.clone(),
+        };
+        Self::set(e, tier_symbol, &commitment);
+
+        let mut committers = Self::committers(e, tier_symbol);
+        committers.push_back(committer.clone());
+        e.storage().persistent().set(
+            &DataKey::Lottery(LotteryKey::Committers(tier_symbol.clone())),
+            &committers,
+        );
+        commitment
+    }
+
+    /// Check `reveal` opens `committer`'s commitment and mark it revealed
+    pub fn reveal(e: &Env, tier_symbol: &Symbol, committer: &Address, reveal: &Reveal) {
+        let mut commitment =
+            Self::get(e, tier_symbol, committer).unwrap_or_else(|| panic!("Commitment not found"));
+        if commitment.revealed {
+            panic!("Commitment already revealed");
+        }
+        if !CommitmentScheme::verify_reveal(e, &commitment.hash, reveal) {
+            panic!("Reveal does not match commitment");
+        }
+        commitment.revealed = true;
+        Self::set(e, tier_symbol, &commitment);
+    }
+
+    /// Delete up to `max_count` of the tier's stored commitments, oldest
+    /// first. Returns the number deleted.
+    pub fn prune(e: &Env, tier_symbol: &Symbol, max_count: u32) -> u32 {
+        let committers = Self::committers(e, tier_symbol);
+        let count = committers.len().min(max_count);
+        for committer in committers.slice(0..count).iter() {
+            e.storage()
+                .persistent()
+                .remove(&DataKey::Lottery(LotteryKey::Commitment(
+                    tier_symbol.clone(),
+                    committer,
+                )));
+        }
+
+        let key = DataKey::Lottery(LotteryKey::Committers(tier_symbol.clone()));
+        if count == committers.len() {
+            e.storage().persistent().remove(&key);
+        } else {
+            e.storage()
+                .persistent()
+                .set(&key, &committers.slice(count..committers.len()));
+        }
+        count
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
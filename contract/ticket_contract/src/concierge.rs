@@ -0,0 +1,45 @@
+/// Named VIP allocations
+/// Concierge comps go to named guests out of a tier's comp reserve rather than
+/// the box office allocation `batch_mint` draws on. Every allocation carries a
+/// reason code (sponsor, artist, press, ...) kept per ticket, and allocations
+/// are counted per tier apart from sales so analytics can tell them apart.
+
+use soroban_sdk::{Env, Symbol};
+
+use crate::storage_types::{CatalogKey, DataKey};
+
+/// Most recipients one `allocate_named` call may name
+pub const MAX_NAMED_ALLOCATIONS: u32 = 50;
+
+/// Allocation reasons and per-tier counts
+pub struct ConciergeManager;
+
+impl ConciergeManager {
+    /// Reason code `token_id` was allocated under, if it was a named allocation
+    pub fn reason(e: &Env, token_id: u32) -> Option<Symbol> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Catalog(CatalogKey::AllocationReason(token_id)))
+    }
+
+    /// Named allocations made from the tier
+    pub fn count(e: &Env, tier_symbol: &Symbol) -> u32 {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Catalog(CatalogKey::NamedAllocations(
+                tier_symbol.clone(),
+            )))
+            .unwrap_or(0)
+    }
+
+    pub fn record(e: &Env, tier_symbol: &Symbol, token_id: u32, reason: &Symbol) {
+        e.storage().persistent().set(
+            &DataKey::Catalog(CatalogKey::AllocationReason(token_id)),
+            reason,
+        );
+        e.storage().persistent().set(
+            &DataKey::Catalog(CatalogKey::NamedAllocations(tier_symbol.clone())),
+            &(Self::count(e, tier_symbol) + 1),
+        );
+    }
+}
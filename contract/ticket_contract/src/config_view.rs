@@ -0,0 +1,67 @@
+/// Configuration introspection
+/// Integrators bootstrapping a UI would otherwise call half a dozen views
+/// (event info, pricing, pause state, refund settings, roles, schema version).
+/// `get_config` gathers them into one struct for a single call.
+
+use soroban_sdk::{contracttype, Address, String};
+
+use crate::refund_policy::RefundMode;
+use crate::roles::RoleCounts;
+use crate::storage_types::{EventInfo, PayoutSchedule, PricingMode};
+
+/// The public part of `PricingConfig`, without the DEX fallback and
+/// oracle bookkeeping
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PricingSummary {
+    pub pricing_mode: PricingMode,
+    pub oracle_address: Address,
+    pub oracle_pair: String,
+    pub price_floor: i128,
+    pub price_ceiling: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FlagSummary {
+    /// Sales, mints, claims and refunds halted
+    pub paused: bool,
+    pub pricing_frozen: bool,
+    pub metadata_frozen: bool,
+    /// Abandoned-event recovery triggered; withdrawals blocked
+    pub recovery_active: bool,
+    pub cancelled: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeSummary {
+    pub refund_mode: RefundMode,
+    /// Share of refund liability the treasury keeps back, in bps
+    pub reserve_coverage_bps: u32,
+    pub payout_schedule: Option<PayoutSchedule>,
+    /// Tickets one wallet may hold, 0 when uncapped
+    pub wallet_cap: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RolesSummary {
+    pub admin: Address,
+    pub counts: RoleCounts,
+    /// Receives withdrawn revenue
+    pub payout_address: Address,
+    /// Ledgers a queued configuration change waits before applying
+    pub timelock_delay: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContractConfig {
+    pub schema_version: u32,
+    pub event: EventInfo,
+    pub pricing: PricingSummary,
+    pub flags: FlagSummary,
+    pub fees: FeeSummary,
+    pub roles: RolesSummary,
+}
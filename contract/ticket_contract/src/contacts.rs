@@ -0,0 +1,54 @@
+/// Attendee contact opt-ins
+/// Ticket holders may opt in to organizer communications by attaching a salted
+/// hash or opaque reference to their ticket. No personal data is stored on
+/// chain; the organizer matches the reference against their own records.
+/// Opt-ins can be withdrawn at any time and lapse when the ticket is refunded.
+
+use soroban_sdk::{BytesN, Env};
+
+use crate::storage_types::{DataKey, TicketKey};
+
+/// Contact reference storage and the per-event opt-in count
+pub struct ContactManager;
+
+impl ContactManager {
+    pub fn get(e: &Env, token_id: u32) -> Option<BytesN<32>> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Tickets(TicketKey::Contact(token_id)))
+    }
+
+    /// Opted-in tickets across the event
+    pub fn count(e: &Env) -> u32 {
+        e.storage()
+            .instance()
+            .get(&DataKey::Tickets(TicketKey::ContactCount))
+            .unwrap_or(0)
+    }
+
+    fn set_count(e: &Env, count: u32) {
+        e.storage()
+            .instance()
+            .set(&DataKey::Tickets(TicketKey::ContactCount), &count);
+    }
+
+    /// Attach or replace the ticket's contact reference
+    pub fn register(e: &Env, token_id: u32, contact_hash: &BytesN<32>) {
+        if Self::get(e, token_id).is_none() {
+            Self::set_count(e, Self::count(e) + 1);
+        }
+        e.storage()
+            .persistent()
+            .set(&DataKey::Tickets(TicketKey::Contact(token_id)), contact_hash);
+    }
+
+    /// Drop the ticket's contact reference, if any
+    pub fn revoke(e: &Env, token_id: u32) {
+        if Self::get(e, token_id).is_some() {
+            e.storage()
+                .persistent()
+                .remove(&DataKey::Tickets(TicketKey::Contact(token_id)));
+            Self::set_count(e, Self::count(e) - 1);
+        }
+    }
+}
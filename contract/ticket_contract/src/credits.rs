@@ -0,0 +1,67 @@
+/// Price-lock credits
+/// Buyers prepay today for a credit worth up to `value_cap` and pick the tier
+/// later; unredeemed credits are refundable once the deadline passes
+
+use soroban_sdk::{contracttype, Address, Env};
+
+use crate::storage_types::{DataKey, TicketKey};
+
+/// Credit currently on sale
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CreditOffer {
+    /// Amount charged for one credit
+    pub price: i128,
+    /// Highest tier price the credit can be redeemed for
+    pub value_cap: i128,
+    /// Last moment to buy or redeem; refunds open after it
+    pub deadline: u64,
+}
+
+/// Credit held by one address
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Credit {
+    pub payment_token: Address,
+    pub amount_paid: i128,
+    pub value_cap: i128,
+    pub deadline: u64,
+}
+
+/// Credit offer and per-holder credit storage
+pub struct CreditManager;
+
+impl CreditManager {
+    pub fn get_offer(e: &Env) -> Option<CreditOffer> {
+        e.storage()
+            .instance()
+            .get(&DataKey::Tickets(TicketKey::CreditOffer))
+    }
+
+    /// Replace the offer; `None` stops credit sales. Credits already sold keep their terms.
+    pub fn set_offer(e: &Env, offer: Option<CreditOffer>) {
+        let key = DataKey::Tickets(TicketKey::CreditOffer);
+        match offer {
+            Some(offer) => e.storage().instance().set(&key, &offer),
+            None => e.storage().instance().remove(&key),
+        }
+    }
+
+    pub fn get(e: &Env, holder: &Address) -> Option<Credit> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Tickets(TicketKey::Credit(holder.clone())))
+    }
+
+    pub fn set(e: &Env, holder: &Address, credit: &Credit) {
+        e.storage()
+            .persistent()
+            .set(&DataKey::Tickets(TicketKey::Credit(holder.clone())), credit);
+    }
+
+    pub fn remove(e: &Env, holder: &Address) {
+        e.storage()
+            .persistent()
+            .remove(&DataKey::Tickets(TicketKey::Credit(holder.clone())));
+    }
+}
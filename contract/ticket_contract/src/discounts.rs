@@ -0,0 +1,294 @@
+/// Discount resolution
+/// Decides how early-bird, promo-code and loyalty discounts combine when more
+/// than one of them applies to the same purchase
+
+use soroban_sdk::{contracttype, Address, Env, Symbol, Vec};
+
+use crate::storage_types::{DataKey, PricingKey};
+
+/// Source of a discount
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DiscountKind {
+    EarlyBird,
+    Promo,
+    Loyalty,
+    /// Taken off each ticket of a bundle, after the other discounts
+    Bundle,
+    /// Series benefit for holding a linked event's ticket, after the others
+    Series,
+    /// Brings the price down to a returning attendee's grandfathered price
+    Returning,
+    /// Part of the price settled in loyalty points rather than the payment token
+    Points,
+}
+
+/// How applicable discounts are combined
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum StackingMode {
+    /// Add every applicable discount together
+    Stack,
+    /// Apply only the largest applicable discount
+    BestOf,
+    /// Apply only the first applicable discount in this order; kinds not listed never apply
+    Priority(Vec<DiscountKind>),
+}
+
+/// Discount resolution config
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DiscountPolicy {
+    pub mode: StackingMode,
+    /// Upper bound on the combined discount, in bps of the undiscounted price
+    pub max_combined_bps: u32,
+}
+
+/// A discount taken off one ticket
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AppliedDiscount {
+    pub kind: DiscountKind,
+    pub amount: i128,
+}
+
+/// Itemized price of a single ticket
+pub struct PriceQuote {
+    /// Price before discounts (demand curve, oracle multiplier and bounds applied)
+    pub base_price: i128,
+    pub discounts: Vec<AppliedDiscount>,
+    /// Oracle multiplier the base price was computed with
+    pub oracle_multiplier: i128,
+    /// Amount added by the payment token's rounding rule (included in `price`)
+    pub rounding: i128,
+    /// Amount charged
+    pub price: i128,
+}
+
+impl PriceQuote {
+    /// Fees charged on the ticket beyond `price`. Purchases charge none;
+    /// receipts and purchase previews both read them from here.
+    pub fn fees(&self) -> i128 {
+        0
+    }
+}
+
+/// Discount registry and resolution
+pub struct DiscountManager;
+
+impl DiscountManager {
+    /// Current policy; defaults to stacking everything, uncapped
+    pub fn get_policy(e: &Env) -> DiscountPolicy {
+        e.storage()
+            .instance()
+            .get(&DataKey::Pricing(PricingKey::DiscountPolicy))
+            .unwrap_or(DiscountPolicy {
+                mode: StackingMode::Stack,
+                max_combined_bps: 10_000,
+            })
+    }
+
+    pub fn set_policy(e: &Env, policy: &DiscountPolicy) {
+        if policy.max_combined_bps > 10_000 {
+            panic!("Combined discount above 100%");
+        }
+        e.storage()
+            .instance()
+            .set(&DataKey::Pricing(PricingKey::DiscountPolicy), policy);
+    }
+
+    pub fn get_promo_bps(e: &Env, code: &Symbol) -> Option<u32> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Pricing(PricingKey::PromoCode(code.clone())))
+    }
+
+    /// Register a promo code (0 bps removes it)
+    pub fn set_promo_bps(e: &Env, code: &Symbol, discount_bps: u32) {
+        Self::check_bps(discount_bps);
+        let key = DataKey::Pricing(PricingKey::PromoCode(code.clone()));
+        if discount_bps == 0 {
+            e.storage().persistent().remove(&key);
+        } else {
+            e.storage().persistent().set(&key, &discount_bps);
+        }
+    }
+
+    pub fn get_loyalty_bps(e: &Env, holder: &Address) -> u32 {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Pricing(PricingKey::LoyaltyDiscount(
+                holder.clone(),
+            )))
+            .unwrap_or(0)
+    }
+
+    /// Set a holder's loyalty discount (0 bps removes it)
+    pub fn set_loyalty_bps(e: &Env, holder: &Address, discount_bps: u32) {
+        Self::check_bps(discount_bps);
+        let key = DataKey::Pricing(PricingKey::LoyaltyDiscount(holder.clone()));
+        if discount_bps == 0 {
+            e.storage().persistent().remove(&key);
+        } else {
+            e.storage().persistent().set(&key, &discount_bps);
+        }
+    }
+
+    /// Combine `(kind, bps)` candidates under `policy` and price them against `gross`.
+    /// Candidates with 0 bps are treated as not applicable. The combined cap and
+    /// `max_amount` are charged in the order discounts are selected, so a discount
+    /// that would overshoot either is trimmed.
+    pub fn apply(
+        e: &Env,
+        policy: &DiscountPolicy,
+        candidates: &[(DiscountKind, u32)],
+        gross: i128,
+        max_amount: i128,
+    ) -> Vec<AppliedDiscount> {
+        let mut applied = Vec::new(e);
+        let mut budget = (policy.max_combined_bps, max_amount.max(0));
+
+        match &policy.mode {
+            StackingMode::Stack => {
+                for (kind, bps) in candidates.iter() {
+                    Self::take(&mut applied, &mut budget, gross, *kind, *bps);
+                }
+            }
+            StackingMode::BestOf => {
+                let mut best: Option<(DiscountKind, u32)> = None;
+                for (kind, bps) in candidates.iter() {
+                    if *bps > best.map(|(_, best_bps)| best_bps).unwrap_or(0) {
+                        best = Some((*kind, *bps));
+                    }
+                }
+                if let Some((kind, bps)) = best {
+                    Self::take(&mut applied, &mut budget, gross, kind, bps);
+                }
+            }
+            StackingMode::Priority(order) => {
+                for kind in order.iter() {
+                    if let Some((_, bps)) =
+                        candidates.iter().find(|(k, bps)| *k == kind && *bps > 0)
+                    {
+                        Self::take(&mut applied, &mut budget, gross, kind, *bps);
+                        break;
+                    }
+                }
+            }
+        }
+
+        applied
+    }
+
+    /// Sum of discount amounts
+    pub fn total(applied: &Vec<AppliedDiscount>) -> i128 {
+        let mut total = 0;
+        for discount in applied.iter() {
+            total += discount.amount;
+        }
+        total
+    }
+
+    /// Charge one discount against the remaining `(bps, amount)` budget
+    fn take(
+        applied: &mut Vec<AppliedDiscount>,
+        budget: &mut (u32, i128),
+        gross: i128,
+        kind: DiscountKind,
+        bps: u32,
+    ) {
+        let bps = bps.min(budget.0);
+        let amount = crate::mul_bps(gross, bps as i128)
+            .unwrap_or(i128::MAX)
+            .min(budget.1);
+        if amount <= 0 {
+            return;
+        }
+
+        budget.0 -= bps;
+        budget.1 -= amount;
+        applied.push_back(AppliedDiscount { kind, amount });
+    }
+
+    fn check_bps(discount_bps: u32) {
+        if discount_bps > 10_000 {
+            panic!("Discount above 100%");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::vec;
+
+    fn discount(kind: DiscountKind, amount: i128) -> AppliedDiscount {
+        AppliedDiscount { kind, amount }
+    }
+
+    #[test]
+    fn test_apply_modes() {
+        let e = Env::default();
+        let candidates = [
+            (DiscountKind::EarlyBird, 1000),
+            (DiscountKind::Promo, 2000),
+            (DiscountKind::Loyalty, 0),
+        ];
+
+        let mut policy = DiscountPolicy {
+            mode: StackingMode::Stack,
+            max_combined_bps: 10_000,
+        };
+        let applied = DiscountManager::apply(&e, &policy, &candidates, 1000, 1000);
+        assert_eq!(
+            applied,
+            vec![
+                &e,
+                discount(DiscountKind::EarlyBird, 100),
+                discount(DiscountKind::Promo, 200)
+            ]
+        );
+
+        // Cap trims the last discount selected
+        policy.max_combined_bps = 2500;
+        let applied = DiscountManager::apply(&e, &policy, &candidates, 1000, 1000);
+        assert_eq!(
+            applied,
+            vec![
+                &e,
+                discount(DiscountKind::EarlyBird, 100),
+                discount(DiscountKind::Promo, 150)
+            ]
+        );
+
+        // So does the room left above the price floor
+        let applied = DiscountManager::apply(&e, &policy, &candidates, 1000, 120);
+        assert_eq!(
+            applied,
+            vec![
+                &e,
+                discount(DiscountKind::EarlyBird, 100),
+                discount(DiscountKind::Promo, 20)
+            ]
+        );
+
+        policy.mode = StackingMode::BestOf;
+        let applied = DiscountManager::apply(&e, &policy, &candidates, 1000, 1000);
+        assert_eq!(applied, vec![&e, discount(DiscountKind::Promo, 200)]);
+
+        // Loyalty does not apply, so the next kind in line wins
+        policy.mode = StackingMode::Priority(vec![
+            &e,
+            DiscountKind::Loyalty,
+            DiscountKind::EarlyBird,
+            DiscountKind::Promo,
+        ]);
+        let applied = DiscountManager::apply(&e, &policy, &candidates, 1000, 1000);
+        assert_eq!(applied, vec![&e, discount(DiscountKind::EarlyBird, 100)]);
+
+        // Kinds missing from the order never apply
+        policy.mode = StackingMode::Priority(vec![&e, DiscountKind::Loyalty]);
+        let applied = DiscountManager::apply(&e, &policy, &candidates, 1000, 1000);
+        assert_eq!(DiscountManager::total(&applied), 0);
+    }
+}
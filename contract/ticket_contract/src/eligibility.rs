@@ -0,0 +1,117 @@
+/// Snapshot-based eligibility
+/// Restricts a tier to wallets that held an asset (another NFT collection or a
+/// token balance) at a snapshot ledger. The organizer uploads a merkle root over
+/// the snapshot's holdings; each participant proves their holding once and may
+/// then buy or enter the lottery for that tier. Replacing the snapshot voids
+/// proofs made against the old root.
+
+use soroban_sdk::{contracttype, xdr::ToXdr, Address, BytesN, Env, Symbol, Vec};
+
+use crate::archive::ArchiveManager;
+use crate::storage_types::{DataKey, TicketKey};
+
+/// Holder snapshot a tier is gated on
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HolderSnapshot {
+    /// NFT or token contract whose holders were snapshotted
+    pub asset: Address,
+    pub snapshot_ledger: u32,
+    /// Smallest balance at `snapshot_ledger` that qualifies
+    pub min_balance: i128,
+    /// Merkle root (built like `ArchiveManager::merkle_root`) over holding leaves
+    pub root: BytesN<32>,
+    pub holder_count: u32,
+}
+
+/// One leaf of the snapshot tree, hashed as sha256(xdr(holding))
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SnapshotHolding {
+    pub holder: Address,
+    pub balance: i128,
+}
+
+/// Snapshot storage and membership checks
+pub struct EligibilityManager;
+
+impl EligibilityManager {
+    pub fn get(e: &Env, tier_symbol: &Symbol) -> Option<HolderSnapshot> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Tickets(TicketKey::HolderSnapshot(
+                tier_symbol.clone(),
+            )))
+    }
+
+    /// `None` opens the tier to everyone again
+    pub fn set(e: &Env, tier_symbol: &Symbol, snapshot: Option<HolderSnapshot>) {
+        let key = DataKey::Tickets(TicketKey::HolderSnapshot(tier_symbol.clone()));
+        match snapshot {
+            Some(snapshot) => {
+                if snapshot.holder_count == 0 {
+                    panic!("Empty holder snapshot");
+                }
+                e.storage().persistent().set(&key, &snapshot)
+            }
+            None => e.storage().persistent().remove(&key),
+        }
+    }
+
+    pub fn holding_leaf(e: &Env, holding: &SnapshotHolding) -> BytesN<32> {
+        e.crypto().sha256(&holding.clone().to_xdr(e)).to_bytes()
+    }
+
+    /// Check `holding` against the tier's snapshot and remember the holder as eligible
+    pub fn prove(
+        e: &Env,
+        tier_symbol: &Symbol,
+        holding: &SnapshotHolding,
+        index: u32,
+        proof: &Vec<BytesN<32>>,
+    ) {
+        let snapshot =
+            Self::get(e, tier_symbol).unwrap_or_else(|| panic!("Tier has no holder snapshot"));
+        if holding.balance < snapshot.min_balance {
+            panic!("Balance below snapshot threshold");
+        }
+        if !ArchiveManager::verify_inclusion(
+            e,
+            Self::holding_leaf(e, holding),
+            index,
+            snapshot.holder_count,
+            proof,
+            &snapshot.root,
+        ) {
+            panic!("Invalid snapshot proof");
+        }
+
+        e.storage().persistent().set(
+            &DataKey::Tickets(TicketKey::SnapshotProven(
+                tier_symbol.clone(),
+                holding.holder.clone(),
+            )),
+            &snapshot.root,
+        );
+    }
+
+    /// Ungated tiers admit everyone; gated tiers only holders proven against the current root
+    pub fn is_eligible(e: &Env, tier_symbol: &Symbol, participant: &Address) -> bool {
+        let Some(snapshot) = Self::get(e, tier_symbol) else {
+            return true;
+        };
+        e.storage()
+            .persistent()
+            .get::<_, BytesN<32>>(&DataKey::Tickets(TicketKey::SnapshotProven(
+                tier_symbol.clone(),
+                participant.clone(),
+            )))
+            == Some(snapshot.root)
+    }
+
+    pub fn require_eligible(e: &Env, tier_symbol: &Symbol, participant: &Address) {
+        if !Self::is_eligible(e, tier_symbol, participant) {
+            panic!("Not eligible at snapshot");
+        }
+    }
+}
@@ -1,8 +1,15 @@
 /// Entropy Management Module
 /// Manages randomness sources and provides secure entropy generation
-/// Combines Stellar ledger hash with optional oracle data for robust randomness
+/// Combines Stellar ledger hash with optional oracle data for robust randomness.
+/// A tier's draw can also require a beacon: successive ledger hashes folded
+/// into an accumulator by anyone over several distinct ledgers, so no single
+/// ledger's producer controls the draw's entropy.
+/// A draw's entropy is fixed by its target ledger alone: that ledger's own
+/// entropy when it is drawn or captured there, else a deterministic fallback.
 
-use soroban_sdk::{contracttype, Bytes, Env};
+use soroban_sdk::{contracttype, Bytes, Env, Symbol};
+
+use crate::storage_types::{DataKey, LotteryKey};
 
 /// Entropy source configuration
 #[contracttype]
@@ -30,6 +37,17 @@ pub struct EntropyState {
     pub entropy_ready: bool,
 }
 
+/// Entropy beacon accumulated for a tier's next draw
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BeaconState {
+    /// Hash chain over every contributed ledger hash
+    pub accumulator: Bytes,
+    /// Distinct ledgers folded in so far
+    pub contributions: u32,
+    pub last_ledger: u32,
+}
+
 /// Entropy generator using Stellar native capabilities
 pub struct EntropyManager;
 
@@ -125,6 +143,119 @@ impl EntropyManager {
         soroban_sdk::crypto::sha256(&combined)
     }
 
+    /// Record the current ledger's entropy for the tier's draw targeting it.
+    /// Idempotent within the target ledger.
+    pub fn capture_target_entropy(e: &Env, tier_symbol: &Symbol, target: u32) -> Bytes {
+        if e.ledger().sequence() != target {
+            panic!("Not the target ledger");
+        }
+        let key = DataKey::Lottery(LotteryKey::TargetEntropy(tier_symbol.clone()));
+        if let Some(entropy) = e.storage().persistent().get(&key) {
+            return entropy;
+        }
+        let entropy = Self::generate_multi_source_entropy(e, target);
+        e.storage().persistent().set(&key, &entropy);
+        entropy
+    }
+
+    /// Take the entropy of the tier's draw targeting `target`: the target
+    /// ledger's own entropy when drawn in it or captured there, otherwise a
+    /// fallback derived from `target` alone. The ledger the draw runs in never
+    /// changes the result.
+    pub fn take_target_entropy(e: &Env, tier_symbol: &Symbol, target: u32) -> Bytes {
+        let key = DataKey::Lottery(LotteryKey::TargetEntropy(tier_symbol.clone()));
+        if let Some(entropy) = e.storage().persistent().get(&key) {
+            e.storage().persistent().remove(&key);
+            return entropy;
+        }
+        if e.ledger().sequence() == target {
+            return Self::generate_multi_source_entropy(e, target);
+        }
+
+        let mut combined = soroban_sdk::Vec::new(e);
+        combined.extend_from_array(b"lapsed").unwrap();
+        combined.extend_from_array(&target.to_le_bytes()).unwrap();
+        soroban_sdk::crypto::sha256(&combined)
+    }
+
+    pub fn get_beacon(e: &Env, tier_symbol: &Symbol) -> BeaconState {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Lottery(LotteryKey::Beacon(tier_symbol.clone())))
+            .unwrap_or(BeaconState {
+                accumulator: Bytes::new(e),
+                contributions: 0,
+                last_ledger: 0,
+            })
+    }
+
+    /// Fold the current ledger's hash into the tier's beacon, once per ledger
+    pub fn contribute_beacon(e: &Env, tier_symbol: &Symbol) -> BeaconState {
+        let mut state = Self::get_beacon(e, tier_symbol);
+        let sequence = e.ledger().sequence();
+        if state.contributions > 0 && state.last_ledger == sequence {
+            panic!("Beacon already contributed this ledger");
+        }
+
+        let mut combined = soroban_sdk::Vec::new(e);
+        combined
+            .extend_from_array(&state.accumulator.to_array::<32>().unwrap_or([0u8; 32]))
+            .unwrap();
+        combined
+            .extend_from_array(&e.ledger().hash().to_array::<32>().unwrap_or([0u8; 32]))
+            .unwrap();
+        combined.extend_from_array(&sequence.to_le_bytes()).unwrap();
+
+        state.accumulator = soroban_sdk::crypto::sha256(&combined);
+        state.contributions += 1;
+        state.last_ledger = sequence;
+        e.storage()
+            .persistent()
+            .set(&DataKey::Lottery(LotteryKey::Beacon(tier_symbol.clone())), &state);
+        state
+    }
+
+    /// Contributions a tier's draw requires before randomness can be generated
+    pub fn beacon_minimum(e: &Env, tier_symbol: &Symbol) -> u32 {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Lottery(LotteryKey::BeaconMinimum(tier_symbol.clone())))
+            .unwrap_or(0)
+    }
+
+    /// 0 turns accumulation off for the tier
+    pub fn set_beacon_minimum(e: &Env, tier_symbol: &Symbol, minimum: u32) {
+        let key = DataKey::Lottery(LotteryKey::BeaconMinimum(tier_symbol.clone()));
+        if minimum == 0 {
+            e.storage().persistent().remove(&key);
+        } else {
+            e.storage().persistent().set(&key, &minimum);
+        }
+    }
+
+    /// Whether the tier's beacon has the contributions its next draw requires
+    pub fn beacon_ready(e: &Env, tier_symbol: &Symbol) -> bool {
+        Self::get_beacon(e, tier_symbol).contributions >= Self::beacon_minimum(e, tier_symbol)
+    }
+
+    /// Take the tier's beacon for a draw, checking it has enough contributions,
+    /// and start a fresh one for the next draw. `None` when the tier does not
+    /// use a beacon.
+    pub fn consume_beacon(e: &Env, tier_symbol: &Symbol) -> Option<Bytes> {
+        let minimum = Self::beacon_minimum(e, tier_symbol);
+        if minimum == 0 {
+            return None;
+        }
+        let state = Self::get_beacon(e, tier_symbol);
+        if state.contributions < minimum {
+            panic!("Not enough beacon contributions");
+        }
+        e.storage()
+            .persistent()
+            .remove(&DataKey::Lottery(LotteryKey::Beacon(tier_symbol.clone())));
+        Some(state.accumulator)
+    }
+
     /// Validate entropy has sufficient entropy bits (non-trivial randomness)
     pub fn validate_entropy(entropy: &Bytes) -> bool {
         // Entropy should be 32 bytes (256 bits) from SHA256
@@ -0,0 +1,63 @@
+/// Lottery entry caps
+/// Allocation loads every entry of a round in one transaction, each from its
+/// own ledger entry, so a round flooded with spam registrations could grow
+/// past what one execution can read and brick the draw. The highest cap is
+/// derived from the per-transaction ledger entry limit and what allocation
+/// touches per entry; each tier's rounds are capped at a configurable number
+/// of entries up to it, and registrations beyond the cap are turned away.
+
+use soroban_sdk::{Env, Symbol};
+
+use crate::storage_types::{DataKey, LotteryKey, RegistrationError};
+
+/// Ledger entries one transaction may read
+pub const TX_LEDGER_ENTRY_LIMIT: u32 = 100;
+/// Ledger entries allocation touches regardless of the entry count: contract
+/// instance and code, allocation and VRF state, the randomness batch, entry
+/// policy, reveal bonus, claim window, results, proof chain and admin log
+pub const ALLOCATION_FIXED_ENTRIES: u32 = 20;
+/// Ledger entries allocation touches per lottery entry: the entry, the
+/// entrant's reveal marker, and the winner index and claim deadline it gets
+/// if drawn
+pub const ALLOCATION_ENTRIES_PER_ENTRY: u32 = 4;
+/// Highest cap the organizer may configure: the most entries one
+/// `execute_lottery_allocation` can process
+pub const MAX_LOTTERY_ENTRY_CAP: u32 =
+    (TX_LEDGER_ENTRY_LIMIT - ALLOCATION_FIXED_ENTRIES) / ALLOCATION_ENTRIES_PER_ENTRY;
+/// Entry cap of a tier's rounds when none is configured
+pub const DEFAULT_MAX_LOTTERY_ENTRIES: u32 = MAX_LOTTERY_ENTRY_CAP;
+
+/// Per-tier entry caps
+pub struct EntryCapManager;
+
+impl EntryCapManager {
+    pub fn get(e: &Env, tier_symbol: &Symbol) -> u32 {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Lottery(LotteryKey::EntryCap(tier_symbol.clone())))
+            .unwrap_or(DEFAULT_MAX_LOTTERY_ENTRIES)
+    }
+
+    /// `current_entries` is the entry count of the tier's current round,
+    /// which the new cap may not undercut
+    pub fn set(e: &Env, tier_symbol: &Symbol, cap: u32, current_entries: u32) {
+        if cap == 0 || cap > MAX_LOTTERY_ENTRY_CAP {
+            panic!("Invalid entry cap");
+        }
+        if cap < current_entries {
+            panic!("Cap below current entries");
+        }
+        e.storage().persistent().set(
+            &DataKey::Lottery(LotteryKey::EntryCap(tier_symbol.clone())),
+            &cap,
+        );
+    }
+
+    /// Whether a round holding `entry_count` entries can take one more
+    pub fn check(e: &Env, tier_symbol: &Symbol, entry_count: u32) -> Result<(), RegistrationError> {
+        if entry_count >= Self::get(e, tier_symbol) {
+            return Err(RegistrationError::EntryCapReached);
+        }
+        Ok(())
+    }
+}
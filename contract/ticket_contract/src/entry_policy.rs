@@ -0,0 +1,98 @@
+/// Duplicate lottery entries
+/// Anti-sniping only throttles how fast one address can enter. The organizer
+/// picks per tier whether an address may enter once, may enter repeatedly to
+/// improve its odds but win at most once, or may enter and win without limit.
+
+use soroban_sdk::{contracttype, Address, Env, Symbol, Vec};
+
+use crate::allocation::AllocationResult;
+use crate::lottery_stats::LotteryStatsManager;
+use crate::rounds::RoundManager;
+use crate::storage_types::{DataKey, LotteryKey, RegistrationError};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum EntryPolicy {
+    /// A second entry from the same address is rejected
+    OneEntryPerAddress,
+    /// Every entry counts towards the draw, but an address wins at most once
+    WeightedByEntries,
+    /// Entries and wins are not limited per address
+    Unlimited,
+}
+
+/// Entry policy storage and enforcement
+pub struct EntryPolicyManager;
+
+impl EntryPolicyManager {
+    pub fn get(e: &Env, tier_symbol: &Symbol) -> EntryPolicy {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Lottery(LotteryKey::EntryPolicy(tier_symbol.clone())))
+            .unwrap_or(EntryPolicy::Unlimited)
+    }
+
+    /// Only before the current round's first entry, so entries already in never
+    /// break the policy
+    pub fn set(e: &Env, tier_symbol: &Symbol, policy: &EntryPolicy) {
+        let entry_count: u32 = e
+            .storage()
+            .persistent()
+            .get(&DataKey::Lottery(LotteryKey::EntryCount(
+                tier_symbol.clone(),
+                RoundManager::current(e, tier_symbol),
+            )))
+            .unwrap_or(0);
+        if entry_count > 0 {
+            panic!("Lottery already has entries");
+        }
+        e.storage().persistent().set(
+            &DataKey::Lottery(LotteryKey::EntryPolicy(tier_symbol.clone())),
+            policy,
+        );
+    }
+
+    /// Reject an entry the tier's policy does not allow
+    pub fn check_entry(
+        e: &Env,
+        tier_symbol: &Symbol,
+        participant: &Address,
+    ) -> Result<(), RegistrationError> {
+        if Self::get(e, tier_symbol) == EntryPolicy::OneEntryPerAddress
+            && LotteryStatsManager::has_entered(e, tier_symbol, participant)
+        {
+            return Err(RegistrationError::AlreadyEntered);
+        }
+        Ok(())
+    }
+
+    /// Whether winners must be distinct addresses
+    pub fn dedupes_winners(policy: &EntryPolicy) -> bool {
+        !matches!(policy, EntryPolicy::Unlimited)
+    }
+
+    /// Keep the first win of each address, up to `quantity` winners. Results
+    /// are renumbered so allocation indexes stay contiguous.
+    pub fn dedupe_winners(
+        e: &Env,
+        results: &Vec<AllocationResult>,
+        quantity: u32,
+    ) -> Vec<AllocationResult> {
+        let mut winners: Vec<Address> = Vec::new(e);
+        let mut deduped = Vec::new(e);
+        for result in results.iter() {
+            if deduped.len() >= quantity {
+                break;
+            }
+            if winners.contains(&result.winner) {
+                continue;
+            }
+            winners.push_back(result.winner.clone());
+            deduped.push_back(AllocationResult {
+                allocation_index: deduped.len(),
+                ..result
+            });
+        }
+        deduped
+    }
+}
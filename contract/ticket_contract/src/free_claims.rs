@@ -0,0 +1,100 @@
+/// Free ticket claims
+/// A tier with a base price of 0 is free: `purchase` turns it away and
+/// attendees take tickets with `claim_free_ticket`, which moves no tokens.
+/// Each address may claim up to the tier's cap, one ticket unless configured.
+/// When the organizer publishes a hash of the tier's registration questions,
+/// each claim must carry a hash of the attendee's answers, kept against the
+/// ticket so the answers can be checked off-chain.
+
+use soroban_sdk::{contracttype, Address, BytesN, Env, Symbol};
+
+use crate::storage_types::{CatalogKey, DataKey};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FreeClaimConfig {
+    /// Tickets one address may claim from the tier
+    pub max_per_address: u32,
+    /// sha256 of the tier's registration questions, when claims must answer them
+    pub questions_hash: Option<BytesN<32>>,
+}
+
+/// Claim caps, per-address counters and registration answers
+pub struct FreeClaimManager;
+
+impl FreeClaimManager {
+    /// One ticket per address and no questions unless configured
+    pub fn get(e: &Env, tier_symbol: &Symbol) -> FreeClaimConfig {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Catalog(CatalogKey::FreeClaimConfig(
+                tier_symbol.clone(),
+            )))
+            .unwrap_or(FreeClaimConfig {
+                max_per_address: 1,
+                questions_hash: None,
+            })
+    }
+
+    /// `None` restores the default
+    pub fn set(e: &Env, tier_symbol: &Symbol, config: Option<FreeClaimConfig>) {
+        let key = DataKey::Catalog(CatalogKey::FreeClaimConfig(tier_symbol.clone()));
+        match config {
+            Some(config) => {
+                if config.max_per_address == 0 {
+                    panic!("Invalid claim cap");
+                }
+                e.storage().persistent().set(&key, &config)
+            }
+            None => e.storage().persistent().remove(&key),
+        }
+    }
+
+    pub fn claimed(e: &Env, tier_symbol: &Symbol, claimant: &Address) -> u32 {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Catalog(CatalogKey::FreeClaims(
+                tier_symbol.clone(),
+                claimant.clone(),
+            )))
+            .unwrap_or(0)
+    }
+
+    /// Count one more claim by `claimant`, checking their cap and that answers
+    /// come with the claim when the tier asks registration questions
+    pub fn record(
+        e: &Env,
+        tier_symbol: &Symbol,
+        claimant: &Address,
+        answers_hash: &Option<BytesN<32>>,
+    ) {
+        let config = Self::get(e, tier_symbol);
+        if config.questions_hash.is_some() && answers_hash.is_none() {
+            panic!("Registration answers required");
+        }
+        let claimed = Self::claimed(e, tier_symbol, claimant);
+        if claimed >= config.max_per_address {
+            panic!("Free claim limit reached");
+        }
+        e.storage().persistent().set(
+            &DataKey::Catalog(CatalogKey::FreeClaims(
+                tier_symbol.clone(),
+                claimant.clone(),
+            )),
+            &(claimed + 1),
+        );
+    }
+
+    pub fn answers(e: &Env, token_id: u32) -> Option<BytesN<32>> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Catalog(CatalogKey::RegistrationAnswers(token_id)))
+    }
+
+    pub fn set_answers(e: &Env, token_id: u32, answers_hash: &BytesN<32>) {
+        e.storage().persistent().set(
+            &DataKey::Catalog(CatalogKey::RegistrationAnswers(token_id)),
+            answers_hash,
+        );
+    }
+}
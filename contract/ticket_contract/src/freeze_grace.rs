@@ -0,0 +1,83 @@
+/// Refund grace after an emergency freeze
+/// Pricing is frozen when something has gone wrong, so tickets bought shortly
+/// before the freeze may have sold at a wrong price. With a grace policy set,
+/// each freeze records the policy in force; tickets bought within `lookback`
+/// seconds before the freeze stay refundable for `window` seconds after it,
+/// even past the normal refund cutoff. Only the latest freeze is remembered.
+
+use soroban_sdk::{contracttype, Env};
+
+use crate::storage_types::{DataKey, Ticket, TreasuryKey};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FreezeGrace {
+    /// How long before a freeze a purchase must have been made to qualify
+    pub lookback: u64,
+    /// How long after the freeze qualifying tickets stay refundable
+    pub window: u64,
+}
+
+/// A freeze and the grace policy in force when it happened
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FreezeRecord {
+    pub frozen_at: u64,
+    pub grace: FreezeGrace,
+}
+
+/// Grace policy storage and eligibility
+pub struct FreezeGraceManager;
+
+impl FreezeGraceManager {
+    pub fn get_policy(e: &Env) -> Option<FreezeGrace> {
+        e.storage()
+            .instance()
+            .get(&DataKey::Treasury(TreasuryKey::FreezeGrace))
+    }
+
+    /// `None` turns grace off for future freezes
+    pub fn set_policy(e: &Env, grace: Option<FreezeGrace>) {
+        let key = DataKey::Treasury(TreasuryKey::FreezeGrace);
+        match grace {
+            Some(grace) => e.storage().instance().set(&key, &grace),
+            None => e.storage().instance().remove(&key),
+        }
+    }
+
+    pub fn last_freeze(e: &Env) -> Option<FreezeRecord> {
+        e.storage()
+            .instance()
+            .get(&DataKey::Treasury(TreasuryKey::LastFreeze))
+    }
+
+    /// Record a freeze starting now under the current policy, if any
+    pub fn on_freeze(e: &Env) {
+        if let Some(grace) = Self::get_policy(e) {
+            e.storage().instance().set(
+                &DataKey::Treasury(TreasuryKey::LastFreeze),
+                &FreezeRecord {
+                    frozen_at: e.ledger().timestamp(),
+                    grace,
+                },
+            );
+        }
+    }
+
+    /// Whether the latest freeze's grace still lets tickets be refunded
+    pub fn window_open(e: &Env) -> bool {
+        Self::last_freeze(e).is_some_and(|record| {
+            e.ledger().timestamp() <= record.frozen_at.saturating_add(record.grace.window)
+        })
+    }
+
+    /// Whether `ticket` may be refunded now under the latest freeze's grace
+    pub fn covers(e: &Env, ticket: &Ticket) -> bool {
+        let Some(record) = Self::last_freeze(e) else {
+            return false;
+        };
+        ticket.purchase_time <= record.frozen_at
+            && ticket.purchase_time >= record.frozen_at.saturating_sub(record.grace.lookback)
+            && Self::window_open(e)
+    }
+}
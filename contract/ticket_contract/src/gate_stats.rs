@@ -0,0 +1,120 @@
+/// Gate and scanner throughput
+/// Scanners check tickets in at a named gate. Every scan is counted per gate,
+/// in fixed time buckets, and per scanner, so operations can watch how fast
+/// each gate is moving and rebalance staff from on-chain data alone.
+
+use soroban_sdk::{contracttype, Address, Env, Symbol, Vec};
+
+use crate::storage_types::{DataKey, VenueKey};
+
+/// Width of a gate throughput bucket
+pub const GATE_BUCKET_SECONDS: u64 = 5 * 60;
+/// Buckets returned by `get_gate_stats`, covering the last hour
+pub const RECENT_GATE_BUCKETS: u64 = 12;
+
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct GateTotals {
+    pub check_ins: u32,
+    pub first_scan: u64,
+    pub last_scan: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GateStats {
+    pub gate_id: Symbol,
+    pub check_ins: u32,
+    pub first_scan: u64,
+    pub last_scan: u64,
+    pub bucket_seconds: u64,
+    /// Check-ins per bucket over the last `RECENT_GATE_BUCKETS`, oldest
+    /// first; the last entry is the bucket in progress
+    pub recent: Vec<u32>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScannerStats {
+    pub check_ins: u32,
+    pub last_scan: u64,
+    pub last_gate: Symbol,
+}
+
+/// Per-gate and per-scanner check-in counters
+pub struct GateStatsManager;
+
+impl GateStatsManager {
+    /// Count one check-in by `scanner` at `gate_id`
+    pub fn record(e: &Env, gate_id: &Symbol, scanner: &Address) {
+        let now = e.ledger().timestamp();
+
+        let totals_key = DataKey::Venue(VenueKey::GateTotals(gate_id.clone()));
+        let mut totals: GateTotals = e
+            .storage()
+            .persistent()
+            .get(&totals_key)
+            .unwrap_or_default();
+        if totals.check_ins == 0 {
+            totals.first_scan = now;
+        }
+        totals.check_ins += 1;
+        totals.last_scan = now;
+        e.storage().persistent().set(&totals_key, &totals);
+
+        let bucket_key = DataKey::Venue(VenueKey::GateBucket(
+            gate_id.clone(),
+            now / GATE_BUCKET_SECONDS,
+        ));
+        let in_bucket: u32 = e.storage().persistent().get(&bucket_key).unwrap_or(0);
+        e.storage().persistent().set(&bucket_key, &(in_bucket + 1));
+
+        let scanner_key = DataKey::Venue(VenueKey::ScannerStats(scanner.clone()));
+        let check_ins = Self::scanner_stats(e, scanner).map_or(0, |stats| stats.check_ins);
+        e.storage().persistent().set(
+            &scanner_key,
+            &ScannerStats {
+                check_ins: check_ins + 1,
+                last_scan: now,
+                last_gate: gate_id.clone(),
+            },
+        );
+    }
+
+    pub fn gate_stats(e: &Env, gate_id: &Symbol) -> GateStats {
+        let totals: GateTotals = e
+            .storage()
+            .persistent()
+            .get(&DataKey::Venue(VenueKey::GateTotals(gate_id.clone())))
+            .unwrap_or_default();
+
+        let current = e.ledger().timestamp() / GATE_BUCKET_SECONDS;
+        let mut recent = Vec::new(e);
+        for bucket in current.saturating_sub(RECENT_GATE_BUCKETS - 1)..=current {
+            recent.push_back(
+                e.storage()
+                    .persistent()
+                    .get(&DataKey::Venue(VenueKey::GateBucket(
+                        gate_id.clone(),
+                        bucket,
+                    )))
+                    .unwrap_or(0),
+            );
+        }
+
+        GateStats {
+            gate_id: gate_id.clone(),
+            check_ins: totals.check_ins,
+            first_scan: totals.first_scan,
+            last_scan: totals.last_scan,
+            bucket_seconds: GATE_BUCKET_SECONDS,
+            recent,
+        }
+    }
+
+    pub fn scanner_stats(e: &Env, scanner: &Address) -> Option<ScannerStats> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Venue(VenueKey::ScannerStats(scanner.clone())))
+    }
+}
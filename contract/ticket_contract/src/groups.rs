@@ -0,0 +1,41 @@
+/// Group tickets
+/// A group purchase mints one ticket to the buyer that holds extra seats for
+/// companions. Before the event the holder splits seats off into individual
+/// soulbound tickets minted straight to companion wallets, so everyone presents
+/// their own token at the gate. Splitting is free: each split ticket carries an
+/// equal share of what the group paid, taken off the holder's ticket, so the
+/// total refundable amount never changes.
+
+use soroban_sdk::Env;
+
+use crate::storage_types::{DataKey, TicketKey};
+
+/// Most tickets one group purchase may cover, the holder included
+pub const MAX_GROUP_SIZE: u32 = 10;
+
+/// Unsplit companion seats per group ticket
+pub struct GroupManager;
+
+impl GroupManager {
+    pub fn seats(e: &Env, token_id: u32) -> u32 {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Tickets(TicketKey::GroupSeats(token_id)))
+            .unwrap_or(0)
+    }
+
+    pub fn set_seats(e: &Env, token_id: u32, seats: u32) {
+        let key = DataKey::Tickets(TicketKey::GroupSeats(token_id));
+        if seats == 0 {
+            e.storage().persistent().remove(&key);
+        } else {
+            e.storage().persistent().set(&key, &seats);
+        }
+    }
+
+    /// Share of `price_paid` one of `seats + 1` remaining attendees carries.
+    /// Any remainder stays with the holder.
+    pub fn seat_share(price_paid: i128, seats: u32) -> i128 {
+        price_paid / (seats as i128 + 1)
+    }
+}
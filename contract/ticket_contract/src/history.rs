@@ -0,0 +1,66 @@
+/// Per-owner purchase history
+/// Tickets are soulbound, so the address a ticket is minted to owns it for its
+/// whole life; indexing token ids by that address at mint time is enough to list
+/// every ticket an owner ever held, including refunded ones. A ticket bought
+/// through capped resale or moved in a custodial migration is indexed for its
+/// new owner as well.
+
+use soroban_sdk::{contracttype, Address, Env, Symbol, Vec};
+
+use crate::storage_types::{DataKey, Ticket, TicketKey, TicketStatus};
+
+/// One ticket in an owner's history
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PurchaseRecord {
+    pub token_id: u32,
+    pub tier_symbol: Symbol,
+    pub price_paid: i128,
+    pub purchase_time: u64,
+    /// Current status, read from the ticket so refunds and check-ins are reflected
+    pub status: TicketStatus,
+}
+
+/// Owner-indexed ticket list
+pub struct HistoryManager;
+
+impl HistoryManager {
+    pub fn record_mint(e: &Env, owner: &Address, token_id: u32) {
+        let key = DataKey::Tickets(TicketKey::OwnerTickets(owner.clone()));
+        let mut ids: Vec<u32> = e
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| Vec::new(e));
+        ids.push_back(token_id);
+        e.storage().persistent().set(&key, &ids);
+    }
+
+    /// Up to `limit` records starting at position `start`, oldest first
+    pub fn get_history(e: &Env, owner: &Address, start: u32, limit: u32) -> Vec<PurchaseRecord> {
+        let ids: Vec<u32> = e
+            .storage()
+            .persistent()
+            .get(&DataKey::Tickets(TicketKey::OwnerTickets(owner.clone())))
+            .unwrap_or_else(|| Vec::new(e));
+
+        let mut records = Vec::new(e);
+        let end = start.saturating_add(limit).min(ids.len());
+        for i in start..end {
+            let token_id = ids.get(i).unwrap();
+            let ticket: Ticket = e
+                .storage()
+                .persistent()
+                .get(&DataKey::Tickets(TicketKey::Ticket(token_id)))
+                .unwrap();
+            records.push_back(PurchaseRecord {
+                token_id,
+                tier_symbol: ticket.tier_symbol,
+                price_paid: ticket.price_paid,
+                purchase_time: ticket.purchase_time,
+                status: ticket.status,
+            });
+        }
+        records
+    }
+}
@@ -0,0 +1,80 @@
+/// Post-purchase hooks
+/// Lets integrators run their own contract after every sale (companion badges,
+/// rewards, notifications). The hook contract must expose:
+///
+///   on_purchase(buyer: Address, token_id: u32, tier: Symbol, price: i128)
+///
+/// In `Required` mode a failing hook reverts the purchase. In `BestEffort` mode
+/// the call is isolated with `try_on_purchase`: the hook's own state changes are
+/// rolled back on failure and the sale goes through. A hook that exhausts the
+/// transaction budget still fails the whole transaction in either mode.
+
+use soroban_sdk::{contractclient, contracttype, Address, Env, Symbol};
+
+use crate::storage_types::{DataKey, TicketKey};
+
+/// Interface a purchase hook contract implements.
+/// `contractclient` generates `PurchaseHookClient`.
+#[contractclient(name = "PurchaseHookClient")]
+pub trait PurchaseHook {
+    fn on_purchase(env: Env, buyer: Address, token_id: u32, tier: Symbol, price: i128);
+}
+
+/// How hook failures affect the sale
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HookMode {
+    /// Hook failure reverts the purchase
+    Required,
+    /// Hook failure is reported with a `hook_failed` event and ignored
+    BestEffort,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HookConfig {
+    pub address: Address,
+    pub mode: HookMode,
+}
+
+/// Hook configuration and dispatch
+pub struct HookManager;
+
+impl HookManager {
+    pub fn get(e: &Env) -> Option<HookConfig> {
+        e.storage()
+            .instance()
+            .get(&DataKey::Tickets(TicketKey::PurchaseHook))
+    }
+
+    pub fn set(e: &Env, hook: Option<HookConfig>) {
+        let key = DataKey::Tickets(TicketKey::PurchaseHook);
+        match hook {
+            Some(hook) => e.storage().instance().set(&key, &hook),
+            None => e.storage().instance().remove(&key),
+        }
+    }
+
+    /// Call the configured hook, if any, for one minted ticket
+    pub fn on_purchase(e: &Env, buyer: &Address, token_id: u32, tier_symbol: &Symbol, price: i128) {
+        let Some(hook) = Self::get(e) else {
+            return;
+        };
+
+        let client = PurchaseHookClient::new(e, &hook.address);
+        match hook.mode {
+            HookMode::Required => client.on_purchase(buyer, &token_id, tier_symbol, &price),
+            HookMode::BestEffort => {
+                let ok = matches!(
+                    client.try_on_purchase(buyer, &token_id, tier_symbol, &price),
+                    Ok(Ok(()))
+                );
+                if !ok {
+                    #[allow(deprecated)]
+                    e.events()
+                        .publish((Symbol::new(e, "hook_failed"), hook.address), token_id);
+                }
+            }
+        }
+    }
+}
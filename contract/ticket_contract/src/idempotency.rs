@@ -0,0 +1,32 @@
+/// Purchase idempotency keys
+/// Backends retry submissions that time out without knowing whether the first
+/// attempt landed. A purchase made with an idempotency key records the token
+/// ids it minted under the buyer and key; repeating the key returns those ids
+/// instead of charging and minting again. Keys are scoped to the buyer, so two
+/// buyers may use the same key independently.
+
+use soroban_sdk::{Address, BytesN, Env, Vec};
+
+use crate::storage_types::{DataKey, TicketKey};
+
+/// Completed purchases by buyer and idempotency key
+pub struct IdempotencyManager;
+
+impl IdempotencyManager {
+    /// Token ids minted by the buyer's earlier purchase under `key`, if any
+    pub fn get(e: &Env, buyer: &Address, key: &BytesN<32>) -> Option<Vec<u32>> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Tickets(TicketKey::IdempotencyKey(
+                buyer.clone(),
+                key.clone(),
+            )))
+    }
+
+    pub fn record(e: &Env, buyer: &Address, key: &BytesN<32>, token_ids: &Vec<u32>) {
+        e.storage().persistent().set(
+            &DataKey::Tickets(TicketKey::IdempotencyKey(buyer.clone(), key.clone())),
+            token_ids,
+        );
+    }
+}
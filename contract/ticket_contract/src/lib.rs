@@ -3,69 +3,272 @@
 #[cfg(test)]
 mod test;
 
-use soroban_sdk::{contract, contractimpl, token, Address, Bytes, Env, String, Symbol, Vec};
+#[cfg(any(test, feature = "testutils"))]
+pub mod testutils;
+
+#[cfg(feature = "bench")]
+pub mod bench;
+
+use soroban_sdk::{
+    contract, contractimpl, panic_with_error, token, vec, Address, Bytes, BytesN, Env, IntoVal,
+    Map, String, Symbol, Vec,
+};
 use stellar_access::ownable::{self as ownable, Ownable};
 use stellar_tokens::non_fungible::{Base, NonFungibleToken};
 
 mod storage_types;
 use storage_types::{
-    AllocationConfig, AllocationStrategyType, AntiSnipingConfig, DataKey, EventInfo, PricingConfig,
-    PricingStrategy, Ticket, Tier, VRFState,
+    AllocationConfig, AllocationStrategyType, AntiSnipingConfig, CatalogKey, ConfigChange, DataKey,
+    DistributionMode, EventMetadata, GovernanceKey, Invitation, LotteryKey, OracleCache,
+    PendingChange, PendingPayment, PricingConfig, PricingKey, RefundAgent, ReleaseSchedule,
+    TicketKey, TreasuryKey, VRFState, SCHEMA_VERSION,
+};
+// Contract types that off-chain clients need to name
+pub use storage_types::{
+    EntryError, EntryWindow, EventInfo, InitConfig, InitError, InitRoles, PayoutSchedule,
+    PricingError, PricingMode, PricingStrategy, PurchaseChannel, PurchaseError, PurchasePreview,
+    RefundError, RegistrationError, Ticket, TicketStatus, Tier, TierError, TierInput,
 };
 
 mod oracle;
 use oracle::{fetch_price_with_fallback, oracle_price_to_multiplier, DEFAULT_STALENESS_SECONDS};
 
 mod vrf;
-use vrf::{RandomnessOutput, VRFEngine, VRFProof};
+use vrf::{RandomnessOutput, VRFDomain, VRFEngine, VRFProof};
 
 mod commitment;
-use commitment::{Commitment, CommitmentScheme, Reveal};
+use commitment::{Commitment, CommitmentManager, CommitmentScheme, Reveal};
 
 mod allocation;
-use allocation::{
-    AllocationEngine, AllocationResult, AntiSnipingConfig as AllocAntiSnipingConfig, LotteryEntry,
-};
+pub use allocation::AllocationResult;
+use allocation::{AllocationEngine, AntiSnipingConfig as AllocAntiSnipingConfig, LotteryEntry};
 
 mod entropy;
-use entropy::{EntropyManager, EntropySource, EntropyState};
+use entropy::{BeaconState, EntropyManager, EntropySource, EntropyState};
+
+mod timelock;
+use timelock::TimelockManager;
+
+mod metadata;
+use metadata::{MetadataBuilder, TokenMetadata};
+
+mod treasury;
+use treasury::TreasuryManager;
+
+mod channels;
+use channels::{ChannelAllocation, ChannelManager, SalesChannel};
+
+mod discounts;
+use discounts::{AppliedDiscount, DiscountKind, DiscountManager, DiscountPolicy, PriceQuote};
+
+mod receipts;
+use receipts::{Receipt, ReceiptManager};
+
+mod archive;
+pub use archive::AllocationProof;
+use archive::ArchiveManager;
+
+mod credits;
+use credits::{Credit, CreditManager, CreditOffer};
+
+mod history;
+use history::{HistoryManager, PurchaseRecord};
+
+mod hooks;
+use hooks::{HookConfig, HookManager};
+
+mod plans;
+use plans::{ChargePlan, PlanManager};
+
+mod rounding;
+use rounding::{RoundingManager, RoundingRule};
+
+mod limits;
+use limits::{SaleLimitManager, SaleLimits};
+
+mod subscriptions;
+use subscriptions::SubscriptionManager;
+
+mod eligibility;
+use eligibility::{EligibilityManager, HolderSnapshot, SnapshotHolding};
+
+mod announcements;
+use announcements::{Announcement, AnnouncementManager};
+
+mod contacts;
+use contacts::ContactManager;
+
+mod access;
+use access::AccessManager;
+
+mod refund_limits;
+use refund_limits::{RefundLimitManager, RefundLimits, RefundStats};
+
+mod refund_policy;
+use refund_policy::{RefundMode, RefundPolicyManager};
+
+mod bundles;
+use bundles::{Bundle, BundleManager};
+
+mod stats;
+use stats::{SalesStats, StatsManager};
+
+mod receipt_tokens;
+use receipt_tokens::{ReceiptToken, ReceiptTokenManager};
+
+mod batch_mint;
+use batch_mint::{BatchMintManager, MintJob};
+
+mod validation;
+use validation::TierValidator;
+
+mod freeze_grace;
+use freeze_grace::{FreezeGrace, FreezeGraceManager, FreezeRecord};
+
+mod groups;
+use groups::{GroupManager, MAX_GROUP_SIZE};
+
+mod lottery_stats;
+use lottery_stats::{LotteryStats, LotteryStatsManager};
+
+mod entry_policy;
+use entry_policy::{EntryPolicy, EntryPolicyManager};
+
+mod admin_log;
+use admin_log::{AdminAction, AdminLogManager};
+
+mod roles;
+use roles::{Role, RoleManager};
+
+mod config_view;
+use config_view::{ContractConfig, FeeSummary, FlagSummary, PricingSummary, RolesSummary};
+
+mod accounting;
+use accounting::{AccountingManager, ReferenceFeed, RevenueReport};
+
+mod idempotency;
+use idempotency::IdempotencyManager;
+
+mod seating;
+use seating::SeatManager;
+
+mod recovery;
+use recovery::RecoveryManager;
+
+mod payout_schedule;
+use payout_schedule::PayoutScheduleManager;
+
+mod cancellation;
+use cancellation::CancellationManager;
+
+mod bond;
+use bond::{BondManager, OrganizerBond};
+
+mod resale;
+use resale::{Listing, ResaleManager, ResalePolicy};
+
+mod migration;
+use migration::{MigrationExemption, MigrationManager, MAX_MIGRATION_BATCH};
+
+mod series;
+use series::{SeriesBenefit, SeriesManager};
+
+mod returning;
+use returning::{ReturningManager, ReturningOffer};
+
+mod points;
+use points::{PointsManager, PointsPolicy};
+
+mod concierge;
+use concierge::{ConciergeManager, MAX_NAMED_ALLOCATIONS};
+
+mod reveals;
+use reveals::RevealManager;
+
+mod rounds;
+use rounds::RoundManager;
+
+mod backfill;
+use backfill::BackfillManager;
+
+mod winner_index;
+use winner_index::{WinnerIndexManager, WinnerStatus};
+
+mod free_claims;
+use free_claims::{FreeClaimConfig, FreeClaimManager};
+
+mod tier_archive;
+use tier_archive::{ArchivedTier, TierArchiveManager};
+
+mod locales;
+use locales::{LocaleManager, LocalizedText};
+
+mod gate_stats;
+use gate_stats::{GateStats, GateStatsManager, ScannerStats};
+
+mod will_call;
+use will_call::{PickupHold, WillCallManager};
+
+mod offers;
+use offers::{OfferManager, TicketOffer};
+
+mod entry_caps;
+use entry_caps::EntryCapManager;
+
+mod proof_schemes;
+use proof_schemes::{ProofScheme, ProofSchemeManager};
 
 // Dynamic pricing constants
 const PRICE_INCREASE_BPS: i128 = 500; // 5% increase per tier threshold
-const EARLY_BIRD_DISCOUNT_BPS: i128 = 1000; // 10% discount max
+const EARLY_BIRD_DISCOUNT_BPS: u32 = 1000; // 10% discount max
 const ORACLE_PRECISION: i128 = 10000; // Assuming oracle returns multiplier in bps (e.g. 10000 = 1x)
 
+// Longest attendee name accepted by `verify_identity`
+const MAX_IDENTITY_NAME_LEN: usize = 128;
+
+// Most guest entries a tier may attach to each ticket
+const MAX_GUEST_SLOTS: u32 = 10;
+
+// Custom attribute limits
+const MAX_ATTRIBUTES: u32 = 16;
+const MAX_ATTRIBUTE_VALUE_LEN: u32 = 128;
+
+// Most units `simulate_purchase` prices one by one
+const MAX_SIMULATED_QUANTITY: u32 = 100;
+
+/// `amount * bps / 10000` without forming the full product, so any result that
+/// fits in an i128 is returned. `None` only when the result itself overflows.
+pub(crate) fn mul_bps(amount: i128, bps: i128) -> Option<i128> {
+    let whole = (amount / 10000).checked_mul(bps)?;
+    let part = (amount % 10000).checked_mul(bps)? / 10000;
+    whole.checked_add(part)
+}
+
 #[contract]
 pub struct SoulboundTicketContract;
 
 #[contractimpl]
 impl SoulboundTicketContract {
-    pub fn initialize(
-        e: &Env,
-        admin: Address,
-        name: String,
-        symbol: String,
-        uri: String,
-        start_time: u64,
-        refund_cutoff_time: u64,
-    ) {
+    /// One-time setup. The whole config is validated before anything is stored;
+    /// calling again fails with `AlreadyInitialized` and leaves state untouched.
+    pub fn initialize(e: &Env, config: InitConfig) -> Result<(), InitError> {
         if e.storage().instance().has(&DataKey::Admin) {
-            panic!("already initialized");
+            return Err(InitError::AlreadyInitialized);
         }
+        if config.start_time <= e.ledger().timestamp() {
+            return Err(InitError::StartTimeInPast);
+        }
+        if config.refund_cutoff_time > config.start_time {
+            return Err(InitError::RefundCutoffAfterStart);
+        }
+        if config.name.len() as usize > metadata::MAX_NAME_LEN {
+            return Err(InitError::NameTooLong);
+        }
+        let admin = config.admin;
 
-        // Init Event Info
-        let event_info = EventInfo {
-            start_time,
-            refund_cutoff_time,
-        };
-        e.storage().instance().set(&DataKey::EventInfo, &event_info);
-        e.storage().instance().set(&DataKey::Admin, &admin);
-
-        // Init Token Counter
-        e.storage().instance().set(&DataKey::TokenIdCounter, &0u32);
-
-        // Init default PricingConfig (placeholder addresses, standard bounds)
-        let default_config = PricingConfig {
+        // Default PricingConfig (placeholder addresses, standard bounds)
+        let pricing = config.pricing.unwrap_or_else(|| PricingConfig {
+            pricing_mode: PricingMode::Fixed, // No oracle until one is configured
             oracle_address: admin.clone(), // Update via set_pricing_config after deployment
             dex_pool_address: admin.clone(), // Update via set_pricing_config after deployment
             price_floor: 0,
@@ -76,26 +279,307 @@ impl SoulboundTicketContract {
             oracle_pair: String::from_str(e, "XLM/USD"),
             oracle_reference_price: oracle::DIA_ORACLE_DECIMALS, // $1.00 baseline (1.0 * 10^8)
             max_oracle_age_seconds: DEFAULT_STALENESS_SECONDS,
+        });
+        if !Self::is_valid_pricing_config(e, &admin, &pricing) {
+            return Err(InitError::InvalidPricingConfig);
+        }
+        if let Some(schedule) = &config.payout_schedule {
+            if !PayoutScheduleManager::is_valid(schedule, config.start_time) {
+                return Err(InitError::InvalidPayoutSchedule);
+            }
+            PayoutScheduleManager::set(e, schedule);
+        }
+
+        // Init Event Info
+        let event_info = EventInfo {
+            start_time: config.start_time,
+            refund_cutoff_time: config.refund_cutoff_time,
+            metadata: config.metadata.unwrap_or_else(|| EventMetadata {
+                venue_name: String::from_str(e, ""),
+                geo_hint: String::from_str(e, ""),
+                description_hash: BytesN::from_array(e, &[0u8; 32]),
+                cover_image_uri: String::from_str(e, ""),
+                category: Symbol::new(e, "general"),
+            }),
         };
+        e.storage().instance().set(&DataKey::EventInfo, &event_info);
+        e.storage().instance().set(&DataKey::Admin, &admin);
+        e.storage()
+            .instance()
+            .set(&DataKey::SchemaVersion, &SCHEMA_VERSION);
         e.storage()
             .instance()
-            .set(&DataKey::PricingConfig, &default_config);
+            .set(&DataKey::Pricing(PricingKey::Config), &pricing);
+        if let Some(public_key) = &config.vrf_public_key {
+            ProofSchemeManager::publish_key(e, public_key);
+        }
+        RecoveryManager::record_activity(e);
+
+        if let Some(roles) = config.roles {
+            for member in roles.council.iter() {
+                RoleManager::set(e, Role::Council, &member, true);
+            }
+            for relayer in roles.relayers.iter() {
+                RoleManager::set(e, Role::Relayer, &relayer, true);
+            }
+            for processor in roles.payment_processors.iter() {
+                RoleManager::set(e, Role::PaymentProcessor, &processor, true);
+            }
+        }
 
         // Init Token Metadata via OpenZeppelin Base
-        Base::set_metadata(e, uri, name, symbol);
+        Base::set_metadata(e, config.uri, config.name, config.symbol);
         ownable::set_owner(e, &admin);
+        Ok(())
     }
 
-    // Set Pricing Config
+    // Set Pricing Config (queued behind the timelock when one is configured)
     pub fn set_pricing_config(e: &Env, config: PricingConfig) {
         let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
         admin.require_auth();
-        e.storage().instance().set(&DataKey::PricingConfig, &config);
+        AdminLogManager::record(e, &admin, "set_pricing_config", (config.clone(),));
+        if !Self::is_valid_pricing_config(e, &admin, &config) {
+            panic!("Dynamic pricing requires an oracle");
+        }
+        TimelockManager::submit(e, ConfigChange::Pricing(config));
+    }
+
+    /// Dynamic pricing needs an oracle that is neither the admin placeholder nor
+    /// this contract
+    fn is_valid_pricing_config(e: &Env, admin: &Address, config: &PricingConfig) -> bool {
+        config.pricing_mode == PricingMode::Fixed
+            || (config.oracle_address != *admin
+                && config.oracle_address != e.current_contract_address())
+    }
+
+    /// Storage layout version, used by migrations to detect old layouts.
+    /// Contracts deployed before versioning report 1.
+    pub fn get_schema_version(e: &Env) -> u32 {
+        e.storage()
+            .instance()
+            .get(&DataKey::SchemaVersion)
+            .unwrap_or(1)
+    }
+
+    /// Update venue, description and media details shown by discovery frontends
+    pub fn update_event_metadata(e: &Env, metadata: EventMetadata) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "update_event_metadata", (metadata.clone(),));
+        Self::require_metadata_mutable(e);
+
+        let mut event_info: EventInfo = e.storage().instance().get(&DataKey::EventInfo).unwrap();
+        event_info.metadata = metadata.clone();
+        e.storage().instance().set(&DataKey::EventInfo, &event_info);
+
+        #[allow(deprecated)]
+        e.events()
+            .publish((Symbol::new(e, "event_metadata_updated"),), metadata);
+    }
+
+    pub fn get_event_info(e: &Env) -> EventInfo {
+        e.storage().instance().get(&DataKey::EventInfo).unwrap()
+    }
+
+    /// Event info, public pricing config, pause and freeze flags, refund and
+    /// reserve settings, role summary and schema version in one call
+    pub fn get_config(e: &Env) -> ContractConfig {
+        let pricing: PricingConfig = e
+            .storage()
+            .instance()
+            .get(&DataKey::Pricing(PricingKey::Config))
+            .unwrap();
+
+        ContractConfig {
+            schema_version: Self::get_schema_version(e),
+            event: Self::get_event_info(e),
+            pricing: PricingSummary {
+                pricing_mode: pricing.pricing_mode,
+                oracle_address: pricing.oracle_address,
+                oracle_pair: pricing.oracle_pair,
+                price_floor: pricing.price_floor,
+                price_ceiling: pricing.price_ceiling,
+            },
+            flags: FlagSummary {
+                paused: Self::is_paused(e),
+                pricing_frozen: pricing.is_frozen,
+                metadata_frozen: Self::is_metadata_frozen(e),
+                recovery_active: RecoveryManager::is_active(e),
+                cancelled: CancellationManager::is_cancelled(e),
+            },
+            fees: FeeSummary {
+                refund_mode: RefundPolicyManager::get_mode(e),
+                reserve_coverage_bps: TreasuryManager::get_coverage_bps(e),
+                payout_schedule: PayoutScheduleManager::get(e),
+                wallet_cap: e
+                    .storage()
+                    .instance()
+                    .get(&DataKey::Tickets(TicketKey::WalletCap))
+                    .unwrap_or(0),
+            },
+            roles: RolesSummary {
+                admin: e.storage().instance().get(&DataKey::Admin).unwrap(),
+                counts: RoleManager::counts(e),
+                payout_address: TreasuryManager::payout_address(e),
+                timelock_delay: TimelockManager::get_delay(e),
+            },
+        }
+    }
+
+    /// Post a notice to ticket holders; `title_hash` is the sha256 of its title
+    /// and `uri` points at the full text. Returns the announcement id.
+    pub fn post_announcement(e: &Env, title_hash: BytesN<32>, uri: String) -> u32 {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "post_announcement", (title_hash.clone(), uri.clone()));
+        AnnouncementManager::post(e, title_hash, uri)
+    }
+
+    /// Up to `limit` logged privileged actions starting at id `start`, oldest
+    /// first. Only the most recent `MAX_ADMIN_LOG` actions are retained.
+    pub fn get_admin_log(e: &Env, start: u32, limit: u32) -> Vec<AdminAction> {
+        AdminLogManager::page(e, start, limit)
+    }
+
+    /// The most recent announcements, oldest first
+    pub fn get_announcements(e: &Env) -> Vec<Announcement> {
+        AnnouncementManager::list(e)
+    }
+
+    /// ==================== TIMELOCK FUNCTIONS ====================
+
+    /// Set the delay (in ledgers) applied to sensitive configuration changes.
+    /// Enabling the timelock is immediate; changing an active delay is itself queued.
+    pub fn set_timelock_delay(e: &Env, delay_ledgers: u32) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "set_timelock_delay", (delay_ledgers,));
+        TimelockManager::submit(e, ConfigChange::TimelockDelay(delay_ledgers));
+    }
+
+    pub fn get_timelock_delay(e: &Env) -> u32 {
+        TimelockManager::get_delay(e)
+    }
+
+    /// Update the refund cutoff (queued behind the timelock when one is configured)
+    pub fn set_refund_cutoff(e: &Env, refund_cutoff_time: u64) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "set_refund_cutoff", (refund_cutoff_time,));
+        TimelockManager::submit(e, ConfigChange::RefundCutoff(refund_cutoff_time));
+    }
+
+    /// Changes that are queued and the ledger after which each takes effect
+    pub fn get_pending_changes(e: &Env) -> Vec<PendingChange> {
+        TimelockManager::get_pending(e)
+    }
+
+    /// Apply every queued change whose delay has elapsed. Callable by anyone.
+    pub fn apply_pending(e: &Env) -> u32 {
+        TimelockManager::apply_ready(e)
+    }
+
+    /// Withdraw a queued change before it takes effect
+    pub fn cancel_pending_change(e: &Env, change_id: u32) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "cancel_pending_change", (change_id,));
+        if !TimelockManager::cancel(e, change_id) {
+            panic!("Pending change not found");
+        }
+    }
+
+    /// ==================== EMERGENCY COUNCIL ====================
+
+    /// Add or remove an incident-response council member. Members can halt
+    /// pricing and sales instantly; only the owner can lift either halt.
+    pub fn set_council_member(e: &Env, member: Address, allowed: bool) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "set_council_member", (member.clone(), allowed));
+        RoleManager::set(e, Role::Council, &member, allowed);
+    }
+
+    pub fn is_council_member(e: &Env, member: Address) -> bool {
+        RoleManager::has(e, Role::Council, &member)
+    }
+
+    fn require_council(e: &Env, member: &Address) {
+        member.require_auth();
+        if !Self::is_council_member(e, member.clone()) {
+            panic!("Not a council member");
+        }
+    }
+
+    /// Freeze pricing at once; unfreezing goes through `emergency_freeze`
+    pub fn council_freeze(e: &Env, member: Address) {
+        Self::require_council(e, &member);
+        AdminLogManager::record(e, &member, "council_freeze", ());
+        let mut config: PricingConfig =
+            e.storage().instance().get(&DataKey::Pricing(PricingKey::Config)).unwrap();
+        if !config.is_frozen {
+            FreezeGraceManager::on_freeze(e);
+        }
+        config.is_frozen = true;
+        e.storage()
+            .instance()
+            .set(&DataKey::Pricing(PricingKey::Config), &config);
+
+        #[allow(deprecated)]
+        e.events()
+            .publish((Symbol::new(e, "council_freeze"), member), ());
+    }
+
+    /// Veto a queued configuration change (e.g. a pricing update) before it applies
+    pub fn council_veto(e: &Env, member: Address, change_id: u32) {
+        Self::require_council(e, &member);
+        AdminLogManager::record(e, &member, "council_veto", (change_id,));
+        if !TimelockManager::cancel(e, change_id) {
+            panic!("Pending change not found");
+        }
+
+        #[allow(deprecated)]
+        e.events()
+            .publish((Symbol::new(e, "council_veto"), member), change_id);
+    }
+
+    /// Circuit breaker: halt sales, mints, claims and refunds
+    pub fn pause_all(e: &Env, member: Address) {
+        Self::require_council(e, &member);
+        AdminLogManager::record(e, &member, "pause_all", ());
+        e.storage()
+            .instance()
+            .set(&DataKey::Governance(GovernanceKey::Paused), &true);
+
+        #[allow(deprecated)]
+        e.events().publish((Symbol::new(e, "paused"), member), ());
+    }
+
+    pub fn unpause_all(e: &Env) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "unpause_all", ());
+        e.storage()
+            .instance()
+            .remove(&DataKey::Governance(GovernanceKey::Paused));
+    }
+
+    pub fn is_paused(e: &Env) -> bool {
+        e.storage()
+            .instance()
+            .get(&DataKey::Governance(GovernanceKey::Paused))
+            .unwrap_or(false)
+    }
+
+    fn require_not_paused(e: &Env) {
+        if Self::is_paused(e) {
+            panic!("Contract is paused");
+        }
     }
 
     /// ==================== VRF & LOTTERY FUNCTIONS ====================
 
-    /// Initialize VRF lottery system for a tier
+    /// Initialize VRF lottery system for a tier's current round
     /// Sets up commitment scheme and allocation strategy
     pub fn initialize_lottery(
         e: &Env,
@@ -108,11 +592,29 @@ impl SoulboundTicketContract {
     ) {
         let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
         admin.require_auth();
+        AdminLogManager::record(
+            e,
+            &admin,
+            "initialize_lottery",
+            (
+                tier_symbol.clone(),
+                strategy_type.clone(),
+                total_allocations,
+                finalization_ledger,
+                reveal_start_ledger,
+                reveal_end_ledger,
+            ),
+        );
 
-        // Validate tier exists
-        let key = DataKey::Tier(tier_symbol.clone());
-        if !e.storage().persistent().has(&key) {
-            panic!("Tier not found");
+        // Validate tier exists and reserves supply for lottery winners
+        let key = DataKey::Tickets(TicketKey::Tier(tier_symbol.clone()));
+        let tier: Tier = e
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| panic!("Tier not found"));
+        if tier.distribution == DistributionMode::DirectSale {
+            panic!("Tier distribution mode does not allow a lottery");
         }
 
         // Validate ledger timeline
@@ -134,9 +636,11 @@ impl SoulboundTicketContract {
             reveal_end_ledger,
         };
 
-        e.storage()
-            .persistent()
-            .set(&DataKey::AllocationState(tier_symbol), &config);
+        let round = RoundManager::current(e, &tier_symbol);
+        e.storage().persistent().set(
+            &DataKey::Lottery(LotteryKey::AllocationState(tier_symbol.clone(), round)),
+            &config,
+        );
 
         // Initialize anti-sniping config
         let anti_sniping = AllocAntiSnipingConfig {
@@ -148,28 +652,56 @@ impl SoulboundTicketContract {
 
         e.storage()
             .persistent()
-            .set(&DataKey::AntiSnipingConfig(tier_symbol), &anti_sniping);
+            .set(&DataKey::Lottery(LotteryKey::AntiSnipingConfig(tier_symbol)), &anti_sniping);
     }
 
-    /// Register as participant in lottery
-    pub fn register_lottery_entry(e: &Env, tier_symbol: Symbol, commitment_hash: Option<Bytes>) {
-        let participant = Address::random(e); // In real usage, this would be the caller
+    /// Register as participant in lottery. Fails with `EntryCapReached` once
+    /// the current round holds the tier's entry cap.
+    pub fn register_lottery_entry(
+        e: &Env,
+        participant: Address,
+        tier_symbol: Symbol,
+        commitment_hash: Option<Bytes>,
+    ) -> Result<(), RegistrationError> {
         participant.require_auth();
+        Self::require_not_paused(e);
+
+        let tier: Tier = e
+            .storage()
+            .persistent()
+            .get(&DataKey::Tickets(TicketKey::Tier(tier_symbol.clone())))
+            .unwrap_or_else(|| panic!("Tier not found"));
+        if tier.frozen {
+            panic!("Tier is frozen");
+        }
+        EligibilityManager::require_eligible(e, &tier_symbol, &participant);
+        EntryPolicyManager::check_entry(e, &tier_symbol, &participant)?;
+        let round = RoundManager::current(e, &tier_symbol);
+        if e.storage()
+            .persistent()
+            .get::<_, AllocationConfig>(&DataKey::Lottery(LotteryKey::AllocationState(
+                tier_symbol.clone(),
+                round,
+            )))
+            .is_some_and(|state| state.allocation_complete)
+        {
+            panic!("Allocation already complete");
+        }
+        let count_key = DataKey::Lottery(LotteryKey::EntryCount(tier_symbol.clone(), round));
+        let count: u32 = e.storage().persistent().get(&count_key).unwrap_or(0);
+        EntryCapManager::check(e, &tier_symbol, count)?;
 
         // Check anti-sniping
-        let anti_sniping_key = DataKey::AntiSnipingConfig(tier_symbol.clone());
+        let anti_sniping_key = DataKey::Lottery(LotteryKey::AntiSnipingConfig(tier_symbol.clone()));
         if let Some(anti_sniping) = e.storage().persistent().get::<_, AllocAntiSnipingConfig>(&anti_sniping_key) {
             let mut recent_entries: Vec<LotteryEntry> = Vec::new(e);
-            let count_key = DataKey::LotteryEntryCount(tier_symbol.clone());
+            let count_key = DataKey::Lottery(LotteryKey::EntryCount(tier_symbol.clone(), round));
             let entry_count: u32 = e.storage().persistent().get(&count_key).unwrap_or(0);
 
             for i in 0..entry_count.min(10) {
-                if let Some(entry) = e
-                    .storage()
-                    .persistent()
-                    .get::<_, LotteryEntry>(&DataKey::LotteryEntry(tier_symbol.clone(), i))
-                {
-                    recent_entries.push_back(entry).unwrap();
+                let entry_key = DataKey::Lottery(LotteryKey::Entry(tier_symbol.clone(), round, i));
+                if let Some(entry) = e.storage().persistent().get::<_, LotteryEntry>(&entry_key) {
+                    recent_entries.push_back(entry);
                 }
             }
 
@@ -187,74 +719,500 @@ impl SoulboundTicketContract {
         };
 
         // Store entry
-        let count_key = DataKey::LotteryEntryCount(tier_symbol.clone());
-        let mut count: u32 = e.storage().persistent().get(&count_key).unwrap_or(0);
-        e.storage()
-            .persistent()
-            .set(&DataKey::LotteryEntry(tier_symbol.clone(), count), &entry);
+        e.storage().persistent().set(
+            &DataKey::Lottery(LotteryKey::Entry(tier_symbol.clone(), round, count)),
+            &entry,
+        );
         e.storage()
             .persistent()
-            .set(&count_key, count.saturating_add(1));
+            .set(&count_key, &count.saturating_add(1));
+        LotteryStatsManager::record_participant(e, &tier_symbol, &participant);
+        Ok(())
     }
 
-    /// Generate batch randomness for lottery finalization
-    pub fn generate_lottery_randomness(e: &Env, tier_symbol: Symbol, batch_size: u32) -> Vec<RandomnessOutput> {
+    /// Set how repeat entries from one address are treated. Only allowed
+    /// before the tier's current round has any entries.
+    pub fn set_lottery_entry_policy(e: &Env, tier_symbol: Symbol, policy: EntryPolicy) {
         let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
         admin.require_auth();
+        AdminLogManager::record(
+            e,
+            &admin,
+            "set_lottery_entry_policy",
+            (tier_symbol.clone(), policy.clone()),
+        );
+        EntryPolicyManager::set(e, &tier_symbol, &policy);
+    }
 
-        // Verify allocation state exists
-        let state_key = DataKey::AllocationState(tier_symbol.clone());
-        let state: AllocationConfig = e
+    pub fn get_lottery_entry_policy(e: &Env, tier_symbol: Symbol) -> EntryPolicy {
+        EntryPolicyManager::get(e, &tier_symbol)
+    }
+
+    /// Cap the entries each round of the tier's lottery accepts, at no more
+    /// than `MAX_LOTTERY_ENTRY_CAP` and no fewer than the current round holds
+    pub fn set_lottery_entry_cap(e: &Env, tier_symbol: Symbol, cap: u32) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(
+            e,
+            &admin,
+            "set_lottery_entry_cap",
+            (tier_symbol.clone(), cap),
+        );
+
+        let round = RoundManager::current(e, &tier_symbol);
+        let entry_count: u32 = e
             .storage()
             .persistent()
-            .get(&state_key)
-            .unwrap_or_else(|| panic!("Allocation not initialized"));
+            .get(&DataKey::Lottery(LotteryKey::EntryCount(
+                tier_symbol.clone(),
+                round,
+            )))
+            .unwrap_or(0);
+        EntryCapManager::set(e, &tier_symbol, cap, entry_count);
+    }
 
-        // Check if we're at finalization ledger
-        if e.ledger().sequence() < state.finalization_ledger {
-            panic!("Cannot finalize before finalization ledger");
+    pub fn get_lottery_entry_cap(e: &Env, tier_symbol: Symbol) -> u32 {
+        EntryCapManager::get(e, &tier_symbol)
+    }
+
+    /// Commit to a seed for the tier's draw as sha256(seed || nonce), before
+    /// the reveal window opens. One commitment per address and tier.
+    pub fn commit_seed(
+        e: &Env,
+        committer: Address,
+        tier_symbol: Symbol,
+        hash: Bytes,
+    ) -> Commitment {
+        committer.require_auth();
+        let state = Self::allocation_state(e, &tier_symbol);
+        if e.ledger().sequence() >= state.reveal_start_ledger {
+            panic!("Commit phase closed");
         }
+        CommitmentManager::create(e, &tier_symbol, &committer, hash)
+    }
 
-        // Generate entropy
-        let entropy = EntropyManager::generate_multi_source_entropy(e, 0);
+    /// Open a commitment inside the tier's reveal window. Commitments left
+    /// unrevealed when the window closes expire.
+    pub fn reveal_seed(e: &Env, committer: Address, tier_symbol: Symbol, seed: Bytes, nonce: u32) {
+        committer.require_auth();
+        let state = Self::allocation_state(e, &tier_symbol);
+        let sequence = e.ledger().sequence();
+        if sequence < state.reveal_start_ledger {
+            panic!("Reveal window not open");
+        }
+        if sequence > state.reveal_end_ledger {
+            panic!("Commitment expired");
+        }
+        let reveal = Reveal {
+            seed: seed.clone(),
+            nonce,
+            revealed_at: e.ledger().timestamp(),
+        };
+        CommitmentManager::reveal(e, &tier_symbol, &committer, &reveal);
+        RevealManager::record(e, &tier_symbol, &committer);
 
-        // Generate batch randomness
-        let randomness_outputs = VRFEngine::generate_batch_randomness(e, batch_size, entropy);
+        #[allow(deprecated)]
+        e.events().publish(
+            (Symbol::new(e, "seed_revealed"), committer),
+            (tier_symbol, seed, nonce),
+        );
+    }
 
-        // Store randomness hash for verification
-        let randomness_hash = VRFEngine::hash_randomness_batch(e, &randomness_outputs);
-        let vrf_state = VRFState {
-            randomness_generated: true,
-            randomness_hash,
-            batch_nonce: 0,
-            finalization_ledger: state.finalization_ledger,
-        };
+    pub fn get_commitment(e: &Env, tier_symbol: Symbol, committer: Address) -> Option<Commitment> {
+        CommitmentManager::get(e, &tier_symbol, &committer)
+    }
 
-        e.storage()
-            .persistent()
-            .set(&DataKey::VRFState, &vrf_state);
+    /// Whether `committer`'s commitment was left unrevealed past the reveal window
+    pub fn is_commitment_expired(e: &Env, tier_symbol: Symbol, committer: Address) -> bool {
+        let state = Self::allocation_state(e, &tier_symbol);
+        CommitmentManager::get(e, &tier_symbol, &committer)
+            .is_some_and(|c| !c.revealed && e.ledger().sequence() > state.reveal_end_ledger)
+    }
 
-        randomness_outputs
+    /// Delete up to `max_count` of the tier's commitments once its reveal
+    /// window has closed. Open to anyone. Returns the number deleted.
+    pub fn prune_commitments(e: &Env, tier_symbol: Symbol, max_count: u32) -> u32 {
+        let state = Self::allocation_state(e, &tier_symbol);
+        if e.ledger().sequence() <= state.reveal_end_ledger {
+            panic!("Reveal window still open");
+        }
+        CommitmentManager::prune(e, &tier_symbol, max_count)
     }
 
-    /// Execute lottery allocation based on registered entries and randomness
-    pub fn execute_lottery_allocation(
-        e: &Env,
-        tier_symbol: Symbol,
-        randomness_values: Vec<u128>,
-    ) {
+    /// Give entries from addresses that reveal for the tier's draw `bonus`
+    /// extra weight in the lottery. Fixed once the reveal window opens, so it
+    /// can't be tuned to who actually revealed.
+    pub fn set_reveal_bonus(e: &Env, tier_symbol: Symbol, bonus: u32) {
         let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
         admin.require_auth();
+        AdminLogManager::record(e, &admin, "set_reveal_bonus", (tier_symbol.clone(), bonus));
+        let state = Self::allocation_state(e, &tier_symbol);
+        if e.ledger().sequence() >= state.reveal_start_ledger {
+            panic!("Reveal window already open");
+        }
+        RevealManager::set_bonus(e, &tier_symbol, bonus);
+    }
 
-        let state_key = DataKey::AllocationState(tier_symbol.clone());
-        let mut state: AllocationConfig = e
+    pub fn get_reveal_bonus(e: &Env, tier_symbol: Symbol) -> u32 {
+        RevealManager::bonus(e, &tier_symbol)
+    }
+
+    pub fn has_revealed(e: &Env, tier_symbol: Symbol, participant: Address) -> bool {
+        RevealManager::has_revealed(e, &tier_symbol, &participant)
+    }
+
+    /// Seeds revealed for the tier's draw
+    pub fn get_revealed_count(e: &Env, tier_symbol: Symbol) -> u32 {
+        RevealManager::revealed_count(e, &tier_symbol)
+    }
+
+    /// Reveals `participant` has made over every tier
+    pub fn get_reveal_score(e: &Env, participant: Address) -> u32 {
+        RevealManager::score(e, &participant)
+    }
+
+    /// Allocation config of the tier's current round
+    fn allocation_state(e: &Env, tier_symbol: &Symbol) -> AllocationConfig {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Lottery(LotteryKey::AllocationState(
+                tier_symbol.clone(),
+                RoundManager::current(e, tier_symbol),
+            )))
+            .unwrap_or_else(|| panic!("Allocation not initialized"))
+    }
+
+    /// Entry count, remaining allocations, unique participants, current odds
+    /// per entry and window status for a tier's lottery
+    pub fn get_lottery_stats(e: &Env, tier_symbol: Symbol) -> LotteryStats {
+        LotteryStatsManager::stats(e, &tier_symbol)
+    }
+
+    /// Commit to the ledger a tier's draw (or backfill draw) takes its
+    /// randomness from: `randomization_delay_ledgers` (at least one) after
+    /// this one. A request cannot be withdrawn or renewed; a draw generated
+    /// after the target ledger passed uncaptured uses a deterministic fallback
+    /// (see `capture_finalization_entropy`). Returns the target ledger.
+    pub fn request_lottery_finalization(e: &Env, tier_symbol: Symbol) -> u32 {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "request_lottery_finalization", tier_symbol.clone());
+
+        // A finished draw can still be backfilled once winners expire
+        let state = Self::allocation_state(e, &tier_symbol);
+        if state.allocation_complete && state.allocated_count >= state.total_allocations {
+            panic!("Allocation already complete");
+        }
+        if e.ledger().sequence() < state.finalization_ledger {
+            panic!("Cannot finalize before finalization ledger");
+        }
+        if !state.allocation_complete && Self::main_draw_generated(e, &tier_symbol) {
+            panic!("Randomness already generated");
+        }
+
+        let target_key = DataKey::Lottery(LotteryKey::FinalizationTarget(tier_symbol.clone()));
+        if e.storage().persistent().has(&target_key) {
+            panic!("Finalization already requested");
+        }
+        if !EntropyManager::beacon_ready(e, &tier_symbol) {
+            panic!("Not enough beacon contributions");
+        }
+        let anti_sniping_key = DataKey::Lottery(LotteryKey::AntiSnipingConfig(tier_symbol.clone()));
+        let delay = e
+            .storage()
+            .persistent()
+            .get::<_, AllocAntiSnipingConfig>(&anti_sniping_key)
+            .map(|config| config.randomization_delay_ledgers)
+            .unwrap_or(0)
+            .max(1);
+        let target = e.ledger().sequence() + delay;
+        e.storage().persistent().set(&target_key, &target);
+
+        #[allow(deprecated)]
+        e.events().publish((Symbol::new(e, "finalization_requested"), tier_symbol), target);
+
+        target
+    }
+
+    /// Require `minimum` distinct-ledger beacon contributions before the tier's
+    /// draws can be requested (0 turns the beacon off)
+    pub fn set_beacon_minimum(e: &Env, tier_symbol: Symbol, minimum: u32) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "set_beacon_minimum", (tier_symbol.clone(), minimum));
+
+        EntropyManager::set_beacon_minimum(e, &tier_symbol, minimum);
+    }
+
+    /// Fold this ledger's hash into the tier's entropy beacon. Open to anyone,
+    /// once per ledger, until the tier's next draw is requested.
+    pub fn contribute_beacon(e: &Env, tier_symbol: Symbol) -> BeaconState {
+        let state = Self::allocation_state(e, &tier_symbol);
+        if state.allocation_complete {
+            panic!("Allocation already complete");
+        }
+        // The beacon is settled once a draw is requested
+        if Self::get_finalization_target(e, tier_symbol.clone()).is_some() {
+            panic!("Beacon closed for pending draw");
+        }
+        let beacon = EntropyManager::contribute_beacon(e, &tier_symbol);
+
+        #[allow(deprecated)]
+        e.events().publish(
+            (Symbol::new(e, "beacon_contribution"), tier_symbol),
+            (beacon.contributions, beacon.last_ledger),
+        );
+
+        beacon
+    }
+
+    pub fn get_beacon(e: &Env, tier_symbol: Symbol) -> BeaconState {
+        EntropyManager::get_beacon(e, &tier_symbol)
+    }
+
+    /// Target ledger of the tier's pending finalization request
+    pub fn get_finalization_target(e: &Env, tier_symbol: Symbol) -> Option<u32> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Lottery(LotteryKey::FinalizationTarget(tier_symbol)))
+    }
+
+    /// Record the target ledger's entropy for the tier's pending draw, so it
+    /// can be generated in a later ledger with the same result. Open to
+    /// anyone, only in the target ledger itself.
+    pub fn capture_finalization_entropy(e: &Env, tier_symbol: Symbol) -> Bytes {
+        let target = Self::get_finalization_target(e, tier_symbol.clone())
+            .unwrap_or_else(|| panic!("Finalization not requested"));
+        EntropyManager::capture_target_entropy(e, &tier_symbol, target)
+    }
+
+    /// Whether the tier's current round already has its main draw
+    fn main_draw_generated(e: &Env, tier_symbol: &Symbol) -> bool {
+        let round = RoundManager::current(e, tier_symbol);
+        e.storage()
+            .persistent()
+            .has(&DataKey::Lottery(LotteryKey::VRFState(
+                tier_symbol.clone(),
+                round,
+            )))
+    }
+
+    /// Generate batch randomness for lottery finalization, from the ledger
+    /// committed to by `request_lottery_finalization`
+    pub fn generate_lottery_randomness(e: &Env, tier_symbol: Symbol, batch_size: u32) -> Vec<RandomnessOutput> {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(
+            e,
+            &admin,
+            "generate_lottery_randomness",
+            (tier_symbol.clone(), batch_size),
+        );
+        Self::finalize_lottery_randomness(e, tier_symbol, batch_size, None)
+    }
+
+    /// Generate the randomness of a lottery using signed proofs, one output
+    /// per signature. Signature `i` is the organizer's signature over
+    /// `get_vrf_signing_message(tier, i)`.
+    pub fn generate_signed_randomness(
+        e: &Env,
+        tier_symbol: Symbol,
+        signatures: Vec<BytesN<64>>,
+    ) -> Vec<RandomnessOutput> {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(
+            e,
+            &admin,
+            "generate_signed_randomness",
+            (tier_symbol.clone(), signatures.clone()),
+        );
+        let batch_size = signatures.len();
+        Self::finalize_lottery_randomness(e, tier_symbol, batch_size, Some(signatures))
+    }
+
+    fn finalize_lottery_randomness(
+        e: &Env,
+        tier_symbol: Symbol,
+        batch_size: u32,
+        signatures: Option<Vec<BytesN<64>>>,
+    ) -> Vec<RandomnessOutput> {
+        // Verify allocation state exists
+        let state = Self::allocation_state(e, &tier_symbol);
+        if state.allocation_complete {
+            panic!("Allocation already complete");
+        }
+
+        // Check if we're at finalization ledger
+        if e.ledger().sequence() < state.finalization_ledger {
+            panic!("Cannot finalize before finalization ledger");
+        }
+        // The main draw is generated once; allocation only uses its outputs
+        if Self::main_draw_generated(e, &tier_symbol) {
+            panic!("Randomness already generated");
+        }
+        let randomness_outputs =
+            Self::draw_committed_randomness(e, &tier_symbol, batch_size, signatures);
+
+        // Store randomness hash for verification
+        let randomness_hash = VRFEngine::hash_randomness_batch(e, &randomness_outputs);
+        let vrf_state = VRFState {
+            randomness_generated: true,
+            randomness_hash,
+            batch_nonce: 0,
+            finalization_ledger: state.finalization_ledger,
+        };
+
+        let lottery_round = RoundManager::current(e, &tier_symbol);
+        e.storage().persistent().set(
+            &DataKey::Lottery(LotteryKey::VRFState(tier_symbol.clone(), lottery_round)),
+            &vrf_state,
+        );
+
+        randomness_outputs
+    }
+
+    /// `batch_size` randomness outputs from the ledger committed to by
+    /// `request_lottery_finalization`, consuming the request, and stored for
+    /// `get_randomness_batch`. `signatures` must be given exactly when the
+    /// tier's lottery uses signed proofs.
+    fn draw_committed_randomness(
+        e: &Env,
+        tier_symbol: &Symbol,
+        batch_size: u32,
+        signatures: Option<Vec<BytesN<64>>>,
+    ) -> Vec<RandomnessOutput> {
+        // Only the committed target ledger's entropy may be used
+        let target_key = DataKey::Lottery(LotteryKey::FinalizationTarget(tier_symbol.clone()));
+        let target: u32 = e
+            .storage()
+            .persistent()
+            .get(&target_key)
+            .unwrap_or_else(|| panic!("Finalization not requested"));
+        if e.ledger().sequence() < target {
+            panic!("Randomness ledger not reached");
+        }
+        e.storage().persistent().remove(&target_key);
+
+        // Generate batch randomness in this draw's domain
+        let round_key = DataKey::Lottery(LotteryKey::DrawRound(tier_symbol.clone()));
+        let round: u32 = e.storage().persistent().get(&round_key).unwrap_or(0);
+        let domain = Self::vrf_domain(e, tier_symbol, round);
+        e.storage().persistent().set(&round_key, &(round + 1));
+        // Entropy is fixed by the target ledger, whichever ledger this runs in
+        let entropy = EntropyManager::take_target_entropy(e, tier_symbol, target);
+        let ledger_hash = entropy.to_array::<32>().unwrap_or([0u8; 32]);
+        let randomness_outputs = match (ProofSchemeManager::get(e, tier_symbol), signatures) {
+            (ProofScheme::HashChain, None) => {
+                // Fold in the tier's beacon when it uses one
+                let mut seed = entropy;
+                if let Some(beacon) = EntropyManager::consume_beacon(e, tier_symbol) {
+                    seed = EntropyManager::mix_entropy_sources(e, &vec![e, seed, beacon]);
+                }
+                VRFEngine::generate_batch_randomness_at(
+                    e,
+                    &domain,
+                    batch_size,
+                    seed,
+                    &ledger_hash,
+                    target,
+                )
+            }
+            (ProofScheme::Ed25519Signature, Some(signatures)) => {
+                if signatures.is_empty() || signatures.len() != batch_size {
+                    panic!("Signature count mismatch");
+                }
+                let public_key = ProofSchemeManager::public_key(e).unwrap();
+                VRFEngine::generate_signed_randomness(
+                    e,
+                    &domain,
+                    &public_key,
+                    &signatures,
+                    &ledger_hash,
+                    target,
+                )
+            }
+            (ProofScheme::HashChain, Some(_)) => panic!("Lottery uses hash-chain proofs"),
+            (ProofScheme::Ed25519Signature, None) => panic!("Lottery uses signed proofs"),
+        };
+        let lottery_round = RoundManager::current(e, tier_symbol);
+        ArchiveManager::store_randomness(e, tier_symbol, lottery_round, &randomness_outputs);
+
+        randomness_outputs
+    }
+
+    /// Outputs of draw `draw` of the tier's lottery `round` from index
+    /// `start`, for re-deriving winners. Draw 0 is the round's main draw and
+    /// hashes to `randomness_hash` of its VRF state; backfills follow.
+    pub fn get_randomness_batch(
+        e: &Env,
+        tier_symbol: Symbol,
+        round: u32,
+        draw: u32,
+        start: u32,
+        limit: u32,
+    ) -> Vec<RandomnessOutput> {
+        ArchiveManager::randomness_page(e, &tier_symbol, round, draw, start, limit)
+    }
+
+    /// Draws of the tier's lottery `round` with archived randomness
+    pub fn get_randomness_draw_count(e: &Env, tier_symbol: Symbol, round: u32) -> u32 {
+        ArchiveManager::randomness_draws(e, &tier_symbol, round)
+    }
+
+    /// Set the event id mixed into the VRF domain of every later draw
+    pub fn set_vrf_event_id(e: &Env, event_id: BytesN<32>) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "set_vrf_event_id", event_id.clone());
+
+        e.storage()
+            .instance()
+            .set(&DataKey::Lottery(LotteryKey::VRFEventId), &event_id);
+    }
+
+    /// Domain of the tier's draw `round` (counting from 0)
+    fn vrf_domain(e: &Env, tier_symbol: &Symbol, round: u32) -> VRFDomain {
+        VRFDomain {
+            contract: e.current_contract_address(),
+            event_id: e
+                .storage()
+                .instance()
+                .get(&DataKey::Lottery(LotteryKey::VRFEventId))
+                .unwrap_or_else(|| BytesN::from_array(e, &[0; 32])),
+            tier_symbol: tier_symbol.clone(),
+            round,
+        }
+    }
+
+    /// Randomness state of the tier's lottery `round`
+    pub fn get_vrf_state(e: &Env, tier_symbol: Symbol, round: u32) -> Option<VRFState> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Lottery(LotteryKey::VRFState(tier_symbol, round)))
+    }
+
+    /// Execute lottery allocation for the tier's current round from its
+    /// registered entries and the outputs of the round's main draw
+    pub fn execute_lottery_allocation(e: &Env, tier_symbol: Symbol) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "execute_lottery_allocation", tier_symbol.clone());
+
+        let round = RoundManager::current(e, &tier_symbol);
+        let state_key = DataKey::Lottery(LotteryKey::AllocationState(tier_symbol.clone(), round));
+        let mut state: AllocationConfig = e
             .storage()
             .persistent()
             .get(&state_key)
             .unwrap_or_else(|| panic!("Allocation not initialized"));
+        if state.allocation_complete {
+            panic!("Allocation already complete");
+        }
 
         // Verify randomness has been generated
-        let vrf_state_key = DataKey::VRFState;
+        let vrf_state_key = DataKey::Lottery(LotteryKey::VRFState(tier_symbol.clone(), round));
         let vrf_state: VRFState = e
             .storage()
             .persistent()
@@ -264,42 +1222,73 @@ impl SoulboundTicketContract {
         if !vrf_state.randomness_generated {
             panic!("Randomness not ready");
         }
+        let mut randomness_values = Vec::new(e);
+        for output in ArchiveManager::randomness(e, &tier_symbol, round, 0).iter() {
+            randomness_values.push_back(output.value);
+        }
 
         // Load entries
-        let count_key = DataKey::LotteryEntryCount(tier_symbol.clone());
-        let entry_count: u32 = e.storage().persistent().get(&count_key).unwrap_or(0);
-        let mut entries: Vec<LotteryEntry> = Vec::new(e);
+        let entries = Self::lottery_entries(e, &tier_symbol, round);
 
-        for i in 0..entry_count {
-            if let Some(entry) = e
-                .storage()
-                .persistent()
-                .get::<_, LotteryEntry>(&DataKey::LotteryEntry(tier_symbol.clone(), i))
-            {
-                entries.push_back(entry).unwrap();
-            }
-        }
+        // When winners must be distinct, rank every entry the randomness
+        // covers so repeat winners can be replaced by the next in line
+        let policy = EntryPolicyManager::get(e, &tier_symbol);
+        let draw_count = if EntryPolicyManager::dedupes_winners(&policy) {
+            entries.len()
+        } else {
+            state.total_allocations
+        };
 
         // Execute allocation based on strategy
         let results: Vec<AllocationResult> = match state.strategy {
             AllocationStrategyType::FCFS => {
-                AllocationEngine::allocate_fcfs(e, &entries, state.total_allocations)
+                AllocationEngine::allocate_fcfs(e, &entries, draw_count)
             }
             AllocationStrategyType::Lottery => {
-                AllocationEngine::allocate_lottery(e, &entries, &randomness_values, state.total_allocations)
+                match RevealManager::weights(e, &tier_symbol, &entries) {
+                    Some(weights) => AllocationEngine::allocate_weighted_lottery(
+                        e,
+                        &entries,
+                        &weights,
+                        &randomness_values,
+                        draw_count,
+                    ),
+                    None => AllocationEngine::allocate_lottery(
+                        e,
+                        &entries,
+                        &randomness_values,
+                        draw_count,
+                    ),
+                }
             }
             AllocationStrategyType::TimeWeighted => {
-                AllocationEngine::allocate_time_weighted(e, &entries, &randomness_values, state.total_allocations)
+                AllocationEngine::allocate_time_weighted(e, &entries, &randomness_values, draw_count)
             }
             _ => {
                 panic!("Strategy not yet implemented");
             }
         };
+        let results = if EntryPolicyManager::dedupes_winners(&policy) {
+            EntryPolicyManager::dedupe_winners(e, &results, state.total_allocations)
+        } else {
+            results
+        };
 
         // Store results
-        e.storage()
-            .persistent()
-            .set(&DataKey::LotteryResults(tier_symbol.clone()), &results);
+        e.storage().persistent().set(
+            &DataKey::Lottery(LotteryKey::Results(tier_symbol.clone(), round)),
+            &results,
+        );
+        BackfillManager::stamp_deadlines(e, &tier_symbol, round, &results);
+        WinnerIndexManager::record(e, &tier_symbol, round, &results);
+        let proof = ArchiveManager::seal(
+            e,
+            &tier_symbol,
+            round,
+            &entries,
+            &randomness_values,
+            &results,
+        );
 
         // Update state
         state.allocated_count = (results.len() as u32).min(state.total_allocations);
@@ -307,338 +1296,4704 @@ impl SoulboundTicketContract {
         e.storage()
             .persistent()
             .set(&state_key, &state);
+
+        SubscriptionManager::notify(e, &proof);
+    }
+
+    /// Registered entries of the tier's lottery `round` still in storage
+    fn lottery_entries(e: &Env, tier_symbol: &Symbol, round: u32) -> Vec<LotteryEntry> {
+        let count_key = DataKey::Lottery(LotteryKey::EntryCount(tier_symbol.clone(), round));
+        let entry_count: u32 = e.storage().persistent().get(&count_key).unwrap_or(0);
+        let mut entries: Vec<LotteryEntry> = Vec::new(e);
+
+        for i in 0..entry_count {
+            if let Some(entry) = e
+                .storage()
+                .persistent()
+                .get::<_, LotteryEntry>(&DataKey::Lottery(LotteryKey::Entry(
+                    tier_symbol.clone(),
+                    round,
+                    i,
+                )))
+            {
+                entries.push_back(entry);
+            }
+        }
+        entries
+    }
+
+    /// Let winners of the tier's later draws claim for `seconds` after the
+    /// draw, 0 for no limit. Results past their deadline can be expired with
+    /// `expire_unclaimed` and backfilled.
+    pub fn set_claim_window(e: &Env, tier_symbol: Symbol, seconds: u64) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(
+            e,
+            &admin,
+            "set_claim_window",
+            (tier_symbol.clone(), seconds),
+        );
+
+        BackfillManager::set_claim_window(e, &tier_symbol, seconds);
+    }
+
+    pub fn get_claim_window(e: &Env, tier_symbol: Symbol) -> u64 {
+        BackfillManager::claim_window(e, &tier_symbol)
+    }
+
+    /// Last timestamp allocation `index` of the tier's lottery `round` can be
+    /// claimed at, if it has a deadline
+    pub fn get_claim_deadline(e: &Env, tier_symbol: Symbol, round: u32, index: u32) -> Option<u64> {
+        BackfillManager::deadline(e, &tier_symbol, round, index)
+    }
+
+    pub fn is_allocation_forfeited(e: &Env, tier_symbol: Symbol, round: u32, index: u32) -> bool {
+        BackfillManager::is_forfeited(e, &tier_symbol, round, index)
+    }
+
+    /// Forfeit the current round's results left unclaimed past their
+    /// deadline, freeing their allocations for `backfill_draw`. Open to
+    /// anyone. Returns the number forfeited.
+    pub fn expire_unclaimed(e: &Env, tier_symbol: Symbol) -> u32 {
+        let round = RoundManager::current(e, &tier_symbol);
+        let mut state = Self::allocation_state(e, &tier_symbol);
+        if !state.allocation_complete {
+            panic!("Allocation not complete");
+        }
+        let results = Self::get_round_winners(e, tier_symbol.clone(), round);
+        let expired = BackfillManager::expire(e, &tier_symbol, round, &results);
+        if expired > 0 {
+            state.allocated_count = state.allocated_count.saturating_sub(expired);
+            e.storage().persistent().set(
+                &DataKey::Lottery(LotteryKey::AllocationState(tier_symbol.clone(), round)),
+                &state,
+            );
+
+            #[allow(deprecated)]
+            e.events().publish(
+                (Symbol::new(e, "winners_expired"), tier_symbol),
+                (round, expired),
+            );
+        }
+        expired
+    }
+
+    /// Draw the current round's freed allocations among entrants who have not
+    /// won, with randomness from the ledger committed to by
+    /// `request_lottery_finalization`. The draw is sealed as its own
+    /// backfill proof and its winners get a fresh claim deadline.
+    pub fn backfill_draw(e: &Env, tier_symbol: Symbol) -> Vec<AllocationResult> {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "backfill_draw", tier_symbol.clone());
+        Self::run_backfill(e, tier_symbol, None)
+    }
+
+    /// `backfill_draw` for a lottery using signed proofs, with one signature
+    /// per output the draw needs: the freed allocations, or every remaining
+    /// entrant when winners must be distinct
+    pub fn backfill_signed_draw(
+        e: &Env,
+        tier_symbol: Symbol,
+        signatures: Vec<BytesN<64>>,
+    ) -> Vec<AllocationResult> {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(
+            e,
+            &admin,
+            "backfill_signed_draw",
+            (tier_symbol.clone(), signatures.clone()),
+        );
+        Self::run_backfill(e, tier_symbol, Some(signatures))
     }
 
-    /// Verify a randomness proof
+    fn run_backfill(
+        e: &Env,
+        tier_symbol: Symbol,
+        signatures: Option<Vec<BytesN<64>>>,
+    ) -> Vec<AllocationResult> {
+        let round = RoundManager::current(e, &tier_symbol);
+        let mut state = Self::allocation_state(e, &tier_symbol);
+        if !state.allocation_complete {
+            panic!("Allocation not complete");
+        }
+        let freed = state
+            .total_allocations
+            .saturating_sub(state.allocated_count);
+        if freed == 0 {
+            panic!("No allocations to backfill");
+        }
+        let mut results = Self::get_round_winners(e, tier_symbol.clone(), round);
+        let entries = Self::lottery_entries(e, &tier_symbol, round);
+        let pool = BackfillManager::remaining_entrants(e, &entries, &results);
+        if pool.is_empty() {
+            panic!("No entrants left to draw");
+        }
+
+        // As in the main draw, rank the whole pool when winners must be distinct
+        let policy = EntryPolicyManager::get(e, &tier_symbol);
+        let dedupe = EntryPolicyManager::dedupes_winners(&policy);
+        let draw_count = if dedupe { pool.len() } else { freed };
+        let outputs = Self::draw_committed_randomness(e, &tier_symbol, draw_count, signatures);
+        let mut randomness_values = Vec::new(e);
+        for output in outputs.iter() {
+            randomness_values.push_back(output.value);
+        }
+        let drawn = AllocationEngine::allocate_lottery(e, &pool, &randomness_values, draw_count);
+        let drawn = if dedupe {
+            EntryPolicyManager::dedupe_winners(e, &drawn, freed)
+        } else {
+            drawn
+        };
+
+        // Number the new results after the round's existing ones
+        let mut backfilled = Vec::new(e);
+        for result in drawn.iter() {
+            backfilled.push_back(AllocationResult {
+                allocation_index: results.len() + result.allocation_index,
+                ..result
+            });
+        }
+        results.append(&backfilled);
+        e.storage().persistent().set(
+            &DataKey::Lottery(LotteryKey::Results(tier_symbol.clone(), round)),
+            &results,
+        );
+        BackfillManager::stamp_deadlines(e, &tier_symbol, round, &backfilled);
+        WinnerIndexManager::record(e, &tier_symbol, round, &backfilled);
+        let proof = ArchiveManager::seal_backfill(
+            e,
+            &tier_symbol,
+            round,
+            &pool,
+            &randomness_values,
+            &backfilled,
+        );
+
+        state.allocated_count += backfilled.len();
+        e.storage().persistent().set(
+            &DataKey::Lottery(LotteryKey::AllocationState(tier_symbol.clone(), round)),
+            &state,
+        );
+
+        #[allow(deprecated)]
+        e.events().publish(
+            (Symbol::new(e, "lottery_backfill"), tier_symbol),
+            (round, backfilled.len(), proof.proof_hash),
+        );
+
+        backfilled
+    }
+
+    /// Sealed backfill draws of the tier's lottery `round`, oldest first
+    pub fn get_backfill_proofs(e: &Env, tier_symbol: Symbol, round: u32) -> Vec<AllocationProof> {
+        ArchiveManager::backfill_proofs(e, &tier_symbol, round)
+    }
+
+    /// Have `subscriber` called with the allocation proof when the tier's draw
+    /// completes (see `subscriptions` for the interface)
+    pub fn add_lottery_subscriber(e: &Env, tier_symbol: Symbol, subscriber: Address) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(
+            e,
+            &admin,
+            "add_lottery_subscriber",
+            (tier_symbol.clone(), subscriber.clone()),
+        );
+        SubscriptionManager::add(e, &tier_symbol, &subscriber);
+    }
+
+    pub fn remove_lottery_subscriber(e: &Env, tier_symbol: Symbol, subscriber: Address) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(
+            e,
+            &admin,
+            "remove_lottery_subscriber",
+            (tier_symbol.clone(), subscriber.clone()),
+        );
+        SubscriptionManager::remove(e, &tier_symbol, &subscriber);
+    }
+
+    pub fn get_lottery_subscribers(e: &Env, tier_symbol: Symbol) -> Vec<Address> {
+        SubscriptionManager::get(e, &tier_symbol)
+    }
+
+    /// Hash-chained summary of the tier's finished draw in lottery `round`
+    /// (entries root, randomness hash, winners root) for verification against
+    /// off-chain archives
+    pub fn export_allocation_proof(e: &Env, tier_symbol: Symbol, round: u32) -> AllocationProof {
+        ArchiveManager::get_proof(e, &tier_symbol, round)
+            .unwrap_or_else(|| panic!("Allocation not finalized"))
+    }
+
+    /// Delete up to `max_count` lottery entries of a sealed draw to reduce storage rent.
+    /// The sealed `entries_root` still allows entries to be proven afterwards.
+    pub fn prune_lottery_entries(e: &Env, tier_symbol: Symbol, round: u32, max_count: u32) -> u32 {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(
+            e,
+            &admin,
+            "prune_lottery_entries",
+            (tier_symbol.clone(), round, max_count),
+        );
+
+        let proof = ArchiveManager::get_proof(e, &tier_symbol, round)
+            .unwrap_or_else(|| panic!("Allocation not finalized"));
+        ArchiveManager::prune_entries(e, &tier_symbol, round, proof.entry_count, max_count)
+    }
+
+    /// Check that `entry` was entry number `index` of the sealed draw in
+    /// lottery `round`
+    pub fn verify_lottery_entry(
+        e: &Env,
+        tier_symbol: Symbol,
+        round: u32,
+        entry: LotteryEntry,
+        index: u32,
+        proof: Vec<BytesN<32>>,
+    ) -> bool {
+        let sealed = ArchiveManager::get_proof(e, &tier_symbol, round)
+            .unwrap_or_else(|| panic!("Allocation not finalized"));
+        ArchiveManager::verify_inclusion(
+            e,
+            ArchiveManager::entry_leaf(e, &entry),
+            index,
+            sealed.entry_count,
+            &proof,
+            &sealed.entries_root,
+        )
+    }
+
+    /// Verify a randomness proof from the tier's draw `round`
     pub fn verify_lottery_randomness(
         e: &Env,
-        proof: &VRFProof,
+        proof: VRFProof,
+        tier_symbol: Symbol,
+        round: u32,
         original_input: Bytes,
         expected_ledger: u32,
     ) -> bool {
-        VRFEngine::verify_vrf_proof(e, proof, original_input, expected_ledger)
+        let domain = Self::vrf_domain(e, &tier_symbol, round);
+        VRFEngine::verify_vrf_proof(e, &proof, &domain, original_input, expected_ledger)
     }
 
-    /// Get lottery results transparency
-    pub fn get_lottery_winners(e: &Env, tier_symbol: Symbol) -> Vec<AllocationResult> {
-        e.storage()
+    /// Choose how the tier's lottery randomness is proven. Signed proofs need
+    /// the VRF key published at initialization. Not while a draw is requested.
+    pub fn set_lottery_proof_scheme(e: &Env, tier_symbol: Symbol, scheme: ProofScheme) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(
+            e,
+            &admin,
+            "set_lottery_proof_scheme",
+            (tier_symbol.clone(), scheme),
+        );
+
+        let target_key = DataKey::Lottery(LotteryKey::FinalizationTarget(tier_symbol.clone()));
+        if e.storage().persistent().has(&target_key) {
+            panic!("Finalization already requested");
+        }
+        ProofSchemeManager::set(e, &tier_symbol, scheme);
+    }
+
+    pub fn get_lottery_proof_scheme(e: &Env, tier_symbol: Symbol) -> ProofScheme {
+        ProofSchemeManager::get(e, &tier_symbol)
+    }
+
+    /// Organizer's ed25519 key for signed lottery randomness
+    pub fn get_vrf_public_key(e: &Env) -> Option<BytesN<32>> {
+        ProofSchemeManager::public_key(e)
+    }
+
+    /// Message to sign for output `nonce` of the tier's pending draw. It binds
+    /// the draw's target ledger, so it exists only once
+    /// `request_lottery_finalization` has fixed one.
+    pub fn get_vrf_signing_message(e: &Env, tier_symbol: Symbol, nonce: u32) -> BytesN<32> {
+        let target_key = DataKey::Lottery(LotteryKey::FinalizationTarget(tier_symbol.clone()));
+        let target: u32 = e
+            .storage()
             .persistent()
-            .get(&DataKey::LotteryResults(tier_symbol.clone()))
-            .unwrap_or_else(|| Vec::new(e))
+            .get(&target_key)
+            .unwrap_or_else(|| panic!("Finalization not requested"));
+        let round_key = DataKey::Lottery(LotteryKey::DrawRound(tier_symbol.clone()));
+        let round: u32 = e.storage().persistent().get(&round_key).unwrap_or(0);
+        let domain = Self::vrf_domain(e, &tier_symbol, round);
+        VRFEngine::signing_message(e, &domain, target, nonce)
+    }
+
+    /// Verify a signed randomness output from the tier's draw `round` against
+    /// the published VRF key. Traps on a signature that does not verify.
+    pub fn verify_signed_lottery_randomness(
+        e: &Env,
+        output: RandomnessOutput,
+        tier_symbol: Symbol,
+        round: u32,
+    ) -> bool {
+        let Some(public_key) = ProofSchemeManager::public_key(e) else {
+            return false;
+        };
+        let domain = Self::vrf_domain(e, &tier_symbol, round);
+        VRFEngine::verify_signed_output(e, &output, &domain, &public_key)
+    }
+
+    /// Get lottery results transparency, for the tier's current round
+    pub fn get_lottery_winners(e: &Env, tier_symbol: Symbol) -> Vec<AllocationResult> {
+        let round = RoundManager::current(e, &tier_symbol);
+        Self::get_round_winners(e, tier_symbol, round)
+    }
+
+    /// Winners of the tier's lottery `round`
+    pub fn get_round_winners(e: &Env, tier_symbol: Symbol, round: u32) -> Vec<AllocationResult> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Lottery(LotteryKey::Results(tier_symbol, round)))
+            .unwrap_or_else(|| Vec::new(e))
+    }
+
+    /// Whether `address` won the tier's current lottery round, with their
+    /// first allocation and how much they have claimed
+    pub fn did_win(e: &Env, tier_symbol: Symbol, address: Address) -> Option<WinnerStatus> {
+        WinnerIndexManager::status(e, &tier_symbol, &address)
+    }
+
+    /// Lottery round the tier's entries, draws and claims currently apply to
+    pub fn get_lottery_round(e: &Env, tier_symbol: Symbol) -> u32 {
+        RoundManager::current(e, &tier_symbol)
+    }
+
+    /// Start the tier's next lottery round once the current one is allocated,
+    /// keeping earlier rounds' entries, randomness and results. The new round
+    /// takes entries after `initialize_lottery`. Claims only apply to the
+    /// current round, so it cannot open while any winner can still claim;
+    /// without a claim window every result must be claimed first. Returns the
+    /// new round.
+    pub fn open_new_round(e: &Env, tier_symbol: Symbol) -> u32 {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "open_new_round", tier_symbol.clone());
+
+        let current = RoundManager::current(e, &tier_symbol);
+        let results = Self::get_round_winners(e, tier_symbol.clone(), current);
+        if BackfillManager::open_claims(e, &tier_symbol, current, &results) > 0 {
+            panic!("Winners can still claim");
+        }
+        let round = RoundManager::open_next(e, &tier_symbol);
+
+        #[allow(deprecated)]
+        e.events()
+            .publish((Symbol::new(e, "lottery_round_opened"), tier_symbol), round);
+
+        round
+    }
+
+    /// Get allocation fairness score (0-100) of the tier's current round
+    pub fn get_allocation_fairness(e: &Env, tier_symbol: Symbol) -> u32 {
+        let round = RoundManager::current(e, &tier_symbol);
+        let count_key = DataKey::Lottery(LotteryKey::EntryCount(tier_symbol.clone(), round));
+        let entry_count: u32 = e.storage().persistent().get(&count_key).unwrap_or(0);
+
+        let results_key = DataKey::Lottery(LotteryKey::Results(tier_symbol, round));
+        if let Some(results) = e
+            .storage()
+            .persistent()
+            .get::<_, Vec<AllocationResult>>(&results_key)
+        {
+            AllocationEngine::compute_fairness_score(e, &results, entry_count)
+        } else {
+            0
+        }
+    }
+
+    /// ==================== PRICING FUNCTIONS ====================
+
+    /// multipliers.  Call this once after deployment pointing at a real oracle,
+    /// or whenever you want to re-baseline the reference price.
+    pub fn update_oracle_reference(e: &Env, new_reference_price: i128) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "update_oracle_reference", (new_reference_price,));
+        if !e
+            .storage()
+            .instance()
+            .has(&DataKey::Pricing(PricingKey::Config))
+        {
+            panic!("Pricing not configured");
+        }
+        TimelockManager::submit(e, ConfigChange::OracleReference(new_reference_price));
+    }
+
+    // Emergency freeze toggle
+    pub fn emergency_freeze(e: &Env, freeze: bool) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "emergency_freeze", (freeze,));
+        let mut config: PricingConfig =
+            e.storage().instance().get(&DataKey::Pricing(PricingKey::Config)).unwrap();
+        if freeze && !config.is_frozen {
+            FreezeGraceManager::on_freeze(e);
+        }
+        config.is_frozen = freeze;
+        e.storage()
+            .instance()
+            .set(&DataKey::Pricing(PricingKey::Config), &config);
+    }
+
+    /// Keep tickets bought shortly before a pricing freeze refundable for a
+    /// while after it, past the normal cutoff. Applies to freezes from now on;
+    /// `None` turns it off.
+    pub fn set_freeze_grace(e: &Env, grace: Option<FreezeGrace>) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "set_freeze_grace", (grace.clone(),));
+        FreezeGraceManager::set_policy(e, grace);
+    }
+
+    pub fn get_freeze_grace(e: &Env) -> Option<FreezeGrace> {
+        FreezeGraceManager::get_policy(e)
+    }
+
+    pub fn get_last_freeze(e: &Env) -> Option<FreezeRecord> {
+        FreezeGraceManager::last_freeze(e)
+    }
+
+    /// Halt (or resume) sales of a single tier while the rest of the event keeps selling
+    pub fn freeze_tier(e: &Env, tier_symbol: Symbol, frozen: bool) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "freeze_tier", (tier_symbol.clone(), frozen));
+
+        let key = DataKey::Tickets(TicketKey::Tier(tier_symbol));
+        let mut tier: Tier = e
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| panic!("Tier not found"));
+        tier.frozen = frozen;
+        e.storage().persistent().set(&key, &tier);
+    }
+
+    // Add a new ticket tier
+    pub fn add_tier(
+        e: &Env,
+        tier_symbol: Symbol,
+        name: String,
+        base_price: i128,
+        max_supply: u32,
+        strategy: PricingStrategy,
+    ) -> Result<(), TierError> {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(
+            e,
+            &admin,
+            "add_tier",
+            (tier_symbol.clone(), name.clone(), base_price, max_supply, strategy.clone()),
+        );
+
+        Self::create_tier(
+            e,
+            &TierInput {
+                symbol: tier_symbol,
+                name,
+                base_price,
+                max_supply,
+                strategy,
+            },
+        )
+    }
+
+    /// Create many tiers in one call. Every tier is validated as it is created
+    /// and any failure reverts the whole batch.
+    pub fn add_tiers(e: &Env, tiers: Vec<TierInput>) -> Result<(), TierError> {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "add_tiers", (tiers.clone(),));
+
+        if tiers.is_empty() {
+            panic!("No tiers to add");
+        }
+        for input in tiers.iter() {
+            Self::create_tier(e, &input)?;
+        }
+        Ok(())
+    }
+
+    /// Take a tier nothing has been minted from out of the catalog, freeing
+    /// its symbol and venue capacity. It can be restored within the grace period.
+    pub fn archive_tier(e: &Env, tier_symbol: Symbol) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "archive_tier", tier_symbol.clone());
+
+        let key = DataKey::Tickets(TicketKey::Tier(tier_symbol.clone()));
+        let tier: Tier = e
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| panic!("Tier not found"));
+        if tier.minted > 0 {
+            panic!("Tier has minted tickets");
+        }
+        let total_supply = Self::get_total_tier_supply(e).saturating_sub(tier.max_supply);
+        e.storage().persistent().remove(&key);
+        e.storage()
+            .instance()
+            .set(&DataKey::Tickets(TicketKey::TotalTierSupply), &total_supply);
+        TierArchiveManager::archive(e, &tier_symbol, tier);
+
+        #[allow(deprecated)]
+        e.events()
+            .publish((Symbol::new(e, "tier_archived"),), tier_symbol);
+    }
+
+    /// Put an archived tier back as it was, while its symbol is still free
+    pub fn restore_tier(e: &Env, tier_symbol: Symbol) -> Result<(), TierError> {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "restore_tier", tier_symbol.clone());
+
+        let key = DataKey::Tickets(TicketKey::Tier(tier_symbol.clone()));
+        if e.storage().persistent().has(&key) {
+            return Err(TierError::TierExists);
+        }
+        let archived =
+            TierArchiveManager::get(e, &tier_symbol).unwrap_or_else(|| panic!("Tier not archived"));
+        let total_supply = Self::get_total_tier_supply(e)
+            .checked_add(archived.tier.max_supply)
+            .ok_or(TierError::VenueCapacityExceeded)?;
+        if let Some(capacity) = Self::get_venue_capacity(e) {
+            if total_supply > capacity {
+                return Err(TierError::VenueCapacityExceeded);
+            }
+        }
+        let tier = TierArchiveManager::take(e, &tier_symbol);
+        e.storage().persistent().set(&key, &tier);
+        e.storage()
+            .instance()
+            .set(&DataKey::Tickets(TicketKey::TotalTierSupply), &total_supply);
+
+        #[allow(deprecated)]
+        e.events()
+            .publish((Symbol::new(e, "tier_restored"),), tier_symbol);
+        Ok(())
+    }
+
+    pub fn get_archived_tier(e: &Env, tier_symbol: Symbol) -> Option<ArchivedTier> {
+        TierArchiveManager::get(e, &tier_symbol)
+    }
+
+    /// Cap the combined `max_supply` of all tiers (0 removes the cap)
+    pub fn set_venue_capacity(e: &Env, capacity: u32) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "set_venue_capacity", (capacity,));
+
+        let key = DataKey::Tickets(TicketKey::VenueCapacity);
+        if capacity == 0 {
+            e.storage().instance().remove(&key);
+            return;
+        }
+        if Self::get_total_tier_supply(e) > capacity {
+            panic!("Tiers already exceed venue capacity");
+        }
+        e.storage().instance().set(&key, &capacity);
+    }
+
+    pub fn get_venue_capacity(e: &Env) -> Option<u32> {
+        e.storage()
+            .instance()
+            .get(&DataKey::Tickets(TicketKey::VenueCapacity))
+    }
+
+    /// Combined `max_supply` of every tier
+    pub fn get_total_tier_supply(e: &Env) -> u32 {
+        e.storage()
+            .instance()
+            .get(&DataKey::Tickets(TicketKey::TotalTierSupply))
+            .unwrap_or(0)
+    }
+
+    /// Validate and store a new tier, counting its supply against the venue capacity
+    fn create_tier(e: &Env, input: &TierInput) -> Result<(), TierError> {
+        let key = DataKey::Tickets(TicketKey::Tier(input.symbol.clone()));
+        if e.storage().persistent().has(&key) {
+            return Err(TierError::TierExists);
+        }
+        TierValidator::validate(e, input)?;
+
+        let total_supply = Self::get_total_tier_supply(e)
+            .checked_add(input.max_supply)
+            .ok_or(TierError::VenueCapacityExceeded)?;
+        if let Some(capacity) = Self::get_venue_capacity(e) {
+            if total_supply > capacity {
+                return Err(TierError::VenueCapacityExceeded);
+            }
+        }
+
+        let tier = Tier {
+            name: input.name.clone(),
+            base_price: input.base_price,
+            current_price: input.base_price,
+            max_supply: input.max_supply,
+            minted: 0,
+            active: true,
+            strategy: input.strategy.clone(),
+            frozen: false,
+            distribution: DistributionMode::DirectSale,
+            lottery_minted: 0,
+            identity_required: false,
+            entry_window: None,
+            guest_slots: 0,
+        };
+
+        e.storage().persistent().set(&key, &tier);
+        e.storage()
+            .instance()
+            .set(&DataKey::Tickets(TicketKey::TotalTierSupply), &total_supply);
+        Ok(())
+    }
+
+    /// Fetch the current external price multiplier using the real DIA oracle.
+    ///
+    /// Strategy:
+    ///  1. Call `DiaOraclePriceClient::try_get_value(pair)` on the configured oracle.
+    ///  2. Verify that the returned timestamp is within `max_oracle_age_seconds`.
+    ///  3. If the oracle is stale or the cross-contract call fails, fall back to
+    ///     `DexPriceRouterClient::try_get_spot_price(pair)` on the DEX address.
+    ///  4. If both fail, return `ORACLE_PRECISION` (neutral — no adjustment).
+    ///
+    /// The raw price (8 decimals, $1.00 == 100_000_000) is converted into a
+    /// `ORACLE_PRECISION`-scaled multiplier using the stored `oracle_reference_price`.
+    fn query_oracle_multiplier(e: &Env, config: &PricingConfig) -> i128 {
+        match fetch_price_with_fallback(
+            e,
+            &config.oracle_address,
+            &config.dex_pool_address,
+            config.oracle_pair.clone(),
+            config.max_oracle_age_seconds,
+        ) {
+            Some(result) => oracle_price_to_multiplier(
+                result.price,
+                config.oracle_reference_price,
+                ORACLE_PRECISION,
+            ),
+            // Both oracle and DEX unavailable: apply neutral multiplier (no adjustment)
+            None => ORACLE_PRECISION,
+        }
+    }
+
+    /// Oracle multiplier for pricing. Served from the cache until it is older than
+    /// `update_frequency` seconds, so most purchases skip the cross-contract call.
+    /// Fixed pricing mode never calls the oracle and uses a neutral multiplier.
+    fn fetch_oracle_multiplier(e: &Env, config: &PricingConfig) -> i128 {
+        if config.pricing_mode == PricingMode::Fixed {
+            return ORACLE_PRECISION;
+        }
+        if let Some(cache) = Self::get_oracle_cache(e) {
+            if e.ledger().timestamp().saturating_sub(cache.fetched_at) < config.update_frequency {
+                return cache.multiplier;
+            }
+        }
+
+        Self::refresh_oracle_multiplier(e, config)
+    }
+
+    fn refresh_oracle_multiplier(e: &Env, config: &PricingConfig) -> i128 {
+        let multiplier = Self::query_oracle_multiplier(e, config);
+        e.storage().instance().set(
+            &DataKey::Pricing(PricingKey::OracleCache),
+            &OracleCache {
+                multiplier,
+                fetched_at: e.ledger().timestamp(),
+            },
+        );
+        multiplier
+    }
+
+    /// Re-query the oracle now, regardless of the cache age. Returns the new multiplier.
+    pub fn force_refresh_oracle(e: &Env) -> i128 {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "force_refresh_oracle", ());
+        let config: PricingConfig = e
+            .storage()
+            .instance()
+            .get(&DataKey::Pricing(PricingKey::Config))
+            .unwrap();
+        if config.pricing_mode == PricingMode::Fixed {
+            panic!("Oracle disabled in fixed pricing mode");
+        }
+        Self::refresh_oracle_multiplier(e, &config)
+    }
+
+    pub fn get_oracle_cache(e: &Env) -> Option<OracleCache> {
+        e.storage()
+            .instance()
+            .get(&DataKey::Pricing(PricingKey::OracleCache))
+    }
+
+    // Dynamic pricing query
+    pub fn get_ticket_price(e: &Env, tier_symbol: Symbol) -> Result<i128, TierError> {
+        let key = DataKey::Tickets(TicketKey::Tier(tier_symbol));
+        let tier: Tier = e
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(TierError::TierNotFound)?;
+
+        // We only return the price here. It is updated during `purchase`.
+        Ok(Self::quote_price(e, &tier, None, None, None).price)
+    }
+
+    /// Price of one ticket when paying in `payment_token`, after its rounding rule
+    pub fn get_ticket_price_in(
+        e: &Env,
+        tier_symbol: Symbol,
+        payment_token: Address,
+    ) -> Result<i128, TierError> {
+        let key = DataKey::Tickets(TicketKey::Tier(tier_symbol));
+        let tier: Tier = e
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(TierError::TierNotFound)?;
+        Ok(Self::quote_price(e, &tier, None, None, Some(&payment_token)).price)
+    }
+
+    /// Round every price charged in `payment_token` up to a multiple of the
+    /// rule's increment. `None` removes the rule.
+    pub fn set_rounding_rule(e: &Env, payment_token: Address, rule: Option<RoundingRule>) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(
+            e,
+            &admin,
+            "set_rounding_rule",
+            (payment_token.clone(), rule.clone()),
+        );
+        if let Some(rule) = &rule {
+            if rule.increment <= 0 {
+                panic!("Invalid rounding increment");
+            }
+        }
+        RoundingManager::set(e, &payment_token, rule);
+    }
+
+    pub fn get_rounding_rule(e: &Env, payment_token: Address) -> Option<RoundingRule> {
+        RoundingManager::get(e, &payment_token)
+    }
+
+    /// Price sales in `payment_token` in the reference unit through `feed`.
+    /// `None` removes the feed; tickets already sold keep their recorded rate.
+    pub fn set_reference_feed(e: &Env, payment_token: Address, feed: Option<ReferenceFeed>) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(
+            e,
+            &admin,
+            "set_reference_feed",
+            (payment_token.clone(), feed.clone()),
+        );
+        AccountingManager::set_feed(e, &payment_token, feed);
+    }
+
+    pub fn get_reference_feed(e: &Env, payment_token: Address) -> Option<ReferenceFeed> {
+        AccountingManager::get_feed(e, &payment_token)
+    }
+
+    /// Gross, refunded and net revenue in the reference unit, with raw and
+    /// reference subtotals per payment token
+    pub fn get_revenue_report(e: &Env) -> RevenueReport {
+        AccountingManager::report(e)
+    }
+
+    /// Price one ticket of `tier` for `buyer`, fetching the oracle multiplier.
+    /// When the payment token is known its rounding rule is applied last.
+    fn quote_price(
+        e: &Env,
+        tier: &Tier,
+        buyer: Option<&Address>,
+        promo_code: Option<&Symbol>,
+        payment_token: Option<&Address>,
+    ) -> PriceQuote {
+        let config: PricingConfig = e
+            .storage()
+            .instance()
+            .get(&DataKey::Pricing(PricingKey::Config))
+            .unwrap();
+
+        let mut quote = if config.is_frozen {
+            PriceQuote {
+                base_price: tier.current_price,
+                discounts: Vec::new(e),
+                oracle_multiplier: ORACLE_PRECISION,
+                rounding: 0,
+                price: tier.current_price,
+            }
+        } else {
+            let oracle_multiplier = Self::fetch_oracle_multiplier(e, &config);
+            Self::compute_discounted_price(e, &config, tier, oracle_multiplier, buyer, promo_code)
+                .unwrap_or_else(|err| panic_with_error!(e, err))
+        };
+
+        if let Some(rule) = payment_token.and_then(|token| RoundingManager::get(e, token)) {
+            quote.rounding = RoundingManager::adjustment(&rule, quote.price);
+            quote.price = quote
+                .price
+                .checked_add(quote.rounding)
+                .unwrap_or_else(|| panic_with_error!(e, PricingError::PriceOverflow));
+        }
+        quote
+    }
+
+    /// Undiscounted price of a single ticket for `tier` at its current minted count
+    fn compute_price(
+        config: &PricingConfig,
+        tier: &Tier,
+        oracle_multiplier: i128,
+    ) -> Result<i128, PricingError> {
+        // Base price
+        let mut price = tier.base_price;
+
+        // Apply strategy variations
+        match tier.strategy {
+            PricingStrategy::Standard => {
+                // Demand based: base_price * (1 + thresholds_passed * 5%)
+                let increase_bps = PRICE_INCREASE_BPS
+                    .checked_mul(Self::demand_thresholds(tier))
+                    .ok_or(PricingError::PriceOverflow)?;
+                price = Self::add_bps(price, increase_bps)?;
+            }
+            PricingStrategy::TimeDecay => {
+                // Early-bird discount is resolved in `compute_discounted_price`
+            }
+            PricingStrategy::AbTestA => {
+                // High demand sensitivity (10% increase per threshold)
+                let increase_bps = (PRICE_INCREASE_BPS * 2)
+                    .checked_mul(Self::demand_thresholds(tier))
+                    .ok_or(PricingError::PriceOverflow)?;
+                price = Self::add_bps(price, increase_bps)?;
+            }
+            PricingStrategy::AbTestB => {
+                // Floor starts higher (+20%)
+                price = Self::add_bps(price, 2000)?;
+            }
+        }
+
+        // Apply external Oracle factors using the real DIA oracle integration
+        price = mul_bps(price, oracle_multiplier).ok_or(PricingError::PriceOverflow)?;
+
+        // Apply bounds
+        Ok(price.max(config.price_floor).min(config.price_ceiling))
+    }
+
+    /// Number of fifths of `max_supply` already minted (0..=4 while supply remains).
+    /// Scaling `minted` rather than dividing `max_supply` keeps tiny supplies
+    /// (1..4) on the same 5-step curve instead of gaining a threshold per ticket.
+    fn demand_thresholds(tier: &Tier) -> i128 {
+        (tier.minted as i128 * 5) / (tier.max_supply.max(1) as i128)
+    }
+
+    /// `price` raised by `bps`, failing if the result does not fit in an i128
+    fn add_bps(price: i128, bps: i128) -> Result<i128, PricingError> {
+        mul_bps(price, bps)
+            .and_then(|increase| price.checked_add(increase))
+            .ok_or(PricingError::PriceOverflow)
+    }
+
+    /// Price a single ticket after combining every discount that applies to this
+    /// purchase under the configured `DiscountPolicy`. All purchase paths price here.
+    /// The discounted price never drops below the floor.
+    fn compute_discounted_price(
+        e: &Env,
+        config: &PricingConfig,
+        tier: &Tier,
+        oracle_multiplier: i128,
+        buyer: Option<&Address>,
+        promo_code: Option<&Symbol>,
+    ) -> Result<PriceQuote, PricingError> {
+        let gross = Self::compute_price(config, tier, oracle_multiplier)?;
+
+        // If purchased way before event (more than a week out), apply early-bird discount
+        let mut early_bird = 0;
+        if tier.strategy == PricingStrategy::TimeDecay {
+            let event_info: EventInfo = e.storage().instance().get(&DataKey::EventInfo).unwrap();
+            let start = event_info.start_time.saturating_sub(604800); // 1 week before
+            if e.ledger().timestamp() < start {
+                early_bird = EARLY_BIRD_DISCOUNT_BPS;
+            }
+        }
+
+        let promo = match promo_code {
+            Some(code) => DiscountManager::get_promo_bps(e, code)
+                .unwrap_or_else(|| panic!("Unknown promo code")),
+            None => 0,
+        };
+        let loyalty = buyer
+            .map(|holder| DiscountManager::get_loyalty_bps(e, holder))
+            .unwrap_or(0);
+
+        let discounts = DiscountManager::apply(
+            e,
+            &DiscountManager::get_policy(e),
+            &[
+                (DiscountKind::EarlyBird, early_bird),
+                (DiscountKind::Promo, promo),
+                (DiscountKind::Loyalty, loyalty),
+            ],
+            gross,
+            gross - config.price_floor,
+        );
+
+        Ok(PriceQuote {
+            base_price: gross,
+            price: gross - DiscountManager::total(&discounts),
+            discounts,
+            oracle_multiplier,
+            rounding: 0,
+        })
+    }
+
+    /// ==================== DISCOUNTS ====================
+
+    /// Set how early-bird, promo and loyalty discounts combine
+    pub fn set_discount_policy(e: &Env, policy: DiscountPolicy) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "set_discount_policy", (policy.clone(),));
+        DiscountManager::set_policy(e, &policy);
+    }
+
+    pub fn get_discount_policy(e: &Env) -> DiscountPolicy {
+        DiscountManager::get_policy(e)
+    }
+
+    /// Register a promo code worth `discount_bps` off (0 removes the code)
+    pub fn set_promo_code(e: &Env, code: Symbol, discount_bps: u32) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "set_promo_code", (code.clone(), discount_bps));
+        DiscountManager::set_promo_bps(e, &code, discount_bps);
+    }
+
+    /// Grant `holder` a standing loyalty discount (0 removes it)
+    pub fn set_loyalty_discount(e: &Env, holder: Address, discount_bps: u32) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "set_loyalty_discount", (holder.clone(), discount_bps));
+        DiscountManager::set_loyalty_bps(e, &holder, discount_bps);
+    }
+
+    /// Preview the outcome of buying `quantity` tickets without mutating state.
+    /// Each unit is priced at the minted count it would be bought at, so the
+    /// total follows the demand curve exactly as sequential purchases would.
+    /// At most `MAX_SIMULATED_QUANTITY` units are priced; prices are left
+    /// unset once an error is found.
+    pub fn simulate_purchase(
+        e: &Env,
+        buyer: Address,
+        tier_symbol: Symbol,
+        quantity: u32,
+    ) -> PurchasePreview {
+        let next_token_id = Base::next_token_id(e);
+        let mut preview = PurchasePreview {
+            unit_price: 0,
+            total_cost: 0,
+            fees: 0,
+            discount: 0,
+            first_token_id: next_token_id,
+            last_token_id: next_token_id,
+            error: None,
+        };
+
+        let tier_key = DataKey::Tickets(TicketKey::Tier(tier_symbol.clone()));
+        let mut tier: Tier = match e.storage().persistent().get(&tier_key) {
+            Some(tier) => tier,
+            None => {
+                preview.error = Some(PurchaseError::TierNotFound);
+                return preview;
+            }
+        };
+        if quantity == 0 || quantity > MAX_SIMULATED_QUANTITY {
+            preview.error = Some(PurchaseError::InvalidQuantity);
+            return preview;
+        }
+        match next_token_id.checked_add(quantity - 1) {
+            Some(last_token_id) => preview.last_token_id = last_token_id,
+            None => {
+                preview.error = Some(PurchaseError::SoldOut);
+                return preview;
+            }
+        }
+
+        let wallet_cap = e
+            .storage()
+            .instance()
+            .get::<_, u32>(&DataKey::Tickets(TicketKey::WalletCap));
+        if !tier.active {
+            preview.error = Some(PurchaseError::TierInactive);
+        } else if tier.frozen {
+            preview.error = Some(PurchaseError::TierFrozen);
+        } else if tier.minted >= tier.max_supply {
+            preview.error = Some(PurchaseError::SoldOut);
+        } else if tier
+            .minted
+            .checked_add(quantity)
+            .map_or(true, |minted| minted > tier.max_supply)
+        {
+            preview.error = Some(PurchaseError::InsufficientSupply);
+        } else if tier.distribution == DistributionMode::LotteryOnly {
+            preview.error = Some(PurchaseError::LotteryOnly);
+        } else if Self::direct_sale_capacity(&tier) < quantity {
+            preview.error = Some(PurchaseError::DirectAllocationExhausted);
+        } else if ChannelManager::available(e, &tier_symbol, &tier, SalesChannel::General)
+            < quantity
+        {
+            preview.error = Some(PurchaseError::ChannelAllocationExhausted);
+        } else if !EligibilityManager::is_eligible(e, &tier_symbol, &buyer) {
+            preview.error = Some(PurchaseError::NotEligible);
+        } else if SaleLimitManager::tx_remaining(e, &tier_symbol, &buyer) < quantity {
+            preview.error = Some(PurchaseError::TxLimitExceeded);
+        } else if SaleLimitManager::ledger_remaining(e, &tier_symbol) < quantity {
+            preview.error = Some(PurchaseError::LedgerLimitExceeded);
+        } else if wallet_cap.is_some_and(|cap| {
+            Self::balance(e, buyer.clone())
+                .checked_add(quantity)
+                .map_or(true, |held| held > cap)
+        }) {
+            preview.error = Some(PurchaseError::WalletCapExceeded);
+        }
+        if preview.error.is_some() {
+            return preview;
+        }
+
+        let config: PricingConfig = e
+            .storage()
+            .instance()
+            .get(&DataKey::Pricing(PricingKey::Config))
+            .unwrap();
+        if config.is_frozen {
+            preview.unit_price = tier.current_price;
+            match tier.current_price.checked_mul(quantity as i128) {
+                Some(total) => preview.total_cost = total,
+                None => preview.error = Some(PurchaseError::PriceOverflow),
+            }
+            return preview;
+        }
+
+        let oracle_multiplier = Self::fetch_oracle_multiplier(e, &config);
+        for i in 0..quantity {
+            let quote = match Self::compute_discounted_price(
+                e,
+                &config,
+                &tier,
+                oracle_multiplier,
+                Some(&buyer),
+                None,
+            ) {
+                Ok(quote) => quote,
+                Err(_) => {
+                    preview.error = Some(PurchaseError::PriceOverflow);
+                    return preview;
+                }
+            };
+            if i == 0 {
+                preview.unit_price = quote.price;
+            }
+            let totals = preview
+                .total_cost
+                .checked_add(quote.price)
+                .zip(preview.fees.checked_add(quote.fees()));
+            match totals {
+                Some((total_cost, fees)) => {
+                    preview.total_cost = total_cost;
+                    preview.fees = fees;
+                }
+                None => {
+                    preview.error = Some(PurchaseError::PriceOverflow);
+                    return preview;
+                }
+            }
+            preview.discount = preview
+                .discount
+                .saturating_add(DiscountManager::total(&quote.discounts));
+            tier.minted += 1;
+        }
+
+        preview
+    }
+
+    /// Cap the number of tickets a single wallet may hold (0 removes the cap)
+    pub fn set_wallet_cap(e: &Env, max_per_wallet: u32) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "set_wallet_cap", (max_per_wallet,));
+        if max_per_wallet == 0 {
+            e.storage()
+                .instance()
+                .remove(&DataKey::Tickets(TicketKey::WalletCap));
+        } else {
+            e.storage()
+                .instance()
+                .set(&DataKey::Tickets(TicketKey::WalletCap), &max_per_wallet);
+        }
+    }
+
+    /// Limit how many tickets of a tier one transaction and one ledger may sell.
+    /// `None` removes the limits.
+    pub fn set_sale_limits(e: &Env, tier_symbol: Symbol, limits: Option<SaleLimits>) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(
+            e,
+            &admin,
+            "set_sale_limits",
+            (tier_symbol.clone(), limits.clone()),
+        );
+
+        if !e
+            .storage()
+            .persistent()
+            .has(&DataKey::Tickets(TicketKey::Tier(tier_symbol.clone())))
+        {
+            panic!("Tier not found");
+        }
+        SaleLimitManager::set(e, &tier_symbol, limits);
+    }
+
+    pub fn get_sale_limits(e: &Env, tier_symbol: Symbol) -> Option<SaleLimits> {
+        SaleLimitManager::get(e, &tier_symbol)
+    }
+
+    /// Gate a tier's purchases and lottery entries on holding an asset at a
+    /// snapshot ledger. `None` removes the gate.
+    pub fn set_holder_snapshot(e: &Env, tier_symbol: Symbol, snapshot: Option<HolderSnapshot>) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(
+            e,
+            &admin,
+            "set_holder_snapshot",
+            (tier_symbol.clone(), snapshot.clone()),
+        );
+
+        if !e
+            .storage()
+            .persistent()
+            .has(&DataKey::Tickets(TicketKey::Tier(tier_symbol.clone())))
+        {
+            panic!("Tier not found");
+        }
+        EligibilityManager::set(e, &tier_symbol, snapshot);
+    }
+
+    pub fn get_holder_snapshot(e: &Env, tier_symbol: Symbol) -> Option<HolderSnapshot> {
+        EligibilityManager::get(e, &tier_symbol)
+    }
+
+    /// Prove `holding` is leaf `index` of the tier's holder snapshot. Anyone may
+    /// submit the proof; it makes `holding.holder` eligible until the snapshot changes.
+    pub fn prove_holder_eligibility(
+        e: &Env,
+        tier_symbol: Symbol,
+        holding: SnapshotHolding,
+        index: u32,
+        proof: Vec<BytesN<32>>,
+    ) {
+        EligibilityManager::prove(e, &tier_symbol, &holding, index, &proof);
+    }
+
+    pub fn is_snapshot_eligible(e: &Env, tier_symbol: Symbol, participant: Address) -> bool {
+        EligibilityManager::is_eligible(e, &tier_symbol, &participant)
+    }
+
+    /// Mint the NFT and store its ticket record under the id the base mint assigned,
+    /// so `get_ticket(id)` and `owner_of(id)` always refer to the same token.
+    fn mint_ticket(e: &Env, to: &Address, ticket: &Ticket) -> u32 {
+        let token_id = Self::issue_ticket(e, to, ticket);
+        StatsManager::record_sale(e);
+        token_id
+    }
+
+    /// Mint and store a ticket without counting it as a sale
+    fn issue_ticket(e: &Env, to: &Address, ticket: &Ticket) -> u32 {
+        let token_id = Base::sequential_mint(e, to);
+        e.storage()
+            .persistent()
+            .set(&DataKey::Tickets(TicketKey::Ticket(token_id)), ticket);
+        HistoryManager::record_mint(e, to, token_id);
+        token_id
+    }
+
+    // Batch Minting for Organizer
+    pub fn batch_mint(e: &Env, to: Address, tier_symbol: Symbol, amount: u32) -> Vec<u32> {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "batch_mint", (to.clone(), tier_symbol.clone(), amount));
+        Self::require_not_paused(e);
+
+        let token_ids = Self::mint_free(e, &to, &tier_symbol, amount);
+
+        #[allow(deprecated)]
+        e.events().publish(
+            (Symbol::new(e, "batch_mint"), to),
+            (tier_symbol, token_ids.clone()),
+        );
+
+        token_ids
+    }
+
+    /// Mint the next chunk of a large organizer mint. Start a job with
+    /// `cursor` 0 and pass back the returned cursor until it equals `amount`;
+    /// a stale or replayed cursor is rejected so no chunk is minted twice.
+    pub fn batch_mint_chunk(
+        e: &Env,
+        to: Address,
+        tier_symbol: Symbol,
+        amount: u32,
+        cursor: u32,
+    ) -> u32 {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(
+            e,
+            &admin,
+            "batch_mint_chunk",
+            (to.clone(), tier_symbol.clone(), amount, cursor),
+        );
+        Self::require_not_paused(e);
+
+        let mut job = match BatchMintManager::get(e, &to, &tier_symbol) {
+            Some(job) => {
+                if job.amount != amount {
+                    panic!("Mint job amount mismatch");
+                }
+                job
+            }
+            None => {
+                if amount == 0 {
+                    panic!("Invalid mint amount");
+                }
+                let tier: Tier = e
+                    .storage()
+                    .persistent()
+                    .get(&DataKey::Tickets(TicketKey::Tier(tier_symbol.clone())))
+                    .unwrap_or_else(|| panic!("Tier not found"));
+                if tier.minted + amount > tier.max_supply {
+                    panic!("Exceeds tier max supply");
+                }
+                MintJob { amount, minted: 0 }
+            }
+        };
+        if cursor != job.minted {
+            panic!("Stale mint cursor");
+        }
+
+        let token_ids = Self::mint_free(e, &to, &tier_symbol, BatchMintManager::next_chunk(&job));
+        job.minted += token_ids.len();
+        BatchMintManager::save(e, &to, &tier_symbol, &job);
+
+        #[allow(deprecated)]
+        e.events().publish(
+            (Symbol::new(e, "batch_mint"), to),
+            (tier_symbol, token_ids),
+        );
+
+        job.minted
+    }
+
+    pub fn get_mint_job(e: &Env, to: Address, tier_symbol: Symbol) -> Option<MintJob> {
+        BatchMintManager::get(e, &to, &tier_symbol)
+    }
+
+    /// Mint `amount` free tickets of a tier from its box office allocation
+    fn mint_free(e: &Env, to: &Address, tier_symbol: &Symbol, amount: u32) -> Vec<u32> {
+        Self::mint_free_from(e, to, tier_symbol, amount, SalesChannel::BoxOffice)
+    }
+
+    /// Mint `amount` free tickets of a tier, counted against `channel`
+    fn mint_free_from(
+        e: &Env,
+        to: &Address,
+        tier_symbol: &Symbol,
+        amount: u32,
+        channel: SalesChannel,
+    ) -> Vec<u32> {
+        let key = DataKey::Tickets(TicketKey::Tier(tier_symbol.clone()));
+        let mut tier: Tier = e
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| panic!("Tier not found"));
+
+        if tier.minted + amount > tier.max_supply {
+            panic!("Exceeds tier max supply");
+        }
+        Self::require_direct_sale(&tier, amount);
+        ChannelManager::record_sale(e, tier_symbol, &tier, channel, amount);
+
+        let mut token_ids = Vec::new(e);
+        for _ in 0..amount {
+            let ticket = Ticket {
+                tier_symbol: tier_symbol.clone(),
+                purchase_time: e.ledger().timestamp(),
+                price_paid: 0, // Admin mints are free
+                is_valid: true,
+                status: TicketStatus::Active,
+                identity_hash: None,
+                payment_token: None,
+                entry_window: Self::ticket_entry_window(e, &tier),
+                bundle_id: None,
+                guest_slots: tier.guest_slots,
+                channel: PurchaseChannel::Comp,
+            };
+            token_ids.push_back(Self::mint_ticket(e, to, &ticket));
+        }
+
+        tier.minted += amount;
+        e.storage().persistent().set(&key, &tier);
+
+        token_ids
+    }
+
+    /// Comp one ticket of the tier to each named guest out of its comp
+    /// reserve, recording the reason code given for each. Returns the token
+    /// ids in the order of `allocations`.
+    pub fn allocate_named(
+        e: &Env,
+        tier_symbol: Symbol,
+        allocations: Vec<(Address, Symbol)>,
+    ) -> Vec<u32> {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(
+            e,
+            &admin,
+            "allocate_named",
+            (tier_symbol.clone(), allocations.clone()),
+        );
+        Self::require_not_paused(e);
+
+        if allocations.is_empty() || allocations.len() > MAX_NAMED_ALLOCATIONS {
+            panic!("Invalid allocation count");
+        }
+        if ChannelManager::reserved_remaining(e, &tier_symbol, SalesChannel::Comp)
+            < allocations.len()
+        {
+            panic!("Comp reserve exhausted");
+        }
+
+        let mut token_ids = Vec::new(e);
+        for (guest, reason) in allocations.iter() {
+            let token_id = Self::mint_free_from(e, &guest, &tier_symbol, 1, SalesChannel::Comp)
+                .get(0)
+                .unwrap();
+            ConciergeManager::record(e, &tier_symbol, token_id, &reason);
+            token_ids.push_back(token_id);
+        }
+
+        #[allow(deprecated)]
+        e.events().publish(
+            (Symbol::new(e, "named_allocation"), tier_symbol),
+            (allocations, token_ids.clone()),
+        );
+
+        token_ids
+    }
+
+    /// Reason code `token_id` was allocated under by `allocate_named`
+    pub fn get_allocation_reason(e: &Env, token_id: u32) -> Option<Symbol> {
+        ConciergeManager::reason(e, token_id)
+    }
+
+    /// Tickets of the tier given out by `allocate_named`, apart from sales
+    pub fn get_named_allocation_count(e: &Env, tier_symbol: Symbol) -> u32 {
+        ConciergeManager::count(e, &tier_symbol)
+    }
+
+    // Purchase a ticket
+    pub fn purchase(e: &Env, buyer: Address, payment_token: Address, tier_symbol: Symbol) -> u32 {
+        buyer.require_auth();
+        Self::process_purchase(e, &buyer, &payment_token, tier_symbol, None, None, None)
+    }
+
+    /// Purchase a ticket redeeming a promo code
+    pub fn purchase_with_promo(
+        e: &Env,
+        buyer: Address,
+        payment_token: Address,
+        tier_symbol: Symbol,
+        promo_code: Symbol,
+    ) -> u32 {
+        buyer.require_auth();
+        Self::process_purchase(
+            e,
+            &buyer,
+            &payment_token,
+            tier_symbol,
+            Some(promo_code),
+            None,
+            None,
+        )
+    }
+
+    /// Purchase a ticket bound to a salted hash of the attendee's name.
+    /// Required for tiers with ID checks at the door.
+    pub fn purchase_with_identity(
+        e: &Env,
+        buyer: Address,
+        payment_token: Address,
+        tier_symbol: Symbol,
+        identity_hash: BytesN<32>,
+    ) -> u32 {
+        buyer.require_auth();
+        Self::process_purchase(
+            e,
+            &buyer,
+            &payment_token,
+            tier_symbol,
+            None,
+            Some(identity_hash),
+            None,
+        )
+    }
+
+    /// Purchase that can be safely retried: if the buyer already completed a
+    /// purchase under `idempotency_key`, its token id is returned and nothing
+    /// is charged or minted.
+    pub fn purchase_idempotent(
+        e: &Env,
+        buyer: Address,
+        payment_token: Address,
+        tier_symbol: Symbol,
+        idempotency_key: BytesN<32>,
+    ) -> u32 {
+        buyer.require_auth();
+        if let Some(token_ids) = IdempotencyManager::get(e, &buyer, &idempotency_key) {
+            return token_ids.get(0).unwrap();
+        }
+        let token_id =
+            Self::process_purchase(e, &buyer, &payment_token, tier_symbol, None, None, None);
+        IdempotencyManager::record(e, &buyer, &idempotency_key, &vec![e, token_id]);
+        token_id
+    }
+
+    /// Token ids minted by the buyer's purchase under `idempotency_key`, if it
+    /// completed
+    pub fn get_idempotent_purchase(
+        e: &Env,
+        buyer: Address,
+        idempotency_key: BytesN<32>,
+    ) -> Option<Vec<u32>> {
+        IdempotencyManager::get(e, &buyer, &idempotency_key)
+    }
+
+    /// Purchase paid from a token allowance the buyer granted this contract,
+    /// failing if the price is above `max_price`. The buyer authorizes only
+    /// this call, not a token transfer of the exact price, so smart wallets
+    /// that gather signatures over time (multisig, policy-based accounts)
+    /// aren't invalidated when the dynamic price moves before submission.
+    pub fn purchase_with_allowance(
+        e: &Env,
+        buyer: Address,
+        payment_token: Address,
+        tier_symbol: Symbol,
+        max_price: i128,
+    ) -> u32 {
+        buyer.require_auth();
+        Self::process_purchase(
+            e,
+            &buyer,
+            &payment_token,
+            tier_symbol,
+            None,
+            None,
+            Some(max_price),
+        )
+    }
+
+    /// Allow or revoke a relayer for `purchase_on_behalf`
+    pub fn set_relayer(e: &Env, relayer: Address, allowed: bool) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "set_relayer", (relayer.clone(), allowed));
+        RoleManager::set(e, Role::Relayer, &relayer, allowed);
+    }
+
+    pub fn is_relayer(e: &Env, relayer: Address) -> bool {
+        RoleManager::has(e, Role::Relayer, &relayer)
+    }
+
+    /// Gasless purchase: an authorized relayer submits the transaction and pays the fee,
+    /// while the buyer still authorizes the purchase arguments and pays the ticket price.
+    pub fn purchase_on_behalf(
+        e: &Env,
+        relayer: Address,
+        buyer: Address,
+        payment_token: Address,
+        tier_symbol: Symbol,
+        buyer_signature_payload: Bytes,
+    ) -> u32 {
+        relayer.require_auth();
+        if !Self::is_relayer(e, relayer) {
+            panic!("Relayer not authorized");
+        }
+
+        buyer.require_auth_for_args(
+            (
+                payment_token.clone(),
+                tier_symbol.clone(),
+                buyer_signature_payload,
+            )
+                .into_val(e),
+        );
+
+        Self::process_purchase(e, &buyer, &payment_token, tier_symbol, None, None, None)
+    }
+
+    /// ==================== CHARGE PLANS ====================
+
+    /// Let the organizer buy up to `max_charges` tickets of a tier for `buyer`
+    /// at no more than `max_price` each, paid from a token allowance the buyer
+    /// grants this contract separately. Returns the plan id.
+    pub fn create_charge_plan(
+        e: &Env,
+        buyer: Address,
+        payment_token: Address,
+        tier_symbol: Symbol,
+        max_price: i128,
+        max_charges: u32,
+    ) -> u32 {
+        buyer.require_auth();
+        if max_price <= 0 || max_charges == 0 {
+            panic!("Invalid charge plan");
+        }
+
+        PlanManager::create(
+            e,
+            &ChargePlan {
+                buyer,
+                payment_token,
+                tier_symbol,
+                max_price,
+                max_charges,
+                charges_made: 0,
+                revoked: false,
+            },
+        )
+    }
+
+    /// Stop all future charges on a plan
+    pub fn revoke_charge_plan(e: &Env, buyer: Address, plan_id: u32) {
+        buyer.require_auth();
+        let mut plan = PlanManager::get(e, plan_id).unwrap_or_else(|| panic!("Plan not found"));
+        if plan.buyer != buyer {
+            panic!("Not the plan owner");
+        }
+        plan.revoked = true;
+        PlanManager::set(e, plan_id, &plan);
+    }
+
+    /// Buy the next ticket of a plan from the buyer's allowance. Admin only.
+    pub fn charge_plan(e: &Env, plan_id: u32) -> u32 {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "charge_plan", (plan_id,));
+
+        let mut plan = PlanManager::get(e, plan_id).unwrap_or_else(|| panic!("Plan not found"));
+        if plan.revoked {
+            panic!("Plan revoked");
+        }
+        if plan.charges_made >= plan.max_charges {
+            panic!("Plan exhausted");
+        }
+        plan.charges_made += 1;
+        PlanManager::set(e, plan_id, &plan);
+
+        Self::process_purchase(
+            e,
+            &plan.buyer,
+            &plan.payment_token,
+            plan.tier_symbol,
+            None,
+            None,
+            Some(plan.max_price),
+        )
+    }
+
+    pub fn get_charge_plan(e: &Env, plan_id: u32) -> Option<ChargePlan> {
+        PlanManager::get(e, plan_id)
+    }
+
+    /// ==================== BUNDLES ====================
+
+    /// Define a bundle of tickets across tiers sold at `bundle.discount_bps` off.
+    /// Admin only. Returns the bundle id.
+    pub fn create_bundle(e: &Env, bundle: Bundle) -> u32 {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "create_bundle", (bundle.clone(),));
+        for item in bundle.items.iter() {
+            if !e
+                .storage()
+                .persistent()
+                .has(&DataKey::Tickets(TicketKey::Tier(item.tier_symbol.clone())))
+            {
+                panic!("Tier not found");
+            }
+        }
+        BundleManager::create(e, &bundle)
+    }
+
+    pub fn set_bundle_active(e: &Env, bundle_id: u32, active: bool) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "set_bundle_active", (bundle_id, active));
+        let mut bundle =
+            BundleManager::get(e, bundle_id).unwrap_or_else(|| panic!("Bundle not found"));
+        bundle.active = active;
+        BundleManager::set(e, bundle_id, &bundle);
+    }
+
+    pub fn get_bundle(e: &Env, bundle_id: u32) -> Option<Bundle> {
+        BundleManager::get(e, bundle_id)
+    }
+
+    /// Buy every ticket of a bundle in one payment. Each ticket is priced at its
+    /// tier's current price less the bundle discount; if any tier cannot sell
+    /// its units the whole purchase fails. Payment for the total is taken only
+    /// after every ticket is minted. Returns the minted token ids.
+    pub fn purchase_bundle(
+        e: &Env,
+        buyer: Address,
+        payment_token: Address,
+        bundle_id: u32,
+    ) -> Vec<u32> {
+        buyer.require_auth();
+        Self::process_bundle_purchase(e, &buyer, &payment_token, bundle_id)
+    }
+
+    /// `purchase_bundle` that can be safely retried: repeating
+    /// `idempotency_key` returns the token ids of the buyer's completed
+    /// purchase instead of buying the bundle again
+    pub fn purchase_bundle_idempotent(
+        e: &Env,
+        buyer: Address,
+        payment_token: Address,
+        bundle_id: u32,
+        idempotency_key: BytesN<32>,
+    ) -> Vec<u32> {
+        buyer.require_auth();
+        if let Some(token_ids) = IdempotencyManager::get(e, &buyer, &idempotency_key) {
+            return token_ids;
+        }
+        let token_ids = Self::process_bundle_purchase(e, &buyer, &payment_token, bundle_id);
+        IdempotencyManager::record(e, &buyer, &idempotency_key, &token_ids);
+        token_ids
+    }
+
+    fn process_bundle_purchase(
+        e: &Env,
+        buyer: &Address,
+        payment_token: &Address,
+        bundle_id: u32,
+    ) -> Vec<u32> {
+        let bundle =
+            BundleManager::get(e, bundle_id).unwrap_or_else(|| panic!("Bundle not found"));
+        if !bundle.active {
+            panic!("Bundle is not active");
+        }
+
+        let rounding_rule = RoundingManager::get(e, payment_token);
+        let reference_rate = AccountingManager::current_rate(e, payment_token);
+        let mut token_ids = Vec::new(e);
+        let mut total: i128 = 0;
+        for item in bundle.items.iter() {
+            let key = DataKey::Tickets(TicketKey::Tier(item.tier_symbol.clone()));
+            for _ in 0..item.quantity {
+                let mut tier = Self::load_sellable_tier(e, buyer, &item.tier_symbol);
+                if tier.identity_required {
+                    panic!("Identity hash required");
+                }
+                ChannelManager::record_sale(e, &item.tier_symbol, &tier, SalesChannel::General, 1);
+
+                // Rounding goes last, after the bundle discount
+                let mut quote = Self::quote_price(e, &tier, Some(buyer), None, None);
+                let discount = mul_bps(quote.price, bundle.discount_bps as i128)
+                    .unwrap_or_else(|| panic_with_error!(e, PricingError::PriceOverflow));
+                if discount > 0 {
+                    quote.discounts.push_back(AppliedDiscount {
+                        kind: DiscountKind::Bundle,
+                        amount: discount,
+                    });
+                    quote.price -= discount;
+                }
+                if let Some(rule) = &rounding_rule {
+                    quote.rounding = RoundingManager::adjustment(rule, quote.price);
+                    quote.price += quote.rounding;
+                }
+                let price = quote.price;
+                total = total
+                    .checked_add(price)
+                    .unwrap_or_else(|| panic_with_error!(e, PricingError::PriceOverflow));
+
+                let ticket = Ticket {
+                    tier_symbol: item.tier_symbol.clone(),
+                    purchase_time: e.ledger().timestamp(),
+                    price_paid: price,
+                    is_valid: true,
+                    status: TicketStatus::Active,
+                    identity_hash: None,
+                    payment_token: Some(payment_token.clone()),
+                    entry_window: Self::ticket_entry_window(e, &tier),
+                    bundle_id: Some(bundle_id),
+                    guest_slots: tier.guest_slots,
+                    channel: PurchaseChannel::Bundle,
+                };
+                let token_id = Self::mint_ticket(e, buyer, &ticket);
+                Self::issue_receipt(e, buyer, payment_token, token_id, &item.tier_symbol, &quote);
+                AccountingManager::record_sale(
+                    e,
+                    token_id,
+                    payment_token,
+                    price,
+                    reference_rate.as_ref(),
+                );
+                token_ids.push_back(token_id);
+
+                tier.minted += 1;
+                tier.current_price = price;
+                e.storage().persistent().set(&key, &tier);
+            }
+        }
+
+        let token_client = token::Client::new(e, payment_token);
+        token_client.transfer(buyer, &e.current_contract_address(), &total);
+        TreasuryManager::add_liability(e, payment_token, total);
+        for token_id in token_ids.iter() {
+            let ticket = Self::load_ticket(e, token_id);
+            HookManager::on_purchase(e, buyer, token_id, &ticket.tier_symbol, ticket.price_paid);
+        }
+
+        let mut config: PricingConfig =
+            e.storage().instance().get(&DataKey::Pricing(PricingKey::Config)).unwrap();
+        config.last_update_time = e.ledger().timestamp();
+        e.storage()
+            .instance()
+            .set(&DataKey::Pricing(PricingKey::Config), &config);
+
+        #[allow(deprecated)]
+        e.events().publish(
+            (Symbol::new(e, "bundle_purchase"), buyer.clone()),
+            (bundle_id, token_ids.clone(), total),
+        );
+
+        token_ids
+    }
+
+    /// ==================== GROUP TICKETS ====================
+
+    /// Buy `quantity` seats of a tier as one ticket for the buyer plus
+    /// `quantity - 1` companion seats to split off later. Each seat is priced
+    /// and counted against the tier like a single purchase. Payment is taken
+    /// once the ticket is minted. Returns the token id.
+    pub fn purchase_group(
+        e: &Env,
+        buyer: Address,
+        payment_token: Address,
+        tier_symbol: Symbol,
+        quantity: u32,
+    ) -> u32 {
+        buyer.require_auth();
+        if !(2..=MAX_GROUP_SIZE).contains(&quantity) {
+            panic!("Invalid group size");
+        }
+
+        let key = DataKey::Tickets(TicketKey::Tier(tier_symbol.clone()));
+        let mut total: i128 = 0;
+        // One receipt covers the group: seat prices, discounts and rounding summed
+        let mut group_quote: Option<PriceQuote> = None;
+        for _ in 0..quantity {
+            let mut tier = Self::load_sellable_tier(e, &buyer, &tier_symbol);
+            Self::require_paid_tier(&tier);
+            if tier.identity_required {
+                panic!("Identity hash required");
+            }
+            ChannelManager::record_sale(e, &tier_symbol, &tier, SalesChannel::General, 1);
+
+            let quote = Self::quote_price(e, &tier, Some(&buyer), None, Some(&payment_token));
+            let price = quote.price;
+            total = total
+                .checked_add(price)
+                .unwrap_or_else(|| panic_with_error!(e, PricingError::PriceOverflow));
+            group_quote = Some(match group_quote {
+                None => quote,
+                Some(mut sum) => {
+                    sum.base_price += quote.base_price;
+                    sum.discounts.append(&quote.discounts);
+                    sum.rounding += quote.rounding;
+                    sum.oracle_multiplier = quote.oracle_multiplier;
+                    sum.price = total;
+                    sum
+                }
+            });
+            tier.minted += 1;
+            tier.current_price = price;
+            e.storage().persistent().set(&key, &tier);
+        }
+
+        let tier: Tier = e.storage().persistent().get(&key).unwrap();
+        let ticket = Ticket {
+            tier_symbol: tier_symbol.clone(),
+            purchase_time: e.ledger().timestamp(),
+            price_paid: total,
+            is_valid: true,
+            status: TicketStatus::Active,
+            identity_hash: None,
+            payment_token: Some(payment_token.clone()),
+            entry_window: Self::ticket_entry_window(e, &tier),
+            bundle_id: None,
+            guest_slots: tier.guest_slots,
+            channel: Self::sale_channel(e, &tier_symbol, true),
+        };
+        let token_id = Self::mint_ticket(e, &buyer, &ticket);
+        GroupManager::set_seats(e, token_id, quantity - 1);
+        Self::issue_receipt(
+            e,
+            &buyer,
+            &payment_token,
+            token_id,
+            &tier_symbol,
+            &group_quote.unwrap(),
+        );
+
+        let token_client = token::Client::new(e, &payment_token);
+        token_client.transfer(&buyer, &e.current_contract_address(), &total);
+        TreasuryManager::add_liability(e, &payment_token, total);
+        let reference_rate = AccountingManager::current_rate(e, &payment_token);
+        AccountingManager::record_sale(e, token_id, &payment_token, total, reference_rate.as_ref());
+
+        #[allow(deprecated)]
+        e.events().publish(
+            (Symbol::new(e, "group_purchase"), buyer.clone()),
+            (token_id, tier_symbol.clone(), quantity, total),
+        );
+        HookManager::on_purchase(e, &buyer, token_id, &tier_symbol, total);
+
+        token_id
+    }
+
+    /// Split companion seats off a group ticket into tickets minted to
+    /// `companions`, one each, before the event starts. Companions are held to
+    /// the wallet cap and get no guest slots. Returns their token ids.
+    pub fn split_ticket(
+        e: &Env,
+        owner: Address,
+        token_id: u32,
+        companions: Vec<Address>,
+    ) -> Vec<u32> {
+        owner.require_auth();
+        Self::require_not_paused(e);
+        if Self::owner_of(e, token_id) != owner {
+            panic!("Not the ticket owner");
+        }
+        let event_info: EventInfo = e.storage().instance().get(&DataKey::EventInfo).unwrap();
+        if e.ledger().timestamp() >= event_info.start_time {
+            panic!("Event already started");
+        }
+
+        let ticket_key = DataKey::Tickets(TicketKey::Ticket(token_id));
+        let mut ticket: Ticket = e.storage().persistent().get(&ticket_key).unwrap();
+        if !ticket.is_valid || ticket.status != TicketStatus::Active {
+            panic!("Ticket not splittable");
+        }
+        let mut seats = GroupManager::seats(e, token_id);
+        if companions.is_empty() || companions.len() > seats {
+            panic!("Not enough seats");
+        }
+
+        let wallet_cap = e
+            .storage()
+            .instance()
+            .get::<_, u32>(&DataKey::Tickets(TicketKey::WalletCap));
+        let mut token_ids = Vec::new(e);
+        for companion in companions.iter() {
+            if let Some(cap) = wallet_cap {
+                if Self::balance(e, companion.clone()) >= cap {
+                    panic!("Wallet cap exceeded");
+                }
+            }
+            let share = GroupManager::seat_share(ticket.price_paid, seats);
+            ticket.price_paid -= share;
+            seats -= 1;
+
+            let mut companion_ticket = ticket.clone();
+            companion_ticket.price_paid = share;
+            companion_ticket.guest_slots = 0;
+            let companion_id = Self::issue_ticket(e, &companion, &companion_ticket);
+            StatsManager::record_split(e);
+            AccountingManager::copy_rate(e, token_id, companion_id);
+            token_ids.push_back(companion_id);
+        }
+        e.storage().persistent().set(&ticket_key, &ticket);
+        GroupManager::set_seats(e, token_id, seats);
+
+        #[allow(deprecated)]
+        e.events().publish(
+            (Symbol::new(e, "ticket_split"), owner),
+            (token_id, token_ids.clone()),
+        );
+
+        token_ids
+    }
+
+    /// Companion seats still held on a group ticket
+    pub fn get_group_seats(e: &Env, token_id: u32) -> u32 {
+        GroupManager::seats(e, token_id)
+    }
+
+    /// ==================== ASSIGNED SEATING ====================
+
+    /// Seat a valid ticket of the tier in `seat_id`, moving it from any seat
+    /// it already holds
+    pub fn assign_seat(e: &Env, tier_symbol: Symbol, seat_id: u32, token_id: u32) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "assign_seat", (tier_symbol.clone(), seat_id, token_id));
+
+        let ticket: Ticket = e
+            .storage()
+            .persistent()
+            .get(&DataKey::Tickets(TicketKey::Ticket(token_id)))
+            .unwrap_or_else(|| panic!("Ticket not found"));
+        if ticket.tier_symbol != tier_symbol {
+            panic!("Ticket is not in this tier");
+        }
+        if !ticket.is_valid {
+            panic!("Ticket is not valid");
+        }
+        SeatManager::assign(e, &tier_symbol, seat_id, token_id);
+    }
+
+    /// Free the seat held by a ticket
+    pub fn release_seat(e: &Env, token_id: u32) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "release_seat", (token_id,));
+
+        if SeatManager::seat_of(e, token_id).is_none() {
+            panic!("Ticket has no seat");
+        }
+        let ticket = Self::load_ticket(e, token_id);
+        SeatManager::release(e, &ticket.tier_symbol, token_id);
+    }
+
+    pub fn get_seat(e: &Env, token_id: u32) -> Option<u32> {
+        SeatManager::seat_of(e, token_id)
+    }
+
+    /// Bumped on every seat assignment or release in the tier, so venue
+    /// systems can tell whether their copy of the seat map is current
+    pub fn seat_version(e: &Env, tier_symbol: Symbol) -> u32 {
+        SeatManager::version(e, &tier_symbol)
+    }
+
+    /// Page through the tier's (seat_id, token_id, owner) assignments, at most
+    /// `MAX_SEAT_PAGE` at a time
+    pub fn export_seat_assignments(
+        e: &Env,
+        tier_symbol: Symbol,
+        start: u32,
+        limit: u32,
+    ) -> Vec<(u32, u32, Address)> {
+        SeatManager::page(e, &tier_symbol, start, limit)
+    }
+
+    /// Shared purchase path once the buyer's authorization has been checked.
+    /// With `allowance_cap`, payment is pulled from the buyer's token allowance
+    /// (no transfer signed by the buyer) and the price may not exceed the cap.
+    /// Returns the minted token id.
+    fn process_purchase(
+        e: &Env,
+        buyer: &Address,
+        payment_token: &Address,
+        tier_symbol: Symbol,
+        promo_code: Option<Symbol>,
+        identity_hash: Option<BytesN<32>>,
+        allowance_cap: Option<i128>,
+    ) -> u32 {
+        let key = DataKey::Tickets(TicketKey::Tier(tier_symbol.clone()));
+        let mut tier = Self::load_sellable_tier(e, buyer, &tier_symbol);
+        Self::require_paid_tier(&tier);
+        if tier.identity_required && identity_hash.is_none() {
+            panic!("Identity hash required");
+        }
+        ChannelManager::record_sale(e, &tier_symbol, &tier, SalesChannel::General, 1);
+
+        let quote = Self::quote_price(
+            e,
+            &tier,
+            Some(buyer),
+            promo_code.as_ref(),
+            Some(payment_token),
+        );
+        let price = quote.price;
+
+        // Process payment into the contract treasury
+        let token_client = token::Client::new(e, payment_token);
+        let treasury = e.current_contract_address();
+        match allowance_cap {
+            None => token_client.transfer(buyer, &treasury, &price),
+            Some(cap) => {
+                if price > cap {
+                    panic!("Price exceeds cap");
+                }
+                token_client.transfer_from(&treasury, buyer, &treasury, &price);
+            }
+        }
+        TreasuryManager::add_liability(e, payment_token, price);
+
+        // Mint Token
+        let ticket = Ticket {
+            tier_symbol: tier_symbol.clone(),
+            purchase_time: e.ledger().timestamp(),
+            price_paid: price,
+            is_valid: true,
+            status: TicketStatus::Active,
+            identity_hash,
+            payment_token: Some(payment_token.clone()),
+            entry_window: Self::ticket_entry_window(e, &tier),
+            bundle_id: None,
+            guest_slots: tier.guest_slots,
+            channel: Self::sale_channel(e, &tier_symbol, true),
+        };
+        let token_id = Self::mint_ticket(e, buyer, &ticket);
+        Self::issue_receipt(e, buyer, payment_token, token_id, &tier_symbol, &quote);
+        let reference_rate = AccountingManager::current_rate(e, payment_token);
+        AccountingManager::record_sale(e, token_id, payment_token, price, reference_rate.as_ref());
+
+        tier.minted += 1;
+        tier.current_price = price; // Update the current recorded price for this tier
+        e.storage().persistent().set(&key, &tier);
+
+        // Update pricing config last update time
+        let mut config: PricingConfig =
+            e.storage().instance().get(&DataKey::Pricing(PricingKey::Config)).unwrap();
+        config.last_update_time = e.ledger().timestamp();
+        e.storage()
+            .instance()
+            .set(&DataKey::Pricing(PricingKey::Config), &config);
+
+        #[allow(deprecated)]
+        e.events().publish(
+            (Symbol::new(e, "purchase"), buyer.clone()),
+            (token_id, tier_symbol.clone(), price),
+        );
+        HookManager::on_purchase(e, buyer, token_id, &tier_symbol, price);
+
+        token_id
+    }
+
+    /// Load a tier, check that one more ticket can be sold to `buyer` and count
+    /// it against the tier's sale limits
+    fn load_sellable_tier(e: &Env, buyer: &Address, tier_symbol: &Symbol) -> Tier {
+        Self::load_tier_for_sale(e, buyer, tier_symbol, true)
+    }
+
+    /// Free tiers are claimed with `claim_free_ticket`, never paid for
+    fn require_paid_tier(tier: &Tier) {
+        if tier.base_price == 0 {
+            panic!("Free tier, use claim_free_ticket");
+        }
+    }
+
+    /// Channel a sale of the tier is recorded under: a whitelist sale while
+    /// the tier's eligibility snapshot applies to the buyer
+    fn sale_channel(e: &Env, tier_symbol: &Symbol, check_eligibility: bool) -> PurchaseChannel {
+        if check_eligibility && EligibilityManager::get(e, tier_symbol).is_some() {
+            PurchaseChannel::Whitelist
+        } else {
+            PurchaseChannel::DirectSale
+        }
+    }
+
+    /// `load_sellable_tier`, optionally skipping the tier's eligibility rules
+    fn load_tier_for_sale(
+        e: &Env,
+        buyer: &Address,
+        tier_symbol: &Symbol,
+        check_eligibility: bool,
+    ) -> Tier {
+        Self::require_not_paused(e);
+        if CancellationManager::is_cancelled(e) {
+            panic!("Event cancelled");
+        }
+        let tier: Tier = e
+            .storage()
+            .persistent()
+            .get(&DataKey::Tickets(TicketKey::Tier(tier_symbol.clone())))
+            .unwrap_or_else(|| panic!("Tier not found"));
+
+        if !tier.active {
+            panic!("Tier is not active");
+        }
+        if tier.frozen {
+            panic!("Tier is frozen");
+        }
+        if tier.minted >= tier.max_supply {
+            panic!("Tier sold out");
+        }
+        Self::require_direct_sale(&tier, 1);
+        if check_eligibility {
+            EligibilityManager::require_eligible(e, tier_symbol, buyer);
+        }
+        if tier.minted >= Self::unlocked_supply(e, &tier, tier_symbol.clone()) {
+            panic!("Supply not yet released");
+        }
+        if let Some(cap) = e
+            .storage()
+            .instance()
+            .get::<_, u32>(&DataKey::Tickets(TicketKey::WalletCap))
+        {
+            if Self::balance(e, buyer.clone()) >= cap {
+                panic!("Wallet cap exceeded");
+            }
+        }
+        SaleLimitManager::record_sale(e, tier_symbol, buyer, 1);
+
+        tier
+    }
+
+    /// ==================== PURCHASE HOOKS ====================
+
+    /// Call `hook.address` after every sale (see `hooks` for the interface);
+    /// `None` removes the hook
+    pub fn set_purchase_hook(e: &Env, hook: Option<HookConfig>) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "set_purchase_hook", (hook.clone(),));
+        HookManager::set(e, hook);
+    }
+
+    pub fn get_purchase_hook(e: &Env) -> Option<HookConfig> {
+        HookManager::get(e)
+    }
+
+    /// ==================== RELEASE SCHEDULE ====================
+
+    /// Unlock a tier's purchasable supply in scheduled waves
+    pub fn set_release_schedule(e: &Env, tier_symbol: Symbol, schedule: ReleaseSchedule) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(
+            e,
+            &admin,
+            "set_release_schedule",
+            (tier_symbol.clone(), schedule.clone()),
+        );
+
+        if !e
+            .storage()
+            .persistent()
+            .has(&DataKey::Tickets(TicketKey::Tier(tier_symbol.clone())))
+        {
+            panic!("Tier not found");
+        }
+        if schedule.wave_size == 0 || schedule.wave_interval == 0 {
+            panic!("Invalid release schedule");
+        }
+
+        e.storage().persistent().set(
+            &DataKey::Tickets(TicketKey::ReleaseSchedule(tier_symbol)),
+            &schedule,
+        );
+    }
+
+    /// Remove a tier's schedule, releasing all remaining supply
+    pub fn clear_release_schedule(e: &Env, tier_symbol: Symbol) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "clear_release_schedule", (tier_symbol.clone(),));
+        e.storage()
+            .persistent()
+            .remove(&DataKey::Tickets(TicketKey::ReleaseSchedule(tier_symbol)));
+    }
+
+    pub fn get_release_schedule(e: &Env, tier_symbol: Symbol) -> Option<ReleaseSchedule> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Tickets(TicketKey::ReleaseSchedule(tier_symbol)))
+    }
+
+    /// Total supply of the tier that is purchasable at the current timestamp.
+    /// `None` for an unknown tier.
+    pub fn get_unlocked_supply(e: &Env, tier_symbol: Symbol) -> Option<u32> {
+        let tier: Tier = e
+            .storage()
+            .persistent()
+            .get(&DataKey::Tickets(TicketKey::Tier(tier_symbol.clone())))?;
+        Some(Self::unlocked_supply(e, &tier, tier_symbol))
+    }
+
+    fn unlocked_supply(e: &Env, tier: &Tier, tier_symbol: Symbol) -> u32 {
+        match Self::get_release_schedule(e, tier_symbol) {
+            None => tier.max_supply,
+            Some(schedule) => {
+                let now = e.ledger().timestamp();
+                if now < schedule.start_time {
+                    return 0;
+                }
+                let waves = (now - schedule.start_time) / schedule.wave_interval + 1;
+                let unlocked = (schedule.wave_size as u64).saturating_mul(waves);
+                unlocked.min(tier.max_supply as u64) as u32
+            }
+        }
+    }
+
+    /// Tickets still available to direct sales once lottery reservations are set aside
+    fn direct_sale_capacity(tier: &Tier) -> u32 {
+        // Refunded lottery tickets leave `minted` but not `lottery_minted`
+        let direct_minted = tier.minted.saturating_sub(tier.lottery_minted);
+        match tier.distribution {
+            DistributionMode::DirectSale => tier.max_supply.saturating_sub(tier.minted),
+            DistributionMode::LotteryOnly => 0,
+            DistributionMode::Mixed(reserve) => tier
+                .max_supply
+                .saturating_sub(reserve)
+                .saturating_sub(direct_minted),
+        }
+    }
+
+    fn require_direct_sale(tier: &Tier, quantity: u32) {
+        if tier.distribution == DistributionMode::LotteryOnly {
+            panic!("Tier is lottery-only");
+        }
+        if Self::direct_sale_capacity(tier) < quantity {
+            panic!("Direct sale allocation exhausted");
+        }
+    }
+
+    /// Set how a tier's supply is distributed between direct sales and lottery winners
+    pub fn set_distribution_mode(e: &Env, tier_symbol: Symbol, mode: DistributionMode) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(
+            e,
+            &admin,
+            "set_distribution_mode",
+            (tier_symbol.clone(), mode.clone()),
+        );
+
+        let key = DataKey::Tickets(TicketKey::Tier(tier_symbol));
+        let mut tier: Tier = e
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| panic!("Tier not found"));
+        if let DistributionMode::Mixed(reserve) = mode {
+            if reserve > tier.max_supply {
+                panic!("Reserve exceeds tier max supply");
+            }
+        }
+        tier.distribution = mode;
+        e.storage().persistent().set(&key, &tier);
+    }
+
+    /// Lottery winners of the tier's current round claim (and pay for) their allocated ticket.
+    /// Draws on the supply reserved for the lottery rather than direct-sale inventory.
+    pub fn claim_lottery_ticket(
+        e: &Env,
+        winner: Address,
+        payment_token: Address,
+        tier_symbol: Symbol,
+    ) -> u32 {
+        winner.require_auth();
+        Self::require_not_paused(e);
+
+        let key = DataKey::Tickets(TicketKey::Tier(tier_symbol.clone()));
+        let mut tier: Tier = e
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| panic!("Tier not found"));
+        if !tier.active {
+            panic!("Tier is not active");
+        }
+        if tier.frozen {
+            panic!("Tier is frozen");
+        }
+        if tier.minted >= tier.max_supply {
+            panic!("Tier sold out");
+        }
+
+        let round = RoundManager::current(e, &tier_symbol);
+        let results = Self::get_round_winners(e, tier_symbol.clone(), round);
+        let allocation = BackfillManager::next_unclaimed(e, &tier_symbol, round, &results, &winner)
+            .unwrap_or_else(|| panic!("No unclaimed lottery allocation"));
+        if BackfillManager::deadline(e, &tier_symbol, round, allocation.allocation_index)
+            .is_some_and(|deadline| e.ledger().timestamp() > deadline)
+        {
+            panic!("Claim window closed");
+        }
+        let claimed_key = DataKey::Lottery(LotteryKey::Claimed(
+            tier_symbol.clone(),
+            round,
+            winner.clone(),
+        ));
+        let claimed = BackfillManager::claimed(e, &tier_symbol, round, &winner);
+        ChannelManager::record_sale(e, &tier_symbol, &tier, SalesChannel::Lottery, 1);
+
+        let quote = Self::quote_price(e, &tier, Some(&winner), None, Some(&payment_token));
+        let price = quote.price;
+        token::Client::new(e, &payment_token).transfer(
+            &winner,
+            &e.current_contract_address(),
+            &price,
+        );
+        TreasuryManager::add_liability(e, &payment_token, price);
+
+        let ticket = Ticket {
+            tier_symbol: tier_symbol.clone(),
+            purchase_time: e.ledger().timestamp(),
+            price_paid: price,
+            is_valid: true,
+            status: TicketStatus::Active,
+            identity_hash: None,
+            payment_token: Some(payment_token.clone()),
+            entry_window: Self::ticket_entry_window(e, &tier),
+            bundle_id: None,
+            guest_slots: tier.guest_slots,
+            channel: PurchaseChannel::Lottery,
+        };
+        let token_id = Self::mint_ticket(e, &winner, &ticket);
+        Self::issue_receipt(e, &winner, &payment_token, token_id, &tier_symbol, &quote);
+        let reference_rate = AccountingManager::current_rate(e, &payment_token);
+        AccountingManager::record_sale(e, token_id, &payment_token, price, reference_rate.as_ref());
+
+        tier.minted += 1;
+        tier.lottery_minted += 1;
+        e.storage().persistent().set(&key, &tier);
+        e.storage().persistent().set(&claimed_key, &(claimed + 1));
+        HookManager::on_purchase(e, &winner, token_id, &tier_symbol, price);
+
+        token_id
+    }
+
+    /// ==================== SALES CHANNELS ====================
+
+    /// Hold back `quantity` tickets of a tier for a sales channel.
+    /// Purchases sell through `General`, organizer mints through `BoxOffice`,
+    /// lottery claims through `Lottery`, and invitation claims through `Invitation`.
+    pub fn set_channel_allocation(
+        e: &Env,
+        tier_symbol: Symbol,
+        channel: SalesChannel,
+        quantity: u32,
+    ) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(
+            e,
+            &admin,
+            "set_channel_allocation",
+            (tier_symbol.clone(), channel.clone(), quantity),
+        );
+
+        let tier: Tier = e
+            .storage()
+            .persistent()
+            .get(&DataKey::Tickets(TicketKey::Tier(tier_symbol.clone())))
+            .unwrap_or_else(|| panic!("Tier not found"));
+        ChannelManager::set_allocation(e, &tier_symbol, &tier, channel, quantity);
+    }
+
+    /// Roll a channel's unsold reserved inventory back into general sale
+    pub fn release_channel(e: &Env, tier_symbol: Symbol, channel: SalesChannel) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(
+            e,
+            &admin,
+            "release_channel",
+            (tier_symbol.clone(), channel.clone()),
+        );
+        ChannelManager::release(e, &tier_symbol, channel);
+    }
+
+    pub fn get_channel_allocations(
+        e: &Env,
+        tier_symbol: Symbol,
+    ) -> Map<SalesChannel, ChannelAllocation> {
+        ChannelManager::get_allocations(e, &tier_symbol)
+    }
+
+    /// ==================== INVITATIONS ====================
+
+    /// Hold one ticket of a tier for a guest who has no wallet yet.
+    /// `invite_hash` is the sha256 of a secret sent to the guest (e.g. by email);
+    /// the first wallet to present the secret before `expiry` receives the ticket.
+    pub fn create_invitation(e: &Env, tier_symbol: Symbol, invite_hash: BytesN<32>, expiry: u64) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(
+            e,
+            &admin,
+            "create_invitation",
+            (tier_symbol.clone(), invite_hash.clone(), expiry),
+        );
+
+        if expiry <= e.ledger().timestamp() {
+            panic!("Invitation already expired");
+        }
+        let key = DataKey::Tickets(TicketKey::Invitation(invite_hash));
+        if e.storage().persistent().has(&key) {
+            panic!("Invitation already exists");
+        }
+
+        let tier: Tier = e
+            .storage()
+            .persistent()
+            .get(&DataKey::Tickets(TicketKey::Tier(tier_symbol.clone())))
+            .unwrap_or_else(|| panic!("Tier not found"));
+        Self::require_direct_sale(&tier, 1);
+        ChannelManager::reserve(e, &tier_symbol, &tier, SalesChannel::Invitation, 1);
+
+        e.storage().persistent().set(
+            &key,
+            &Invitation {
+                tier_symbol,
+                expiry,
+            },
+        );
+    }
+
+    /// Mint the invited ticket to `recipient`, typically a freshly created
+    /// wallet. The tier must still be on sale.
+    pub fn claim_invitation(e: &Env, recipient: Address, preimage: Bytes) -> u32 {
+        recipient.require_auth();
+
+        let invite_hash = e.crypto().sha256(&preimage).to_bytes();
+        let invite_key = DataKey::Tickets(TicketKey::Invitation(invite_hash));
+        let invitation: Invitation = e
+            .storage()
+            .persistent()
+            .get(&invite_key)
+            .unwrap_or_else(|| panic!("Invitation not found"));
+        if e.ledger().timestamp() > invitation.expiry {
+            panic!("Invitation expired");
+        }
+
+        let tier_key = DataKey::Tickets(TicketKey::Tier(invitation.tier_symbol.clone()));
+        let mut tier = Self::load_sellable_tier(e, &recipient, &invitation.tier_symbol);
+        ChannelManager::record_sale(
+            e,
+            &invitation.tier_symbol,
+            &tier,
+            SalesChannel::Invitation,
+            1,
+        );
+
+        let ticket = Ticket {
+            tier_symbol: invitation.tier_symbol.clone(),
+            purchase_time: e.ledger().timestamp(),
+            price_paid: 0, // Invitations are comped
+            is_valid: true,
+            status: TicketStatus::Active,
+            identity_hash: None,
+            payment_token: None,
+            entry_window: Self::ticket_entry_window(e, &tier),
+            bundle_id: None,
+            guest_slots: tier.guest_slots,
+            channel: PurchaseChannel::Comp,
+        };
+        let token_id = Self::mint_ticket(e, &recipient, &ticket);
+
+        tier.minted += 1;
+        e.storage().persistent().set(&tier_key, &tier);
+        e.storage().persistent().remove(&invite_key);
+
+        #[allow(deprecated)]
+        e.events().publish(
+            (Symbol::new(e, "invitation_claimed"), recipient),
+            (token_id, invitation.tier_symbol),
+        );
+
+        token_id
+    }
+
+    /// Release the ticket held by an expired, unclaimed invitation back to
+    /// general sale. Anyone may call this once the invitation has expired.
+    pub fn expire_invitation(e: &Env, invite_hash: BytesN<32>) {
+        let key = DataKey::Tickets(TicketKey::Invitation(invite_hash));
+        let invitation: Invitation = e
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| panic!("Invitation not found"));
+        if e.ledger().timestamp() <= invitation.expiry {
+            panic!("Invitation not expired");
+        }
+
+        ChannelManager::unreserve(e, &invitation.tier_symbol, SalesChannel::Invitation, 1);
+        e.storage().persistent().remove(&key);
+    }
+
+    pub fn get_invitation(e: &Env, invite_hash: BytesN<32>) -> Option<Invitation> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Tickets(TicketKey::Invitation(invite_hash)))
+    }
+
+    /// ==================== PROMOTIONAL OFFERS ====================
+
+    /// Offer `to` one free ticket of the tier. The ticket is held out of
+    /// general sale and only minted if `to` accepts within the offer window.
+    /// Returns the offer id.
+    pub fn offer_ticket(e: &Env, to: Address, tier_symbol: Symbol) -> u32 {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "offer_ticket", (to.clone(), tier_symbol.clone()));
+        Self::require_not_paused(e);
+
+        let tier: Tier = e
+            .storage()
+            .persistent()
+            .get(&DataKey::Tickets(TicketKey::Tier(tier_symbol.clone())))
+            .unwrap_or_else(|| panic!("Tier not found"));
+        Self::require_direct_sale(&tier, 1);
+        ChannelManager::reserve(e, &tier_symbol, &tier, SalesChannel::Offer, 1);
+
+        let offer_id = OfferManager::create(e, &to, &tier_symbol);
+
+        #[allow(deprecated)]
+        e.events().publish(
+            (Symbol::new(e, "ticket_offered"), to),
+            (offer_id, tier_symbol),
+        );
+
+        offer_id
+    }
+
+    /// Accept an open offer, minting its ticket to the recipient
+    pub fn accept_offer(e: &Env, offer_id: u32) -> u32 {
+        Self::require_not_paused(e);
+        let offer = OfferManager::take(e, offer_id);
+        offer.recipient.require_auth();
+        if e.ledger().timestamp() > offer.expiry {
+            panic!("Offer expired");
+        }
+
+        let token_id = Self::mint_free_from(
+            e,
+            &offer.recipient,
+            &offer.tier_symbol,
+            1,
+            SalesChannel::Offer,
+        )
+        .get(0)
+        .unwrap();
+
+        #[allow(deprecated)]
+        e.events().publish(
+            (Symbol::new(e, "offer_accepted"), offer.recipient),
+            (offer_id, token_id),
+        );
+
+        token_id
+    }
+
+    /// Let the recipient turn an offer down, returning its ticket to general sale
+    pub fn decline_offer(e: &Env, offer_id: u32) {
+        let offer = OfferManager::take(e, offer_id);
+        offer.recipient.require_auth();
+        ChannelManager::unreserve(e, &offer.tier_symbol, SalesChannel::Offer, 1);
+    }
+
+    /// Release the ticket held by a lapsed offer back to general sale.
+    /// Anyone may call this once the acceptance window has passed.
+    pub fn expire_offer(e: &Env, offer_id: u32) {
+        let offer = OfferManager::take(e, offer_id);
+        if e.ledger().timestamp() <= offer.expiry {
+            panic!("Offer not expired");
+        }
+        ChannelManager::unreserve(e, &offer.tier_symbol, SalesChannel::Offer, 1);
+    }
+
+    pub fn get_offer(e: &Env, offer_id: u32) -> Option<TicketOffer> {
+        OfferManager::get(e, offer_id)
+    }
+
+    /// ==================== PRICE-LOCK CREDITS ====================
+
+    /// Open (or with `None`, close) sales of credits redeemable for any tier
+    /// priced up to `value_cap` until `deadline`
+    pub fn set_credit_offer(e: &Env, offer: Option<CreditOffer>) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "set_credit_offer", (offer.clone(),));
+
+        if let Some(offer) = &offer {
+            if offer.price <= 0 || offer.value_cap <= 0 {
+                panic!("Invalid credit offer");
+            }
+            if offer.deadline <= e.ledger().timestamp() {
+                panic!("Credit deadline already passed");
+            }
+        }
+        CreditManager::set_offer(e, offer);
+    }
+
+    pub fn get_credit_offer(e: &Env) -> Option<CreditOffer> {
+        CreditManager::get_offer(e)
+    }
+
+    /// Buy one credit at the current offer. An address holds at most one credit.
+    pub fn purchase_credit(e: &Env, buyer: Address, payment_token: Address) {
+        buyer.require_auth();
+        Self::require_not_paused(e);
+
+        let offer = CreditManager::get_offer(e).unwrap_or_else(|| panic!("Credit sales closed"));
+        if e.ledger().timestamp() > offer.deadline {
+            panic!("Credit sales closed");
+        }
+        if CreditManager::get(e, &buyer).is_some() {
+            panic!("Credit already held");
+        }
+
+        token::Client::new(e, &payment_token).transfer(
+            &buyer,
+            &e.current_contract_address(),
+            &offer.price,
+        );
+        TreasuryManager::add_liability(e, &payment_token, offer.price);
+
+        let credit = Credit {
+            payment_token,
+            amount_paid: offer.price,
+            value_cap: offer.value_cap,
+            deadline: offer.deadline,
+        };
+        CreditManager::set(e, &buyer, &credit);
+
+        #[allow(deprecated)]
+        e.events().publish(
+            (Symbol::new(e, "credit_purchased"), buyer),
+            (credit.amount_paid, credit.value_cap),
+        );
+    }
+
+    /// Exchange the buyer's credit for a ticket of `tier_symbol`, provided the
+    /// tier's current price is within the credit's value cap. The ticket records
+    /// the amount paid for the credit, which is what a later refund returns.
+    pub fn redeem_credit(e: &Env, buyer: Address, tier_symbol: Symbol) -> u32 {
+        buyer.require_auth();
+
+        let credit = CreditManager::get(e, &buyer).unwrap_or_else(|| panic!("No credit held"));
+        if e.ledger().timestamp() > credit.deadline {
+            panic!("Credit expired");
+        }
+
+        let key = DataKey::Tickets(TicketKey::Tier(tier_symbol.clone()));
+        let mut tier = Self::load_sellable_tier(e, &buyer, &tier_symbol);
+        if tier.identity_required {
+            panic!("Identity hash required");
+        }
+        let price =
+            Self::quote_price(e, &tier, Some(&buyer), None, Some(&credit.payment_token)).price;
+        if price > credit.value_cap {
+            panic!("Tier price exceeds credit value");
+        }
+        ChannelManager::record_sale(e, &tier_symbol, &tier, SalesChannel::General, 1);
+
+        let ticket = Ticket {
+            tier_symbol: tier_symbol.clone(),
+            purchase_time: e.ledger().timestamp(),
+            price_paid: credit.amount_paid,
+            is_valid: true,
+            status: TicketStatus::Active,
+            identity_hash: None,
+            payment_token: Some(credit.payment_token.clone()),
+            entry_window: Self::ticket_entry_window(e, &tier),
+            bundle_id: None,
+            guest_slots: tier.guest_slots,
+            channel: PurchaseChannel::DirectSale,
+        };
+        let token_id = Self::mint_ticket(e, &buyer, &ticket);
+        CreditManager::remove(e, &buyer);
+        // Credits are valued when redeemed, as that is when they become a sale
+        let reference_rate = AccountingManager::current_rate(e, &credit.payment_token);
+        AccountingManager::record_sale(
+            e,
+            token_id,
+            &credit.payment_token,
+            credit.amount_paid,
+            reference_rate.as_ref(),
+        );
+
+        tier.minted += 1;
+        e.storage().persistent().set(&key, &tier);
+
+        #[allow(deprecated)]
+        e.events().publish(
+            (Symbol::new(e, "credit_redeemed"), buyer.clone()),
+            (token_id, tier_symbol.clone(), credit.amount_paid),
+        );
+        HookManager::on_purchase(e, &buyer, token_id, &tier_symbol, credit.amount_paid);
+
+        token_id
+    }
+
+    /// Return the amount paid for an unredeemed credit once its deadline has passed
+    pub fn refund_credit(e: &Env, holder: Address) -> i128 {
+        holder.require_auth();
+        Self::require_not_paused(e);
+
+        let credit = CreditManager::get(e, &holder).unwrap_or_else(|| panic!("No credit held"));
+        if e.ledger().timestamp() <= credit.deadline {
+            panic!("Credit not yet expired");
+        }
+
+        token::Client::new(e, &credit.payment_token).transfer(
+            &e.current_contract_address(),
+            &holder,
+            &credit.amount_paid,
+        );
+        TreasuryManager::release_liability(e, &credit.payment_token, credit.amount_paid);
+        CreditManager::remove(e, &holder);
+
+        credit.amount_paid
+    }
+
+    pub fn get_credit(e: &Env, holder: Address) -> Option<Credit> {
+        CreditManager::get(e, &holder)
+    }
+
+    /// ==================== FIAT SETTLEMENT ====================
+
+    /// Allow or revoke a trusted payment processor (card / fiat on-ramp)
+    pub fn set_payment_processor(e: &Env, processor: Address, allowed: bool) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "set_payment_processor", (processor.clone(), allowed));
+        RoleManager::set(e, Role::PaymentProcessor, &processor, allowed);
+    }
+
+    pub fn is_payment_processor(e: &Env, processor: Address) -> bool {
+        RoleManager::has(e, Role::PaymentProcessor, &processor)
+    }
+
+    fn require_payment_processor(e: &Env, processor: &Address) {
+        processor.require_auth();
+        if !Self::is_payment_processor(e, processor.clone()) {
+            panic!("Payment processor not authorized");
+        }
+    }
+
+    /// Reserve and mint a ticket whose off-chain payment has not settled yet.
+    /// The ticket is not valid for entry until `confirm_payment` is called.
+    pub fn issue_pending(
+        e: &Env,
+        processor: Address,
+        buyer: Address,
+        tier_symbol: Symbol,
+        payment_ref: String,
+    ) -> u32 {
+        Self::require_payment_processor(e, &processor);
+
+        let pending_key = DataKey::Tickets(TicketKey::PendingPayment(payment_ref));
+        if e.storage().persistent().has(&pending_key) {
+            panic!("Payment reference already used");
+        }
+
+        let tier_key = DataKey::Tickets(TicketKey::Tier(tier_symbol.clone()));
+        let mut tier = Self::load_sellable_tier(e, &buyer, &tier_symbol);
+        ChannelManager::record_sale(e, &tier_symbol, &tier, SalesChannel::General, 1);
+        let price = Self::quote_price(e, &tier, Some(&buyer), None, None).price;
+
+        let ticket = Ticket {
+            tier_symbol: tier_symbol.clone(),
+            purchase_time: e.ledger().timestamp(),
+            price_paid: price,
+            is_valid: false,
+            status: TicketStatus::PendingPayment,
+            identity_hash: None,
+            payment_token: None,
+            entry_window: Self::ticket_entry_window(e, &tier),
+            bundle_id: None,
+            guest_slots: tier.guest_slots,
+            channel: PurchaseChannel::DirectSale,
+        };
+        let token_id = Self::mint_ticket(e, &buyer, &ticket);
+
+        tier.minted += 1;
+        e.storage().persistent().set(&tier_key, &tier);
+
+        let pending = PendingPayment {
+            token_id,
+            buyer,
+            processor,
+            issued_at: e.ledger().timestamp(),
+        };
+        e.storage().persistent().set(&pending_key, &pending);
+
+        token_id
+    }
+
+    /// Settle a pending payment, making its ticket valid
+    pub fn confirm_payment(e: &Env, processor: Address, payment_ref: String) {
+        Self::require_payment_processor(e, &processor);
+
+        let pending_key = DataKey::Tickets(TicketKey::PendingPayment(payment_ref));
+        let pending: PendingPayment = e
+            .storage()
+            .persistent()
+            .get(&pending_key)
+            .unwrap_or_else(|| panic!("Pending payment not found"));
+
+        let ticket_key = DataKey::Tickets(TicketKey::Ticket(pending.token_id));
+        let mut ticket: Ticket = e.storage().persistent().get(&ticket_key).unwrap();
+        ticket.is_valid = true;
+        ticket.status = TicketStatus::Active;
+        e.storage().persistent().set(&ticket_key, &ticket);
+
+        e.storage().persistent().remove(&pending_key);
+    }
+
+    /// Cancel a failed payment: burn its ticket and return the seat to the tier
+    pub fn void_payment(e: &Env, processor: Address, payment_ref: String) {
+        Self::require_payment_processor(e, &processor);
+
+        let pending_key = DataKey::Tickets(TicketKey::PendingPayment(payment_ref));
+        let pending: PendingPayment = e
+            .storage()
+            .persistent()
+            .get(&pending_key)
+            .unwrap_or_else(|| panic!("Pending payment not found"));
+
+        let ticket_key = DataKey::Tickets(TicketKey::Ticket(pending.token_id));
+        let mut ticket: Ticket = e.storage().persistent().get(&ticket_key).unwrap();
+        ticket.is_valid = false;
+        ticket.status = TicketStatus::Voided;
+        e.storage().persistent().set(&ticket_key, &ticket);
+        Base::burn(e, &pending.buyer, pending.token_id);
+        StatsManager::record_void(e, &ticket.tier_symbol);
+        SeatManager::release(e, &ticket.tier_symbol, pending.token_id);
+
+        let tier_key = DataKey::Tickets(TicketKey::Tier(ticket.tier_symbol));
+        let mut tier: Tier = e.storage().persistent().get(&tier_key).unwrap();
+        tier.minted -= 1;
+        e.storage().persistent().set(&tier_key, &tier);
+
+        e.storage().persistent().remove(&pending_key);
+    }
+
+    pub fn get_pending_payment(e: &Env, payment_ref: String) -> Option<PendingPayment> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Tickets(TicketKey::PendingPayment(payment_ref)))
+    }
+
+    /// ==================== WILL CALL ====================
+
+    /// Buy a ticket as `agent` for an attendee to collect at the venue. The
+    /// contract holds the ticket, not valid for entry, until it is released
+    /// with the code hashing to `code_hash`. Returns the token id.
+    pub fn purchase_for_pickup(
+        e: &Env,
+        agent: Address,
+        payment_token: Address,
+        tier_symbol: Symbol,
+        code_hash: BytesN<32>,
+    ) -> u32 {
+        agent.require_auth();
+        let token_id =
+            Self::process_purchase(e, &agent, &payment_token, tier_symbol, None, None, None);
+
+        let ticket_key = DataKey::Tickets(TicketKey::Ticket(token_id));
+        let mut ticket: Ticket = e.storage().persistent().get(&ticket_key).unwrap();
+        ticket.is_valid = false;
+        ticket.status = TicketStatus::HeldForPickup;
+        e.storage().persistent().set(&ticket_key, &ticket);
+        let holder = e.current_contract_address();
+        Base::update(e, Some(&agent), Some(&holder), token_id);
+        WillCallManager::hold(
+            e,
+            token_id,
+            &PickupHold {
+                agent: agent.clone(),
+                code_hash,
+            },
+        );
+
+        #[allow(deprecated)]
+        e.events()
+            .publish((Symbol::new(e, "held_for_pickup"), agent), token_id);
+        token_id
+    }
+
+    /// Hand a will-call ticket to `recipient` once they present its pickup
+    /// code. The ticket becomes valid and stays bound to their wallet.
+    pub fn release_pickup(e: &Env, token_id: u32, code_preimage: Bytes, recipient: Address) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        Self::require_not_paused(e);
+
+        let hold = WillCallManager::release(e, token_id, &code_preimage);
+        if let Some(cap) = e
+            .storage()
+            .instance()
+            .get::<_, u32>(&DataKey::Tickets(TicketKey::WalletCap))
+        {
+            if Self::balance(e, recipient.clone()) >= cap {
+                panic!("Wallet cap exceeded");
+            }
+        }
+
+        let ticket_key = DataKey::Tickets(TicketKey::Ticket(token_id));
+        let mut ticket: Ticket = e.storage().persistent().get(&ticket_key).unwrap();
+        ticket.is_valid = true;
+        ticket.status = TicketStatus::Active;
+        e.storage().persistent().set(&ticket_key, &ticket);
+        let holder = e.current_contract_address();
+        Base::update(e, Some(&holder), Some(&recipient), token_id);
+        HistoryManager::record_mint(e, &recipient, token_id);
+
+        #[allow(deprecated)]
+        e.events().publish(
+            (Symbol::new(e, "pickup_released"), recipient),
+            (token_id, hold.agent),
+        );
+    }
+
+    pub fn get_pickup_hold(e: &Env, token_id: u32) -> Option<PickupHold> {
+        WillCallManager::get(e, token_id)
+    }
+
+    /// ==================== RECEIPTS ====================
+
+    pub fn get_receipt(e: &Env, receipt_id: u32) -> Option<Receipt> {
+        ReceiptManager::get(e, receipt_id)
+    }
+
+    /// Receipt ids issued to `buyer`, oldest first
+    pub fn get_buyer_receipts(e: &Env, buyer: Address) -> Vec<u32> {
+        ReceiptManager::get_buyer_receipts(e, &buyer)
+    }
+
+    /// Issue a transferable receipt token alongside purchases of a tier
+    pub fn set_receipt_token_enabled(e: &Env, tier_symbol: Symbol, enabled: bool) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(
+            e,
+            &admin,
+            "set_receipt_token_enabled",
+            (tier_symbol.clone(), enabled),
+        );
+        if !e
+            .storage()
+            .persistent()
+            .has(&DataKey::Tickets(TicketKey::Tier(tier_symbol.clone())))
+        {
+            panic!("Tier not found");
+        }
+        ReceiptTokenManager::set_enabled(e, &tier_symbol, enabled);
+    }
+
+    pub fn is_receipt_token_enabled(e: &Env, tier_symbol: Symbol) -> bool {
+        ReceiptTokenManager::is_enabled(e, &tier_symbol)
+    }
+
+    pub fn get_receipt_token(e: &Env, receipt_id: u32) -> Option<ReceiptToken> {
+        ReceiptTokenManager::get(e, receipt_id)
+    }
+
+    /// Move a receipt token to another wallet. The ticket itself stays put.
+    pub fn transfer_receipt_token(e: &Env, from: Address, to: Address, receipt_id: u32) {
+        from.require_auth();
+        ReceiptTokenManager::transfer(e, &from, &to, receipt_id);
+    }
+
+    /// Record the receipt for a paid ticket, with a receipt token if its tier has them
+    fn issue_receipt(
+        e: &Env,
+        buyer: &Address,
+        payment_token: &Address,
+        token_id: u32,
+        tier_symbol: &Symbol,
+        quote: &PriceQuote,
+    ) {
+        let receipt_id = ReceiptManager::record(e, buyer, payment_token, vec![e, token_id], quote);
+        if ReceiptTokenManager::is_enabled(e, tier_symbol) {
+            ReceiptTokenManager::mint(e, &ReceiptManager::get(e, receipt_id).unwrap());
+        }
+    }
+
+    // Refund a ticket. Fails with a `RefundError` when the owner has used up
+    // their refund allowance under the configured `RefundLimits`.
+    pub fn refund(
+        e: &Env,
+        owner: Address,
+        payment_token: Address,
+        token_id: u32,
+    ) -> Result<(), RefundError> {
+        owner.require_auth();
+
+        let current_owner = Self::owner_of(e, token_id);
+        if owner != current_owner {
+            panic!("Not the ticket owner");
+        }
+
+        Self::process_refund(e, &owner, &payment_token, token_id)?;
+        Ok(())
+    }
+
+    /// Cap refunds per address over the event (`None` removes the caps).
+    /// Goes through the timelock
+    pub fn set_refund_limits(e: &Env, limits: Option<RefundLimits>) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "set_refund_limits", (limits.clone(),));
+        RefundLimitManager::check_limits(&limits);
+        TimelockManager::submit(e, ConfigChange::RefundLimits(limits));
+    }
+
+    pub fn get_refund_limits(e: &Env) -> Option<RefundLimits> {
+        RefundLimitManager::get_limits(e)
+    }
+
+    /// Choose how much of the price paid a refund returns. Goes through the timelock
+    pub fn set_refund_mode(e: &Env, mode: RefundMode) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "set_refund_mode", (mode.clone(),));
+        RefundPolicyManager::check_mode(&mode);
+        TimelockManager::submit(e, ConfigChange::RefundMode(mode));
+    }
+
+    pub fn get_refund_mode(e: &Env) -> RefundMode {
+        RefundPolicyManager::get_mode(e)
+    }
+
+    /// What refunding `ticket` pays out under the current refund mode.
+    /// Tickets not paid on-chain pay nothing.
+    fn refund_payout(e: &Env, token_id: u32, ticket: &Ticket) -> i128 {
+        let Some(paid_in) = &ticket.payment_token else {
+            return 0;
+        };
+        if CancellationManager::is_cancelled(e) {
+            return ticket.price_paid;
+        }
+        RefundPolicyManager::payout(&RefundPolicyManager::get_mode(e), ticket.price_paid, || {
+            let tier: Tier = e
+                .storage()
+                .persistent()
+                .get(&DataKey::Tickets(TicketKey::Tier(ticket.tier_symbol.clone())))
+                .unwrap();
+            // A group ticket is compared against the current price of every seat it holds
+            let admits = 1 + GroupManager::seats(e, token_id) as i128;
+            Self::quote_price(e, &tier, None, None, Some(paid_in))
+                .price
+                .saturating_mul(admits)
+        })
+    }
+
+    /// Refunds `owner` has taken through `refund`, for abuse analytics
+    pub fn get_refund_stats(e: &Env, owner: Address) -> RefundStats {
+        RefundLimitManager::get_stats(e, &owner)
+    }
+
+    /// Refund-policy checks, treasury payout and burn shared by all refund paths.
+    /// Returns the amount refunded.
+    fn process_refund(
+        e: &Env,
+        owner: &Address,
+        payment_token: &Address,
+        token_id: u32,
+    ) -> Result<i128, RefundError> {
+        Self::require_not_paused(e);
+        let ticket: Ticket = e
+            .storage()
+            .persistent()
+            .get(&DataKey::Tickets(TicketKey::Ticket(token_id)))
+            .unwrap();
+
+        let event_info: EventInfo = e.storage().instance().get(&DataKey::EventInfo).unwrap();
+        if e.ledger().timestamp() > event_info.refund_cutoff_time
+            && !FreezeGraceManager::covers(e, &ticket)
+            && !CancellationManager::is_cancelled(e)
+        {
+            panic!("Refund window closed");
+        }
+        if !ticket.is_valid {
+            panic!("Ticket already invalidated");
+        }
+        if ticket.status == TicketStatus::CheckedIn {
+            panic!("Ticket already checked in");
+        }
+        // Every refund path counts against the owner's `RefundLimits`
+        RefundLimitManager::record(e, owner, Self::refund_payout(e, token_id, &ticket))?;
+
+        // Refund from the contract treasury in the token the ticket was paid in,
+        // as much as the refund mode allows; the rest is kept as revenue.
+        // Tickets not paid on-chain are burned without a payout.
+        let refunded = match &ticket.payment_token {
+            Some(paid_in) => {
+                if paid_in != payment_token {
+                    panic!("Payment token mismatch");
+                }
+                let payout = Self::refund_payout(e, token_id, &ticket);
+                BondManager::cover_shortfall(e, paid_in, payout);
+                let token_client = token::Client::new(e, paid_in);
+                token_client.transfer(&e.current_contract_address(), owner, &payout);
+                TreasuryManager::release_liability(e, paid_in, ticket.price_paid);
+                AccountingManager::record_refund(e, token_id, paid_in, payout);
+                payout
+            }
+            None => 0,
+        };
+
+        Self::retire_refunded(e, owner, token_id, ticket);
+        Ok(refunded)
+    }
+
+    /// Invalidate and burn a refunded ticket, returning its slot to the tier
+    fn retire_refunded(e: &Env, owner: &Address, token_id: u32, mut ticket: Ticket) {
+        ticket.is_valid = false;
+        ticket.status = TicketStatus::Refunded;
+        e.storage()
+            .persistent()
+            .set(&DataKey::Tickets(TicketKey::Ticket(token_id)), &ticket);
+        Base::burn(e, owner, token_id);
+        ContactManager::revoke(e, token_id);
+        ResaleManager::delist(e, token_id);
+        StatsManager::record_refund(e, &ticket.tier_symbol);
+        SeatManager::release(e, &ticket.tier_symbol, token_id);
+
+        // Return the slot, and any unsplit group seats, to the tier so they can
+        // be sold again at the current price
+        let seats = GroupManager::seats(e, token_id);
+        GroupManager::set_seats(e, token_id, 0);
+        let tier_key = DataKey::Tickets(TicketKey::Tier(ticket.tier_symbol));
+        let mut tier: Tier = e.storage().persistent().get(&tier_key).unwrap();
+        tier.minted -= 1 + seats;
+        e.storage().persistent().set(&tier_key, &tier);
+    }
+
+    /// ==================== CAPPED RESALE ====================
+
+    /// Let holders of the tier's tickets resell them under `policy`; `None`
+    /// makes them soulbound again. Open listings keep the price cap worked
+    /// out when they were listed but pay the royalty in force when bought.
+    pub fn set_resale_policy(e: &Env, tier_symbol: Symbol, policy: Option<ResalePolicy>) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(
+            e,
+            &admin,
+            "set_resale_policy",
+            (tier_symbol.clone(), policy.clone()),
+        );
+
+        if !e
+            .storage()
+            .persistent()
+            .has(&DataKey::Tickets(TicketKey::Tier(tier_symbol.clone())))
+        {
+            panic!("Tier not found");
+        }
+        ResaleManager::set_policy(e, &tier_symbol, policy);
+    }
+
+    pub fn get_resale_policy(e: &Env, tier_symbol: Symbol) -> Option<ResalePolicy> {
+        ResaleManager::get_policy(e, &tier_symbol)
+    }
+
+    /// List an unused ticket for resale at `price`, in the token it was paid
+    /// in. Replaces any earlier listing of the ticket.
+    pub fn list_for_resale(e: &Env, token_id: u32, price: i128) {
+        let seller = Self::owner_of(e, token_id);
+        seller.require_auth();
+        let ticket = Self::load_ticket(e, token_id);
+        let policy = Self::check_resale(e, &ticket, price);
+        let price_cap = policy.price_cap(e, ticket.price_paid);
+        if price > price_cap {
+            panic!("Price exceeds resale cap");
+        }
+        ResaleManager::list(
+            e,
+            token_id,
+            &Listing {
+                seller,
+                price,
+                price_cap,
+            },
+        );
+    }
+
+    pub fn delist(e: &Env, token_id: u32) {
+        let listing =
+            ResaleManager::get_listing(e, token_id).unwrap_or_else(|| panic!("Not listed"));
+        listing.seller.require_auth();
+        ResaleManager::delist(e, token_id);
+    }
+
+    pub fn get_listing(e: &Env, token_id: u32) -> Option<Listing> {
+        ResaleManager::get_listing(e, token_id)
+    }
+
+    /// Buy a listed ticket. The royalty goes to the payout address and the
+    /// rest of the price to the seller; the ticket then belongs to `buyer`.
+    pub fn buy_listed(e: &Env, buyer: Address, token_id: u32, payment_token: Address) {
+        buyer.require_auth();
+        let listing =
+            ResaleManager::get_listing(e, token_id).unwrap_or_else(|| panic!("Not listed"));
+        if buyer == listing.seller {
+            panic!("Cannot buy own listing");
+        }
+        let ticket = Self::load_ticket(e, token_id);
+        let policy = Self::check_resale(e, &ticket, listing.price);
+        if ticket.payment_token != Some(payment_token.clone()) {
+            panic!("Payment token mismatch");
+        }
+        if let Some(cap) = e
+            .storage()
+            .instance()
+            .get::<_, u32>(&DataKey::Tickets(TicketKey::WalletCap))
+        {
+            if Self::balance(e, buyer.clone()) >= cap {
+                panic!("Wallet cap exceeded");
+            }
+        }
+
+        let royalty = policy.royalty(listing.price);
+        let token_client = token::Client::new(e, &payment_token);
+        token_client.transfer(&buyer, &listing.seller, &(listing.price - royalty));
+        if royalty > 0 {
+            token_client.transfer(&buyer, &TreasuryManager::payout_address(e), &royalty);
+        }
+
+        ResaleManager::delist(e, token_id);
+        Base::update(e, Some(&listing.seller), Some(&buyer), token_id);
+        e.storage().persistent().set(
+            &DataKey::Tickets(TicketKey::Ticket(token_id)),
+            &Ticket {
+                channel: PurchaseChannel::Resale,
+                ..ticket
+            },
+        );
+        ContactManager::revoke(e, token_id);
+        HistoryManager::record_mint(e, &buyer, token_id);
+
+        #[allow(deprecated)]
+        e.events().publish(
+            (Symbol::new(e, "resale"), listing.seller, buyer),
+            (token_id, listing.price, royalty),
+        );
+    }
+
+    /// Check `ticket` may be resold at a positive `price`, returning the tier's
+    /// policy. The price cap is left to the caller.
+    fn check_resale(e: &Env, ticket: &Ticket, price: i128) -> ResalePolicy {
+        Self::require_not_paused(e);
+        if CancellationManager::is_cancelled(e) {
+            panic!("Event cancelled");
+        }
+        let policy = ResaleManager::get_policy(e, &ticket.tier_symbol)
+            .unwrap_or_else(|| panic!("Soulbound: tier not resellable"));
+        if !ticket.is_valid || ticket.status != TicketStatus::Active {
+            panic!("Ticket not resellable");
+        }
+        if ticket.payment_token.is_none() {
+            panic!("Ticket has no face value");
+        }
+        if price <= 0 {
+            panic!("Invalid price");
+        }
+        policy
+    }
+
+    /// ==================== EVENT SERIES ====================
+
+    /// Link another event's ticket contract into a series with this one: its
+    /// ticket holders may claim `benefit` here once per ticket. `None` unlinks.
+    pub fn set_series_benefit(e: &Env, source_event: Address, benefit: Option<SeriesBenefit>) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(
+            e,
+            &admin,
+            "set_series_benefit",
+            (source_event.clone(), benefit.clone()),
+        );
+
+        if source_event == e.current_contract_address() {
+            panic!("Cannot link event to itself");
+        }
+        SeriesManager::set(e, &source_event, benefit);
+    }
+
+    pub fn get_series_benefit(e: &Env, source_event: Address) -> Option<SeriesBenefit> {
+        SeriesManager::get(e, &source_event)
+    }
+
+    pub fn is_series_benefit_claimed(
+        e: &Env,
+        source_event: Address,
+        source_token_id: u32,
+    ) -> bool {
+        SeriesManager::is_claimed(e, &source_event, source_token_id)
+    }
+
+    /// Buy one ticket of the benefit's tier with the series discount, as the
+    /// holder of `source_token_id` in the linked `source_event`. Each source
+    /// ticket claims once. Returns the minted token id.
+    pub fn claim_series_benefit(
+        e: &Env,
+        holder: Address,
+        source_event: Address,
+        source_token_id: u32,
+        payment_token: Address,
+    ) -> u32 {
+        holder.require_auth();
+        let benefit = SeriesManager::claim(e, &holder, &source_event, source_token_id);
+
+        let (token_id, price) = Self::sell_discounted(
+            e,
+            &holder,
+            &payment_token,
+            &benefit.tier_symbol,
+            !benefit.guaranteed,
+            |quote| {
+                let amount = mul_bps(quote.price, benefit.discount_bps as i128)
+                    .unwrap_or_else(|| panic_with_error!(e, PricingError::PriceOverflow));
+                AppliedDiscount {
+                    kind: DiscountKind::Series,
+                    amount,
+                }
+            },
+        );
+
+        #[allow(deprecated)]
+        e.events().publish(
+            (Symbol::new(e, "series_claim"), holder),
+            (source_event, source_token_id, token_id, price),
+        );
+
+        token_id
+    }
+
+    /// Sell one ticket of a tier to `buyer` with a discount worked out from
+    /// the regular quote, rounding last. Returns the token id and price paid.
+    fn sell_discounted(
+        e: &Env,
+        buyer: &Address,
+        payment_token: &Address,
+        tier_symbol: &Symbol,
+        check_eligibility: bool,
+        discount: impl FnOnce(&PriceQuote) -> AppliedDiscount,
+    ) -> (u32, i128) {
+        let key = DataKey::Tickets(TicketKey::Tier(tier_symbol.clone()));
+        let mut tier = Self::load_tier_for_sale(e, buyer, tier_symbol, check_eligibility);
+        Self::require_paid_tier(&tier);
+        if tier.identity_required {
+            panic!("Identity hash required");
+        }
+        ChannelManager::record_sale(e, tier_symbol, &tier, SalesChannel::General, 1);
+
+        let mut quote = Self::quote_price(e, &tier, Some(buyer), None, None);
+        let discount = discount(&quote);
+        if discount.amount > 0 {
+            quote.price -= discount.amount;
+            quote.discounts.push_back(discount);
+        }
+        if let Some(rule) = RoundingManager::get(e, payment_token) {
+            quote.rounding = RoundingManager::adjustment(&rule, quote.price);
+            quote.price += quote.rounding;
+        }
+        let price = quote.price;
+
+        let ticket = Ticket {
+            tier_symbol: tier_symbol.clone(),
+            purchase_time: e.ledger().timestamp(),
+            price_paid: price,
+            is_valid: true,
+            status: TicketStatus::Active,
+            identity_hash: None,
+            payment_token: Some(payment_token.clone()),
+            entry_window: Self::ticket_entry_window(e, &tier),
+            bundle_id: None,
+            guest_slots: tier.guest_slots,
+            channel: Self::sale_channel(e, tier_symbol, check_eligibility),
+        };
+        let token_id = Self::mint_ticket(e, buyer, &ticket);
+        Self::issue_receipt(e, buyer, payment_token, token_id, tier_symbol, &quote);
+        let reference_rate = AccountingManager::current_rate(e, payment_token);
+        AccountingManager::record_sale(e, token_id, payment_token, price, reference_rate.as_ref());
+        tier.minted += 1;
+        tier.current_price = price;
+        e.storage().persistent().set(&key, &tier);
+
+        token::Client::new(e, payment_token).transfer(buyer, &e.current_contract_address(), &price);
+        TreasuryManager::add_liability(e, payment_token, price);
+
+        let mut config: PricingConfig =
+            e.storage().instance().get(&DataKey::Pricing(PricingKey::Config)).unwrap();
+        config.last_update_time = e.ledger().timestamp();
+        e.storage()
+            .instance()
+            .set(&DataKey::Pricing(PricingKey::Config), &config);
+
+        HookManager::on_purchase(e, buyer, token_id, tier_symbol, price);
+        (token_id, price)
+    }
+
+    /// ==================== RETURNING ATTENDEES ====================
+
+    /// Offer the tier's returning attendees last edition's price until
+    /// `offer.window_end`. `None` withdraws the offer.
+    pub fn set_returning_offer(e: &Env, tier_symbol: Symbol, offer: Option<ReturningOffer>) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(
+            e,
+            &admin,
+            "set_returning_offer",
+            (tier_symbol.clone(), offer.clone()),
+        );
+
+        ReturningManager::set(e, &tier_symbol, offer);
+    }
+
+    pub fn get_returning_offer(e: &Env, tier_symbol: Symbol) -> Option<ReturningOffer> {
+        ReturningManager::get(e, &tier_symbol)
+    }
+
+    pub fn has_returning_claim(e: &Env, tier_symbol: Symbol, attendee: Address) -> bool {
+        ReturningManager::has_claimed(e, &tier_symbol, &attendee)
+    }
+
+    /// Buy one ticket of the tier at the grandfathered price, proving the buyer
+    /// is leaf `index` of the offer's attendance root. The regular quote is
+    /// charged instead when it is lower. Returns the token id.
+    pub fn purchase_returning(
+        e: &Env,
+        buyer: Address,
+        payment_token: Address,
+        tier_symbol: Symbol,
+        index: u32,
+        proof: Vec<BytesN<32>>,
+    ) -> u32 {
+        buyer.require_auth();
+        let offer = ReturningManager::claim(e, &tier_symbol, &buyer, index, &proof);
+
+        let (token_id, price) =
+            Self::sell_discounted(e, &buyer, &payment_token, &tier_symbol, true, |quote| {
+                AppliedDiscount {
+                    kind: DiscountKind::Returning,
+                    amount: (quote.price - offer.price).max(0),
+                }
+            });
+
+        #[allow(deprecated)]
+        e.events().publish(
+            (Symbol::new(e, "returning_purchase"), buyer),
+            (tier_symbol, token_id, price),
+        );
+
+        token_id
+    }
+
+    /// ==================== FREE CLAIMS ====================
+
+    /// Set how many tickets of a free tier one address may claim and whether
+    /// claims must answer registration questions. `None` restores one ticket
+    /// per address with no questions.
+    pub fn set_free_claim_config(e: &Env, tier_symbol: Symbol, config: Option<FreeClaimConfig>) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(
+            e,
+            &admin,
+            "set_free_claim_config",
+            (tier_symbol.clone(), config.clone()),
+        );
+        if !e
+            .storage()
+            .persistent()
+            .has(&DataKey::Tickets(TicketKey::Tier(tier_symbol.clone())))
+        {
+            panic!("Tier not found");
+        }
+
+        FreeClaimManager::set(e, &tier_symbol, config);
+    }
+
+    pub fn get_free_claim_config(e: &Env, tier_symbol: Symbol) -> FreeClaimConfig {
+        FreeClaimManager::get(e, &tier_symbol)
+    }
+
+    /// Tickets `claimant` has claimed from the free tier
+    pub fn get_free_claims(e: &Env, tier_symbol: Symbol, claimant: Address) -> u32 {
+        FreeClaimManager::claimed(e, &tier_symbol, &claimant)
+    }
+
+    /// Hash of the registration answers given when `token_id` was claimed
+    pub fn get_registration_answers(e: &Env, token_id: u32) -> Option<BytesN<32>> {
+        FreeClaimManager::answers(e, token_id)
+    }
+
+    /// Claim a ticket of a tier whose base price is 0. No payment token is
+    /// involved; `answers_hash` is required when the tier asks registration
+    /// questions. Returns the token id.
+    pub fn claim_free_ticket(
+        e: &Env,
+        claimant: Address,
+        tier_symbol: Symbol,
+        answers_hash: Option<BytesN<32>>,
+    ) -> u32 {
+        claimant.require_auth();
+        let key = DataKey::Tickets(TicketKey::Tier(tier_symbol.clone()));
+        let mut tier = Self::load_sellable_tier(e, &claimant, &tier_symbol);
+        if tier.base_price != 0 {
+            panic!("Tier is not free");
+        }
+        if tier.identity_required {
+            panic!("Identity hash required");
+        }
+        FreeClaimManager::record(e, &tier_symbol, &claimant, &answers_hash);
+        ChannelManager::record_sale(e, &tier_symbol, &tier, SalesChannel::General, 1);
+
+        let ticket = Ticket {
+            tier_symbol: tier_symbol.clone(),
+            purchase_time: e.ledger().timestamp(),
+            price_paid: 0,
+            is_valid: true,
+            status: TicketStatus::Active,
+            identity_hash: None,
+            payment_token: None,
+            entry_window: Self::ticket_entry_window(e, &tier),
+            bundle_id: None,
+            guest_slots: tier.guest_slots,
+            channel: Self::sale_channel(e, &tier_symbol, true),
+        };
+        let token_id = Self::mint_ticket(e, &claimant, &ticket);
+        if let Some(answers_hash) = &answers_hash {
+            FreeClaimManager::set_answers(e, token_id, answers_hash);
+        }
+
+        tier.minted += 1;
+        e.storage().persistent().set(&key, &tier);
+
+        #[allow(deprecated)]
+        e.events().publish(
+            (Symbol::new(e, "free_claim"), claimant.clone()),
+            (token_id, tier_symbol.clone()),
+        );
+        HookManager::on_purchase(e, &claimant, token_id, &tier_symbol, 0);
+
+        token_id
+    }
+
+    /// ==================== LOYALTY POINTS ====================
+
+    /// Let buyers settle part of the tier's price in loyalty points under
+    /// `policy`; `None` stops the tier accepting points
+    pub fn set_points_policy(e: &Env, tier_symbol: Symbol, policy: Option<PointsPolicy>) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(
+            e,
+            &admin,
+            "set_points_policy",
+            (tier_symbol.clone(), policy.clone()),
+        );
+
+        if !e
+            .storage()
+            .persistent()
+            .has(&DataKey::Tickets(TicketKey::Tier(tier_symbol.clone())))
+        {
+            panic!("Tier not found");
+        }
+        PointsManager::set_policy(e, &tier_symbol, policy);
+    }
+
+    pub fn get_points_policy(e: &Env, tier_symbol: Symbol) -> Option<PointsPolicy> {
+        PointsManager::get_policy(e, &tier_symbol)
+    }
+
+    /// Loyalty points spent on `token_id`
+    pub fn get_points_paid(e: &Env, token_id: u32) -> i128 {
+        PointsManager::points_paid(e, token_id)
+    }
+
+    /// Buy a ticket spending up to `max_points` loyalty points, within the
+    /// tier's points share, and paying the rest in `payment_token`. Both are
+    /// debited in this call. Returns the token id.
+    pub fn purchase_with_points(
+        e: &Env,
+        buyer: Address,
+        payment_token: Address,
+        tier_symbol: Symbol,
+        max_points: i128,
+    ) -> u32 {
+        buyer.require_auth();
+        let policy = PointsManager::get_policy(e, &tier_symbol)
+            .unwrap_or_else(|| panic!("Tier does not accept points"));
+
+        let mut points = 0;
+        let (token_id, price) =
+            Self::sell_discounted(e, &buyer, &payment_token, &tier_symbol, true, |quote| {
+                let (covered, spent) = policy.split(quote.price, max_points);
+                points = spent;
+                AppliedDiscount {
+                    kind: DiscountKind::Points,
+                    amount: covered,
+                }
+            });
+        if points > 0 {
+            token::Client::new(e, &policy.points_token).transfer(
+                &buyer,
+                &TreasuryManager::payout_address(e),
+                &points,
+            );
+            PointsManager::record_points_paid(e, token_id, points);
+        }
+
+        #[allow(deprecated)]
+        e.events().publish(
+            (Symbol::new(e, "points_purchase"), buyer),
+            (tier_symbol, token_id, price, points),
+        );
+
+        token_id
+    }
+
+    /// ==================== CUSTODIAL MIGRATIONS ====================
+
+    /// Allow `from` to move its tickets to `to` once, e.g. when a custodian
+    /// rotates wallets
+    pub fn approve_migration(e: &Env, from: Address, to: Address) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "approve_migration", (from.clone(), to.clone()));
+        MigrationManager::approve(e, &from, &to);
+    }
+
+    pub fn revoke_migration(e: &Env, from: Address, to: Address) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "revoke_migration", (from.clone(), to.clone()));
+        MigrationManager::revoke(e, &from, &to);
+    }
+
+    pub fn get_migration(e: &Env, from: Address, to: Address) -> Option<MigrationExemption> {
+        MigrationManager::get(e, &from, &to)
+    }
+
+    /// Move `token_ids` from `from` to `to` under an approved exemption,
+    /// using it up. Open resale listings of the tickets are dropped.
+    pub fn migrate_tickets(e: &Env, from: Address, to: Address, token_ids: Vec<u32>) {
+        from.require_auth();
+        if token_ids.is_empty() || token_ids.len() > MAX_MIGRATION_BATCH {
+            panic!("Invalid migration batch");
+        }
+        MigrationManager::consume(e, &from, &to);
+
+        for token_id in token_ids.iter() {
+            if Self::owner_of(e, token_id) != from {
+                panic!("Not the ticket owner");
+            }
+            ResaleManager::delist(e, token_id);
+            Base::update(e, Some(&from), Some(&to), token_id);
+            HistoryManager::record_mint(e, &to, token_id);
+        }
+
+        #[allow(deprecated)]
+        e.events().publish((Symbol::new(e, "tickets_migrated"), from, to), token_ids);
+    }
+
+    /// ==================== CANCELLATION & BOND ====================
+
+    /// Put up a bond in `token` before the event starts. It is returned after
+    /// the event completes unless the event is cancelled or refunds have to
+    /// draw on it.
+    pub fn post_bond(e: &Env, token: Address, amount: i128) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "post_bond", (token.clone(), amount));
+
+        let event_info: EventInfo = e.storage().instance().get(&DataKey::EventInfo).unwrap();
+        if e.ledger().timestamp() >= event_info.start_time {
+            panic!("Event already started");
+        }
+        BondManager::post(e, &token, amount);
+        token::Client::new(e, &token).transfer(&admin, &e.current_contract_address(), &amount);
+    }
+
+    /// Return a bond that was never drawn on to the admin once the event is
+    /// complete
+    pub fn release_bond(e: &Env) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "release_bond", ());
+
+        let bond = BondManager::release(e);
+        token::Client::new(e, &bond.token).transfer(
+            &e.current_contract_address(),
+            &admin,
+            &bond.remaining,
+        );
+    }
+
+    pub fn get_bond(e: &Env) -> Option<OrganizerBond> {
+        BondManager::get(e)
+    }
+
+    /// Cancel the event for good: sales stop, every unused ticket can be
+    /// refunded in full regardless of the cutoff, and the bond is slashed to
+    /// cover refunds
+    pub fn cancel_event(e: &Env) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "cancel_event", ());
+
+        if CancellationManager::is_cancelled(e) {
+            panic!("Event already cancelled");
+        }
+        CancellationManager::cancel(e);
+        BondManager::slash(e);
+
+        #[allow(deprecated)]
+        e.events().publish((Symbol::new(e, "event_cancelled"), admin), ());
+    }
+
+    pub fn is_cancelled(e: &Env) -> bool {
+        CancellationManager::is_cancelled(e)
+    }
+
+    /// ==================== COMMUNITY RECOVERY ====================
+
+    /// Switch an abandoned event into recovery mode: the event has started and
+    /// there has been no admin activity for `ABANDONMENT_LEDGERS`. Any ticket
+    /// holder may trigger it. Withdrawals are blocked from then on.
+    pub fn trigger_recovery(e: &Env, holder: Address) {
+        holder.require_auth();
+        if RecoveryManager::is_active(e) {
+            panic!("Recovery already active");
+        }
+        if Self::balance(e, holder.clone()) == 0 {
+            panic!("Not a ticket holder");
+        }
+        if !RecoveryManager::is_abandoned(e) {
+            panic!("Event not abandoned");
+        }
+        RecoveryManager::activate(e);
+        BondManager::slash(e);
+
+        #[allow(deprecated)]
+        e.events().publish(
+            (Symbol::new(e, "recovery_triggered"), holder),
+            RecoveryManager::last_activity(e),
+        );
+    }
+
+    /// Burn an unused ticket for its pro-rata share of the treasury while in
+    /// recovery mode. Works even when paused. Returns the amount paid out.
+    pub fn claim_recovery_refund(e: &Env, holder: Address, token_id: u32) -> i128 {
+        holder.require_auth();
+        if !RecoveryManager::is_active(e) {
+            panic!("Recovery not active");
+        }
+        if Self::owner_of(e, token_id) != holder {
+            panic!("Not the ticket owner");
+        }
+        let ticket = Self::load_ticket(e, token_id);
+        if !ticket.is_valid || ticket.status != TicketStatus::Active {
+            panic!("Ticket not claimable");
+        }
+
+        let payout = match &ticket.payment_token {
+            Some(paid_in) => {
+                let payout = RecoveryManager::payout(e, paid_in, ticket.price_paid);
+                BondManager::cover_shortfall(e, paid_in, payout);
+                token::Client::new(e, paid_in).transfer(
+                    &e.current_contract_address(),
+                    &holder,
+                    &payout,
+                );
+                TreasuryManager::release_liability(e, paid_in, ticket.price_paid);
+                AccountingManager::record_refund(e, token_id, paid_in, payout);
+                payout
+            }
+            None => 0,
+        };
+        Self::retire_refunded(e, &holder, token_id, ticket);
+
+        #[allow(deprecated)]
+        e.events().publish(
+            (Symbol::new(e, "recovery_claimed"), holder),
+            (token_id, payout),
+        );
+        payout
+    }
+
+    pub fn is_recovery_active(e: &Env) -> bool {
+        RecoveryManager::is_active(e)
+    }
+
+    /// Ledger of the last privileged call or check-in
+    pub fn get_last_admin_activity(e: &Env) -> u32 {
+        RecoveryManager::last_activity(e)
+    }
+
+    /// ==================== REFUND AGENTS ====================
+
+    /// Grant a support agent the right to execute refunds up to `daily_limit`
+    /// (in payment token units per day). A limit of 0 revokes the role.
+    pub fn set_refund_agent(e: &Env, agent: Address, daily_limit: i128) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "set_refund_agent", (agent.clone(), daily_limit));
+
+        let key = DataKey::Treasury(TreasuryKey::RefundAgent(agent));
+        if daily_limit <= 0 {
+            e.storage().persistent().remove(&key);
+            return;
+        }
+
+        let mut refund_agent: RefundAgent =
+            e.storage().persistent().get(&key).unwrap_or(RefundAgent {
+                daily_limit,
+                day: 0,
+                refunded_today: 0,
+            });
+        refund_agent.daily_limit = daily_limit;
+        e.storage().persistent().set(&key, &refund_agent);
+    }
+
+    pub fn get_refund_agent(e: &Env, agent: Address) -> Option<RefundAgent> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Treasury(TreasuryKey::RefundAgent(agent)))
+    }
+
+    /// Customer-service refund executed by a refund agent. Follows the same policy as
+    /// `refund`, including the owner's refund limits, and always pays the recorded
+    /// owner of the ticket.
+    pub fn agent_refund(
+        e: &Env,
+        agent: Address,
+        payment_token: Address,
+        token_id: u32,
+    ) -> Result<i128, RefundError> {
+        agent.require_auth();
+
+        let key = DataKey::Treasury(TreasuryKey::RefundAgent(agent));
+        let mut refund_agent: RefundAgent = e
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| panic!("Not a refund agent"));
+
+        let today = e.ledger().timestamp() / 86_400;
+        if refund_agent.day != today {
+            refund_agent.day = today;
+            refund_agent.refunded_today = 0;
+        }
+
+        let owner = Self::owner_of(e, token_id);
+        let amount = Self::process_refund(e, &owner, &payment_token, token_id)?;
+
+        refund_agent.refunded_today += amount;
+        if refund_agent.refunded_today > refund_agent.daily_limit {
+            panic!("Refund agent daily limit exceeded");
+        }
+        e.storage().persistent().set(&key, &refund_agent);
+
+        Ok(amount)
+    }
+
+    /// ==================== TREASURY FUNCTIONS ====================
+
+    /// Top up the refund reserve from the organizer's own funds
+    pub fn deposit_reserve(e: &Env, token: Address, amount: i128) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "deposit_reserve", (token.clone(), amount));
+        token::Client::new(e, &token).transfer(&admin, &e.current_contract_address(), &amount);
+    }
+
+    /// Withdraw revenue to the payout address. While the refund window is open
+    /// the remaining balance must still cover the configured share of
+    /// refundable liability.
+    pub fn withdraw(e: &Env, token: Address, amount: i128) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "withdraw", (token.clone(), amount));
+
+        if RecoveryManager::is_active(e) {
+            panic!("Recovery mode active");
+        }
+        if !TreasuryManager::can_withdraw(e, &token, amount) {
+            panic!("Withdrawal would breach refund reserve or payout schedule");
+        }
+        PayoutScheduleManager::record_withdrawal(e, &token, amount);
+        token::Client::new(e, &token).transfer(
+            &e.current_contract_address(),
+            &TreasuryManager::payout_address(e),
+            &amount,
+        );
+    }
+
+    /// Send future withdrawals to `payout_address` (e.g. cold storage) instead
+    /// of the admin. Goes through the timelock like other sensitive changes.
+    pub fn set_payout_address(e: &Env, payout_address: Address) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "set_payout_address", (payout_address.clone(),));
+        TimelockManager::submit(e, ConfigChange::PayoutAddress(payout_address));
+    }
+
+    pub fn get_payout_address(e: &Env) -> Address {
+        TreasuryManager::payout_address(e)
+    }
+
+    pub fn get_payout_schedule(e: &Env) -> Option<PayoutSchedule> {
+        PayoutScheduleManager::get(e)
+    }
+
+    /// Most of `token` that `withdraw` would release right now, given the
+    /// refund reserve and the payout schedule
+    pub fn get_withdrawable(e: &Env, token: Address) -> i128 {
+        TreasuryManager::withdrawable(e, &token)
+    }
+
+    /// Set the reserve coverage ratio in bps of refundable liability (10000 = 100%).
+    /// Goes through the timelock; while refunds are open it cannot go below
+    /// `MIN_COVERAGE_BPS`.
+    pub fn set_reserve_coverage(e: &Env, coverage_bps: u32) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "set_reserve_coverage", (coverage_bps,));
+        if coverage_bps > 10_000 {
+            panic!("Coverage cannot exceed 100%");
+        }
+        let event_info: EventInfo = e.storage().instance().get(&DataKey::EventInfo).unwrap();
+        let refunds_open = CancellationManager::is_cancelled(e)
+            || e.ledger().timestamp() <= event_info.refund_cutoff_time;
+        if refunds_open && coverage_bps < treasury::MIN_COVERAGE_BPS {
+            panic!("Coverage below minimum while refunds are open");
+        }
+        TimelockManager::submit(e, ConfigChange::ReserveCoverage(coverage_bps));
+    }
+
+    pub fn get_refund_liability(e: &Env, token: Address) -> i128 {
+        TreasuryManager::get_liability(e, &token)
+    }
+
+    /// Balance of `token` that must stay in the treasury right now
+    pub fn get_required_reserve(e: &Env, token: Address) -> i128 {
+        TreasuryManager::required_reserve(e, &token)
+    }
+
+    // Ticket Validation
+    pub fn validate_ticket(e: &Env, token_id: u32) -> bool {
+        Self::validate_entry(e, token_id).is_ok()
+    }
+
+    /// Whether a ticket admits its holder right now, and if not, why
+    pub fn validate_entry(e: &Env, token_id: u32) -> Result<Ticket, EntryError> {
+        let ticket = Self::entry_ticket(e, token_id)?;
+        let now = e.ledger().timestamp();
+        if now < ticket.entry_window.valid_from {
+            return Err(EntryError::TooEarly);
+        }
+        if now > ticket.entry_window.valid_until {
+            return Err(EntryError::Expired);
+        }
+        Ok(ticket)
+    }
+
+    /// A ticket that exists and has not been refunded or voided
+    fn entry_ticket(e: &Env, token_id: u32) -> Result<Ticket, EntryError> {
+        let ticket: Ticket = e
+            .storage()
+            .persistent()
+            .get(&DataKey::Tickets(TicketKey::Ticket(token_id)))
+            .ok_or(EntryError::TicketNotFound)?;
+        if !ticket.is_valid {
+            return Err(EntryError::TicketInvalid);
+        }
+        Ok(ticket)
+    }
+
+    /// Admit a ticket at the door. Checked-in tickets stay valid but cannot be
+    /// checked in again or refunded.
+    pub fn check_in(e: &Env, token_id: u32) -> Result<(), EntryError> {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        RecoveryManager::record_activity(e);
+
+        let ticket = Self::validate_entry(e, token_id)?;
+        Self::admit(e, token_id, ticket)
+    }
+
+    /// Allow or revoke a gate supervisor for `check_in_override`
+    pub fn set_gate_supervisor(e: &Env, supervisor: Address, allowed: bool) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "set_gate_supervisor", (supervisor.clone(), allowed));
+        RoleManager::set(e, Role::GateSupervisor, &supervisor, allowed);
+    }
+
+    pub fn is_gate_supervisor(e: &Env, supervisor: Address) -> bool {
+        RoleManager::has(e, Role::GateSupervisor, &supervisor)
+    }
+
+    /// Admit a ticket outside its entry window (e.g. a VIP arriving after their
+    /// slot). Only gate supervisors may override; the ticket must still be
+    /// valid and not yet checked in.
+    pub fn check_in_override(
+        e: &Env,
+        supervisor: Address,
+        token_id: u32,
+    ) -> Result<(), EntryError> {
+        supervisor.require_auth();
+        if !Self::is_gate_supervisor(e, supervisor.clone()) {
+            panic!("Not a gate supervisor");
+        }
+
+        let ticket = Self::entry_ticket(e, token_id)?;
+        Self::admit(e, token_id, ticket)?;
+
+        #[allow(deprecated)]
+        e.events().publish((Symbol::new(e, "entry_override"), supervisor), token_id);
+        Ok(())
+    }
+
+    /// Allow or revoke a door scanner for `scan_in`
+    pub fn set_scanner(e: &Env, scanner: Address, allowed: bool) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "set_scanner", (scanner.clone(), allowed));
+        RoleManager::set(e, Role::Scanner, &scanner, allowed);
+    }
+
+    pub fn is_scanner(e: &Env, scanner: Address) -> bool {
+        RoleManager::has(e, Role::Scanner, &scanner)
+    }
+
+    /// Check a ticket in at `gate_id`, counting it towards the gate's and the
+    /// scanner's throughput
+    pub fn scan_in(
+        e: &Env,
+        scanner: Address,
+        gate_id: Symbol,
+        token_id: u32,
+    ) -> Result<(), EntryError> {
+        scanner.require_auth();
+        if !Self::is_scanner(e, scanner.clone()) {
+            panic!("Not a scanner");
+        }
+        RecoveryManager::record_activity(e);
+
+        let ticket = Self::validate_entry(e, token_id)?;
+        Self::admit(e, token_id, ticket)?;
+        GateStatsManager::record(e, &gate_id, &scanner);
+        Ok(())
+    }
+
+    /// Check-ins at a gate overall and per time bucket over the last hour
+    pub fn get_gate_stats(e: &Env, gate_id: Symbol) -> GateStats {
+        GateStatsManager::gate_stats(e, &gate_id)
+    }
+
+    pub fn get_scanner_stats(e: &Env, scanner: Address) -> Option<ScannerStats> {
+        GateStatsManager::scanner_stats(e, &scanner)
+    }
+
+    fn admit(e: &Env, token_id: u32, mut ticket: Ticket) -> Result<(), EntryError> {
+        if ticket.status == TicketStatus::CheckedIn {
+            return Err(EntryError::AlreadyCheckedIn);
+        }
+
+        ticket.status = TicketStatus::CheckedIn;
+        e.storage()
+            .persistent()
+            .set(&DataKey::Tickets(TicketKey::Ticket(token_id)), &ticket);
+
+        #[allow(deprecated)]
+        e.events().publish(
+            (Symbol::new(e, "checked_in"), Self::owner_of(e, token_id)),
+            token_id,
+        );
+        Ok(())
+    }
+
+    /// Admit one guest of a ticket holder who has already checked in, using up
+    /// one of the ticket's guest slots. Returns the slots left.
+    pub fn check_in_guest(e: &Env, token_id: u32) -> Result<u32, EntryError> {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        RecoveryManager::record_activity(e);
+
+        let mut ticket = Self::validate_entry(e, token_id)?;
+        if ticket.status != TicketStatus::CheckedIn {
+            return Err(EntryError::HolderNotCheckedIn);
+        }
+        if ticket.guest_slots == 0 {
+            return Err(EntryError::NoGuestSlots);
+        }
+
+        ticket.guest_slots -= 1;
+        e.storage()
+            .persistent()
+            .set(&DataKey::Tickets(TicketKey::Ticket(token_id)), &ticket);
+
+        #[allow(deprecated)]
+        e.events().publish(
+            (Symbol::new(e, "guest_checked_in"), Self::owner_of(e, token_id)),
+            (token_id, ticket.guest_slots),
+        );
+        Ok(ticket.guest_slots)
+    }
+
+    /// Guest entries each ticket of a tier carries (e.g. bring-a-friend on VIP).
+    /// Applies to tickets minted afterwards.
+    pub fn set_tier_guest_slots(e: &Env, tier_symbol: Symbol, guest_slots: u32) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(
+            e,
+            &admin,
+            "set_tier_guest_slots",
+            (tier_symbol.clone(), guest_slots),
+        );
+
+        if guest_slots > MAX_GUEST_SLOTS {
+            panic!("Too many guest slots");
+        }
+        let key = DataKey::Tickets(TicketKey::Tier(tier_symbol));
+        let mut tier: Tier = e
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| panic!("Tier not found"));
+        tier.guest_slots = guest_slots;
+        e.storage().persistent().set(&key, &tier);
+    }
+
+    /// Give a tier its own entry window (e.g. early entry for VIPs). Applies to
+    /// tickets minted afterwards; `None` restores the event default.
+    pub fn set_tier_entry_window(e: &Env, tier_symbol: Symbol, window: Option<EntryWindow>) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(
+            e,
+            &admin,
+            "set_tier_entry_window",
+            (tier_symbol.clone(), window.clone()),
+        );
+
+        if let Some(window) = &window {
+            if window.valid_from >= window.valid_until {
+                panic!("Invalid entry window");
+            }
+        }
+        let key = DataKey::Tickets(TicketKey::Tier(tier_symbol));
+        let mut tier: Tier = e
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| panic!("Tier not found"));
+        tier.entry_window = window;
+        e.storage().persistent().set(&key, &tier);
+    }
+
+    /// (open, close) times stamped on tickets of the tier minted now
+    pub fn entry_window(e: &Env, tier_symbol: Symbol) -> Result<(u64, u64), TierError> {
+        let tier: Tier = e
+            .storage()
+            .persistent()
+            .get(&DataKey::Tickets(TicketKey::Tier(tier_symbol)))
+            .ok_or(TierError::TierNotFound)?;
+        let window = Self::ticket_entry_window(e, &tier);
+        Ok((window.valid_from, window.valid_until))
+    }
+
+    /// Entry window for a ticket of `tier` minted now: the tier's own window,
+    /// else from event start with no closing time
+    fn ticket_entry_window(e: &Env, tier: &Tier) -> EntryWindow {
+        tier.entry_window.clone().unwrap_or_else(|| {
+            let event_info: EventInfo = e.storage().instance().get(&DataKey::EventInfo).unwrap();
+            EntryWindow {
+                valid_from: event_info.start_time,
+                valid_until: u64::MAX,
+            }
+        })
+    }
+
+    /// Tickets minted to `owner`, oldest first, with their current status
+    pub fn get_purchase_history(
+        e: &Env,
+        owner: Address,
+        start: u32,
+        limit: u32,
+    ) -> Vec<PurchaseRecord> {
+        HistoryManager::get_history(e, &owner, start, limit)
+    }
+
+    /// Tickets issued and refunded over the event, for organizer reputation
+    pub fn get_sales_stats(e: &Env) -> SalesStats {
+        StatsManager::get(e)
+    }
+
+    /// Tickets currently in circulation across all tiers
+    pub fn total_supply(e: &Env) -> u32 {
+        let stats = StatsManager::get(e);
+        stats
+            .tickets_sold
+            .saturating_add(StatsManager::splits(e))
+            .saturating_sub(stats.tickets_refunded)
+    }
+
+    /// Companion tickets split off group tickets; not counted in `get_sales_stats`
+    pub fn get_split_count(e: &Env) -> u32 {
+        StatsManager::splits(e)
+    }
+
+    /// `(minted, max_supply, remaining, returned)` for a tier, where `returned`
+    /// counts refunded or voided tickets whose slots went back on sale. `None`
+    /// for an unknown tier.
+    pub fn tier_supply(e: &Env, tier_symbol: Symbol) -> Option<(u32, u32, u32, u32)> {
+        let tier: Tier = e
+            .storage()
+            .persistent()
+            .get(&DataKey::Tickets(TicketKey::Tier(tier_symbol.clone())))?;
+        Some((
+            tier.minted,
+            tier.max_supply,
+            tier.max_supply.saturating_sub(tier.minted),
+            StatsManager::returned(e, &tier_symbol),
+        ))
+    }
+
+    /// ==================== CONTACT OPT-IN ====================
+
+    /// Opt in to organizer communications for a ticket with a salted hash or
+    /// opaque reference (never raw contact details). Replaces any earlier one.
+    pub fn register_contact(e: &Env, token_id: u32, contact_hash: BytesN<32>) {
+        Self::owner_of(e, token_id).require_auth();
+        ContactManager::register(e, token_id, &contact_hash);
+    }
+
+    /// Withdraw a ticket's opt-in
+    pub fn revoke_contact(e: &Env, token_id: u32) {
+        Self::owner_of(e, token_id).require_auth();
+        ContactManager::revoke(e, token_id);
+    }
+
+    pub fn get_contact(e: &Env, token_id: u32) -> Option<BytesN<32>> {
+        ContactManager::get(e, token_id)
+    }
+
+    /// Number of tickets currently opted in, for the organizer's exports
+    pub fn get_contact_count(e: &Env) -> u32 {
+        ContactManager::count(e)
+    }
+
+    /// ==================== ACCESS GATING ====================
+
+    /// Grant `access` to holders of any of `tiers` (an empty list revokes it)
+    pub fn set_access_tiers(e: &Env, access: Symbol, tiers: Vec<Symbol>) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "set_access_tiers", (access.clone(), tiers.clone()));
+
+        for tier_symbol in tiers.iter() {
+            if !e
+                .storage()
+                .persistent()
+                .has(&DataKey::Tickets(TicketKey::Tier(tier_symbol)))
+            {
+                panic!("Tier not found");
+            }
+        }
+        AccessManager::set_tiers(e, &access, &tiers);
     }
 
-    /// Get allocation fairness score (0-100)
-    pub fn get_allocation_fairness(e: &Env, tier_symbol: Symbol) -> u32 {
-        let count_key = DataKey::LotteryEntryCount(tier_symbol.clone());
-        let entry_count: u32 = e.storage().persistent().get(&count_key).unwrap_or(0);
+    pub fn get_access_tiers(e: &Env, access: Symbol) -> Vec<Symbol> {
+        AccessManager::get_tiers(e, &access)
+    }
 
-        if let Some(results) = e
-            .storage()
-            .persistent()
-            .get::<_, Vec<AllocationResult>>(&DataKey::LotteryResults(tier_symbol))
-        {
-            AllocationEngine::compute_fairness_score(e, &results, entry_count)
-        } else {
-            0
-        }
+    /// Whether `address` holds a valid, unrefunded ticket granting `access`.
+    /// Intended for other contracts gating features on event attendance.
+    pub fn has_access(e: &Env, address: Address, access: Symbol) -> bool {
+        AccessManager::has_access(e, &address, &access)
     }
 
-    /// ==================== PRICING FUNCTIONS ====================
+    /// ==================== IDENTITY BINDING ====================
 
-    /// multipliers.  Call this once after deployment pointing at a real oracle,
-    /// or whenever you want to re-baseline the reference price.
-    pub fn update_oracle_reference(e: &Env, new_reference_price: i128) {
+    /// Require purchases in a tier to carry an attendee name hash.
+    /// Tickets issued through other paths can be bound with `rebind_identity`.
+    pub fn set_identity_required(e: &Env, tier_symbol: Symbol, required: bool) {
         let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
         admin.require_auth();
-        let mut config: PricingConfig =
-            e.storage().instance().get(&DataKey::PricingConfig).unwrap();
-        config.oracle_reference_price = new_reference_price;
-        e.storage().instance().set(&DataKey::PricingConfig, &config);
+        AdminLogManager::record(
+            e,
+            &admin,
+            "set_identity_required",
+            (tier_symbol.clone(), required),
+        );
+
+        let key = DataKey::Tickets(TicketKey::Tier(tier_symbol));
+        let mut tier: Tier = e
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| panic!("Tier not found"));
+        tier.identity_required = required;
+        e.storage().persistent().set(&key, &tier);
     }
 
-    // Emergency freeze toggle
-    pub fn emergency_freeze(e: &Env, freeze: bool) {
-        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
-        admin.require_auth();
-        let mut config: PricingConfig =
-            e.storage().instance().get(&DataKey::PricingConfig).unwrap();
-        config.is_frozen = freeze;
-        e.storage().instance().set(&DataKey::PricingConfig, &config);
+    /// Gate check: does `name` with `salt` match the identity bound to the
+    /// ticket. `None` for an unknown ticket.
+    pub fn verify_identity(e: &Env, token_id: u32, name: String, salt: Bytes) -> Option<bool> {
+        let ticket: Ticket = e
+            .storage()
+            .persistent()
+            .get(&DataKey::Tickets(TicketKey::Ticket(token_id)))?;
+
+        Some(match ticket.identity_hash {
+            Some(identity_hash) => identity_hash == Self::identity_hash(e, &name, &salt),
+            None => false,
+        })
     }
 
-    // Add a new ticket tier
-    pub fn add_tier(
-        e: &Env,
-        tier_symbol: Symbol,
-        name: String,
-        base_price: i128,
-        max_supply: u32,
-        strategy: PricingStrategy,
-    ) {
+    /// Replace the identity bound to a ticket, e.g. to fix a misspelled name
+    pub fn rebind_identity(e: &Env, token_id: u32, identity_hash: BytesN<32>) {
         let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
         admin.require_auth();
+        AdminLogManager::record(e, &admin, "rebind_identity", (token_id, identity_hash.clone()));
 
-        let key = DataKey::Tier(tier_symbol.clone());
-        if e.storage().persistent().has(&key) {
-            panic!("Tier already exists");
+        let key = DataKey::Tickets(TicketKey::Ticket(token_id));
+        let mut ticket: Ticket = e
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| panic!("Ticket not found"));
+        ticket.identity_hash = Some(identity_hash.clone());
+        e.storage().persistent().set(&key, &ticket);
+
+        #[allow(deprecated)]
+        e.events().publish(
+            (Symbol::new(e, "identity_rebound"), token_id),
+            identity_hash,
+        );
+    }
+
+    /// sha256(salt || name)
+    fn identity_hash(e: &Env, name: &String, salt: &Bytes) -> BytesN<32> {
+        let name_len = name.len() as usize;
+        if name_len > MAX_IDENTITY_NAME_LEN {
+            panic!("Name too long");
         }
 
-        let tier = Tier {
-            name,
-            base_price,
-            current_price: base_price,
-            max_supply,
-            minted: 0,
-            active: true,
-            strategy,
-        };
+        let mut buf = [0u8; MAX_IDENTITY_NAME_LEN];
+        name.copy_into_slice(&mut buf[..name_len]);
+        let mut preimage = salt.clone();
+        preimage.extend_from_slice(&buf[..name_len]);
 
-        e.storage().persistent().set(&key, &tier);
+        e.crypto().sha256(&preimage).to_bytes()
     }
 
-    /// Fetch the current external price multiplier using the real DIA oracle.
-    ///
-    /// Strategy:
-    ///  1. Call `DiaOraclePriceClient::try_get_value(pair)` on the configured oracle.
-    ///  2. Verify that the returned timestamp is within `max_oracle_age_seconds`.
-    ///  3. If the oracle is stale or the cross-contract call fails, fall back to
-    ///     `DexPriceRouterClient::try_get_spot_price(pair)` on the DEX address.
-    ///  4. If both fail, return `ORACLE_PRECISION` (neutral — no adjustment).
-    ///
-    /// The raw price (8 decimals, $1.00 == 100_000_000) is converted into a
-    /// `ORACLE_PRECISION`-scaled multiplier using the stored `oracle_reference_price`.
-    fn fetch_oracle_multiplier(e: &Env, config: &PricingConfig) -> i128 {
-        match fetch_price_with_fallback(
-            e,
-            &config.oracle_address,
-            &config.dex_pool_address,
-            config.oracle_pair.clone(),
-            config.max_oracle_age_seconds,
-        ) {
-            Some(result) => oracle_price_to_multiplier(
-                result.price,
-                config.oracle_reference_price,
-                ORACLE_PRECISION,
-            ),
-            // Both oracle and DEX unavailable: apply neutral multiplier (no adjustment)
-            None => ORACLE_PRECISION,
-        }
+    // View functions logic
+    /// The ticket minted as `token_id`, `None` for ids never minted
+    pub fn get_ticket(e: &Env, token_id: u32) -> Option<Ticket> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Tickets(TicketKey::Ticket(token_id)))
     }
 
-    // Dynamic pricing query
-    pub fn get_ticket_price(e: &Env, tier_symbol: Symbol) -> i128 {
-        let config: PricingConfig = e.storage().instance().get(&DataKey::PricingConfig).unwrap();
-        let key = DataKey::Tier(tier_symbol);
-        let tier: Tier = e.storage().persistent().get(&key).unwrap();
+    pub fn ticket_exists(e: &Env, token_id: u32) -> bool {
+        e.storage()
+            .persistent()
+            .has(&DataKey::Tickets(TicketKey::Ticket(token_id)))
+    }
 
-        if config.is_frozen {
-            return tier.current_price;
-        }
+    /// `get_ticket` for paths that need the ticket to exist
+    fn load_ticket(e: &Env, token_id: u32) -> Ticket {
+        Self::get_ticket(e, token_id).unwrap_or_else(|| panic!("Ticket not found"))
+    }
 
-        // Base price
-        let mut price = tier.base_price;
+    /// ==================== METADATA FREEZE ====================
 
-        // Apply strategy variations
-        match tier.strategy {
-            PricingStrategy::Standard => {
-                // Demand based: base_price * (1 + (minted / (max_supply / 5)) * 5%)
-                let thresholds_passed = tier.minted / (tier.max_supply.max(1) / 5).max(1);
-                let increase = price * PRICE_INCREASE_BPS * (thresholds_passed as i128) / 10000;
-                price += increase;
-            }
-            PricingStrategy::TimeDecay => {
-                let event_info: EventInfo =
-                    e.storage().instance().get(&DataKey::EventInfo).unwrap();
-                let now = e.ledger().timestamp();
-                // If purchased way before event, apply 10% discount
-                // Assume linear scale from start to event_start_time
-                let start = event_info.start_time.saturating_sub(604800); // 1 week before
-                if now < start {
-                    price -= price * EARLY_BIRD_DISCOUNT_BPS / 10000;
-                }
-            }
-            PricingStrategy::AbTestA => {
-                // High demand sensitivity (10% increase per threshold)
-                let thresholds_passed = tier.minted / (tier.max_supply.max(1) / 5).max(1);
-                let increase =
-                    price * (PRICE_INCREASE_BPS * 2) * (thresholds_passed as i128) / 10000;
-                price += increase;
-            }
-            PricingStrategy::AbTestB => {
-                // Floor starts higher (+20%)
-                price += price * 2000 / 10000;
-            }
+    /// Permanently lock event metadata and tier/ticket attributes. Admin only.
+    /// Metadata also locks by itself once the event starts.
+    pub fn freeze_metadata(e: &Env) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "freeze_metadata", ());
+        if Self::is_metadata_frozen(e) {
+            panic!("Metadata is frozen");
         }
+        e.storage()
+            .instance()
+            .set(&DataKey::Tickets(TicketKey::MetadataFrozen), &true);
 
-        // Apply external Oracle factors using the real DIA oracle integration
-        let oracle_multiplier = Self::fetch_oracle_multiplier(e, &config);
-        price = price * oracle_multiplier / ORACLE_PRECISION;
+        #[allow(deprecated)]
+        e.events()
+            .publish((Symbol::new(e, "metadata_frozen"),), e.ledger().timestamp());
+    }
 
-        // Apply bounds
-        price = price.max(config.price_floor).min(config.price_ceiling);
+    /// Whether metadata can no longer change, so marketplaces can rely on it
+    pub fn is_metadata_frozen(e: &Env) -> bool {
+        let event_info: EventInfo = e.storage().instance().get(&DataKey::EventInfo).unwrap();
+        e.ledger().timestamp() >= event_info.start_time
+            || e
+                .storage()
+                .instance()
+                .has(&DataKey::Tickets(TicketKey::MetadataFrozen))
+    }
 
-        // We only return the price here. It is updated during `purchase`.
-        price
+    fn require_metadata_mutable(e: &Env) {
+        if Self::is_metadata_frozen(e) {
+            panic!("Metadata is frozen");
+        }
     }
 
-    // Batch Minting for Organizer
-    pub fn batch_mint(e: &Env, to: Address, tier_symbol: Symbol, amount: u32) {
+    /// ==================== CUSTOM ATTRIBUTES ====================
+
+    /// Attach an organizer-defined attribute to a tier. An empty value removes the key.
+    pub fn set_tier_attribute(e: &Env, tier_symbol: Symbol, key: Symbol, value: String) {
         let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
         admin.require_auth();
+        AdminLogManager::record(
+            e,
+            &admin,
+            "set_tier_attribute",
+            (tier_symbol.clone(), key.clone(), value.clone()),
+        );
+        Self::require_metadata_mutable(e);
 
-        let key = DataKey::Tier(tier_symbol.clone());
-        let mut tier: Tier = e
+        if !e
             .storage()
             .persistent()
-            .get(&key)
-            .unwrap_or_else(|| panic!("Tier not found"));
-
-        if tier.minted + amount > tier.max_supply {
-            panic!("Exceeds tier max supply");
+            .has(&DataKey::Tickets(TicketKey::Tier(tier_symbol.clone())))
+        {
+            panic!("Tier not found");
         }
 
-        for _ in 0..amount {
-            // custom sequential increment
-            let mut counter: u32 = e
-                .storage()
-                .instance()
-                .get(&DataKey::TokenIdCounter)
-                .unwrap();
-            counter += 1;
-            let token_id = counter;
-            e.storage()
-                .instance()
-                .set(&DataKey::TokenIdCounter, &counter);
+        Self::put_attribute(
+            e,
+            DataKey::Tickets(TicketKey::TierAttributes(tier_symbol)),
+            key,
+            value,
+        );
+    }
 
-            Base::sequential_mint(e, &to);
+    /// Attach an organizer-defined attribute to a single ticket. An empty value removes the key.
+    pub fn set_ticket_attribute(e: &Env, token_id: u32, key: Symbol, value: String) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(
+            e,
+            &admin,
+            "set_ticket_attribute",
+            (token_id, key.clone(), value.clone()),
+        );
+        Self::require_metadata_mutable(e);
 
-            let ticket = Ticket {
-                tier_symbol: tier_symbol.clone(),
-                purchase_time: e.ledger().timestamp(),
-                price_paid: 0, // Admin mints are free
-                is_valid: true,
-            };
-            e.storage()
-                .persistent()
-                .set(&DataKey::Ticket(token_id), &ticket);
+        if !e
+            .storage()
+            .persistent()
+            .has(&DataKey::Tickets(TicketKey::Ticket(token_id)))
+        {
+            panic!("Ticket not found");
         }
 
-        tier.minted += amount;
-        e.storage().persistent().set(&key, &tier);
+        Self::put_attribute(
+            e,
+            DataKey::Tickets(TicketKey::TicketAttributes(token_id)),
+            key,
+            value,
+        );
     }
 
-    // Purchase a ticket
-    pub fn purchase(e: &Env, buyer: Address, payment_token: Address, tier_symbol: Symbol) {
-        buyer.require_auth();
+    pub fn get_tier_attributes(e: &Env, tier_symbol: Symbol) -> Map<Symbol, String> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Tickets(TicketKey::TierAttributes(tier_symbol)))
+            .unwrap_or_else(|| Map::new(e))
+    }
 
-        let key = DataKey::Tier(tier_symbol.clone());
-        let mut tier: Tier = e
+    /// Attributes for a ticket: its tier's attributes, overridden by any set on the ticket itself.
+    /// `None` for an unknown ticket.
+    pub fn get_attributes(e: &Env, token_id: u32) -> Option<Map<Symbol, String>> {
+        let ticket: Ticket = e
             .storage()
             .persistent()
-            .get(&key)
-            .unwrap_or_else(|| panic!("Tier not found"));
+            .get(&DataKey::Tickets(TicketKey::Ticket(token_id)))?;
 
-        if !tier.active {
-            panic!("Tier is not active");
+        let mut attributes = Self::get_tier_attributes(e, ticket.tier_symbol);
+        let ticket_attributes: Map<Symbol, String> = e
+            .storage()
+            .persistent()
+            .get(&DataKey::Tickets(TicketKey::TicketAttributes(token_id)))
+            .unwrap_or_else(|| Map::new(e));
+        for (key, value) in ticket_attributes.iter() {
+            attributes.set(key, value);
         }
-        if tier.minted >= tier.max_supply {
-            panic!("Tier sold out");
+        Some(attributes)
+    }
+
+    fn put_attribute(e: &Env, storage_key: DataKey, key: Symbol, value: String) {
+        if value.len() > MAX_ATTRIBUTE_VALUE_LEN {
+            panic!("Attribute value too long");
         }
 
-        let price = Self::get_ticket_price(e, tier_symbol.clone());
+        let mut attributes: Map<Symbol, String> = e
+            .storage()
+            .persistent()
+            .get(&storage_key)
+            .unwrap_or_else(|| Map::new(e));
 
-        // Process payment
-        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
-        let token_client = token::Client::new(e, &payment_token);
-        token_client.transfer(&buyer, &admin, &price);
+        if value.is_empty() {
+            attributes.remove(key);
+        } else {
+            if !attributes.contains_key(key.clone()) && attributes.len() >= MAX_ATTRIBUTES {
+                panic!("Too many attributes");
+            }
+            attributes.set(key, value);
+        }
 
-        // Mint Token
-        let mut counter: u32 = e
-            .storage()
-            .instance()
-            .get(&DataKey::TokenIdCounter)
-            .unwrap();
-        counter += 1;
-        let token_id = counter;
-        e.storage()
-            .instance()
-            .set(&DataKey::TokenIdCounter, &counter);
+        e.storage().persistent().set(&storage_key, &attributes);
+    }
 
-        Base::sequential_mint(e, &buyer);
+    /// ==================== LOCALIZATION ====================
 
-        let ticket = Ticket {
-            tier_symbol: tier_symbol.clone(),
-            purchase_time: e.ledger().timestamp(),
-            price_paid: price,
-            is_valid: true,
-        };
-        e.storage()
-            .persistent()
-            .set(&DataKey::Ticket(token_id), &ticket);
+    /// Locale served when a wallet asks for one the event or tier lacks
+    pub fn set_default_locale(e: &Env, locale: Symbol) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(e, &admin, "set_default_locale", locale.clone());
+        Self::require_metadata_mutable(e);
 
-        tier.minted += 1;
-        tier.current_price = price; // Update the current recorded price for this tier
-        e.storage().persistent().set(&key, &tier);
+        LocaleManager::set_default_locale(e, &locale);
+    }
 
-        // Update pricing config last update time
-        let mut config: PricingConfig =
-            e.storage().instance().get(&DataKey::PricingConfig).unwrap();
-        config.last_update_time = e.ledger().timestamp();
-        e.storage().instance().set(&DataKey::PricingConfig, &config);
+    pub fn get_default_locale(e: &Env) -> Option<Symbol> {
+        LocaleManager::default_locale(e)
     }
 
-    // Refund a ticket
-    pub fn refund(e: &Env, owner: Address, payment_token: Address, token_id: u32) {
-        owner.require_auth();
+    /// Set the event's name and description for `locale`. `None` removes it.
+    pub fn set_event_locale(e: &Env, locale: Symbol, text: Option<LocalizedText>) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(
+            e,
+            &admin,
+            "set_event_locale",
+            (locale.clone(), text.clone()),
+        );
+        Self::require_metadata_mutable(e);
 
-        let current_owner = Self::owner_of(e, token_id);
-        if owner != current_owner {
-            panic!("Not the ticket owner");
-        }
+        LocaleManager::put(e, &DataKey::Catalog(CatalogKey::EventLocales), locale, text);
+    }
 
-        let event_info: EventInfo = e.storage().instance().get(&DataKey::EventInfo).unwrap();
-        if e.ledger().timestamp() > event_info.refund_cutoff_time {
-            panic!("Refund window closed");
-        }
+    /// Set a tier's name and description for `locale`. `None` removes it.
+    pub fn set_tier_locale(
+        e: &Env,
+        tier_symbol: Symbol,
+        locale: Symbol,
+        text: Option<LocalizedText>,
+    ) {
+        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        AdminLogManager::record(
+            e,
+            &admin,
+            "set_tier_locale",
+            (tier_symbol.clone(), locale.clone(), text.clone()),
+        );
+        Self::require_metadata_mutable(e);
 
-        let mut ticket: Ticket = e
+        if !e
             .storage()
             .persistent()
-            .get(&DataKey::Ticket(token_id))
-            .unwrap();
-        if !ticket.is_valid {
-            panic!("Ticket already invalidated");
+            .has(&DataKey::Tickets(TicketKey::Tier(tier_symbol.clone())))
+        {
+            panic!("Tier not found");
         }
 
-        // Process refund
-        let admin: Address = e.storage().instance().get(&DataKey::Admin).unwrap();
-        let token_client = token::Client::new(e, &payment_token);
-        token_client.transfer(&admin, &owner, &ticket.price_paid);
+        LocaleManager::put(
+            e,
+            &DataKey::Catalog(CatalogKey::TierLocales(tier_symbol)),
+            locale,
+            text,
+        );
+    }
 
-        // Invalidate and Burn
-        ticket.is_valid = false;
-        e.storage()
-            .persistent()
-            .set(&DataKey::Ticket(token_id), &ticket);
-        Base::burn(e, &owner, token_id);
+    pub fn get_event_locales(e: &Env) -> Map<Symbol, LocalizedText> {
+        LocaleManager::get(e, &DataKey::Catalog(CatalogKey::EventLocales))
     }
 
-    // Ticket Validation
-    pub fn validate_ticket(e: &Env, token_id: u32) -> bool {
-        let key = DataKey::Ticket(token_id);
-        if !e.storage().persistent().has(&key) {
-            return false;
-        }
-        let ticket: Ticket = e.storage().persistent().get(&key).unwrap();
-        ticket.is_valid
+    pub fn get_tier_locales(e: &Env, tier_symbol: Symbol) -> Map<Symbol, LocalizedText> {
+        LocaleManager::get(e, &DataKey::Catalog(CatalogKey::TierLocales(tier_symbol)))
     }
 
-    // View functions logic
-    pub fn get_ticket(e: &Env, token_id: u32) -> Ticket {
-        e.storage()
+    /// The event's text in `preferred`, else in the default locale
+    pub fn localized_event(e: &Env, preferred: Symbol) -> Option<LocalizedText> {
+        LocaleManager::resolve(e, &DataKey::Catalog(CatalogKey::EventLocales), preferred)
+    }
+
+    /// A tier's text in `preferred`, else in the default locale
+    pub fn localized_tier(
+        e: &Env,
+        tier_symbol: Symbol,
+        preferred: Symbol,
+    ) -> Option<LocalizedText> {
+        LocaleManager::resolve(
+            e,
+            &DataKey::Catalog(CatalogKey::TierLocales(tier_symbol)),
+            preferred,
+        )
+    }
+
+    /// Structured metadata for `token_id`, assembled from on-chain state.
+    /// `None` for an unknown ticket.
+    pub fn token_metadata(e: &Env, token_id: u32) -> Option<TokenMetadata> {
+        let ticket: Ticket = e
+            .storage()
+            .persistent()
+            .get(&DataKey::Tickets(TicketKey::Ticket(token_id)))?;
+        let tier: Tier = e
+            .storage()
             .persistent()
-            .get(&DataKey::Ticket(token_id))
-            .unwrap()
+            .get(&DataKey::Tickets(TicketKey::Tier(
+                ticket.tier_symbol.clone(),
+            )))
+            .unwrap();
+        let event_info: EventInfo = e.storage().instance().get(&DataKey::EventInfo).unwrap();
+
+        Some(TokenMetadata {
+            name: MetadataBuilder::name_with_serial(e, &Base::name(e), token_id),
+            token_id,
+            tier_symbol: ticket.tier_symbol,
+            tier_name: tier.name,
+            event_start_time: event_info.start_time,
+            image_uri: Base::token_uri(e, token_id),
+            is_valid: ticket.is_valid,
+            seat: SeatManager::seat_of(e, token_id),
+        })
     }
 }
 
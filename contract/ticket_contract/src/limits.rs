@@ -0,0 +1,101 @@
+/// Per-tier sale velocity limits
+/// Caps how many tickets of a tier one transaction and one ledger may sell, so a
+/// whale or bot cluster cannot clear a drop in a single ledger close. Counters
+/// live in temporary storage keyed by ledger sequence and lapse on their own.
+///
+/// Contracts cannot see a transaction id, so a transaction is counted as one
+/// buyer within one ledger: every call a transaction makes shares both.
+
+use soroban_sdk::{contracttype, Address, Env, Symbol};
+
+use crate::storage_types::{DataKey, TicketKey};
+
+/// 0 leaves a limit off
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SaleLimits {
+    /// Tickets one buyer may take from the tier in a single transaction
+    pub max_per_tx: u32,
+    /// Tickets the tier may sell across all buyers in a single ledger
+    pub max_per_ledger: u32,
+}
+
+/// Sale limit storage and per-ledger counters
+pub struct SaleLimitManager;
+
+impl SaleLimitManager {
+    pub fn get(e: &Env, tier_symbol: &Symbol) -> Option<SaleLimits> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Tickets(TicketKey::SaleLimits(tier_symbol.clone())))
+    }
+
+    /// `None` removes both limits
+    pub fn set(e: &Env, tier_symbol: &Symbol, limits: Option<SaleLimits>) {
+        let key = DataKey::Tickets(TicketKey::SaleLimits(tier_symbol.clone()));
+        match limits {
+            Some(limits) => e.storage().persistent().set(&key, &limits),
+            None => e.storage().persistent().remove(&key),
+        }
+    }
+
+    /// Tickets `buyer` can still take in this transaction
+    pub fn tx_remaining(e: &Env, tier_symbol: &Symbol, buyer: &Address) -> u32 {
+        match Self::get(e, tier_symbol) {
+            Some(limits) if limits.max_per_tx > 0 => limits
+                .max_per_tx
+                .saturating_sub(Self::count(e, &Self::tx_key(e, tier_symbol, buyer))),
+            _ => u32::MAX,
+        }
+    }
+
+    /// Tickets the tier can still sell in this ledger
+    pub fn ledger_remaining(e: &Env, tier_symbol: &Symbol) -> u32 {
+        match Self::get(e, tier_symbol) {
+            Some(limits) if limits.max_per_ledger > 0 => limits
+                .max_per_ledger
+                .saturating_sub(Self::count(e, &Self::ledger_key(e, tier_symbol))),
+            _ => u32::MAX,
+        }
+    }
+
+    /// Count `quantity` sales to `buyer` against the tier's limits
+    pub fn record_sale(e: &Env, tier_symbol: &Symbol, buyer: &Address, quantity: u32) {
+        if Self::get(e, tier_symbol).is_none() {
+            return;
+        }
+        if Self::tx_remaining(e, tier_symbol, buyer) < quantity {
+            panic!("Transaction ticket limit exceeded");
+        }
+        if Self::ledger_remaining(e, tier_symbol) < quantity {
+            panic!("Ledger ticket limit exceeded");
+        }
+
+        for key in [
+            Self::tx_key(e, tier_symbol, buyer),
+            Self::ledger_key(e, tier_symbol),
+        ] {
+            let sold = Self::count(e, &key);
+            e.storage().temporary().set(&key, &(sold + quantity));
+        }
+    }
+
+    fn count(e: &Env, key: &DataKey) -> u32 {
+        e.storage().temporary().get(key).unwrap_or(0)
+    }
+
+    fn tx_key(e: &Env, tier_symbol: &Symbol, buyer: &Address) -> DataKey {
+        DataKey::Tickets(TicketKey::TxSales(
+            tier_symbol.clone(),
+            e.ledger().sequence(),
+            buyer.clone(),
+        ))
+    }
+
+    fn ledger_key(e: &Env, tier_symbol: &Symbol) -> DataKey {
+        DataKey::Tickets(TicketKey::LedgerSales(
+            tier_symbol.clone(),
+            e.ledger().sequence(),
+        ))
+    }
+}
@@ -0,0 +1,80 @@
+/// Localized event and tier metadata
+/// International events show their name and description in several languages.
+/// The event and each tier carry an optional map from locale code (e.g. `en`,
+/// `pt_BR`) to a localized name and description URI. Lookups take the
+/// wallet's preferred locale and fall back to the event's default locale, so
+/// a wallet never has to guess which translations exist.
+
+use soroban_sdk::{contracttype, Env, Map, String, Symbol};
+
+use crate::storage_types::{CatalogKey, DataKey};
+
+/// Most locales the event or a single tier can carry
+pub const MAX_LOCALES: u32 = 16;
+/// Longest localized name or URI
+pub const MAX_LOCALIZED_LEN: u32 = 128;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LocalizedText {
+    pub name: String,
+    /// URI of the localized description, or a short description inline
+    pub description_uri: String,
+}
+
+/// Locale maps and the default locale
+pub struct LocaleManager;
+
+impl LocaleManager {
+    pub fn default_locale(e: &Env) -> Option<Symbol> {
+        e.storage()
+            .instance()
+            .get(&DataKey::Catalog(CatalogKey::DefaultLocale))
+    }
+
+    pub fn set_default_locale(e: &Env, locale: &Symbol) {
+        e.storage()
+            .instance()
+            .set(&DataKey::Catalog(CatalogKey::DefaultLocale), locale);
+    }
+
+    pub fn get(e: &Env, storage_key: &DataKey) -> Map<Symbol, LocalizedText> {
+        e.storage()
+            .persistent()
+            .get(storage_key)
+            .unwrap_or_else(|| Map::new(e))
+    }
+
+    /// Set or, with `None`, remove the text for `locale` under `storage_key`
+    pub fn put(e: &Env, storage_key: &DataKey, locale: Symbol, text: Option<LocalizedText>) {
+        let mut locales = Self::get(e, storage_key);
+        match text {
+            Some(text) => {
+                if text.name.is_empty() {
+                    panic!("Localized name required");
+                }
+                if text.name.len() > MAX_LOCALIZED_LEN
+                    || text.description_uri.len() > MAX_LOCALIZED_LEN
+                {
+                    panic!("Localized text too long");
+                }
+                if !locales.contains_key(locale.clone()) && locales.len() >= MAX_LOCALES {
+                    panic!("Too many locales");
+                }
+                locales.set(locale, text);
+            }
+            None => {
+                locales.remove(locale);
+            }
+        }
+        e.storage().persistent().set(storage_key, &locales);
+    }
+
+    /// Text for `preferred`, else for the default locale
+    pub fn resolve(e: &Env, storage_key: &DataKey, preferred: Symbol) -> Option<LocalizedText> {
+        let locales = Self::get(e, storage_key);
+        locales
+            .get(preferred)
+            .or_else(|| Self::default_locale(e).and_then(|locale| locales.get(locale)))
+    }
+}
@@ -0,0 +1,134 @@
+/// Lottery statistics
+/// Entrants want to know their odds before committing. Everything here is read
+/// from counters kept up to date as entries are registered, so the view costs
+/// the same whether a tier has ten entries or ten thousand.
+
+use soroban_sdk::{contracttype, Address, Env, Symbol};
+
+use crate::rounds::RoundManager;
+use crate::storage_types::{AllocationConfig, DataKey, LotteryKey};
+
+/// Where a tier's lottery stands relative to its timeline
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum LotteryWindow {
+    /// No lottery has been initialized for the tier
+    NotInitialized,
+    /// Accepting entries until the finalization ledger
+    Open,
+    /// Past the finalization ledger, waiting for the draw
+    AwaitingDraw,
+    /// Winners have been allocated
+    Complete,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LotteryStats {
+    pub entry_count: u32,
+    /// Allocations not yet handed out
+    pub allocations_available: u32,
+    pub unique_participants: u32,
+    /// Chance a single entry wins if the draw ran now, in bps
+    pub win_probability_bps: u32,
+    pub window: LotteryWindow,
+}
+
+/// Participant counters and the stats view
+pub struct LotteryStatsManager;
+
+impl LotteryStatsManager {
+    pub fn participant_count(e: &Env, tier_symbol: &Symbol) -> u32 {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Lottery(LotteryKey::ParticipantCount(
+                tier_symbol.clone(),
+                RoundManager::current(e, tier_symbol),
+            )))
+            .unwrap_or(0)
+    }
+
+    /// Whether `participant` has entered the tier's current round before
+    pub fn has_entered(e: &Env, tier_symbol: &Symbol, participant: &Address) -> bool {
+        e.storage()
+            .persistent()
+            .has(&DataKey::Lottery(LotteryKey::Participant(
+                tier_symbol.clone(),
+                RoundManager::current(e, tier_symbol),
+                participant.clone(),
+            )))
+    }
+
+    /// Note an entry by `participant`, counting them once per round
+    pub fn record_participant(e: &Env, tier_symbol: &Symbol, participant: &Address) {
+        if Self::has_entered(e, tier_symbol, participant) {
+            return;
+        }
+        let round = RoundManager::current(e, tier_symbol);
+        e.storage().persistent().set(
+            &DataKey::Lottery(LotteryKey::Participant(
+                tier_symbol.clone(),
+                round,
+                participant.clone(),
+            )),
+            &true,
+        );
+        e.storage().persistent().set(
+            &DataKey::Lottery(LotteryKey::ParticipantCount(tier_symbol.clone(), round)),
+            &(Self::participant_count(e, tier_symbol) + 1),
+        );
+    }
+
+    /// Stats of the tier's current round
+    pub fn stats(e: &Env, tier_symbol: &Symbol) -> LotteryStats {
+        let round = RoundManager::current(e, tier_symbol);
+        let entry_count: u32 = e
+            .storage()
+            .persistent()
+            .get(&DataKey::Lottery(LotteryKey::EntryCount(
+                tier_symbol.clone(),
+                round,
+            )))
+            .unwrap_or(0);
+        let state_key = DataKey::Lottery(LotteryKey::AllocationState(tier_symbol.clone(), round));
+        let config: Option<AllocationConfig> = e.storage().persistent().get(&state_key);
+
+        let (allocations_available, window) = match config {
+            None => (0, LotteryWindow::NotInitialized),
+            Some(config) => {
+                let window = if config.allocation_complete {
+                    LotteryWindow::Complete
+                } else if e.ledger().sequence() < config.finalization_ledger {
+                    LotteryWindow::Open
+                } else {
+                    LotteryWindow::AwaitingDraw
+                };
+                (
+                    config
+                        .total_allocations
+                        .saturating_sub(config.allocated_count),
+                    window,
+                )
+            }
+        };
+
+        LotteryStats {
+            entry_count,
+            allocations_available,
+            unique_participants: Self::participant_count(e, tier_symbol),
+            win_probability_bps: Self::win_probability_bps(allocations_available, entry_count),
+            window,
+        }
+    }
+
+    /// Every entry wins while there are no more entries than allocations
+    fn win_probability_bps(available: u32, entries: u32) -> u32 {
+        if available == 0 {
+            return 0;
+        }
+        if entries <= available {
+            return 10_000;
+        }
+        (available as u64 * 10_000 / entries as u64) as u32
+    }
+}
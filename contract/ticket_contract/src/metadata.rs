@@ -0,0 +1,90 @@
+/// On-chain token metadata assembly
+/// Builds the fields of a SEP-0054 style metadata document directly from
+/// contract state, for indexers that cannot fetch the off-chain JSON
+
+use soroban_sdk::{contracttype, Env, String, Symbol};
+
+/// Longest collection name that can be combined with a serial number
+pub const MAX_NAME_LEN: usize = 64;
+
+/// Structured metadata for a single ticket
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenMetadata {
+    /// Collection name with serial, e.g. "EventTicket #12"
+    pub name: String,
+    /// Token id this metadata describes
+    pub token_id: u32,
+    /// Tier symbol the ticket was minted under
+    pub tier_symbol: Symbol,
+    /// Human-readable tier name
+    pub tier_name: String,
+    /// Event start (UNIX seconds)
+    pub event_start_time: u64,
+    /// Image / metadata URI (base URI followed by the token id)
+    pub image_uri: String,
+    /// Whether the ticket is still valid for entry
+    pub is_valid: bool,
+    /// Assigned seat, if any
+    pub seat: Option<u32>,
+}
+
+/// Metadata helpers
+pub struct MetadataBuilder;
+
+impl MetadataBuilder {
+    /// Append " #<serial>" to `name`. `initialize` keeps the collection name
+    /// within `MAX_NAME_LEN`.
+    pub fn name_with_serial(e: &Env, name: &String, serial: u32) -> String {
+        let name_len = name.len() as usize;
+        if name_len > MAX_NAME_LEN {
+            panic!("Name too long for metadata");
+        }
+
+        // name + " #" + up to 10 decimal digits
+        let mut buf = [0u8; MAX_NAME_LEN + 12];
+        name.copy_into_slice(&mut buf[..name_len]);
+        buf[name_len] = b' ';
+        buf[name_len + 1] = b'#';
+
+        let mut digits = [0u8; 10];
+        let mut digit_count = 0;
+        let mut value = serial;
+        loop {
+            digits[digit_count] = b'0' + (value % 10) as u8;
+            digit_count += 1;
+            value /= 10;
+            if value == 0 {
+                break;
+            }
+        }
+
+        let mut len = name_len + 2;
+        for i in (0..digit_count).rev() {
+            buf[len] = digits[i];
+            len += 1;
+        }
+
+        String::from_bytes(e, &buf[..len])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_name_with_serial() {
+        let e = Env::default();
+        let name = String::from_str(&e, "EventTicket");
+
+        assert_eq!(
+            MetadataBuilder::name_with_serial(&e, &name, 0),
+            String::from_str(&e, "EventTicket #0")
+        );
+        assert_eq!(
+            MetadataBuilder::name_with_serial(&e, &name, 4021),
+            String::from_str(&e, "EventTicket #4021")
+        );
+    }
+}
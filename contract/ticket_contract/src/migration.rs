@@ -0,0 +1,72 @@
+/// Custodial wallet migrations
+/// Tickets stay soulbound, but exchanges and custodians sometimes rotate the
+/// wallets they hold tickets in. The admin approves a specific (from, to)
+/// pair; the custodian then moves tickets across in one call, which uses the
+/// exemption up. Exemptions are kept after use, with the ledger they were
+/// used in, as a record of every migration.
+
+use soroban_sdk::{contracttype, Address, Env};
+
+use crate::storage_types::{DataKey, ResaleKey};
+
+/// Most tickets moved by one migration
+pub const MAX_MIGRATION_BATCH: u32 = 50;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MigrationExemption {
+    pub approved_ledger: u32,
+    /// Set once the exemption has been used
+    pub used_ledger: Option<u32>,
+}
+
+/// Single-use transfer exemptions by address pair
+pub struct MigrationManager;
+
+impl MigrationManager {
+    fn key(from: &Address, to: &Address) -> DataKey {
+        DataKey::Resale(ResaleKey::MigrationExemption(from.clone(), to.clone()))
+    }
+
+    pub fn get(e: &Env, from: &Address, to: &Address) -> Option<MigrationExemption> {
+        e.storage().persistent().get(&Self::key(from, to))
+    }
+
+    pub fn approve(e: &Env, from: &Address, to: &Address) {
+        if from == to {
+            panic!("Invalid migration");
+        }
+        if let Some(exemption) = Self::get(e, from, to) {
+            if exemption.used_ledger.is_none() {
+                panic!("Migration already approved");
+            }
+        }
+        e.storage().persistent().set(
+            &Self::key(from, to),
+            &MigrationExemption {
+                approved_ledger: e.ledger().sequence(),
+                used_ledger: None,
+            },
+        );
+    }
+
+    /// Withdraw an exemption that hasn't been used
+    pub fn revoke(e: &Env, from: &Address, to: &Address) {
+        match Self::get(e, from, to) {
+            Some(exemption) if exemption.used_ledger.is_none() => {
+                e.storage().persistent().remove(&Self::key(from, to))
+            }
+            _ => panic!("No open migration"),
+        }
+    }
+
+    /// Use up the exemption for `from` -> `to`
+    pub fn consume(e: &Env, from: &Address, to: &Address) {
+        let mut exemption = match Self::get(e, from, to) {
+            Some(exemption) if exemption.used_ledger.is_none() => exemption,
+            _ => panic!("Migration not approved"),
+        };
+        exemption.used_ledger = Some(e.ledger().sequence());
+        e.storage().persistent().set(&Self::key(from, to), &exemption);
+    }
+}
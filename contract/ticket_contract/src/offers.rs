@@ -0,0 +1,63 @@
+/// Promotional ticket offers
+/// Rather than airdropping soulbound tickets into wallets that never asked for
+/// them, the organizer offers a ticket to an address. The offer holds one
+/// ticket of the tier out of general sale; the ticket is only minted once the
+/// recipient accepts within `OFFER_ACCEPT_WINDOW`. Unaccepted offers lapse and
+/// their ticket goes back on sale.
+
+use soroban_sdk::{contracttype, Address, Env, Symbol};
+
+use crate::storage_types::{DataKey, LoyaltyKey};
+
+/// Seconds a recipient has to accept an offer
+pub const OFFER_ACCEPT_WINDOW: u64 = 7 * 24 * 60 * 60;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TicketOffer {
+    pub recipient: Address,
+    pub tier_symbol: Symbol,
+    /// Acceptance deadline (UNIX seconds)
+    pub expiry: u64,
+}
+
+/// Open offers by id
+pub struct OfferManager;
+
+impl OfferManager {
+    pub fn get(e: &Env, offer_id: u32) -> Option<TicketOffer> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Loyalty(LoyaltyKey::Offer(offer_id)))
+    }
+
+    /// Store a new offer to `recipient` and return its id
+    pub fn create(e: &Env, recipient: &Address, tier_symbol: &Symbol) -> u32 {
+        let id: u32 = e
+            .storage()
+            .instance()
+            .get(&DataKey::Loyalty(LoyaltyKey::NextOfferId))
+            .unwrap_or(0);
+        let offer = TicketOffer {
+            recipient: recipient.clone(),
+            tier_symbol: tier_symbol.clone(),
+            expiry: e.ledger().timestamp().saturating_add(OFFER_ACCEPT_WINDOW),
+        };
+        e.storage()
+            .persistent()
+            .set(&DataKey::Loyalty(LoyaltyKey::Offer(id)), &offer);
+        e.storage()
+            .instance()
+            .set(&DataKey::Loyalty(LoyaltyKey::NextOfferId), &(id + 1));
+        id
+    }
+
+    /// Remove an open offer and return it
+    pub fn take(e: &Env, offer_id: u32) -> TicketOffer {
+        let offer = Self::get(e, offer_id).unwrap_or_else(|| panic!("Offer not found"));
+        e.storage()
+            .persistent()
+            .remove(&DataKey::Loyalty(LoyaltyKey::Offer(offer_id)));
+        offer
+    }
+}
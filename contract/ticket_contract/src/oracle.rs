@@ -175,5 +175,5 @@ pub fn oracle_price_to_multiplier(
     if reference_price_8dec == 0 {
         return oracle_precision; // no basis for ratio — neutral
     }
-    (raw_price * oracle_precision) / reference_price_8dec
+    raw_price.saturating_mul(oracle_precision) / reference_price_8dec
 }
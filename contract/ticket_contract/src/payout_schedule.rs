@@ -0,0 +1,69 @@
+/// Streaming revenue payouts
+/// Without a schedule the organizer may withdraw anything the refund reserve
+/// doesn't hold back. A `PayoutSchedule`, fixed at initialization, releases
+/// net revenue in stages instead (e.g. 20% at sale, 30% more at event start,
+/// the rest after completion), so buyers stay protected while the organizer
+/// still gets working capital. Both limits apply to every withdrawal.
+
+use soroban_sdk::{Address, Env};
+
+use crate::accounting::AccountingManager;
+use crate::mul_bps;
+use crate::storage_types::{DataKey, EventInfo, PayoutSchedule, TreasuryKey};
+
+/// Schedule lookup and per-token withdrawal tracking
+pub struct PayoutScheduleManager;
+
+impl PayoutScheduleManager {
+    pub fn get(e: &Env) -> Option<PayoutSchedule> {
+        e.storage()
+            .instance()
+            .get(&DataKey::Treasury(TreasuryKey::PayoutSchedule))
+    }
+
+    pub fn set(e: &Env, schedule: &PayoutSchedule) {
+        e.storage()
+            .instance()
+            .set(&DataKey::Treasury(TreasuryKey::PayoutSchedule), schedule);
+    }
+
+    pub fn is_valid(schedule: &PayoutSchedule, start_time: u64) -> bool {
+        schedule.at_sale_bps as u64 + schedule.at_start_bps as u64 <= 10_000
+            && schedule.completion_time >= start_time
+    }
+
+    /// Revenue of `token` withdrawn so far
+    pub fn withdrawn(e: &Env, token: &Address) -> i128 {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Treasury(TreasuryKey::Withdrawn(token.clone())))
+            .unwrap_or(0)
+    }
+
+    pub fn record_withdrawal(e: &Env, token: &Address, amount: i128) {
+        e.storage().persistent().set(
+            &DataKey::Treasury(TreasuryKey::Withdrawn(token.clone())),
+            &(Self::withdrawn(e, token) + amount),
+        );
+    }
+
+    /// How much more of `token` the schedule lets the organizer withdraw now
+    pub fn remaining(e: &Env, token: &Address) -> i128 {
+        let Some(schedule) = Self::get(e) else {
+            return i128::MAX;
+        };
+        let now = e.ledger().timestamp();
+        if now >= schedule.completion_time {
+            return i128::MAX;
+        }
+
+        let event_info: EventInfo = e.storage().instance().get(&DataKey::EventInfo).unwrap();
+        let mut unlocked_bps = schedule.at_sale_bps;
+        if now >= event_info.start_time {
+            unlocked_bps += schedule.at_start_bps;
+        }
+        let revenue = AccountingManager::token_revenue(e, token);
+        let unlocked = mul_bps(revenue.gross - revenue.refunded, unlocked_bps as i128).unwrap_or(0);
+        unlocked - Self::withdrawn(e, token)
+    }
+}
@@ -0,0 +1,53 @@
+/// Pre-approved charge plans
+/// Season subscribers approve a token allowance for this contract once; the
+/// organizer then charges each ticket through `transfer_from` without the
+/// subscriber signing every purchase. Each plan caps the price per charge and
+/// the number of charges, and the subscriber can revoke it at any time.
+
+use soroban_sdk::{contracttype, Address, Env, Symbol};
+
+use crate::storage_types::{DataKey, TicketKey};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChargePlan {
+    pub buyer: Address,
+    pub payment_token: Address,
+    pub tier_symbol: Symbol,
+    /// Highest ticket price a single charge may collect
+    pub max_price: i128,
+    pub max_charges: u32,
+    pub charges_made: u32,
+    pub revoked: bool,
+}
+
+/// Charge plan storage
+pub struct PlanManager;
+
+impl PlanManager {
+    pub fn get(e: &Env, plan_id: u32) -> Option<ChargePlan> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Tickets(TicketKey::ChargePlan(plan_id)))
+    }
+
+    pub fn set(e: &Env, plan_id: u32, plan: &ChargePlan) {
+        e.storage()
+            .persistent()
+            .set(&DataKey::Tickets(TicketKey::ChargePlan(plan_id)), plan);
+    }
+
+    /// Store a new plan and return its id
+    pub fn create(e: &Env, plan: &ChargePlan) -> u32 {
+        let id: u32 = e
+            .storage()
+            .instance()
+            .get(&DataKey::Tickets(TicketKey::NextChargePlanId))
+            .unwrap_or(0);
+        Self::set(e, id, plan);
+        e.storage()
+            .instance()
+            .set(&DataKey::Tickets(TicketKey::NextChargePlanId), &(id + 1));
+        id
+    }
+}
@@ -0,0 +1,74 @@
+/// Loyalty points settlement
+/// A tier can let buyers settle part of a ticket's price in the organizer's
+/// loyalty points, a token contract of its own. The policy sets how many
+/// points settle one unit of the price and the largest share of the price
+/// points may cover. Points spent go to the payout address with the token
+/// payment in the same call; only the token part is treasury revenue and
+/// refundable.
+
+use soroban_sdk::{contracttype, Address, Env, Symbol};
+
+use crate::mul_bps;
+use crate::storage_types::{DataKey, LoyaltyKey};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PointsPolicy {
+    /// Token contract holding buyers' loyalty points
+    pub points_token: Address,
+    /// Points that settle one unit of the ticket price
+    pub points_per_unit: i128,
+    /// Largest share of the price settled in points, in bps
+    pub max_share_bps: u32,
+}
+
+impl PointsPolicy {
+    /// Part of `price` settled by spending up to `max_points`, and the points spent
+    pub fn split(&self, price: i128, max_points: i128) -> (i128, i128) {
+        let share_cap = mul_bps(price, self.max_share_bps as i128).unwrap_or(0);
+        let covered = (max_points.max(0) / self.points_per_unit).min(share_cap);
+        (covered, covered * self.points_per_unit)
+    }
+}
+
+/// Points policies per tier and points spent per ticket
+pub struct PointsManager;
+
+impl PointsManager {
+    pub fn get_policy(e: &Env, tier_symbol: &Symbol) -> Option<PointsPolicy> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Loyalty(LoyaltyKey::PointsPolicy(
+                tier_symbol.clone(),
+            )))
+    }
+
+    /// `None` stops the tier accepting points
+    pub fn set_policy(e: &Env, tier_symbol: &Symbol, policy: Option<PointsPolicy>) {
+        let key = DataKey::Loyalty(LoyaltyKey::PointsPolicy(tier_symbol.clone()));
+        match policy {
+            Some(policy) => {
+                if policy.points_per_unit <= 0 || policy.max_share_bps > 10_000 {
+                    panic!("Invalid points policy");
+                }
+                e.storage().persistent().set(&key, &policy)
+            }
+            None => e.storage().persistent().remove(&key),
+        }
+    }
+
+    pub fn points_paid(e: &Env, token_id: u32) -> i128 {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Loyalty(LoyaltyKey::PointsPaid(token_id)))
+            .unwrap_or(0)
+    }
+
+    pub fn record_points_paid(e: &Env, token_id: u32, points: i128) {
+        if points > 0 {
+            e.storage()
+                .persistent()
+                .set(&DataKey::Loyalty(LoyaltyKey::PointsPaid(token_id)), &points);
+        }
+    }
+}
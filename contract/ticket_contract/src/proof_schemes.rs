@@ -0,0 +1,59 @@
+/// Lottery proof schemes
+/// Hash-chain randomness shows how each output was derived, but the inputs are
+/// chosen by whoever runs the draw, so its proofs trust the admin. A lottery
+/// can instead use signed randomness: each output is seeded by the organizer's
+/// ed25519 signature over a message fixed by the draw's domain, checked against
+/// the public key published at initialization. Anyone can then verify that an
+/// output came from the committed key and not from a key picked afterwards.
+
+use soroban_sdk::{contracttype, BytesN, Env, Symbol};
+
+use crate::storage_types::{DataKey, LotteryKey};
+
+/// How a lottery's randomness outputs are proven
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ProofScheme {
+    /// Outputs hashed from ledger entropy
+    HashChain,
+    /// Outputs seeded by the organizer's signatures over each output's
+    /// signing message
+    Ed25519Signature,
+}
+
+/// Per-tier proof schemes and the organizer's VRF key
+pub struct ProofSchemeManager;
+
+impl ProofSchemeManager {
+    pub fn get(e: &Env, tier_symbol: &Symbol) -> ProofScheme {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Lottery(LotteryKey::ProofScheme(
+                tier_symbol.clone(),
+            )))
+            .unwrap_or(ProofScheme::HashChain)
+    }
+
+    pub fn set(e: &Env, tier_symbol: &Symbol, scheme: ProofScheme) {
+        if scheme == ProofScheme::Ed25519Signature && Self::public_key(e).is_none() {
+            panic!("No VRF key published");
+        }
+        e.storage().persistent().set(
+            &DataKey::Lottery(LotteryKey::ProofScheme(tier_symbol.clone())),
+            &scheme,
+        );
+    }
+
+    /// ed25519 key signed randomness is checked against
+    pub fn public_key(e: &Env) -> Option<BytesN<32>> {
+        e.storage()
+            .instance()
+            .get(&DataKey::Lottery(LotteryKey::VRFPublicKey))
+    }
+
+    pub fn publish_key(e: &Env, public_key: &BytesN<32>) {
+        e.storage()
+            .instance()
+            .set(&DataKey::Lottery(LotteryKey::VRFPublicKey), public_key);
+    }
+}
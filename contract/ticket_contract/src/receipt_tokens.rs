@@ -0,0 +1,88 @@
+/// Transferable receipt tokens
+/// Corporate buyers need a proof of payment they can hand to an accounting
+/// wallet, which the soulbound ticket cannot be. When enabled for a tier, each
+/// purchase also issues a receipt token for the purchase's receipt: it can be
+/// transferred freely but never admits its holder.
+
+use soroban_sdk::{contracttype, Address, Env, Symbol};
+
+use crate::receipts::Receipt;
+use crate::storage_types::{DataKey, TicketKey};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReceiptToken {
+    /// Receipt the token stands for; also the token's id
+    pub receipt_id: u32,
+    pub owner: Address,
+    pub payment_token: Address,
+    pub total_paid: i128,
+}
+
+/// Per-tier opt-in and receipt token ownership
+pub struct ReceiptTokenManager;
+
+impl ReceiptTokenManager {
+    pub fn is_enabled(e: &Env, tier_symbol: &Symbol) -> bool {
+        e.storage()
+            .persistent()
+            .has(&DataKey::Tickets(TicketKey::ReceiptTokenTier(tier_symbol.clone())))
+    }
+
+    pub fn set_enabled(e: &Env, tier_symbol: &Symbol, enabled: bool) {
+        let key = DataKey::Tickets(TicketKey::ReceiptTokenTier(tier_symbol.clone()));
+        if enabled {
+            e.storage().persistent().set(&key, &true);
+        } else {
+            e.storage().persistent().remove(&key);
+        }
+    }
+
+    pub fn get(e: &Env, receipt_id: u32) -> Option<ReceiptToken> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Tickets(TicketKey::ReceiptToken(receipt_id)))
+    }
+
+    fn set(e: &Env, token: &ReceiptToken) {
+        e.storage().persistent().set(
+            &DataKey::Tickets(TicketKey::ReceiptToken(token.receipt_id)),
+            token,
+        );
+    }
+
+    /// Issue the token for `receipt` to its buyer
+    pub fn mint(e: &Env, receipt: &Receipt) {
+        Self::set(
+            e,
+            &ReceiptToken {
+                receipt_id: receipt.id,
+                owner: receipt.buyer.clone(),
+                payment_token: receipt.payment_token.clone(),
+                total_paid: receipt.total,
+            },
+        );
+
+        #[allow(deprecated)]
+        e.events().publish(
+            (Symbol::new(e, "receipt_token_mint"), receipt.buyer.clone()),
+            (receipt.id, receipt.total),
+        );
+    }
+
+    pub fn transfer(e: &Env, from: &Address, to: &Address, receipt_id: u32) {
+        let mut token =
+            Self::get(e, receipt_id).unwrap_or_else(|| panic!("Receipt token not found"));
+        if token.owner != *from {
+            panic!("Not the receipt token owner");
+        }
+        token.owner = to.clone();
+        Self::set(e, &token);
+
+        #[allow(deprecated)]
+        e.events().publish(
+            (Symbol::new(e, "receipt_token_transfer"), from.clone()),
+            (to.clone(), receipt_id),
+        );
+    }
+}
@@ -0,0 +1,94 @@
+/// Purchase receipts
+/// Records the exact charge composition of every paid purchase so disputes
+/// and accounting can reference it by receipt id
+
+use soroban_sdk::{contracttype, Address, Env, Vec};
+
+use crate::discounts::{AppliedDiscount, PriceQuote};
+use crate::storage_types::{DataKey, TicketKey};
+
+/// Itemized record of one purchase
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Receipt {
+    pub id: u32,
+    pub buyer: Address,
+    pub token_ids: Vec<u32>,
+    /// Per-ticket price before discounts
+    pub base_price: i128,
+    /// Per-ticket discounts, in the order they were applied
+    pub discounts: Vec<AppliedDiscount>,
+    pub fees: i128,
+    /// Per-ticket amount added by the payment token's rounding rule
+    pub rounding: i128,
+    /// Oracle multiplier the base price was computed with (10_000 = 1x)
+    pub oracle_multiplier: i128,
+    pub payment_token: Address,
+    /// Amount transferred for all tickets on the receipt
+    pub total: i128,
+    pub issued_at: u64,
+}
+
+/// Receipt storage and buyer index
+pub struct ReceiptManager;
+
+impl ReceiptManager {
+    /// Store a receipt for `token_ids` bought at `quote`. Returns the receipt id.
+    pub fn record(
+        e: &Env,
+        buyer: &Address,
+        payment_token: &Address,
+        token_ids: Vec<u32>,
+        quote: &PriceQuote,
+    ) -> u32 {
+        let id: u32 = e
+            .storage()
+            .instance()
+            .get(&DataKey::Tickets(TicketKey::NextReceiptId))
+            .unwrap_or(0);
+
+        let count = token_ids.len() as i128;
+        let receipt = Receipt {
+            id,
+            buyer: buyer.clone(),
+            total: quote.price * count,
+            token_ids,
+            base_price: quote.base_price,
+            discounts: quote.discounts.clone(),
+            fees: quote.fees() * count,
+            rounding: quote.rounding,
+            oracle_multiplier: quote.oracle_multiplier,
+            payment_token: payment_token.clone(),
+            issued_at: e.ledger().timestamp(),
+        };
+        e.storage()
+            .persistent()
+            .set(&DataKey::Tickets(TicketKey::Receipt(id)), &receipt);
+
+        let mut ids = Self::get_buyer_receipts(e, buyer);
+        ids.push_back(id);
+        e.storage().persistent().set(
+            &DataKey::Tickets(TicketKey::BuyerReceipts(buyer.clone())),
+            &ids,
+        );
+        e.storage()
+            .instance()
+            .set(&DataKey::Tickets(TicketKey::NextReceiptId), &(id + 1));
+
+        id
+    }
+
+    pub fn get(e: &Env, id: u32) -> Option<Receipt> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Tickets(TicketKey::Receipt(id)))
+    }
+
+    /// Receipt ids issued to `buyer`, oldest first
+    pub fn get_buyer_receipts(e: &Env, buyer: &Address) -> Vec<u32> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Tickets(TicketKey::BuyerReceipts(buyer.clone())))
+            .unwrap_or_else(|| Vec::new(e))
+    }
+}
@@ -0,0 +1,88 @@
+/// Community recovery for abandoned events
+/// Every privileged call and door check-in stamps the ledger of the last admin
+/// activity. Once the event has started and the organizer has been silent for
+/// `ABANDONMENT_LEDGERS`, any ticket holder may switch the contract into
+/// recovery mode. Withdrawals stop for good and holders of unused tickets claim
+/// a pro-rata share of what the treasury held in their payment token when the
+/// first claim in that token was made, never more than they paid.
+
+use soroban_sdk::{contracttype, Address, Env};
+
+use crate::storage_types::{DataKey, EventInfo, TreasuryKey};
+use crate::treasury::TreasuryManager;
+
+/// Ledgers without admin activity after which an event counts as abandoned
+/// (about 30 days at 5 second ledgers)
+pub const ABANDONMENT_LEDGERS: u32 = 518_400;
+
+/// Treasury position of a payment token when recovery claims began
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecoverySnapshot {
+    pub balance: i128,
+    pub liability: i128,
+}
+
+/// Admin activity tracking and recovery payouts
+pub struct RecoveryManager;
+
+impl RecoveryManager {
+    pub fn record_activity(e: &Env) {
+        e.storage().instance().set(
+            &DataKey::Treasury(TreasuryKey::LastAdminActivity),
+            &e.ledger().sequence(),
+        );
+    }
+
+    /// Ledger of the last admin activity, 0 if there has been none
+    pub fn last_activity(e: &Env) -> u32 {
+        e.storage()
+            .instance()
+            .get(&DataKey::Treasury(TreasuryKey::LastAdminActivity))
+            .unwrap_or(0)
+    }
+
+    /// Whether the event has started and the admin has been silent long enough
+    pub fn is_abandoned(e: &Env) -> bool {
+        let event_info: EventInfo = e.storage().instance().get(&DataKey::EventInfo).unwrap();
+        e.ledger().timestamp() > event_info.start_time
+            && e.ledger().sequence() - Self::last_activity(e) >= ABANDONMENT_LEDGERS
+    }
+
+    pub fn is_active(e: &Env) -> bool {
+        e.storage()
+            .instance()
+            .has(&DataKey::Treasury(TreasuryKey::RecoveryStarted))
+    }
+
+    pub fn activate(e: &Env) {
+        e.storage().instance().set(
+            &DataKey::Treasury(TreasuryKey::RecoveryStarted),
+            &e.ledger().sequence(),
+        );
+    }
+
+    /// What a ticket bought for `price_paid` in `token` is owed, taken the
+    /// first time from the treasury's current balance and liability
+    pub fn payout(e: &Env, token: &Address, price_paid: i128) -> i128 {
+        let key = DataKey::Treasury(TreasuryKey::RecoverySnapshot(token.clone()));
+        let snapshot: RecoverySnapshot = e.storage().persistent().get(&key).unwrap_or_else(|| {
+            let snapshot = RecoverySnapshot {
+                balance: TreasuryManager::balance(e, token),
+                liability: TreasuryManager::get_liability(e, token),
+            };
+            e.storage().persistent().set(&key, &snapshot);
+            snapshot
+        });
+
+        if snapshot.liability <= snapshot.balance {
+            return price_paid;
+        }
+        snapshot
+            .balance
+            .checked_mul(price_paid)
+            .map(|share| share / snapshot.liability)
+            .unwrap_or_else(|| snapshot.balance / (snapshot.liability / price_paid).max(1))
+            .min(price_paid)
+    }
+}
@@ -0,0 +1,83 @@
+/// Per-address refund limits
+/// Serial refunders can buy early at low dynamic prices and refund once prices
+/// rise, distorting the demand signal. The organizer can cap how many refunds,
+/// and how much refunded value, each address may take over the event.
+
+use soroban_sdk::{contracttype, Address, Env};
+
+use crate::storage_types::{DataKey, RefundError, TreasuryKey};
+
+/// 0 leaves a limit off
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RefundLimits {
+    pub max_count: u32,
+    /// Summed across payment tokens in their own units
+    pub max_value: i128,
+}
+
+/// Refunds an address has taken so far
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RefundStats {
+    pub count: u32,
+    pub value: i128,
+}
+
+/// Refund limit configuration and per-address counters
+pub struct RefundLimitManager;
+
+impl RefundLimitManager {
+    pub fn get_limits(e: &Env) -> Option<RefundLimits> {
+        e.storage()
+            .instance()
+            .get(&DataKey::Treasury(TreasuryKey::RefundLimits))
+    }
+
+    pub fn check_limits(limits: &Option<RefundLimits>) {
+        if let Some(limits) = limits {
+            if limits.max_value < 0 {
+                panic!("Invalid refund limits");
+            }
+        }
+    }
+
+    /// `None` removes the limits; counters keep accruing either way
+    pub fn set_limits(e: &Env, limits: Option<RefundLimits>) {
+        Self::check_limits(&limits);
+        let key = DataKey::Treasury(TreasuryKey::RefundLimits);
+        match limits {
+            Some(limits) => e.storage().instance().set(&key, &limits),
+            None => e.storage().instance().remove(&key),
+        }
+    }
+
+    pub fn get_stats(e: &Env, owner: &Address) -> RefundStats {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Treasury(TreasuryKey::RefundStats(owner.clone())))
+            .unwrap_or_default()
+    }
+
+    /// Count a refund of `amount` to `owner`, or fail if it would break a limit
+    pub fn record(e: &Env, owner: &Address, amount: i128) -> Result<(), RefundError> {
+        let mut stats = Self::get_stats(e, owner);
+        stats.count += 1;
+        stats.value = stats.value.saturating_add(amount);
+
+        if let Some(limits) = Self::get_limits(e) {
+            if limits.max_count > 0 && stats.count > limits.max_count {
+                return Err(RefundError::CountLimitExceeded);
+            }
+            if limits.max_value > 0 && stats.value > limits.max_value {
+                return Err(RefundError::ValueLimitExceeded);
+            }
+        }
+
+        e.storage().persistent().set(
+            &DataKey::Treasury(TreasuryKey::RefundStats(owner.clone())),
+            &stats,
+        );
+        Ok(())
+    }
+}
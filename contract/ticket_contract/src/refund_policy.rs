@@ -0,0 +1,90 @@
+/// Refund payout policy
+/// Prices rise with demand, so refunding the price paid lets speculators hold
+/// cheap early inventory risk-free. The organizer can instead refund the lesser
+/// of the price paid and the tier's current price, or keep a restocking fee.
+/// Whatever is not paid out stays in the treasury as revenue.
+
+use soroban_sdk::{contracttype, Env};
+
+use crate::storage_types::{DataKey, TreasuryKey};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RefundMode {
+    /// Refund the full price paid
+    PricePaid,
+    /// Refund min(price paid, current price of the tier in the same token)
+    LesserOfPaidAndCurrent,
+    /// Refund the price paid minus a fee in bps
+    RestockingFee(u32),
+}
+
+/// Refund mode storage and payout calculation
+pub struct RefundPolicyManager;
+
+impl RefundPolicyManager {
+    pub fn get_mode(e: &Env) -> RefundMode {
+        e.storage()
+            .instance()
+            .get(&DataKey::Treasury(TreasuryKey::RefundMode))
+            .unwrap_or(RefundMode::PricePaid)
+    }
+
+    /// Reject a restocking fee above 100%
+    pub fn check_mode(mode: &RefundMode) {
+        if let RefundMode::RestockingFee(fee_bps) = mode {
+            if *fee_bps > 10_000 {
+                panic!("Restocking fee above 100%");
+            }
+        }
+    }
+
+    pub fn set_mode(e: &Env, mode: &RefundMode) {
+        Self::check_mode(mode);
+        e.storage()
+            .instance()
+            .set(&DataKey::Treasury(TreasuryKey::RefundMode), mode);
+    }
+
+    /// Amount to pay back for a ticket bought at `price_paid`. `current_price`
+    /// is only evaluated in `LesserOfPaidAndCurrent` mode.
+    pub fn payout(
+        mode: &RefundMode,
+        price_paid: i128,
+        current_price: impl FnOnce() -> i128,
+    ) -> i128 {
+        match mode {
+            RefundMode::PricePaid => price_paid,
+            RefundMode::LesserOfPaidAndCurrent => price_paid.min(current_price()).max(0),
+            RefundMode::RestockingFee(fee_bps) => {
+                let fee = crate::mul_bps(price_paid, *fee_bps as i128).unwrap_or(price_paid);
+                price_paid - fee
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_payout_modes() {
+        assert_eq!(
+            RefundPolicyManager::payout(&RefundMode::PricePaid, 100, || 80),
+            100
+        );
+        assert_eq!(
+            RefundPolicyManager::payout(&RefundMode::LesserOfPaidAndCurrent, 100, || 80),
+            80
+        );
+        assert_eq!(
+            RefundPolicyManager::payout(&RefundMode::LesserOfPaidAndCurrent, 100, || 120),
+            100
+        );
+        assert_eq!(
+            RefundPolicyManager::payout(&RefundMode::RestockingFee(1_500), 100, || 0),
+            85
+        );
+    }
+}
@@ -0,0 +1,145 @@
+/// Capped resale
+/// Tickets are soulbound unless their tier has a resale policy. Holders of such
+/// tickets list them here at a price no more than the policy's markup over face
+/// value (what was paid at the original sale), and a buyer takes the listing in
+/// one call: the price is paid in the ticket's payment token, the policy's
+/// royalty goes to the organizer's payout address, the rest to the seller, and
+/// the ticket moves to the buyer. No external marketplace is involved.
+///
+/// For long-lead events the cap can be indexed to inflation: the markup cap is
+/// scaled by how far a price index read from the oracle has risen since the
+/// policy's base reading. The cap is worked out when the ticket is listed and
+/// locked onto the listing, so later index moves don't invalidate it.
+
+use soroban_sdk::{contracttype, Address, Env, String, Symbol};
+
+use crate::mul_bps;
+use crate::oracle::fetch_price_with_fallback;
+use crate::storage_types::{DataKey, PricingConfig, PricingKey, ResaleKey};
+
+/// Inflation index the resale cap follows
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InflationIndex {
+    /// Oracle pair reporting the index (e.g. a CPI feed)
+    pub pair: String,
+    /// Index reading the face value is measured against
+    pub base_index: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ResaleCapMode {
+    /// Face value plus the markup
+    Fixed,
+    /// Face value plus the markup, scaled up by index / base index
+    Indexed(InflationIndex),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ResalePolicy {
+    /// Most a listing may ask above face value, in bps of face value
+    pub max_markup_bps: u32,
+    /// Organizer's share of each resale price, in bps
+    pub royalty_bps: u32,
+    pub cap_mode: ResaleCapMode,
+}
+
+impl ResalePolicy {
+    /// Highest listing price for a ticket with this face value right now
+    pub fn price_cap(&self, e: &Env, face_value: i128) -> i128 {
+        let markup = mul_bps(face_value, self.max_markup_bps as i128).unwrap_or(i128::MAX);
+        let cap = face_value.saturating_add(markup);
+        let ResaleCapMode::Indexed(index) = &self.cap_mode else {
+            return cap;
+        };
+
+        let config: PricingConfig = e
+            .storage()
+            .instance()
+            .get(&DataKey::Pricing(PricingKey::Config))
+            .unwrap();
+        let current = fetch_price_with_fallback(
+            e,
+            &config.oracle_address,
+            &config.dex_pool_address,
+            index.pair.clone(),
+            config.max_oracle_age_seconds,
+        )
+        .unwrap_or_else(|| panic!("Inflation index unavailable"))
+        .price;
+        // Only inflation raises the cap; deflation leaves it at the markup cap
+        if current <= index.base_index {
+            return cap;
+        }
+        cap.checked_mul(current)
+            .map(|scaled| scaled / index.base_index)
+            .unwrap_or(i128::MAX)
+    }
+
+    pub fn royalty(&self, price: i128) -> i128 {
+        mul_bps(price, self.royalty_bps as i128).unwrap_or(price)
+    }
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Listing {
+    pub seller: Address,
+    /// Asking price in the ticket's payment token
+    pub price: i128,
+    /// Highest price the listing may ask, fixed when it was listed
+    pub price_cap: i128,
+}
+
+/// Resale policies and the listing book
+pub struct ResaleManager;
+
+impl ResaleManager {
+    pub fn get_policy(e: &Env, tier_symbol: &Symbol) -> Option<ResalePolicy> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Resale(ResaleKey::ResalePolicy(
+                tier_symbol.clone(),
+            )))
+    }
+
+    /// `None` makes the tier's tickets soulbound again
+    pub fn set_policy(e: &Env, tier_symbol: &Symbol, policy: Option<ResalePolicy>) {
+        let key = DataKey::Resale(ResaleKey::ResalePolicy(tier_symbol.clone()));
+        match policy {
+            Some(policy) => {
+                if policy.royalty_bps > 10_000 {
+                    panic!("Invalid royalty");
+                }
+                if let ResaleCapMode::Indexed(index) = &policy.cap_mode {
+                    if index.base_index <= 0 {
+                        panic!("Invalid base index");
+                    }
+                }
+                e.storage().persistent().set(&key, &policy)
+            }
+            None => e.storage().persistent().remove(&key),
+        }
+    }
+
+    pub fn get_listing(e: &Env, token_id: u32) -> Option<Listing> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Resale(ResaleKey::Listing(token_id)))
+    }
+
+    pub fn list(e: &Env, token_id: u32, listing: &Listing) {
+        e.storage()
+            .persistent()
+            .set(&DataKey::Resale(ResaleKey::Listing(token_id)), listing);
+    }
+
+    /// Remove the ticket's listing, if any
+    pub fn delist(e: &Env, token_id: u32) {
+        e.storage()
+            .persistent()
+            .remove(&DataKey::Resale(ResaleKey::Listing(token_id)));
+    }
+}
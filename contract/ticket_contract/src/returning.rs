@@ -0,0 +1,107 @@
+/// Returning attendee pricing
+/// Wallets that checked in at the previous edition of an event can buy one
+/// ticket of a tier at last year's base price for a limited window. There is
+/// no shared storage across event contracts, so the organizer imports a merkle
+/// root over the previous event's checked-in wallets; each attendee proves
+/// their leaf when they buy. Replacing the offer's root does not give wallets
+/// that already bought a second grandfathered ticket.
+
+use soroban_sdk::{contracttype, xdr::ToXdr, Address, BytesN, Env, Symbol, Vec};
+
+use crate::archive::ArchiveManager;
+use crate::storage_types::{DataKey, LoyaltyKey};
+
+/// Grandfathered price offered to a tier's returning attendees
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReturningOffer {
+    /// Last edition's base price, charged before rounding when below the quote
+    pub price: i128,
+    /// Last timestamp the offer can be taken up
+    pub window_end: u64,
+    /// Merkle root (built like `ArchiveManager::merkle_root`) over attendee leaves
+    pub root: BytesN<32>,
+    pub attendee_count: u32,
+}
+
+/// Offers per tier and the attendees who took them up
+pub struct ReturningManager;
+
+impl ReturningManager {
+    pub fn get(e: &Env, tier_symbol: &Symbol) -> Option<ReturningOffer> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Loyalty(LoyaltyKey::ReturningOffer(
+                tier_symbol.clone(),
+            )))
+    }
+
+    /// `None` withdraws the offer
+    pub fn set(e: &Env, tier_symbol: &Symbol, offer: Option<ReturningOffer>) {
+        let key = DataKey::Loyalty(LoyaltyKey::ReturningOffer(tier_symbol.clone()));
+        match offer {
+            Some(offer) => {
+                if offer.price <= 0 {
+                    panic!("Invalid returning price");
+                }
+                if offer.attendee_count == 0 {
+                    panic!("Empty attendance root");
+                }
+                e.storage().persistent().set(&key, &offer)
+            }
+            None => e.storage().persistent().remove(&key),
+        }
+    }
+
+    /// Leaf of the attendance tree for `attendee`, sha256(xdr(address))
+    pub fn attendee_leaf(e: &Env, attendee: &Address) -> BytesN<32> {
+        e.crypto().sha256(&attendee.clone().to_xdr(e)).to_bytes()
+    }
+
+    pub fn has_claimed(e: &Env, tier_symbol: &Symbol, attendee: &Address) -> bool {
+        e.storage()
+            .persistent()
+            .has(&DataKey::Loyalty(LoyaltyKey::ReturningClaim(
+                tier_symbol.clone(),
+                attendee.clone(),
+            )))
+    }
+
+    /// Check the offer is open and `attendee` is in its root, then mark the
+    /// offer taken for them. Returns the offer.
+    pub fn claim(
+        e: &Env,
+        tier_symbol: &Symbol,
+        attendee: &Address,
+        index: u32,
+        proof: &Vec<BytesN<32>>,
+    ) -> ReturningOffer {
+        let offer =
+            Self::get(e, tier_symbol).unwrap_or_else(|| panic!("Tier has no returning offer"));
+        if e.ledger().timestamp() > offer.window_end {
+            panic!("Returning offer expired");
+        }
+        if Self::has_claimed(e, tier_symbol, attendee) {
+            panic!("Returning offer already taken");
+        }
+        if !ArchiveManager::verify_inclusion(
+            e,
+            Self::attendee_leaf(e, attendee),
+            index,
+            offer.attendee_count,
+            proof,
+            &offer.root,
+        ) {
+            panic!("Invalid attendance proof");
+        }
+
+        e.storage().persistent().set(
+            &DataKey::Loyalty(LoyaltyKey::ReturningClaim(
+                tier_symbol.clone(),
+                attendee.clone(),
+            )),
+            &true,
+        );
+        offer
+    }
+}
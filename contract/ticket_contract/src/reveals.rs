@@ -0,0 +1,108 @@
+/// Reveal scoring
+/// Committing a seed costs nothing, so without a reward most participants
+/// would never reveal. Every reveal is counted for the tier and towards the
+/// revealer's reveal score, which stays with the address across tiers for
+/// future priority. A tier can also give entries from addresses that revealed
+/// for its draw a weight bonus in the lottery. Reveal markers outlive pruned
+/// commitments, so a draw after pruning still applies the bonus.
+
+use soroban_sdk::{Address, Env, Symbol, Vec};
+
+use crate::allocation::LotteryEntry;
+use crate::storage_types::{DataKey, LotteryKey};
+
+/// Largest extra weight a tier may give a revealer's entries
+pub const MAX_REVEAL_BONUS: u32 = 10;
+
+/// Reveals per tier and reveal scores per address
+pub struct RevealManager;
+
+impl RevealManager {
+    /// Count a reveal by `participant` for the tier's draw
+    pub fn record(e: &Env, tier_symbol: &Symbol, participant: &Address) {
+        e.storage().persistent().set(
+            &DataKey::Lottery(LotteryKey::Revealed(
+                tier_symbol.clone(),
+                participant.clone(),
+            )),
+            &true,
+        );
+        e.storage().persistent().set(
+            &DataKey::Lottery(LotteryKey::RevealCount(tier_symbol.clone())),
+            &(Self::revealed_count(e, tier_symbol) + 1),
+        );
+        e.storage().persistent().set(
+            &DataKey::Lottery(LotteryKey::RevealScore(participant.clone())),
+            &Self::score(e, participant).saturating_add(1),
+        );
+    }
+
+    pub fn has_revealed(e: &Env, tier_symbol: &Symbol, participant: &Address) -> bool {
+        e.storage()
+            .persistent()
+            .has(&DataKey::Lottery(LotteryKey::Revealed(
+                tier_symbol.clone(),
+                participant.clone(),
+            )))
+    }
+
+    /// Commitments revealed for the tier's draw
+    pub fn revealed_count(e: &Env, tier_symbol: &Symbol) -> u32 {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Lottery(LotteryKey::RevealCount(
+                tier_symbol.clone(),
+            )))
+            .unwrap_or(0)
+    }
+
+    /// Reveals `participant` has made over every tier
+    pub fn score(e: &Env, participant: &Address) -> u32 {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Lottery(LotteryKey::RevealScore(
+                participant.clone(),
+            )))
+            .unwrap_or(0)
+    }
+
+    pub fn bonus(e: &Env, tier_symbol: &Symbol) -> u32 {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Lottery(LotteryKey::RevealBonus(
+                tier_symbol.clone(),
+            )))
+            .unwrap_or(0)
+    }
+
+    /// 0 draws the tier's lottery unweighted
+    pub fn set_bonus(e: &Env, tier_symbol: &Symbol, bonus: u32) {
+        if bonus > MAX_REVEAL_BONUS {
+            panic!("Reveal bonus too large");
+        }
+        let key = DataKey::Lottery(LotteryKey::RevealBonus(tier_symbol.clone()));
+        if bonus == 0 {
+            e.storage().persistent().remove(&key);
+        } else {
+            e.storage().persistent().set(&key, &bonus);
+        }
+    }
+
+    /// Draw weight of each entry: 1, plus the bonus for revealers. `None`
+    /// when the tier has no bonus.
+    pub fn weights(e: &Env, tier_symbol: &Symbol, entries: &Vec<LotteryEntry>) -> Option<Vec<u32>> {
+        let bonus = Self::bonus(e, tier_symbol);
+        if bonus == 0 {
+            return None;
+        }
+        let mut weights = Vec::new(e);
+        for entry in entries.iter() {
+            if Self::has_revealed(e, tier_symbol, &entry.participant) {
+                weights.push_back(1 + bonus);
+            } else {
+                weights.push_back(1);
+            }
+        }
+        Some(weights)
+    }
+}
@@ -0,0 +1,80 @@
+/// Role membership
+/// Council members, relayers, payment processors, gate supervisors and
+/// scanners are stored as one flag per address. Counts per role are kept alongside so the
+/// configuration view can summarise who holds power without enumerating storage.
+
+use soroban_sdk::{contracttype, Address, Env};
+
+use crate::storage_types::{DataKey, GovernanceKey, TicketKey, VenueKey};
+
+#[derive(Clone, Copy)]
+pub enum Role {
+    Council,
+    Relayer,
+    PaymentProcessor,
+    GateSupervisor,
+    Scanner,
+}
+
+/// Number of addresses holding each role
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RoleCounts {
+    pub council: u32,
+    pub relayers: u32,
+    pub payment_processors: u32,
+    pub gate_supervisors: u32,
+    pub scanners: u32,
+}
+
+/// Role flags and per-role counts
+pub struct RoleManager;
+
+impl RoleManager {
+    fn key(role: Role, member: Address) -> DataKey {
+        match role {
+            Role::Council => DataKey::Governance(GovernanceKey::CouncilMember(member)),
+            Role::Relayer => DataKey::Tickets(TicketKey::Relayer(member)),
+            Role::PaymentProcessor => DataKey::Tickets(TicketKey::PaymentProcessor(member)),
+            Role::GateSupervisor => DataKey::Venue(VenueKey::GateSupervisor(member)),
+            Role::Scanner => DataKey::Venue(VenueKey::Scanner(member)),
+        }
+    }
+
+    pub fn has(e: &Env, role: Role, member: &Address) -> bool {
+        e.storage().persistent().has(&Self::key(role, member.clone()))
+    }
+
+    pub fn counts(e: &Env) -> RoleCounts {
+        e.storage()
+            .instance()
+            .get(&DataKey::Governance(GovernanceKey::RoleCounts))
+            .unwrap_or_default()
+    }
+
+    /// Grant or revoke `role`. Repeating the current state changes nothing.
+    pub fn set(e: &Env, role: Role, member: &Address, allowed: bool) {
+        if Self::has(e, role, member) == allowed {
+            return;
+        }
+        let key = Self::key(role, member.clone());
+        if allowed {
+            e.storage().persistent().set(&key, &true);
+        } else {
+            e.storage().persistent().remove(&key);
+        }
+
+        let mut counts = Self::counts(e);
+        let count = match role {
+            Role::Council => &mut counts.council,
+            Role::Relayer => &mut counts.relayers,
+            Role::PaymentProcessor => &mut counts.payment_processors,
+            Role::GateSupervisor => &mut counts.gate_supervisors,
+            Role::Scanner => &mut counts.scanners,
+        };
+        *count = if allowed { *count + 1 } else { *count - 1 };
+        e.storage()
+            .instance()
+            .set(&DataKey::Governance(GovernanceKey::RoleCounts), &counts);
+    }
+}
@@ -0,0 +1,68 @@
+/// Price rounding per payment token
+/// Dynamic pricing yields amounts like 10_999_873 stroops; organizers can round
+/// every price in a token up to a multiple of its smallest sensible unit
+
+use soroban_sdk::{contracttype, Address, Env};
+
+use crate::storage_types::{DataKey, PricingKey};
+
+/// Round prices up to the next multiple of `increment` (e.g. 100_000 stroops = 0.01 XLM).
+/// Also serves as the minimum charge for any non-free ticket.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoundingRule {
+    pub increment: i128,
+}
+
+/// Rounding rule storage and application
+pub struct RoundingManager;
+
+impl RoundingManager {
+    pub fn get(e: &Env, payment_token: &Address) -> Option<RoundingRule> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Pricing(PricingKey::Rounding(
+                payment_token.clone(),
+            )))
+    }
+
+    /// `None` removes the rule so prices in the token are charged exactly
+    pub fn set(e: &Env, payment_token: &Address, rule: Option<RoundingRule>) {
+        let key = DataKey::Pricing(PricingKey::Rounding(payment_token.clone()));
+        match rule {
+            Some(rule) => e.storage().persistent().set(&key, &rule),
+            None => e.storage().persistent().remove(&key),
+        }
+    }
+
+    /// Amount to add to `price` so it lands on the rule's increment. Free tickets stay free.
+    pub fn adjustment(rule: &RoundingRule, price: i128) -> i128 {
+        if price <= 0 || rule.increment <= 1 {
+            return 0;
+        }
+        let remainder = price % rule.increment;
+        if remainder == 0 {
+            0
+        } else {
+            rule.increment - remainder
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_adjustment_rounds_up_to_increment() {
+        let rule = RoundingRule { increment: 100 };
+        assert_eq!(RoundingManager::adjustment(&rule, 1), 99);
+        assert_eq!(RoundingManager::adjustment(&rule, 250), 50);
+        assert_eq!(RoundingManager::adjustment(&rule, 300), 0);
+        assert_eq!(RoundingManager::adjustment(&rule, 0), 0);
+        assert_eq!(
+            RoundingManager::adjustment(&RoundingRule { increment: 1 }, 7),
+            0
+        );
+    }
+}
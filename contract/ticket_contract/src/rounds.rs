@@ -0,0 +1,47 @@
+/// Lottery rounds
+/// A tier can run its lottery in waves. Each round has its own allocation
+/// config, entries, VRF state, results and sealed proof, all keyed by
+/// `(tier, round)`. Entries, draws and claims apply to the tier's current
+/// round; the next round opens only once the current one has been allocated,
+/// so earlier rounds stay on record.
+
+use soroban_sdk::{Env, Symbol};
+
+use crate::storage_types::{AllocationConfig, DataKey, LotteryKey};
+
+/// Current round per tier
+pub struct RoundManager;
+
+impl RoundManager {
+    /// Round the tier's lottery is on, counting from 0
+    pub fn current(e: &Env, tier_symbol: &Symbol) -> u32 {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Lottery(LotteryKey::CurrentRound(
+                tier_symbol.clone(),
+            )))
+            .unwrap_or(0)
+    }
+
+    /// Move the tier on to its next round once the current one is allocated.
+    /// Returns the new round, which needs `initialize_lottery` before entries.
+    pub fn open_next(e: &Env, tier_symbol: &Symbol) -> u32 {
+        let round = Self::current(e, tier_symbol);
+        let state: AllocationConfig = e
+            .storage()
+            .persistent()
+            .get(&DataKey::Lottery(LotteryKey::AllocationState(
+                tier_symbol.clone(),
+                round,
+            )))
+            .unwrap_or_else(|| panic!("Allocation not initialized"));
+        if !state.allocation_complete {
+            panic!("Current round not finalized");
+        }
+        e.storage().persistent().set(
+            &DataKey::Lottery(LotteryKey::CurrentRound(tier_symbol.clone())),
+            &(round + 1),
+        );
+        round + 1
+    }
+}
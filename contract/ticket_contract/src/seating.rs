@@ -0,0 +1,120 @@
+/// Assigned seating
+/// The organizer assigns numbered seats within a tier to tickets. Venue
+/// systems page through a tier's assignments with `export_seat_assignments`
+/// and watch the tier's seat version, which is bumped on every assignment or
+/// release, to know when they need to sync again. Refunded and voided tickets
+/// give up their seat.
+
+use soroban_sdk::{Address, Env, Symbol, Vec};
+use stellar_tokens::non_fungible::Base;
+
+use crate::storage_types::{DataKey, VenueKey};
+
+/// Most assignments returned by one `page` call
+pub const MAX_SEAT_PAGE: u32 = 100;
+
+/// Seat to ticket map per tier
+pub struct SeatManager;
+
+impl SeatManager {
+    /// Seat held by `token_id`, if any
+    pub fn seat_of(e: &Env, token_id: u32) -> Option<u32> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Venue(VenueKey::TicketSeat(token_id)))
+    }
+
+    /// Ticket holding `seat_id` in the tier, if any
+    pub fn holder_of(e: &Env, tier_symbol: &Symbol, seat_id: u32) -> Option<u32> {
+        e.storage().persistent().get(&DataKey::Venue(VenueKey::Seat(
+            tier_symbol.clone(),
+            seat_id,
+        )))
+    }
+
+    /// Changes made to the tier's seat map so far
+    pub fn version(e: &Env, tier_symbol: &Symbol) -> u32 {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Venue(VenueKey::SeatVersion(tier_symbol.clone())))
+            .unwrap_or(0)
+    }
+
+    /// Seat `token_id` in `seat_id`, moving it if it already holds a seat
+    pub fn assign(e: &Env, tier_symbol: &Symbol, seat_id: u32, token_id: u32) {
+        if Self::holder_of(e, tier_symbol, seat_id).is_some() {
+            panic!("Seat already taken");
+        }
+        Self::release(e, tier_symbol, token_id);
+
+        e.storage().persistent().set(
+            &DataKey::Venue(VenueKey::Seat(tier_symbol.clone(), seat_id)),
+            &token_id,
+        );
+        e.storage()
+            .persistent()
+            .set(&DataKey::Venue(VenueKey::TicketSeat(token_id)), &seat_id);
+        let mut seats = Self::seats(e, tier_symbol);
+        seats.push_back(seat_id);
+        Self::set_seats(e, tier_symbol, &seats);
+        Self::bump_version(e, tier_symbol);
+    }
+
+    /// Free the seat held by `token_id`. Does nothing if it holds none.
+    pub fn release(e: &Env, tier_symbol: &Symbol, token_id: u32) {
+        let Some(seat_id) = Self::seat_of(e, token_id) else {
+            return;
+        };
+        e.storage()
+            .persistent()
+            .remove(&DataKey::Venue(VenueKey::Seat(
+                tier_symbol.clone(),
+                seat_id,
+            )));
+        e.storage()
+            .persistent()
+            .remove(&DataKey::Venue(VenueKey::TicketSeat(token_id)));
+        let mut seats = Self::seats(e, tier_symbol);
+        if let Some(index) = seats.first_index_of(seat_id) {
+            seats.remove(index);
+        }
+        Self::set_seats(e, tier_symbol, &seats);
+        Self::bump_version(e, tier_symbol);
+    }
+
+    /// Up to `limit` (seat_id, token_id, owner) assignments of the tier from
+    /// position `start`, in the order seats were assigned
+    pub fn page(e: &Env, tier_symbol: &Symbol, start: u32, limit: u32) -> Vec<(u32, u32, Address)> {
+        let seats = Self::seats(e, tier_symbol);
+        let end = seats.len().min(start.saturating_add(limit.min(MAX_SEAT_PAGE)));
+
+        let mut assignments = Vec::new(e);
+        for index in start..end {
+            let seat_id = seats.get(index).unwrap();
+            let token_id = Self::holder_of(e, tier_symbol, seat_id).unwrap();
+            assignments.push_back((seat_id, token_id, Base::owner_of(e, token_id)));
+        }
+        assignments
+    }
+
+    fn seats(e: &Env, tier_symbol: &Symbol) -> Vec<u32> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Venue(VenueKey::SeatList(tier_symbol.clone())))
+            .unwrap_or_else(|| Vec::new(e))
+    }
+
+    fn set_seats(e: &Env, tier_symbol: &Symbol, seats: &Vec<u32>) {
+        e.storage().persistent().set(
+            &DataKey::Venue(VenueKey::SeatList(tier_symbol.clone())),
+            seats,
+        );
+    }
+
+    fn bump_version(e: &Env, tier_symbol: &Symbol) {
+        e.storage().persistent().set(
+            &DataKey::Venue(VenueKey::SeatVersion(tier_symbol.clone())),
+            &(Self::version(e, tier_symbol) + 1),
+        );
+    }
+}
@@ -0,0 +1,99 @@
+/// Event series entitlements
+/// Each event is its own contract, so a series is built by linking: this
+/// event's admin names an earlier event's contract and the benefit its ticket
+/// holders get here, a discount on one ticket of a tier and optionally a
+/// guaranteed right to buy it even if the tier's eligibility rules would
+/// exclude them. Ownership and validity of the source ticket are read from the
+/// source contract, and each source ticket can claim its benefit once.
+
+use soroban_sdk::{contractclient, contracttype, Address, Env, Symbol};
+
+use crate::storage_types::{DataKey, LoyaltyKey, Ticket};
+
+/// The parts of another event's ticket contract a claim reads
+#[contractclient(name = "SeriesEventClient")]
+pub trait SeriesEventInterface {
+    fn owner_of(env: Env, token_id: u32) -> Address;
+    fn get_ticket(env: Env, token_id: u32) -> Option<Ticket>;
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SeriesBenefit {
+    /// Tier of this event the benefit buys into
+    pub tier_symbol: Symbol,
+    /// Taken off the ticket price, after other discounts
+    pub discount_bps: u32,
+    /// Claimants skip the tier's eligibility requirements
+    pub guaranteed: bool,
+}
+
+/// Linked source events and claimed source tickets
+pub struct SeriesManager;
+
+impl SeriesManager {
+    pub fn get(e: &Env, source_event: &Address) -> Option<SeriesBenefit> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Loyalty(LoyaltyKey::SeriesBenefit(
+                source_event.clone(),
+            )))
+    }
+
+    /// `None` unlinks the source event
+    pub fn set(e: &Env, source_event: &Address, benefit: Option<SeriesBenefit>) {
+        let key = DataKey::Loyalty(LoyaltyKey::SeriesBenefit(source_event.clone()));
+        match benefit {
+            Some(benefit) => {
+                if benefit.discount_bps > 10_000 {
+                    panic!("Invalid discount");
+                }
+                e.storage().persistent().set(&key, &benefit)
+            }
+            None => e.storage().persistent().remove(&key),
+        }
+    }
+
+    pub fn is_claimed(e: &Env, source_event: &Address, source_token_id: u32) -> bool {
+        e.storage()
+            .persistent()
+            .has(&DataKey::Loyalty(LoyaltyKey::SeriesClaim(
+                source_event.clone(),
+                source_token_id,
+            )))
+    }
+
+    /// Check `holder` holds a valid, unclaimed `source_token_id` of
+    /// `source_event` and mark it claimed. Returns the benefit.
+    pub fn claim(
+        e: &Env,
+        holder: &Address,
+        source_event: &Address,
+        source_token_id: u32,
+    ) -> SeriesBenefit {
+        let benefit =
+            Self::get(e, source_event).unwrap_or_else(|| panic!("Event not in series"));
+        if Self::is_claimed(e, source_event, source_token_id) {
+            panic!("Benefit already claimed");
+        }
+        let source = SeriesEventClient::new(e, source_event);
+        if source.owner_of(&source_token_id) != *holder {
+            panic!("Not the ticket owner");
+        }
+        let valid = source
+            .get_ticket(&source_token_id)
+            .is_some_and(|ticket| ticket.is_valid);
+        if !valid {
+            panic!("Source ticket not valid");
+        }
+
+        e.storage().persistent().set(
+            &DataKey::Loyalty(LoyaltyKey::SeriesClaim(
+                source_event.clone(),
+                source_token_id,
+            )),
+            &true,
+        );
+        benefit
+    }
+}
@@ -0,0 +1,86 @@
+/// Event-wide sales counters
+/// Running totals of tickets issued and refunded, read by the event factory
+/// to build the organizer's reputation record across events, plus per-tier
+/// counts of slots returned to sale. Companion tickets split off a group
+/// ticket are counted separately so they don't inflate sales.
+
+use soroban_sdk::{contracttype, Env, Symbol};
+
+use crate::storage_types::{DataKey, TicketKey};
+
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SalesStats {
+    /// Tickets issued and not voided, including comps and lottery claims
+    pub tickets_sold: u32,
+    pub tickets_refunded: u32,
+}
+
+/// Sales counter storage
+pub struct StatsManager;
+
+impl StatsManager {
+    pub fn get(e: &Env) -> SalesStats {
+        e.storage()
+            .instance()
+            .get(&DataKey::Tickets(TicketKey::SalesStats))
+            .unwrap_or_default()
+    }
+
+    fn set(e: &Env, stats: &SalesStats) {
+        e.storage()
+            .instance()
+            .set(&DataKey::Tickets(TicketKey::SalesStats), stats);
+    }
+
+    pub fn record_sale(e: &Env) {
+        let mut stats = Self::get(e);
+        stats.tickets_sold += 1;
+        Self::set(e, &stats);
+    }
+
+    pub fn record_refund(e: &Env, tier_symbol: &Symbol) {
+        let mut stats = Self::get(e);
+        stats.tickets_refunded += 1;
+        Self::set(e, &stats);
+        Self::record_return(e, tier_symbol);
+    }
+
+    /// Companion tickets split off group tickets
+    pub fn splits(e: &Env) -> u32 {
+        e.storage()
+            .instance()
+            .get(&DataKey::Tickets(TicketKey::SplitTickets))
+            .unwrap_or(0)
+    }
+
+    pub fn record_split(e: &Env) {
+        e.storage().instance().set(
+            &DataKey::Tickets(TicketKey::SplitTickets),
+            &(Self::splits(e) + 1),
+        );
+    }
+
+    /// A ticket whose off-chain payment failed was never sold
+    pub fn record_void(e: &Env, tier_symbol: &Symbol) {
+        let mut stats = Self::get(e);
+        stats.tickets_sold = stats.tickets_sold.saturating_sub(1);
+        Self::set(e, &stats);
+        Self::record_return(e, tier_symbol);
+    }
+
+    /// Tickets of a tier refunded or voided, whose slots went back on sale
+    pub fn returned(e: &Env, tier_symbol: &Symbol) -> u32 {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Tickets(TicketKey::TierReturned(tier_symbol.clone())))
+            .unwrap_or(0)
+    }
+
+    fn record_return(e: &Env, tier_symbol: &Symbol) {
+        e.storage().persistent().set(
+            &DataKey::Tickets(TicketKey::TierReturned(tier_symbol.clone())),
+            &(Self::returned(e, tier_symbol) + 1),
+        );
+    }
+}
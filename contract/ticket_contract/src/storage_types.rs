@@ -1,25 +1,294 @@
-use soroban_sdk::{contracttype, Address, Bytes, String, Symbol};
+use soroban_sdk::{contracterror, contracttype, Address, Bytes, BytesN, String, Symbol, Vec};
 
+use crate::refund_limits::RefundLimits;
+use crate::refund_policy::RefundMode;
+
+/// Current on-chain storage layout version.
+/// Version 1 was the original flat `DataKey` layout (no `SchemaVersion` key stored).
+pub const SCHEMA_VERSION: u32 = 2;
+
+/// Top-level storage keys, namespaced per subsystem
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum DataKey {
     Admin,
+    SchemaVersion,
     EventInfo,
-    TokenIdCounter,
+    Pricing(PricingKey),
+    Tickets(TicketKey),
+    Lottery(LotteryKey),
+    Governance(GovernanceKey),
+    Treasury(TreasuryKey),
+    Venue(VenueKey),
+    Resale(ResaleKey),
+    Loyalty(LoyaltyKey),
+    Catalog(CatalogKey),
+    AdminLog(u32),
+    NextAdminLogId,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PricingKey {
+    Config,
+    DiscountPolicy,
+    PromoCode(Symbol),
+    LoyaltyDiscount(Address),
+    OracleCache,
+    Rounding(Address),
+    ReferenceFeed(Address),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TicketKey {
     Tier(Symbol),
     Ticket(u32),
-    PricingConfig,
-    // VRF and Allocation keys
+    WalletCap,
+    TierAttributes(Symbol),
+    TicketAttributes(u32),
+    Relayer(Address),
+    PaymentProcessor(Address),
+    PendingPayment(String),
+    ChannelAllocations(Symbol),
+    ReleaseSchedule(Symbol),
+    Receipt(u32),
+    NextReceiptId,
+    BuyerReceipts(Address),
+    Invitation(BytesN<32>),
+    CreditOffer,
+    Credit(Address),
+    OwnerTickets(Address),
+    PurchaseHook,
+    ChargePlan(u32),
+    NextChargePlanId,
+    SaleLimits(Symbol),
+    /// Temporary: tickets sold to a buyer in one ledger
+    TxSales(Symbol, u32, Address),
+    /// Temporary: tickets sold in one ledger
+    LedgerSales(Symbol, u32),
+    HolderSnapshot(Symbol),
+    /// Snapshot root a participant last proved their holding against
+    SnapshotProven(Symbol, Address),
+    /// Ring buffer slot
+    Announcement(u32),
+    NextAnnouncementId,
+    Contact(u32),
+    ContactCount,
+    AccessTiers(Symbol),
+    VenueCapacity,
+    /// Sum of `max_supply` over all tiers
+    TotalTierSupply,
+    Bundle(u32),
+    NextBundleId,
+    MetadataFrozen,
+    SalesStats,
+    /// Companion tickets split off group tickets
+    SplitTickets,
+    ReceiptTokenTier(Symbol),
+    ReceiptToken(u32),
+    /// Chunked organizer mint in progress, by recipient and tier
+    MintJob(Address, Symbol),
+    /// Refunded or voided tickets of a tier
+    TierReturned(Symbol),
+    /// Companion seats not yet split off a group ticket
+    GroupSeats(u32),
+    /// Reference-unit rate of the payment token when the ticket was sold
+    ReferenceRate(u32),
+    /// Token ids minted by a buyer's purchase under an idempotency key
+    IdempotencyKey(Address, BytesN<32>),
+}
+
+/// Seating, gates and venue pickup
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum VenueKey {
+    /// Ticket seated in a tier's numbered seat
+    Seat(Symbol, u32),
+    /// Seat held by a ticket
+    TicketSeat(u32),
+    /// Assigned seats of a tier, in assignment order
+    SeatList(Symbol),
+    /// Changes made to a tier's seat map
+    SeatVersion(Symbol),
+    /// May admit tickets outside their entry window
+    GateSupervisor(Address),
+    /// May check tickets in at a gate with `scan_in`
+    Scanner(Address),
+    /// Check-in count and first/last scan of a gate
+    GateTotals(Symbol),
+    /// Check-ins at a gate in one time bucket: (gate, bucket index)
+    GateBucket(Symbol, u64),
+    /// Check-in count and last scan of a scanner
+    ScannerStats(Address),
+    /// Pickup code and buying agent of a will-call ticket
+    PickupHold(u32),
+}
+
+/// Resale and soulbound transfer exemptions
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ResaleKey {
+    ResalePolicy(Symbol),
+    /// Open resale listing of a ticket
+    Listing(u32),
+    /// Single-use soulbound transfer exemption between two addresses
+    MigrationExemption(Address, Address),
+}
+
+/// Cross-event benefits, returning-attendee pricing, points and offers
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum LoyaltyKey {
+    /// Benefit this event grants holders of a linked event's tickets
+    SeriesBenefit(Address),
+    /// Linked event ticket that has claimed its benefit
+    SeriesClaim(Address, u32),
+    /// Grandfathered price offered to returning attendees of a tier
+    ReturningOffer(Symbol),
+    /// Attendee who has bought at the tier's returning price
+    ReturningClaim(Symbol, Address),
+    /// Loyalty points a tier accepts towards its price
+    PointsPolicy(Symbol),
+    /// Loyalty points spent on a ticket
+    PointsPaid(u32),
+    /// Promotional ticket offer awaiting acceptance
+    Offer(u32),
+    NextOfferId,
+}
+
+/// Named allocations, free claims, archived tiers and locales
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CatalogKey {
+    /// Reason code of a named allocation
+    AllocationReason(u32),
+    /// Named allocations made from a tier
+    NamedAllocations(Symbol),
+    /// Claim cap and registration questions of a free tier
+    FreeClaimConfig(Symbol),
+    /// Tickets an address has claimed from a free tier
+    FreeClaims(Symbol, Address),
+    /// Hash of the registration answers given with a free claim
+    RegistrationAnswers(u32),
+    /// Tier taken out of the catalog, restorable for a grace period
+    ArchivedTier(Symbol),
+    /// Locale served when the preferred one is missing
+    DefaultLocale,
+    /// Localized event name and description by locale
+    EventLocales,
+    /// Localized tier name and description by locale
+    TierLocales(Symbol),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum LotteryKey {
     VRFConfig,
-    VRFState,
+    /// Randomness state of a tier's lottery round
+    VRFState(Symbol, u32),
     AllocationStrategy(Symbol),
-    AllocationState(Symbol),
-    LotteryEntry(Symbol, u32),
-    LotteryEntryCount(Symbol),
+    AllocationState(Symbol, u32),
+    /// Entry `index` of a tier's lottery round: (tier, round, index)
+    Entry(Symbol, u32, u32),
+    EntryCount(Symbol, u32),
     WhitelistEntry(Symbol, Address),
     CommitmentHash(Address),
-    LotteryResults(Symbol),
+    Results(Symbol, u32),
     AntiSnipingConfig(Symbol),
+    Claimed(Symbol, u32, Address),
+    AllocationProof(Symbol, u32),
+    LastProofHash,
+    PrunedCount(Symbol, u32),
+    Subscribers(Symbol),
+    Participant(Symbol, u32, Address),
+    ParticipantCount(Symbol, u32),
+    EntryPolicy(Symbol),
+    /// Most entries one round of a tier's lottery accepts
+    EntryCap(Symbol),
+    /// VRF outputs of one draw: (tier, round, draw index); draw 0 is the
+    /// round's main draw and backfills follow
+    RandomnessBatch(Symbol, u32, u32),
+    /// Draws whose outputs are archived for a tier's round
+    RandomnessDraws(Symbol, u32),
+    /// Organizer-assigned event id mixed into the VRF domain
+    VRFEventId,
+    /// Organizer's ed25519 key for signed lottery randomness
+    VRFPublicKey,
+    /// How a tier's lottery randomness is proven
+    ProofScheme(Symbol),
+    /// Randomness batches generated for a tier so far
+    DrawRound(Symbol),
+    /// Ledger a tier's requested draw takes its randomness from
+    FinalizationTarget(Symbol),
+    /// Entropy captured in the target ledger of a tier's requested draw
+    TargetEntropy(Symbol),
+    /// Entropy beacon accumulating for a tier's next draw
+    Beacon(Symbol),
+    /// Beacon contributions a tier's draw requires
+    BeaconMinimum(Symbol),
+    /// Seed commitment made by an address for a tier's draw
+    Commitment(Symbol, Address),
+    /// Addresses with a stored commitment for a tier, oldest first
+    Committers(Symbol),
+    /// Marks an address that revealed its seed for a tier's draw
+    Revealed(Symbol, Address),
+    /// Seeds revealed for a tier's draw
+    RevealCount(Symbol),
+    /// Reveals an address has made over every tier
+    RevealScore(Address),
+    /// Extra draw weight for entries from a tier's revealers
+    RevealBonus(Symbol),
+    /// Lottery round a tier's entries, draws and claims currently apply to
+    CurrentRound(Symbol),
+    /// Seconds a tier's lottery winners have to claim
+    ClaimWindow(Symbol),
+    /// Claim deadline of a result: (tier, round, allocation index)
+    ClaimDeadline(Symbol, u32, u32),
+    /// Marks a result expired unclaimed: (tier, round, allocation index)
+    Forfeited(Symbol, u32, u32),
+    /// Sealed backfill draws of a tier's lottery round, oldest first
+    BackfillProofs(Symbol, u32),
+    /// First allocation index a winner holds: (tier, round, winner)
+    WinnerIndex(Symbol, u32, Address),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TreasuryKey {
+    Liability(Address),
+    CoverageBps,
+    RefundAgent(Address),
+    RefundLimits,
+    RefundStats(Address),
+    RefundMode,
+    FreezeGrace,
+    LastFreeze,
+    TokenRevenue(Address),
+    RevenueTokens,
+    PayoutAddress,
+    /// Ledger of the last privileged call or check-in
+    LastAdminActivity,
+    /// Ledger recovery mode was triggered
+    RecoveryStarted,
+    RecoverySnapshot(Address),
+    PayoutSchedule,
+    /// Revenue withdrawn so far, by token
+    Withdrawn(Address),
+    Bond,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum GovernanceKey {
+    TimelockDelay,
+    PendingChanges,
+    NextChangeId,
+    CouncilMember(Address),
+    Paused,
+    RoleCounts,
+    /// Time the event was cancelled
+    Cancelled,
 }
 
 #[contracttype]
@@ -31,9 +300,20 @@ pub enum PricingStrategy {
     AbTestB,   // Higher sensitivity
 }
 
+/// Whether ticket prices follow the oracle
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PricingMode {
+    /// Tier prices and strategies only; the oracle is never called
+    Fixed,
+    /// Prices scaled by the oracle multiplier (DEX as fallback)
+    Dynamic,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct PricingConfig {
+    pub pricing_mode: PricingMode,
     pub oracle_address: Address,
     pub dex_pool_address: Address, // Fallback
     pub price_floor: i128,
@@ -50,11 +330,95 @@ pub struct PricingConfig {
     pub max_oracle_age_seconds: u64,
 }
 
+/// Last oracle multiplier fetched, reused until `update_frequency` elapses
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OracleCache {
+    pub multiplier: i128,
+    pub fetched_at: u64,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct EventInfo {
     pub start_time: u64,
     pub refund_cutoff_time: u64,
+    pub metadata: EventMetadata,
+}
+
+/// Everything `initialize` needs, validated as a whole
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InitConfig {
+    pub admin: Address,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    /// Must be in the future
+    pub start_time: u64,
+    /// Must not be after `start_time`
+    pub refund_cutoff_time: u64,
+    /// Defaults to empty metadata in the "general" category
+    pub metadata: Option<EventMetadata>,
+    /// Defaults to fixed pricing with no oracle
+    pub pricing: Option<PricingConfig>,
+    pub roles: Option<InitRoles>,
+    /// Defaults to no schedule: revenue is limited only by the refund reserve
+    pub payout_schedule: Option<PayoutSchedule>,
+    /// ed25519 key signed lottery randomness is checked against; without one
+    /// lotteries can only use hash-chain proofs
+    pub vrf_public_key: Option<BytesN<32>>,
+}
+
+/// How escrowed revenue is released to the organizer. Shares are of net
+/// revenue (sales less refunds) per payment token; whatever they leave
+/// unlocked is released at `completion_time`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PayoutSchedule {
+    /// Withdrawable as soon as a sale is made, in bps
+    pub at_sale_bps: u32,
+    /// Further share unlocked when the event starts, in bps
+    pub at_start_bps: u32,
+    /// Everything is unlocked from this time; not before the event starts
+    pub completion_time: u64,
+}
+
+/// Role holders to grant at initialization
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InitRoles {
+    pub council: Vec<Address>,
+    pub relayers: Vec<Address>,
+    pub payment_processors: Vec<Address>,
+}
+
+/// Why `initialize` rejected its config. Codes start at 100 so they don't
+/// collide with `EntryError`.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum InitError {
+    AlreadyInitialized = 100,
+    StartTimeInPast = 101,
+    RefundCutoffAfterStart = 102,
+    InvalidPricingConfig = 103,
+    InvalidPayoutSchedule = 104,
+    /// Longer than `metadata::MAX_NAME_LEN` bytes
+    NameTooLong = 105,
+}
+
+/// Discovery metadata for the event, editable by the organizer
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EventMetadata {
+    pub venue_name: String,
+    /// Coarse location hint, e.g. a geohash or "city, country"
+    pub geo_hint: String,
+    /// SHA-256 of the full off-chain description
+    pub description_hash: BytesN<32>,
+    pub cover_image_uri: String,
+    pub category: Symbol,
 }
 
 #[contracttype]
@@ -67,6 +431,120 @@ pub struct Tier {
     pub minted: u32,
     pub active: bool,
     pub strategy: PricingStrategy,
+    /// Sales halted for incident response, independent of the global freeze
+    pub frozen: bool,
+    pub distribution: DistributionMode,
+    /// Tickets minted through lottery claims (counted within `minted`)
+    pub lottery_minted: u32,
+    /// Purchases must bind a salted attendee name hash for gate ID checks
+    pub identity_required: bool,
+    /// Entry window for this tier's tickets (e.g. early entry for VIPs);
+    /// `None` opens at event start with no closing time
+    pub entry_window: Option<EntryWindow>,
+    /// Guest entries each new ticket of this tier carries
+    pub guest_slots: u32,
+}
+
+/// One tier to create with `add_tiers`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TierInput {
+    pub symbol: Symbol,
+    pub name: String,
+    pub base_price: i128,
+    pub max_supply: u32,
+    pub strategy: PricingStrategy,
+}
+
+/// Period during which a ticket admits its holder
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EntryWindow {
+    pub valid_from: u64,
+    pub valid_until: u64,
+}
+
+/// Why a ticket cannot be used for entry right now
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum EntryError {
+    TicketNotFound = 1,
+    TicketInvalid = 2,
+    TooEarly = 3,
+    Expired = 4,
+    AlreadyCheckedIn = 5,
+    /// Guests are admitted only after the ticket holder
+    HolderNotCheckedIn = 6,
+    NoGuestSlots = 7,
+}
+
+/// Pricing math that does not fit in an i128. Extreme base prices, multipliers
+/// or rounding increments are rejected instead of wrapping or trapping.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum PricingError {
+    PriceOverflow = 200,
+}
+
+/// Why `refund` turned a refund down
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum RefundError {
+    CountLimitExceeded = 300,
+    ValueLimitExceeded = 301,
+}
+
+/// Why a tier could not be created
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum TierError {
+    TierExists = 400,
+    /// Empty or longer than `MAX_TIER_SYMBOL_LEN`
+    InvalidSymbol = 401,
+    /// Empty, too long, padded, or containing invisible characters
+    InvalidName = 402,
+    /// Base price must not be negative; 0 makes the tier free
+    InvalidPrice = 403,
+    /// Supply must be positive
+    InvalidSupply = 404,
+    VenueCapacityExceeded = 405,
+    TierNotFound = 406,
+}
+
+/// Why a lottery registration was turned away
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum RegistrationError {
+    /// The round already holds the tier's maximum number of entries
+    EntryCapReached = 500,
+    /// The tier allows one entry per address and this one has entered
+    AlreadyEntered = 501,
+}
+
+/// Drip release: `wave_size` more tickets unlock every `wave_interval` seconds from `start_time`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReleaseSchedule {
+    pub start_time: u64,
+    pub wave_size: u32,
+    pub wave_interval: u64,
+}
+
+/// How a tier's supply may be distributed
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DistributionMode {
+    /// Purchases and organizer mints only
+    DirectSale,
+    /// Only lottery winners may claim tickets
+    LotteryOnly,
+    /// Direct sales may not consume the given number of tickets reserved for lottery winners
+    Mixed(u32),
 }
 
 #[contracttype]
@@ -76,6 +554,60 @@ pub struct Ticket {
     pub purchase_time: u64,
     pub price_paid: i128,
     pub is_valid: bool,
+    pub status: TicketStatus,
+    /// sha256(salt || attendee name), checked against government ID at the door
+    pub identity_hash: Option<BytesN<32>>,
+    /// Token the ticket was paid in; refunds go out in this token only.
+    /// `None` for tickets not paid on-chain (admin mints, invitations, fiat).
+    pub payment_token: Option<Address>,
+    /// When the ticket admits its holder, fixed at mint from the tier or event
+    pub entry_window: EntryWindow,
+    /// Bundle the ticket was sold in; `price_paid` is then its discounted share
+    pub bundle_id: Option<u32>,
+    /// Guest entries left, redeemed with `check_in_guest`
+    pub guest_slots: u32,
+    /// How the holder obtained the ticket; `Resale` once bought from a listing
+    pub channel: PurchaseChannel,
+}
+
+/// How a ticket was obtained, for analytics, refund policies and resale rules
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PurchaseChannel {
+    DirectSale,
+    Lottery,
+    /// Sold to a buyer proven against the tier's holder snapshot
+    Whitelist,
+    /// Minted free by the organizer or claimed from an invitation
+    Comp,
+    Bundle,
+    Resale,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TicketStatus {
+    Active,
+    /// Minted against an off-chain payment that has not settled yet
+    PendingPayment,
+    /// Off-chain payment failed; the token was burned
+    Voided,
+    /// Refunded to the owner; the token was burned
+    Refunded,
+    /// Admitted at the door
+    CheckedIn,
+    /// Bought by an agent and held by the contract until picked up at will-call
+    HeldForPickup,
+}
+
+/// Off-chain payment awaiting settlement, keyed by the processor's payment reference
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingPayment {
+    pub token_id: u32,
+    pub buyer: Address,
+    pub processor: Address,
+    pub issued_at: u64,
 }
 /// VRF-specific structures for ticket allocation
 
@@ -117,4 +649,85 @@ pub struct VRFState {
     pub randomness_hash: Bytes,
     pub batch_nonce: u32,
     pub finalization_ledger: u32,
-}
\ No newline at end of file
+}
+
+/// Timelocked configuration changes
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ConfigChange {
+    /// Pricing settings; the live freeze flag and update time are kept
+    Pricing(PricingConfig),
+    /// New oracle reference price, applied to the live pricing config
+    OracleReference(i128),
+    RefundCutoff(u64),
+    TimelockDelay(u32),
+    PayoutAddress(Address),
+    /// Reserve coverage in bps of refundable liability
+    ReserveCoverage(u32),
+    RefundMode(RefundMode),
+    /// Per-address refund caps; `None` removes them
+    RefundLimits(Option<RefundLimits>),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingChange {
+    pub id: u32,
+    pub change: ConfigChange,
+    pub queued_ledger: u32,
+    pub effective_ledger: u32,
+}
+
+/// Purchase simulation
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PurchaseError {
+    TierNotFound,
+    TierInactive,
+    TierFrozen,
+    LotteryOnly,
+    DirectAllocationExhausted,
+    ChannelAllocationExhausted,
+    SupplyNotReleased,
+    SoldOut,
+    InsufficientSupply,
+    WalletCapExceeded,
+    InvalidQuantity,
+    PriceOverflow,
+    TxLimitExceeded,
+    LedgerLimitExceeded,
+    NotEligible,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PurchasePreview {
+    pub unit_price: i128,
+    pub total_cost: i128,
+    pub fees: i128,
+    pub discount: i128,
+    pub first_token_id: u32,
+    pub last_token_id: u32,
+    pub error: Option<PurchaseError>,
+}
+
+/// Support agent allowed to execute policy-compliant refunds
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RefundAgent {
+    pub daily_limit: i128,
+    /// Day index (UNIX seconds / 86400) that `refunded_today` applies to
+    pub day: u64,
+    pub refunded_today: i128,
+}
+
+/// Ticket held for a guest who claims it later with the invitation secret
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Invitation {
+    pub tier_symbol: Symbol,
+    /// Claim deadline (UNIX seconds); afterwards the ticket returns to general sale
+    pub expiry: u64,
+}
@@ -0,0 +1,83 @@
+/// Lottery result subscriptions
+/// Lets external contracts (a guild treasury, a rewards program) react when a
+/// tier's allocation completes. A subscriber contract must expose:
+///
+///   on_allocation(proof: AllocationProof)
+///
+/// Each subscriber is called with `try_on_allocation`, so one failing subscriber
+/// is rolled back and reported with a `subscriber_failed` event without
+/// affecting the draw or the other subscribers. A subscriber that exhausts the
+/// transaction budget still fails the whole transaction.
+
+use soroban_sdk::{contractclient, Address, Env, Symbol, Vec};
+
+use crate::archive::AllocationProof;
+use crate::storage_types::{DataKey, LotteryKey};
+
+/// Most subscribers a tier may have, keeping finalization cost bounded
+pub const MAX_SUBSCRIBERS: u32 = 8;
+
+/// Interface a lottery subscriber contract implements.
+/// `contractclient` generates `LotterySubscriberClient`.
+#[contractclient(name = "LotterySubscriberClient")]
+pub trait LotterySubscriber {
+    fn on_allocation(env: Env, proof: AllocationProof);
+}
+
+/// Subscriber registry and dispatch
+pub struct SubscriptionManager;
+
+impl SubscriptionManager {
+    pub fn get(e: &Env, tier_symbol: &Symbol) -> Vec<Address> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Lottery(LotteryKey::Subscribers(tier_symbol.clone())))
+            .unwrap_or_else(|| Vec::new(e))
+    }
+
+    fn save(e: &Env, tier_symbol: &Symbol, subscribers: &Vec<Address>) {
+        let key = DataKey::Lottery(LotteryKey::Subscribers(tier_symbol.clone()));
+        if subscribers.is_empty() {
+            e.storage().persistent().remove(&key);
+        } else {
+            e.storage().persistent().set(&key, subscribers);
+        }
+    }
+
+    pub fn add(e: &Env, tier_symbol: &Symbol, subscriber: &Address) {
+        let mut subscribers = Self::get(e, tier_symbol);
+        if subscribers.contains(subscriber) {
+            panic!("Already subscribed");
+        }
+        if subscribers.len() >= MAX_SUBSCRIBERS {
+            panic!("Too many subscribers");
+        }
+        subscribers.push_back(subscriber.clone());
+        Self::save(e, tier_symbol, &subscribers);
+    }
+
+    pub fn remove(e: &Env, tier_symbol: &Symbol, subscriber: &Address) {
+        let mut subscribers = Self::get(e, tier_symbol);
+        match subscribers.first_index_of(subscriber) {
+            Some(index) => {
+                subscribers.remove(index);
+                Self::save(e, tier_symbol, &subscribers);
+            }
+            None => panic!("Not subscribed"),
+        }
+    }
+
+    /// Call every subscriber of the tier with the sealed draw
+    pub fn notify(e: &Env, proof: &AllocationProof) {
+        for subscriber in Self::get(e, &proof.tier_symbol).iter() {
+            let client = LotterySubscriberClient::new(e, &subscriber);
+            if !matches!(client.try_on_allocation(proof), Ok(Ok(()))) {
+                #[allow(deprecated)]
+                e.events().publish(
+                    (Symbol::new(e, "subscriber_failed"), subscriber),
+                    proof.tier_symbol.clone(),
+                );
+            }
+        }
+    }
+}
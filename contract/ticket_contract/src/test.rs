@@ -2,7 +2,15 @@
 extern crate std;
 
 use super::*;
-use soroban_sdk::{contract, contractimpl, testutils::Address as _, Address, Env, String, Symbol};
+use soroban_sdk::{
+    auth::{Context, ContractContext, CustomAccountInterface},
+    contract, contracterror, contractimpl,
+    crypto::Hash,
+    symbol_short,
+    testutils::{Address as _, Ledger as _},
+    xdr::ToXdr,
+    Address, Env, String, Symbol,
+};
 
 // ---------------------------------------------------------------------------
 // Mock Oracle Contract
@@ -39,21 +47,200 @@ impl MockDex {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Mock Oracle with an adjustable price
+//
+// Same interface as `MockOracle`, but the price can be changed mid-test to
+// observe when the contract re-queries it.
+// ---------------------------------------------------------------------------
+#[contract]
+pub struct MockAdjustableOracle;
+
+#[contractimpl]
+impl MockAdjustableOracle {
+    pub fn set_price(env: Env, price: i128) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("price"), &price);
+    }
+
+    pub fn get_value(env: Env, _pair: String) -> (i128, u64) {
+        let price: i128 = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("price"))
+            .unwrap();
+        (price, env.ledger().timestamp())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Mock Purchase Hook
+//
+// Counts `on_purchase` calls, or panics when told to fail.
+// ---------------------------------------------------------------------------
+#[contract]
+pub struct MockPurchaseHook;
+
+#[contractimpl]
+impl MockPurchaseHook {
+    pub fn set_failing(env: Env, failing: bool) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("failing"), &failing);
+    }
+
+    pub fn calls(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("calls"))
+            .unwrap_or(0)
+    }
+
+    pub fn on_purchase(env: Env, _buyer: Address, _token_id: u32, _tier: Symbol, _price: i128) {
+        let calls = Self::calls(env.clone()) + 1;
+        env.storage()
+            .instance()
+            .set(&symbol_short!("calls"), &calls);
+        if env
+            .storage()
+            .instance()
+            .get(&symbol_short!("failing"))
+            .unwrap_or(false)
+        {
+            panic!("hook failed");
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Mock Lottery Subscriber
+//
+// Records the winner count of the last allocation it was told about, or
+// panics when told to fail.
+// ---------------------------------------------------------------------------
+#[contract]
+pub struct MockLotterySubscriber;
+
+#[contractimpl]
+impl MockLotterySubscriber {
+    pub fn set_failing(env: Env, failing: bool) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("failing"), &failing);
+    }
+
+    pub fn winners(env: Env) -> Option<u32> {
+        env.storage().instance().get(&symbol_short!("winners"))
+    }
+
+    pub fn on_allocation(env: Env, proof: AllocationProof) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("winners"), &proof.winner_count);
+        if env
+            .storage()
+            .instance()
+            .get(&symbol_short!("failing"))
+            .unwrap_or(false)
+        {
+            panic!("subscriber failed");
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Mock Policy Wallet
+//
+// Custom account contract: accepts a signature equal to its stored key, and
+// only for calls to `purchase_with_allowance` or `refund` on one contract.
+// ---------------------------------------------------------------------------
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum MockWalletError {
+    BadSignature = 1,
+    NotAllowed = 2,
+}
+
+#[contract]
+pub struct MockPolicyWallet;
+
+#[contractimpl]
+impl MockPolicyWallet {
+    pub fn __constructor(env: Env, key: BytesN<32>, target: Address) {
+        env.storage().instance().set(&symbol_short!("key"), &key);
+        env.storage().instance().set(&symbol_short!("target"), &target);
+    }
+}
+
+#[contractimpl]
+impl CustomAccountInterface for MockPolicyWallet {
+    type Signature = BytesN<32>;
+    type Error = MockWalletError;
+
+    fn __check_auth(
+        env: Env,
+        _signature_payload: Hash<32>,
+        signature: BytesN<32>,
+        auth_contexts: Vec<Context>,
+    ) -> Result<(), MockWalletError> {
+        let key: BytesN<32> = env.storage().instance().get(&symbol_short!("key")).unwrap();
+        if signature != key {
+            return Err(MockWalletError::BadSignature);
+        }
+        let target: Address = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("target"))
+            .unwrap();
+        let allowed = [
+            Symbol::new(&env, "purchase_with_allowance"),
+            Symbol::new(&env, "refund"),
+        ];
+        for context in auth_contexts.iter() {
+            match context {
+                Context::Contract(call)
+                    if call.contract == target && allowed.contains(&call.fn_name) => {}
+                _ => return Err(MockWalletError::NotAllowed),
+            }
+        }
+        Ok(())
+    }
+}
+
 fn create_contract(e: &Env, admin: &Address) -> SoulboundTicketContractClient<'static> {
     let contract_id = e.register(SoulboundTicketContract, ());
     let client = SoulboundTicketContractClient::new(e, &contract_id);
 
-    client.initialize(
-        admin,
-        &String::from_str(e, "EventTicket"),
-        &String::from_str(e, "TKT"),
-        &String::from_str(e, "https://example.com"),
-        &e.ledger().timestamp(),
-        &(e.ledger().timestamp() + 100000), // Refund cutoff
-    );
+    // Refunds stay open until the event starts
+    let start_time = e.ledger().timestamp() + 100000;
+    client.initialize(&init_config(e, admin, start_time, start_time));
     client
 }
 
+fn init_config(e: &Env, admin: &Address, start_time: u64, refund_cutoff_time: u64) -> InitConfig {
+    InitConfig {
+        admin: admin.clone(),
+        name: String::from_str(e, "EventTicket"),
+        symbol: String::from_str(e, "TKT"),
+        uri: String::from_str(e, "https://example.com"),
+        start_time,
+        refund_cutoff_time,
+        metadata: None,
+        pricing: None,
+        roles: None,
+        payout_schedule: None,
+        vrf_public_key: None,
+    }
+}
+
+fn create_payment_token(e: &Env, admin: &Address, holder: &Address, amount: i128) -> Address {
+    let token = e.register_stellar_asset_contract_v2(admin.clone());
+    soroban_sdk::token::StellarAssetClient::new(e, &token.address()).mint(holder, &amount);
+    token.address()
+}
+
 #[test]
 fn test_initialize_and_tier_creation() {
     let e = Env::default();
@@ -97,10 +284,66 @@ fn test_batch_mint() {
     let balance = client.balance(&user);
     assert_eq!(balance, 5);
 
-    let ticket = client.get_ticket(&1);
+    let ticket = client.get_ticket(&1).unwrap();
     assert_eq!(ticket.tier_symbol, tier_sym);
 }
 
+#[test]
+fn test_views_return_none_for_unknown_ids() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let general = harness.add_tier("GEN", 100, 10);
+    let minted = harness.client.batch_mint(&harness.admin, &general, &1);
+    let token_id = minted.get(0).unwrap();
+    assert!(harness.client.ticket_exists(&token_id));
+    assert!(!harness.client.ticket_exists(&(token_id + 1)));
+    assert_eq!(harness.client.get_ticket(&(token_id + 1)), None);
+
+    let unknown = Symbol::new(&e, "NONE");
+    assert_eq!(
+        harness.client.try_get_ticket_price(&unknown),
+        Err(Ok(TierError::TierNotFound))
+    );
+    assert_eq!(
+        harness.client.try_entry_window(&unknown),
+        Err(Ok(TierError::TierNotFound))
+    );
+}
+
+#[test]
+fn test_batch_mint_chunk_resumes_from_cursor() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let tier_sym = harness.add_tier("SPONSOR", 100, 200);
+    let to = Address::generate(&e);
+
+    // 120 tickets in chunks of at most 50
+    let cursor = harness.client.batch_mint_chunk(&to, &tier_sym, &120, &0);
+    assert_eq!(cursor, 50);
+    assert_eq!(
+        harness.client.get_mint_job(&to, &tier_sym),
+        Some(batch_mint::MintJob { amount: 120, minted: 50 })
+    );
+
+    // Replaying a chunk is rejected instead of minting it twice
+    assert!(harness
+        .client
+        .try_batch_mint_chunk(&to, &tier_sym, &120, &0)
+        .is_err());
+
+    let cursor = harness.client.batch_mint_chunk(&to, &tier_sym, &120, &cursor);
+    let cursor = harness.client.batch_mint_chunk(&to, &tier_sym, &120, &cursor);
+    assert_eq!(cursor, 120);
+    assert_eq!(harness.client.balance(&to), 120);
+    assert_eq!(harness.client.get_mint_job(&to, &tier_sym), None);
+
+    // A new job must fit in the remaining supply up front
+    assert!(harness
+        .client
+        .try_batch_mint_chunk(&to, &tier_sym, &81, &0)
+        .is_err());
+}
+
 #[test]
 #[should_panic(expected = "Soulbound: Tickets cannot be transferred")]
 fn test_soulbound_restriction() {
@@ -215,7 +458,8 @@ fn test_emergency_freeze_and_bounds() {
     );
 
     let config = PricingConfig {
-        oracle_address: admin.clone(), // admin == neutral (no oracle configured), returns ORACLE_PRECISION
+        pricing_mode: PricingMode::Fixed,
+        oracle_address: admin.clone(), // unused in fixed mode
         dex_pool_address: admin.clone(),
         price_floor: 50,
         price_ceiling: 150,
@@ -286,6 +530,7 @@ fn test_oracle_multiplier_integration() {
 
     // Point the PricingConfig at the mock oracle
     let config = PricingConfig {
+        pricing_mode: PricingMode::Dynamic,
         oracle_address: oracle_id.clone(),
         dex_pool_address: dex_id.clone(),
         price_floor: 0,
@@ -345,12 +590,22 @@ fn test_oracle_fallback_neutral_when_unconfigured() {
 // VRF & LOTTERY TESTS
 // ============================================================================
 
+fn test_domain(e: &Env) -> vrf::VRFDomain {
+    vrf::VRFDomain {
+        contract: Address::generate(e),
+        event_id: BytesN::from_array(e, &[0; 32]),
+        tier_symbol: Symbol::new(e, "LOT"),
+        round: 0,
+    }
+}
+
 #[test]
 fn test_vrf_randomness_generation() {
     let e = Env::default();
+    let domain = test_domain(&e);
     let input = e.crypto().sha256(&soroban_sdk::Bytes::new(&e));
 
-    let (output, proof) = vrf::VRFEngine::generate_vrf_randomness(&e, input.clone(), 0);
+    let (output, proof) = vrf::VRFEngine::generate_vrf_randomness(&e, &domain, input.clone(), 0);
 
     // Verify output is 32 bytes
     assert_eq!(output.len(), 32);
@@ -363,10 +618,11 @@ fn test_vrf_randomness_generation() {
 #[test]
 fn test_vrf_batch_randomness() {
     let e = Env::default();
+    let domain = test_domain(&e);
     let seed = e.crypto().sha256(&soroban_sdk::Bytes::new(&e));
     let batch_size = 10u32;
 
-    let randomness = vrf::VRFEngine::generate_batch_randomness(&e, batch_size, seed);
+    let randomness = vrf::VRFEngine::generate_batch_randomness(&e, &domain, batch_size, seed);
 
     // Verify batch size
     assert_eq!(randomness.len() as u32, batch_size);
@@ -375,15 +631,362 @@ fn test_vrf_batch_randomness() {
 #[test]
 fn test_vrf_proof_verification() {
     let e = Env::default();
+    let domain = test_domain(&e);
     let input = e.crypto().sha256(&soroban_sdk::Bytes::new(&e));
-    let (_, proof) = vrf::VRFEngine::generate_vrf_randomness(&e, input.clone(), 0);
+    let (_, proof) = vrf::VRFEngine::generate_vrf_randomness(&e, &domain, input.clone(), 0);
 
     let expected_ledger = proof.ledger_sequence;
-    let is_valid = vrf::VRFEngine::verify_vrf_proof(&e, &proof, input, expected_ledger);
+    let is_valid = vrf::VRFEngine::verify_vrf_proof(&e, &proof, &domain, input, expected_ledger);
 
     assert!(is_valid);
 }
 
+#[test]
+fn test_vrf_domain_separates_draws() {
+    let e = Env::default();
+    let domain = test_domain(&e);
+    let seed = e.crypto().sha256(&soroban_sdk::Bytes::new(&e));
+    let (output, proof) = vrf::VRFEngine::generate_vrf_randomness(&e, &domain, seed.clone(), 0);
+    assert_eq!(proof.domain, domain);
+
+    // The same seed and nonce give different outputs in another tier or round
+    let other_tier = vrf::VRFDomain {
+        tier_symbol: Symbol::new(&e, "VIP"),
+        ..domain.clone()
+    };
+    let next_round = vrf::VRFDomain {
+        round: 1,
+        ..domain.clone()
+    };
+    for other in [other_tier, next_round] {
+        let (other_output, _) =
+            vrf::VRFEngine::generate_vrf_randomness(&e, &other, seed.clone(), 0);
+        assert_ne!(other_output, output);
+        assert!(!vrf::VRFEngine::verify_vrf_proof(
+            &e,
+            &proof,
+            &other,
+            seed.clone(),
+            proof.ledger_sequence
+        ));
+    }
+    assert!(vrf::VRFEngine::verify_vrf_proof(&e, &proof, &domain, seed, proof.ledger_sequence));
+}
+
+#[test]
+fn test_signed_lottery_randomness_proves_organizer_key() {
+    use ed25519_dalek::{Signer, SigningKey};
+
+    let e = Env::default();
+    let organizer_key = SigningKey::from_bytes(&[7; 32]);
+    let public_key = BytesN::from_array(&e, &organizer_key.verifying_key().to_bytes());
+    let harness = testutils::TicketHarness::with_vrf_key(&e, &public_key);
+    assert_eq!(harness.client.get_vrf_public_key(), Some(public_key));
+
+    let tier_sym = harness.add_tier("LOT", 100, 10);
+    harness
+        .client
+        .set_distribution_mode(&tier_sym, &storage_types::DistributionMode::LotteryOnly);
+    harness.client.initialize_lottery(
+        &tier_sym,
+        &AllocationStrategyType::FCFS,
+        &2,
+        &(e.ledger().sequence() + 10),
+        &1,
+        &5,
+    );
+    harness
+        .client
+        .set_lottery_proof_scheme(&tier_sym, &ProofScheme::Ed25519Signature);
+
+    // The message binds the target ledger, so there is nothing to sign before a request
+    assert!(harness
+        .client
+        .try_get_vrf_signing_message(&tier_sym, &0)
+        .is_err());
+    harness.advance_ledgers(10);
+    let target = harness.client.request_lottery_finalization(&tier_sym);
+
+    let sign = |key: &SigningKey, nonce: u32| {
+        let message = harness.client.get_vrf_signing_message(&tier_sym, &nonce);
+        BytesN::from_array(&e, &key.sign(&message.to_array()).to_bytes())
+    };
+    let signatures = vec![&e, sign(&organizer_key, 0), sign(&organizer_key, 1)];
+    let other_key = SigningKey::from_bytes(&[8; 32]);
+    let forged = vec![&e, sign(&organizer_key, 0), sign(&other_key, 1)];
+    harness.advance_ledgers(target - e.ledger().sequence());
+    assert!(harness
+        .client
+        .try_set_lottery_proof_scheme(&tier_sym, &ProofScheme::HashChain)
+        .is_err());
+
+    // Only signatures from the published key are accepted
+    assert!(harness
+        .client
+        .try_generate_lottery_randomness(&tier_sym, &2)
+        .is_err());
+    assert!(harness
+        .client
+        .try_generate_signed_randomness(&tier_sym, &forged)
+        .is_err());
+    let outputs = harness
+        .client
+        .generate_signed_randomness(&tier_sym, &signatures);
+    assert_eq!(outputs.len(), 2);
+
+    let output = outputs.get(1).unwrap();
+    let signature = Bytes::from_array(&e, &signatures.get(1).unwrap().to_array());
+    assert_eq!(output.proof.proof, signature);
+    assert!(harness
+        .client
+        .verify_signed_lottery_randomness(&output, &tier_sym, &0));
+
+    // The signature only proves the output it was made for
+    let moved = vrf::RandomnessOutput {
+        batch_index: 0,
+        ..output
+    };
+    assert!(harness
+        .client
+        .try_verify_signed_lottery_randomness(&moved, &tier_sym, &0)
+        .is_err());
+
+    // Without a published key a lottery cannot switch to signed proofs
+    let unkeyed = testutils::TicketHarness::setup(&e);
+    let tier_sym = unkeyed.add_tier("LOT", 100, 10);
+    assert!(unkeyed
+        .client
+        .try_set_lottery_proof_scheme(&tier_sym, &ProofScheme::Ed25519Signature)
+        .is_err());
+}
+
+// ============================================================================
+// VRF TEST VECTORS (cargo test --features vrf-vectors)
+// ============================================================================
+
+#[cfg(feature = "vrf-vectors")]
+#[test]
+fn test_vrf_derivation_matches_reference_vectors() {
+    let e = Env::default();
+    for vector in vrf::test_vectors() {
+        let output = vrf::VRFEngine::derive_output(
+            &e,
+            &vector.input,
+            &vector.ledger_hash,
+            vector.nonce,
+            &vector.domain_hash,
+        );
+        assert_eq!(output, vector.output);
+
+        let proof =
+            vrf::VRFEngine::derive_proof(&e, &vector.input, vector.ledger_sequence, vector.nonce);
+        assert_eq!(proof, vector.proof);
+
+        let input = soroban_sdk::Bytes::from_array(&e, &vector.input);
+        assert_eq!(e.crypto().sha256(&input).to_array(), vector.input_hash);
+
+        let value = vrf::VRFEngine::output_value(&output);
+        assert_eq!(value, vector.value);
+        assert_eq!(
+            vrf::VRFEngine::compute_selection_index(value, vector.pool_size),
+            vector.selection_index
+        );
+    }
+}
+
+#[cfg(feature = "vrf-vectors")]
+#[test]
+fn test_vrf_generation_uses_reference_derivation() {
+    let e = Env::default();
+    let domain = test_domain(&e);
+    let input = [0x11; 32];
+    let nonce = 3;
+
+    let (output, proof) = vrf::VRFEngine::generate_vrf_randomness(
+        &e,
+        &domain,
+        soroban_sdk::Bytes::from_array(&e, &input),
+        nonce,
+    );
+    let expected = vrf::VRFEngine::derive_output(
+        &e,
+        &input,
+        &e.ledger().hash().to_array::<32>().unwrap_or([0u8; 32]),
+        nonce,
+        &vrf::VRFEngine::domain_hash(&e, &domain),
+    );
+    assert_eq!(output, soroban_sdk::Bytes::from_array(&e, &expected));
+
+    let expected_proof = vrf::VRFEngine::derive_proof(&e, &input, proof.ledger_sequence, nonce);
+    assert_eq!(
+        proof.proof,
+        soroban_sdk::Bytes::from_array(&e, &expected_proof)
+    );
+}
+
+#[test]
+fn test_lottery_draws_advance_vrf_round() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let tier_sym = harness.add_tier("LOT", 100, 10);
+    harness
+        .client
+        .set_distribution_mode(&tier_sym, &storage_types::DistributionMode::LotteryOnly);
+    harness.client.initialize_lottery(
+        &tier_sym,
+        &AllocationStrategyType::FCFS,
+        &3,
+        &(e.ledger().sequence() + 10),
+        &1,
+        &5,
+    );
+    let event_id = BytesN::from_array(&e, &[7; 32]);
+    harness.client.set_vrf_event_id(&event_id);
+
+    harness.advance_ledgers(10);
+    let first = harness.draw_randomness(&tier_sym, 2);
+    // The main draw is generated once per lottery round
+    assert!(harness.client.try_request_lottery_finalization(&tier_sym).is_err());
+    harness.client.execute_lottery_allocation(&tier_sym);
+    harness.client.open_new_round(&tier_sym);
+    harness.client.initialize_lottery(
+        &tier_sym,
+        &AllocationStrategyType::FCFS,
+        &3,
+        &(e.ledger().sequence() + 10),
+        &1,
+        &5,
+    );
+    harness.advance_ledgers(10);
+    let second = harness.draw_randomness(&tier_sym, 2);
+    let domain = first.get(0).unwrap().proof.domain;
+    assert_eq!(domain.contract, harness.client.address);
+    assert_eq!(domain.event_id, event_id);
+    assert_eq!(domain.tier_symbol, tier_sym);
+    assert_eq!(domain.round, 0);
+    assert_eq!(second.get(0).unwrap().proof.domain.round, 1);
+}
+
+#[test]
+fn test_lottery_randomness_waits_for_committed_ledger() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let tier_sym = harness.add_tier("LOT", 100, 10);
+    harness
+        .client
+        .set_distribution_mode(&tier_sym, &storage_types::DistributionMode::LotteryOnly);
+    harness.client.initialize_lottery(
+        &tier_sym,
+        &AllocationStrategyType::FCFS,
+        &3,
+        &(e.ledger().sequence() + 10),
+        &1,
+        &5,
+    );
+    assert!(harness.client.try_request_lottery_finalization(&tier_sym).is_err());
+
+    harness.advance_ledgers(10);
+    assert!(harness.client.try_generate_lottery_randomness(&tier_sym, &2).is_err());
+    let target = harness.client.request_lottery_finalization(&tier_sym);
+    assert_eq!(target, e.ledger().sequence() + 3);
+    assert_eq!(harness.client.get_finalization_target(&tier_sym), Some(target));
+    assert!(harness.client.try_request_lottery_finalization(&tier_sym).is_err());
+
+    // Too early, and entropy can only be captured in the target ledger itself
+    assert!(harness.client.try_generate_lottery_randomness(&tier_sym, &2).is_err());
+    assert!(harness.client.try_capture_finalization_entropy(&tier_sym).is_err());
+    harness.advance_ledgers(3);
+    assert_eq!(e.ledger().sequence(), target);
+    let entropy = harness.client.capture_finalization_entropy(&tier_sym);
+    assert_eq!(harness.client.capture_finalization_entropy(&tier_sym), entropy);
+
+    // The draw can still run later, from the captured entropy; the request
+    // cannot be renewed
+    harness.advance_ledgers(50);
+    assert!(harness.client.try_request_lottery_finalization(&tier_sym).is_err());
+    let outputs = harness.client.generate_lottery_randomness(&tier_sym, &2);
+    let domain = outputs.get(0).unwrap().proof.domain;
+    let ledger_hash = entropy.to_array::<32>().unwrap_or([0u8; 32]);
+    let expected =
+        vrf::VRFEngine::generate_batch_randomness_at(&e, &domain, 2, entropy, &ledger_hash, target);
+    assert_eq!(outputs, expected);
+    assert_eq!(harness.client.get_finalization_target(&tier_sym), None);
+    assert!(harness.client.try_generate_lottery_randomness(&tier_sym, &2).is_err());
+    assert!(harness.client.try_request_lottery_finalization(&tier_sym).is_err());
+}
+
+#[test]
+fn test_lapsed_finalization_falls_back_to_fixed_entropy() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let tier_sym = harness.add_tier("LOT", 100, 10);
+    harness
+        .client
+        .set_distribution_mode(&tier_sym, &storage_types::DistributionMode::LotteryOnly);
+    harness.client.initialize_lottery(
+        &tier_sym,
+        &AllocationStrategyType::FCFS,
+        &3,
+        &(e.ledger().sequence() + 10),
+        &1,
+        &5,
+    );
+    harness.advance_ledgers(10);
+    let target = harness.client.request_lottery_finalization(&tier_sym);
+
+    // Nobody drew or captured in the target ledger: the outputs depend on the
+    // target alone, not on the ledger the draw runs in
+    harness.advance_ledgers(40);
+    let outputs = harness.client.generate_lottery_randomness(&tier_sym, &2);
+    let domain = outputs.get(0).unwrap().proof.domain;
+    let expected = e.as_contract(&harness.client.address, || {
+        let fallback = entropy::EntropyManager::take_target_entropy(&e, &tier_sym, target);
+        let ledger_hash = fallback.to_array::<32>().unwrap_or([0u8; 32]);
+        vrf::VRFEngine::generate_batch_randomness_at(&e, &domain, 2, fallback, &ledger_hash, target)
+    });
+    assert_eq!(outputs, expected);
+    assert_eq!(outputs.get(0).unwrap().proof.ledger_sequence, target);
+}
+
+#[test]
+fn test_beacon_requires_distinct_ledger_contributions() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let tier_sym = harness.add_tier("LOT", 100, 10);
+    harness
+        .client
+        .set_distribution_mode(&tier_sym, &storage_types::DistributionMode::LotteryOnly);
+    harness.client.initialize_lottery(
+        &tier_sym,
+        &AllocationStrategyType::FCFS,
+        &3,
+        &(e.ledger().sequence() + 10),
+        &1,
+        &5,
+    );
+    harness.client.set_beacon_minimum(&tier_sym, &3);
+
+    // One contribution per ledger
+    harness.client.contribute_beacon(&tier_sym);
+    assert!(harness.client.try_contribute_beacon(&tier_sym).is_err());
+    harness.advance_ledgers(1);
+    let beacon = harness.client.contribute_beacon(&tier_sym);
+    assert_eq!(beacon.contributions, 2);
+    assert_eq!(beacon.last_ledger, e.ledger().sequence());
+
+    harness.advance_ledgers(10);
+    assert!(harness.client.try_request_lottery_finalization(&tier_sym).is_err());
+    harness.client.contribute_beacon(&tier_sym);
+    let target = harness.client.request_lottery_finalization(&tier_sym);
+
+    // The beacon is settled once the draw is requested
+    harness.advance_ledgers(1);
+    assert!(harness.client.try_contribute_beacon(&tier_sym).is_err());
+    harness.advance_ledgers(target - e.ledger().sequence());
+    harness.client.generate_lottery_randomness(&tier_sym, &2);
+    assert_eq!(harness.client.get_beacon(&tier_sym).contributions, 0);
+}
+
 #[test]
 fn test_vrf_selection_index_computation() {
     // Test basic selection
@@ -574,6 +1177,7 @@ fn test_fairness_score_computation() {
 #[test]
 fn test_full_lottery_cycle() {
     let e = Env::default();
+    let domain = test_domain(&e);
 
     // 1. Create entries
     let mut entries = soroban_sdk::Vec::new(&e);
@@ -590,7 +1194,7 @@ fn test_full_lottery_cycle() {
 
     // 2. Generate randomness
     let seed = e.crypto().sha256(&soroban_sdk::Bytes::new(&e));
-    let randomness = vrf::VRFEngine::generate_batch_randomness(&e, 10, seed);
+    let randomness = vrf::VRFEngine::generate_batch_randomness(&e, &domain, 10, seed);
 
     // 3. Extract values
     let mut values = soroban_sdk::Vec::new(&e);
@@ -614,6 +1218,7 @@ fn test_full_lottery_cycle() {
 fn test_commit_reveal_lottery_cycle() {
     let e = Env::default();
     e.mock_all_auths();
+    let domain = test_domain(&e);
     let committer = Address::generate(&e);
 
     // Phase 1: Commit
@@ -632,11 +1237,4932 @@ fn test_commit_reveal_lottery_cycle() {
     assert!(is_valid);
 
     // Phase 4: Generate randomness from revealed seed
-    let (vrf_output, proof) = vrf::VRFEngine::generate_vrf_randomness(&e, seed.clone(), 42);
+    let (vrf_output, proof) =
+        vrf::VRFEngine::generate_vrf_randomness(&e, &domain, seed.clone(), 42);
     assert_eq!(vrf_output.len(), 32);
 
     // Phase 5: Verify proof
     let proof_valid =
-        vrf::VRFEngine::verify_vrf_proof(&e, &proof, seed, proof.ledger_sequence);
+        vrf::VRFEngine::verify_vrf_proof(&e, &proof, &domain, seed, proof.ledger_sequence);
     assert!(proof_valid);
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_commitment_lifecycle() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let tier_sym = harness.add_tier("LOT", 100, 10);
+    harness
+        .client
+        .set_distribution_mode(&tier_sym, &storage_types::DistributionMode::LotteryOnly);
+    let start = e.ledger().sequence();
+    harness.client.initialize_lottery(
+        &tier_sym,
+        &AllocationStrategyType::FCFS,
+        &3,
+        &(start + 10),
+        &(start + 2),
+        &(start + 5),
+    );
+    let committer = Address::generate(&e);
+    let late = Address::generate(&e);
+    let seed = e.crypto().sha256(&soroban_sdk::Bytes::new(&e));
+    let (hash, _) = commitment::CommitmentScheme::commit(&e, seed.clone(), 7, committer.clone());
+
+    harness.client.commit_seed(&committer, &tier_sym, &hash);
+    harness.client.commit_seed(&late, &tier_sym, &hash);
+    assert!(harness
+        .client
+        .try_commit_seed(&committer, &tier_sym, &hash)
+        .is_err());
+    assert!(harness
+        .client
+        .try_reveal_seed(&committer, &tier_sym, &seed, &7)
+        .is_err());
+
+    // Reveals open with the window; the wrong nonce is rejected
+    harness.advance_ledgers(2);
+    assert!(harness
+        .client
+        .try_commit_seed(&Address::generate(&e), &tier_sym, &hash)
+        .is_err());
+    assert!(harness
+        .client
+        .try_reveal_seed(&committer, &tier_sym, &seed, &8)
+        .is_err());
+    harness.client.reveal_seed(&committer, &tier_sym, &seed, &7);
+    assert!(
+        harness
+            .client
+            .get_commitment(&tier_sym, &committer)
+            .unwrap()
+            .revealed
+    );
+    assert!(harness
+        .client
+        .try_prune_commitments(&tier_sym, &10)
+        .is_err());
+
+    // Unrevealed commitments expire when the window closes, then get pruned
+    harness.advance_ledgers(4);
+    assert!(harness.client.is_commitment_expired(&tier_sym, &late));
+    assert!(!harness.client.is_commitment_expired(&tier_sym, &committer));
+    assert!(harness
+        .client
+        .try_reveal_seed(&late, &tier_sym, &seed, &7)
+        .is_err());
+    assert_eq!(harness.client.prune_commitments(&tier_sym, &1), 1);
+    assert_eq!(harness.client.get_commitment(&tier_sym, &committer), None);
+    assert!(harness.client.get_commitment(&tier_sym, &late).is_some());
+    assert_eq!(harness.client.prune_commitments(&tier_sym, &10), 1);
+    assert_eq!(harness.client.prune_commitments(&tier_sym, &10), 0);
+}
+
+#[test]
+fn test_revealers_get_lottery_weight_bonus() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let tier_sym = harness.add_tier("LOT", 100, 10);
+    harness
+        .client
+        .set_distribution_mode(&tier_sym, &storage_types::DistributionMode::LotteryOnly);
+    let start = e.ledger().sequence();
+    harness.client.initialize_lottery(
+        &tier_sym,
+        &AllocationStrategyType::Lottery,
+        &1,
+        &(start + 10),
+        &(start + 2),
+        &(start + 5),
+    );
+    let entries = harness.lottery_entries(2);
+    harness.store_lottery_entries(&tier_sym, &entries);
+    let revealer = entries.get(1).unwrap().participant;
+    let seed = e.crypto().sha256(&soroban_sdk::Bytes::new(&e));
+    let (hash, _) = commitment::CommitmentScheme::commit(&e, seed.clone(), 7, revealer.clone());
+
+    assert!(harness.client.try_set_reveal_bonus(&tier_sym, &11).is_err());
+    harness.client.set_reveal_bonus(&tier_sym, &10);
+    harness.client.commit_seed(&revealer, &tier_sym, &hash);
+    harness.advance_ledgers(2);
+    assert!(harness.client.try_set_reveal_bonus(&tier_sym, &0).is_err());
+    harness.client.reveal_seed(&revealer, &tier_sym, &seed, &7);
+    assert_eq!(harness.client.get_revealed_count(&tier_sym), 1);
+    assert_eq!(harness.client.get_reveal_score(&revealer), 1);
+
+    // Pruning the commitment keeps the reveal on record
+    harness.advance_ledgers(8);
+    harness.client.prune_commitments(&tier_sym, &10);
+    assert!(harness.client.has_revealed(&tier_sym, &revealer));
+
+    // With weights 1 and 11, the revealer takes every value but 0 mod 12
+    let value = harness.draw_randomness(&tier_sym, 1).get(0).unwrap().value;
+    harness.client.execute_lottery_allocation(&tier_sym);
+    let winner = harness
+        .client
+        .get_lottery_winners(&tier_sym)
+        .get(0)
+        .unwrap();
+    assert_eq!(winner.randomness_value, value);
+    if value % 12 == 0 {
+        assert_eq!(winner.winner, entries.get(0).unwrap().participant);
+        assert_eq!(winner.weight_applied, 1);
+    } else {
+        assert_eq!(winner.winner, revealer);
+        assert_eq!(winner.weight_applied, 11);
+    }
+}
+
+// ============================================================================
+// TIMELOCK TESTS
+// ============================================================================
+
+#[test]
+fn test_timelock_queues_pricing_change() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let client = create_contract(&e, &admin);
+
+    let tier_sym = Symbol::new(&e, "GEN");
+    client.add_tier(
+        &tier_sym,
+        &String::from_str(&e, "General"),
+        &100,
+        &10,
+        &PricingStrategy::Standard,
+    );
+
+    client.set_timelock_delay(&10);
+    assert_eq!(client.get_timelock_delay(), 10);
+
+    let config = PricingConfig {
+        pricing_mode: PricingMode::Fixed,
+        oracle_address: admin.clone(),
+        dex_pool_address: admin.clone(),
+        price_floor: 150,
+        price_ceiling: i128::MAX,
+        update_frequency: 0,
+        last_update_time: e.ledger().timestamp(),
+        is_frozen: false,
+        oracle_pair: String::from_str(&e, "XLM/USD"),
+        oracle_reference_price: oracle::DIA_ORACLE_DECIMALS,
+        max_oracle_age_seconds: oracle::DEFAULT_STALENESS_SECONDS,
+    };
+    client.set_pricing_config(&config);
+
+    // Queued, not yet live
+    let pending = client.get_pending_changes();
+    assert_eq!(pending.len(), 1);
+    assert_eq!(
+        pending.get(0).unwrap().effective_ledger,
+        e.ledger().sequence() + 10
+    );
+    assert_eq!(client.apply_pending(), 0);
+    assert_eq!(client.get_ticket_price(&tier_sym), 100);
+
+    e.ledger().with_mut(|li| li.sequence_number += 10);
+
+    assert_eq!(client.apply_pending(), 1);
+    assert_eq!(client.get_pending_changes().len(), 0);
+    assert_eq!(client.get_ticket_price(&tier_sym), 150);
+}
+
+#[test]
+fn test_timelocked_pricing_keeps_live_freeze() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let client = create_contract(&e, &admin);
+    client.set_timelock_delay(&10);
+
+    let config = PricingConfig {
+        pricing_mode: PricingMode::Fixed,
+        oracle_address: admin.clone(),
+        dex_pool_address: admin.clone(),
+        price_floor: 150,
+        price_ceiling: i128::MAX,
+        update_frequency: 0,
+        last_update_time: e.ledger().timestamp(),
+        is_frozen: false,
+        oracle_pair: String::from_str(&e, "XLM/USD"),
+        oracle_reference_price: oracle::DIA_ORACLE_DECIMALS,
+        max_oracle_age_seconds: oracle::DEFAULT_STALENESS_SECONDS,
+    };
+    client.set_pricing_config(&config);
+    client.update_oracle_reference(&(2 * oracle::DIA_ORACLE_DECIMALS));
+
+    // A freeze after queueing survives both changes taking effect
+    client.emergency_freeze(&true);
+    e.ledger().with_mut(|li| li.sequence_number += 10);
+    assert_eq!(client.apply_pending(), 2);
+    let live = client.get_config();
+    assert!(live.flags.pricing_frozen);
+    assert_eq!(live.pricing.price_floor, 150);
+}
+
+#[test]
+fn test_timelock_cancel_pending_change() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let client = create_contract(&e, &admin);
+
+    client.set_timelock_delay(&5);
+    client.set_refund_cutoff(&0);
+    assert_eq!(client.get_pending_changes().len(), 1);
+
+    let change_id = client.get_pending_changes().get(0).unwrap().id;
+    client.cancel_pending_change(&change_id);
+    assert_eq!(client.get_pending_changes().len(), 0);
+
+    e.ledger().with_mut(|li| li.sequence_number += 5);
+    assert_eq!(client.apply_pending(), 0);
+}
+
+// ============================================================================
+// PURCHASE SIMULATION TESTS
+// ============================================================================
+
+#[test]
+fn test_simulate_purchase_follows_demand_curve() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let buyer = Address::generate(&e);
+    let client = create_contract(&e, &admin);
+
+    let tier_sym = Symbol::new(&e, "GEN");
+    client.add_tier(
+        &tier_sym,
+        &String::from_str(&e, "General"),
+        &100,
+        &10,
+        &PricingStrategy::Standard,
+    );
+
+    // Units 1-2 at 100, unit 3 crosses the first threshold at 105
+    let preview = client.simulate_purchase(&buyer, &tier_sym, &3);
+    assert_eq!(preview.error, None);
+    assert_eq!(preview.unit_price, 100);
+    assert_eq!(preview.total_cost, 305);
+    assert_eq!(preview.first_token_id, 0);
+    assert_eq!(preview.last_token_id, 2);
+
+    // Nothing was minted
+    assert_eq!(client.balance(&buyer), 0);
+}
+
+#[test]
+fn test_simulate_purchase_reports_errors() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let buyer = Address::generate(&e);
+    let client = create_contract(&e, &admin);
+
+    let tier_sym = Symbol::new(&e, "GEN");
+    client.add_tier(
+        &tier_sym,
+        &String::from_str(&e, "General"),
+        &100,
+        &4,
+        &PricingStrategy::Standard,
+    );
+
+    let missing = client.simulate_purchase(&buyer, &Symbol::new(&e, "NONE"), &1);
+    assert_eq!(missing.error, Some(PurchaseError::TierNotFound));
+
+    let too_many = client.simulate_purchase(&buyer, &tier_sym, &5);
+    assert_eq!(too_many.error, Some(PurchaseError::InsufficientSupply));
+
+    // Huge quantities are rejected rather than overflowing
+    let huge = client.simulate_purchase(&buyer, &tier_sym, &u32::MAX);
+    assert_eq!(huge.error, Some(PurchaseError::InvalidQuantity));
+
+    client.set_wallet_cap(&2);
+    client.batch_mint(&buyer, &tier_sym, &2);
+    let capped = client.simulate_purchase(&buyer, &tier_sym, &1);
+    assert_eq!(capped.error, Some(PurchaseError::WalletCapExceeded));
+    assert_eq!(capped.total_cost, 0);
+
+    client.batch_mint(&admin, &tier_sym, &2);
+    let sold_out = client.simulate_purchase(&buyer, &tier_sym, &1);
+    assert_eq!(sold_out.error, Some(PurchaseError::SoldOut));
+}
+
+#[test]
+fn test_schema_version_set_on_initialize() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let client = create_contract(&e, &admin);
+
+    assert_eq!(client.get_schema_version(), storage_types::SCHEMA_VERSION);
+}
+
+#[test]
+fn test_initialize_validates_config() {
+    let e = Env::default();
+    e.mock_all_auths();
+    e.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    let admin = Address::generate(&e);
+    let council = Address::generate(&e);
+    let client = SoulboundTicketContractClient::new(&e, &e.register(SoulboundTicketContract, ()));
+
+    assert_eq!(
+        client.try_initialize(&init_config(&e, &admin, 1_000, 1_000)),
+        Err(Ok(InitError::StartTimeInPast))
+    );
+    assert_eq!(
+        client.try_initialize(&init_config(&e, &admin, 2_000, 2_001)),
+        Err(Ok(InitError::RefundCutoffAfterStart))
+    );
+
+    let mut config = init_config(&e, &admin, 2_000, 1_500);
+    config.name = String::from_str(&e, &"E".repeat(metadata::MAX_NAME_LEN + 1));
+    assert_eq!(
+        client.try_initialize(&config),
+        Err(Ok(InitError::NameTooLong))
+    );
+
+    config.name = String::from_str(&e, "EventTicket");
+    config.pricing = Some(PricingConfig {
+        pricing_mode: PricingMode::Dynamic,
+        oracle_address: admin.clone(),
+        dex_pool_address: admin.clone(),
+        price_floor: 0,
+        price_ceiling: i128::MAX,
+        update_frequency: 0,
+        last_update_time: 1_000,
+        is_frozen: false,
+        oracle_pair: String::from_str(&e, "XLM/USD"),
+        oracle_reference_price: oracle::DIA_ORACLE_DECIMALS,
+        max_oracle_age_seconds: oracle::DEFAULT_STALENESS_SECONDS,
+    });
+    assert_eq!(
+        client.try_initialize(&config),
+        Err(Ok(InitError::InvalidPricingConfig))
+    );
+
+    config.pricing = None;
+    config.roles = Some(InitRoles {
+        council: soroban_sdk::vec![&e, council.clone()],
+        relayers: soroban_sdk::Vec::new(&e),
+        payment_processors: soroban_sdk::Vec::new(&e),
+    });
+    client.initialize(&config);
+    assert!(client.is_council_member(&council));
+    assert_eq!(client.get_event_info().refund_cutoff_time, 1_500);
+
+    // Re-init is rejected without touching state
+    assert_eq!(
+        client.try_initialize(&init_config(&e, &council, 3_000, 3_000)),
+        Err(Ok(InitError::AlreadyInitialized))
+    );
+    assert_eq!(client.get_event_info().start_time, 2_000);
+}
+
+// ============================================================================
+// TOKEN ID TESTS
+// ============================================================================
+
+#[test]
+fn test_ticket_ids_match_minted_token_ids() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let buyer = Address::generate(&e);
+    let client = create_contract(&e, &admin);
+
+    let tier_sym = Symbol::new(&e, "GEN");
+    client.add_tier(
+        &tier_sym,
+        &String::from_str(&e, "General"),
+        &100,
+        &10,
+        &PricingStrategy::Standard,
+    );
+
+    let token = create_payment_token(&e, &admin, &buyer, 1_000);
+
+    client.batch_mint(&user1, &tier_sym, &2);
+    client.purchase(&buyer, &token, &tier_sym);
+    client.batch_mint(&user1, &tier_sym, &1);
+
+    let owners = [user1.clone(), user1.clone(), buyer.clone(), user1.clone()];
+    for (id, owner) in owners.iter().enumerate() {
+        let id = id as u32;
+        assert_eq!(client.owner_of(&id), *owner);
+        assert_eq!(client.get_ticket(&id).unwrap().tier_symbol, tier_sym);
+    }
+    assert_eq!(client.get_ticket(&2).unwrap().price_paid, 100);
+}
+
+#[test]
+fn test_token_metadata() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user = Address::generate(&e);
+    let client = create_contract(&e, &admin);
+
+    let tier_sym = Symbol::new(&e, "VIP");
+    client.add_tier(
+        &tier_sym,
+        &String::from_str(&e, "VIP Ticket"),
+        &100,
+        &10,
+        &PricingStrategy::Standard,
+    );
+    client.batch_mint(&user, &tier_sym, &2);
+
+    let metadata = client.token_metadata(&1).unwrap();
+    assert_eq!(metadata.name, String::from_str(&e, "EventTicket #1"));
+    assert_eq!(metadata.tier_symbol, tier_sym);
+    assert_eq!(metadata.tier_name, String::from_str(&e, "VIP Ticket"));
+    assert_eq!(metadata.event_start_time, e.ledger().timestamp() + 100000);
+    assert_eq!(metadata.image_uri, client.token_uri(&1));
+    assert!(metadata.is_valid);
+    assert_eq!(metadata.seat, None);
+
+    client.assign_seat(&tier_sym, &14, &1);
+    assert_eq!(client.token_metadata(&1).unwrap().seat, Some(14));
+}
+
+// ============================================================================
+// CUSTOM ATTRIBUTE TESTS
+// ============================================================================
+
+#[test]
+fn test_ticket_attributes_override_tier_attributes() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user = Address::generate(&e);
+    let client = create_contract(&e, &admin);
+
+    let tier_sym = Symbol::new(&e, "VIP");
+    client.add_tier(
+        &tier_sym,
+        &String::from_str(&e, "VIP"),
+        &100,
+        &10,
+        &PricingStrategy::Standard,
+    );
+    client.batch_mint(&user, &tier_sym, &2);
+
+    let dress = Symbol::new(&e, "dress_code");
+    let table = Symbol::new(&e, "table");
+    client.set_tier_attribute(&tier_sym, &dress, &String::from_str(&e, "black tie"));
+    client.set_tier_attribute(&tier_sym, &table, &String::from_str(&e, "any"));
+    client.set_ticket_attribute(&1, &table, &String::from_str(&e, "12"));
+
+    let attrs = client.get_attributes(&1).unwrap();
+    assert_eq!(
+        attrs.get(dress.clone()),
+        Some(String::from_str(&e, "black tie"))
+    );
+    assert_eq!(attrs.get(table.clone()), Some(String::from_str(&e, "12")));
+    assert_eq!(
+        client.get_attributes(&0).unwrap().get(table.clone()),
+        Some(String::from_str(&e, "any"))
+    );
+
+    // Empty value removes the key
+    client.set_tier_attribute(&tier_sym, &dress, &String::from_str(&e, ""));
+    assert_eq!(client.get_tier_attributes(&tier_sym).get(dress), None);
+}
+
+#[test]
+fn test_update_event_metadata() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let client = create_contract(&e, &admin);
+
+    let metadata = storage_types::EventMetadata {
+        venue_name: String::from_str(&e, "Harbour Hall"),
+        geo_hint: String::from_str(&e, "u4pruyd"),
+        description_hash: soroban_sdk::BytesN::from_array(&e, &[7u8; 32]),
+        cover_image_uri: String::from_str(&e, "ipfs://cover"),
+        category: Symbol::new(&e, "music"),
+    };
+    client.update_event_metadata(&metadata);
+
+    let info = client.get_event_info();
+    assert_eq!(info.metadata, metadata);
+    assert_eq!(info.start_time, e.ledger().timestamp() + 100000);
+}
+
+#[test]
+fn test_metadata_freezes_on_call_and_at_event_start() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let tier_sym = harness.add_tier("VIP", 100, 10);
+    let dress = Symbol::new(&e, "dress_code");
+    let value = String::from_str(&e, "black tie");
+
+    assert!(!harness.client.is_metadata_frozen());
+    harness.client.set_tier_attribute(&tier_sym, &dress, &value);
+
+    harness.client.freeze_metadata();
+    assert!(harness.client.is_metadata_frozen());
+    assert!(harness
+        .client
+        .try_set_tier_attribute(&tier_sym, &dress, &String::from_str(&e, ""))
+        .is_err());
+    assert!(harness.client.try_freeze_metadata().is_err());
+    assert_eq!(harness.client.get_tier_attributes(&tier_sym).get(dress), Some(value));
+
+    // Without an explicit freeze, the event start locks metadata
+    let other = testutils::TicketHarness::setup(&e);
+    let metadata = other.client.get_event_info().metadata;
+    other.warp_to(other.client.get_event_info().start_time);
+    assert!(other.client.is_metadata_frozen());
+    assert!(other.client.try_update_event_metadata(&metadata).is_err());
+}
+
+#[test]
+fn test_localized_metadata_falls_back_to_default_locale() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let tier_sym = harness.add_tier("VIP", 100, 10);
+    let text = |name: &str, uri: &str| locales::LocalizedText {
+        name: String::from_str(&e, name),
+        description_uri: String::from_str(&e, uri),
+    };
+    let en = Symbol::new(&e, "en");
+    let fr = Symbol::new(&e, "fr");
+    let de = Symbol::new(&e, "de");
+
+    harness
+        .client
+        .set_event_locale(&en, &Some(text("Summer Fest", "ipfs://en")));
+    harness
+        .client
+        .set_event_locale(&fr, &Some(text("Festival d'\u{e9}t\u{e9}", "ipfs://fr")));
+    harness.client.set_tier_locale(
+        &tier_sym,
+        &fr,
+        &Some(text("Carr\u{e9} VIP", "ipfs://fr-vip")),
+    );
+    assert_eq!(harness.client.get_event_locales().len(), 2);
+    assert_eq!(
+        harness.client.localized_event(&fr),
+        Some(text("Festival d'\u{e9}t\u{e9}", "ipfs://fr"))
+    );
+
+    // Without a default locale, missing translations are just missing
+    assert_eq!(harness.client.localized_event(&de), None);
+    harness.client.set_default_locale(&en);
+    assert_eq!(
+        harness.client.localized_event(&de),
+        Some(text("Summer Fest", "ipfs://en"))
+    );
+    assert_eq!(harness.client.localized_tier(&tier_sym, &de), None);
+    assert_eq!(
+        harness.client.localized_tier(&tier_sym, &fr).unwrap().name,
+        String::from_str(&e, "Carr\u{e9} VIP")
+    );
+
+    harness.client.set_event_locale(&fr, &None);
+    assert_eq!(
+        harness.client.localized_event(&fr),
+        Some(text("Summer Fest", "ipfs://en"))
+    );
+    assert!(harness
+        .client
+        .try_set_tier_locale(&Symbol::new(&e, "NONE"), &en, &Some(text("X", "")))
+        .is_err());
+}
+
+#[test]
+fn test_freeze_tier_halts_only_that_tier() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let buyer = Address::generate(&e);
+    let client = create_contract(&e, &admin);
+    let token = create_payment_token(&e, &admin, &buyer, 1_000);
+
+    let frozen_tier = Symbol::new(&e, "VIP");
+    let open_tier = Symbol::new(&e, "GEN");
+    client.add_tier(
+        &frozen_tier,
+        &String::from_str(&e, "VIP"),
+        &100,
+        &10,
+        &PricingStrategy::Standard,
+    );
+    client.add_tier(
+        &open_tier,
+        &String::from_str(&e, "General"),
+        &50,
+        &10,
+        &PricingStrategy::Standard,
+    );
+
+    client.freeze_tier(&frozen_tier, &true);
+    assert!(client.try_purchase(&buyer, &token, &frozen_tier).is_err());
+    assert_eq!(
+        client.simulate_purchase(&buyer, &frozen_tier, &1).error,
+        Some(PurchaseError::TierFrozen)
+    );
+
+    client.purchase(&buyer, &token, &open_tier);
+    assert_eq!(client.balance(&buyer), 1);
+
+    client.freeze_tier(&frozen_tier, &false);
+    client.purchase(&buyer, &token, &frozen_tier);
+    assert_eq!(client.balance(&buyer), 2);
+}
+
+// ============================================================================
+// RELAYER TESTS
+// ============================================================================
+
+#[test]
+fn test_purchase_on_behalf_by_relayer() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let buyer = Address::generate(&e);
+    let relayer = Address::generate(&e);
+    let client = create_contract(&e, &admin);
+    let token = create_payment_token(&e, &admin, &buyer, 1_000);
+
+    let tier_sym = Symbol::new(&e, "GEN");
+    client.add_tier(
+        &tier_sym,
+        &String::from_str(&e, "General"),
+        &100,
+        &10,
+        &PricingStrategy::Standard,
+    );
+
+    let payload = soroban_sdk::Bytes::from_array(&e, &[1, 2, 3]);
+
+    // Unregistered relayers are rejected
+    assert!(client
+        .try_purchase_on_behalf(&relayer, &buyer, &token, &tier_sym, &payload)
+        .is_err());
+
+    client.set_relayer(&relayer, &true);
+    client.purchase_on_behalf(&relayer, &buyer, &token, &tier_sym, &payload);
+
+    assert_eq!(client.balance(&buyer), 1);
+    assert_eq!(client.balance(&relayer), 0);
+    assert_eq!(
+        soroban_sdk::token::Client::new(&e, &token).balance(&buyer),
+        900
+    );
+}
+
+// ============================================================================
+// FIAT SETTLEMENT TESTS
+// ============================================================================
+
+#[test]
+fn test_pending_payment_confirm_and_void() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let buyer = Address::generate(&e);
+    let processor = Address::generate(&e);
+    let client = create_contract(&e, &admin);
+
+    let tier_sym = Symbol::new(&e, "GEN");
+    client.add_tier(
+        &tier_sym,
+        &String::from_str(&e, "General"),
+        &100,
+        &2,
+        &PricingStrategy::Standard,
+    );
+    client.set_payment_processor(&processor, &true);
+
+    let paid_ref = String::from_str(&e, "pi_paid");
+    let failed_ref = String::from_str(&e, "pi_failed");
+    let paid_id = client.issue_pending(&processor, &buyer, &tier_sym, &paid_ref);
+    let failed_id = client.issue_pending(&processor, &buyer, &tier_sym, &failed_ref);
+
+    // Pending tickets are minted but not yet valid
+    assert_eq!(client.balance(&buyer), 2);
+    assert!(!client.validate_ticket(&paid_id));
+    assert_eq!(
+        client.get_ticket(&paid_id).unwrap().status,
+        storage_types::TicketStatus::PendingPayment
+    );
+
+    client.confirm_payment(&processor, &paid_ref);
+    e.ledger().with_mut(|li| li.timestamp += 100000); // doors open
+    assert!(client.validate_ticket(&paid_id));
+    assert_eq!(client.get_pending_payment(&paid_ref), None);
+
+    // Voiding burns the ticket and frees its supply
+    client.void_payment(&processor, &failed_ref);
+    assert_eq!(client.balance(&buyer), 1);
+    assert_eq!(
+        client.get_ticket(&failed_id).unwrap().status,
+        storage_types::TicketStatus::Voided
+    );
+    client.issue_pending(
+        &processor,
+        &buyer,
+        &tier_sym,
+        &String::from_str(&e, "pi_retry"),
+    );
+}
+
+#[test]
+fn test_will_call_ticket_released_with_pickup_code() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let general = harness.add_tier("GEN", 100, 10);
+    let token = harness.create_payment_token(&harness.admin, 0);
+    let agent = harness.funded_buyer(&token, 100);
+    let attendee = Address::generate(&e);
+    let code = soroban_sdk::Bytes::from_slice(&e, b"PICKUP-42");
+    let code_hash = e.crypto().sha256(&code).to_bytes();
+
+    let token_id = harness
+        .client
+        .purchase_for_pickup(&agent, &token, &general, &code_hash);
+    assert_eq!(harness.client.owner_of(&token_id), harness.client.address);
+    assert_eq!(harness.client.balance(&agent), 0);
+    let ticket = harness.client.get_ticket(&token_id).unwrap();
+    assert_eq!(ticket.status, TicketStatus::HeldForPickup);
+    assert!(!harness.client.validate_ticket(&token_id));
+    let hold = harness.client.get_pickup_hold(&token_id).unwrap();
+    assert_eq!(hold.agent, agent);
+
+    let wrong = soroban_sdk::Bytes::from_slice(&e, b"PICKUP-43");
+    assert!(harness
+        .client
+        .try_release_pickup(&token_id, &wrong, &attendee)
+        .is_err());
+    harness.client.release_pickup(&token_id, &code, &attendee);
+    assert_eq!(harness.client.owner_of(&token_id), attendee);
+    assert_eq!(
+        harness.client.get_ticket(&token_id).unwrap().status,
+        TicketStatus::Active
+    );
+    assert_eq!(harness.client.get_pickup_hold(&token_id), None);
+
+    // Released once, then bound to the attendee like any other ticket
+    assert!(harness
+        .client
+        .try_release_pickup(&token_id, &code, &agent)
+        .is_err());
+    assert!(harness
+        .client
+        .try_transfer(&attendee, &agent, &token_id)
+        .is_err());
+}
+
+#[test]
+#[should_panic(expected = "Payment processor not authorized")]
+fn test_issue_pending_requires_processor_role() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let buyer = Address::generate(&e);
+    let client = create_contract(&e, &admin);
+
+    let tier_sym = Symbol::new(&e, "GEN");
+    client.add_tier(
+        &tier_sym,
+        &String::from_str(&e, "General"),
+        &100,
+        &2,
+        &PricingStrategy::Standard,
+    );
+
+    client.issue_pending(&buyer, &buyer, &tier_sym, &String::from_str(&e, "pi_x"));
+}
+
+// ============================================================================
+// TREASURY TESTS
+// ============================================================================
+
+#[test]
+fn test_withdraw_respects_refund_reserve() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let buyer = Address::generate(&e);
+    let organizer_wallet = Address::generate(&e);
+    let client = create_contract(&e, &admin);
+    let token = create_payment_token(&e, &admin, &buyer, 1_000);
+
+    let tier_sym = Symbol::new(&e, "GEN");
+    client.add_tier(
+        &tier_sym,
+        &String::from_str(&e, "General"),
+        &100,
+        &100,
+        &PricingStrategy::Standard,
+    );
+    client.purchase(&buyer, &token, &tier_sym);
+    client.purchase(&buyer, &token, &tier_sym);
+    assert_eq!(client.get_refund_liability(&token), 200);
+
+    // Full coverage: nothing can leave while refunds are open
+    client.set_payout_address(&organizer_wallet);
+    assert!(client.try_withdraw(&token, &1).is_err());
+
+    // Half coverage frees half the revenue; less is refused while refunds are open
+    assert!(client.try_set_reserve_coverage(&4_999).is_err());
+    client.set_reserve_coverage(&5_000);
+    assert_eq!(client.get_required_reserve(&token), 100);
+    client.withdraw(&token, &100);
+    assert!(client.try_withdraw(&token, &1).is_err());
+
+    // A refund releases its share of the liability
+    client.refund(&buyer, &token, &0);
+    assert_eq!(client.get_refund_liability(&token), 100);
+
+    // After the refund window closes the reserve requirement drops away
+    e.ledger().with_mut(|li| li.timestamp += 100_001);
+    assert_eq!(client.get_required_reserve(&token), 0);
+}
+
+#[test]
+fn test_reserve_coverage_change_is_timelocked() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    harness.client.set_timelock_delay(&10);
+    harness.client.set_reserve_coverage(&5_000);
+    let coverage = || harness.client.get_config().fees.reserve_coverage_bps;
+    assert_eq!(coverage(), 10_000);
+
+    harness.advance_ledgers(10);
+    assert_eq!(harness.client.apply_pending(), 1);
+    assert_eq!(coverage(), 5_000);
+}
+
+#[test]
+fn test_refund_mode_change_is_timelocked() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    harness.client.set_timelock_delay(&10);
+    let fee = RefundMode::RestockingFee(1_000);
+    harness.client.set_refund_mode(&fee);
+    assert_eq!(harness.client.get_refund_mode(), RefundMode::PricePaid);
+    // Invalid modes are refused when queued, not when applied
+    let too_high = RefundMode::RestockingFee(10_001);
+    assert!(harness.client.try_set_refund_mode(&too_high).is_err());
+
+    harness.advance_ledgers(10);
+    assert_eq!(harness.client.apply_pending(), 1);
+    assert_eq!(harness.client.get_refund_mode(), fee);
+}
+
+#[test]
+fn test_refund_limits_change_is_timelocked() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    harness.client.set_timelock_delay(&10);
+    let limits = Some(RefundLimits {
+        max_count: 1,
+        max_value: 0,
+    });
+    harness.client.set_refund_limits(&limits);
+    assert_eq!(harness.client.get_refund_limits(), None);
+    let negative = Some(RefundLimits {
+        max_count: 0,
+        max_value: -1,
+    });
+    assert!(harness.client.try_set_refund_limits(&negative).is_err());
+
+    harness.advance_ledgers(10);
+    assert_eq!(harness.client.apply_pending(), 1);
+    assert_eq!(harness.client.get_refund_limits(), limits);
+}
+
+#[test]
+fn test_payout_address_change_is_timelocked() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let cold_wallet = Address::generate(&e);
+    let token = harness.create_payment_token(&harness.admin, 1_000);
+    let token_client = soroban_sdk::token::Client::new(&e, &token);
+    harness.client.deposit_reserve(&token, &100);
+
+    // Until a payout address is set, revenue goes to the admin
+    assert_eq!(harness.client.get_payout_address(), harness.admin);
+    harness.client.set_timelock_delay(&10);
+    harness.client.set_payout_address(&cold_wallet);
+    harness.client.withdraw(&token, &40);
+    assert_eq!(token_client.balance(&harness.admin), 940);
+
+    harness.advance_ledgers(10);
+    harness.client.apply_pending();
+    assert_eq!(harness.client.get_payout_address(), cold_wallet);
+    harness.client.withdraw(&token, &60);
+    assert_eq!(token_client.balance(&cold_wallet), 60);
+    assert_eq!(token_client.balance(&harness.admin), 940);
+}
+
+#[test]
+fn test_payout_schedule_streams_revenue() {
+    let e = Env::default();
+    e.mock_all_auths();
+    e.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    let admin = Address::generate(&e);
+    let buyer = Address::generate(&e);
+    let client = SoulboundTicketContractClient::new(&e, &e.register(SoulboundTicketContract, ()));
+    let schedule = PayoutSchedule {
+        at_sale_bps: 2_000,
+        at_start_bps: 3_000,
+        completion_time: 5_000,
+    };
+
+    let mut config = init_config(&e, &admin, 2_000, 1_500);
+    config.payout_schedule = Some(PayoutSchedule {
+        at_sale_bps: 6_000,
+        ..schedule.clone()
+    });
+    assert_eq!(
+        client.try_initialize(&config),
+        Err(Ok(InitError::InvalidPayoutSchedule))
+    );
+    config.payout_schedule = Some(PayoutSchedule {
+        completion_time: 1_999,
+        ..schedule.clone()
+    });
+    assert_eq!(
+        client.try_initialize(&config),
+        Err(Ok(InitError::InvalidPayoutSchedule))
+    );
+    config.payout_schedule = Some(schedule.clone());
+    client.initialize(&config);
+    assert_eq!(client.get_payout_schedule(), Some(schedule));
+
+    let token = create_payment_token(&e, &admin, &buyer, 1_000);
+    let tier_sym = Symbol::new(&e, "GEN");
+    client.add_tier(
+        &tier_sym,
+        &String::from_str(&e, "General"),
+        &100,
+        &100,
+        &PricingStrategy::Standard,
+    );
+    client.purchase(&buyer, &token, &tier_sym);
+    client.purchase(&buyer, &token, &tier_sym);
+    client.set_reserve_coverage(&5_000);
+
+    // 20% of sales straight away
+    assert_eq!(client.get_withdrawable(&token), 40);
+    client.withdraw(&token, &40);
+    assert!(client.try_withdraw(&token, &1).is_err());
+
+    // Another 30% once the event starts
+    e.ledger().with_mut(|li| li.timestamp = 2_000);
+    assert_eq!(client.get_withdrawable(&token), 60);
+    assert!(client.try_withdraw(&token, &61).is_err());
+    client.withdraw(&token, &60);
+
+    // The rest after completion
+    e.ledger().with_mut(|li| li.timestamp = 5_000);
+    assert_eq!(client.get_withdrawable(&token), 100);
+    client.withdraw(&token, &100);
+}
+
+#[test]
+fn test_bond_covers_refunds_after_cancellation() {
+    let e = Env::default();
+    let now = e.ledger().timestamp();
+    let harness = testutils::TicketHarness::setup(&e);
+    let tier_sym = harness.add_tier("GEN", 100, 10);
+    let token = harness.create_payment_token(&harness.admin, 300);
+    let first = harness.funded_buyer(&token, 100);
+    let second = harness.funded_buyer(&token, 100);
+    let token_client = soroban_sdk::token::Client::new(&e, &token);
+
+    harness.client.post_bond(&token, &300);
+    assert!(harness.client.try_post_bond(&token, &1).is_err());
+    let first_id = harness.client.purchase(&first, &token, &tier_sym);
+    let second_id = harness.client.purchase(&second, &token, &tier_sym);
+
+    // The bond never counts as revenue
+    harness.client.set_reserve_coverage(&5_000);
+    assert_eq!(harness.client.get_withdrawable(&token), 100);
+    harness.client.withdraw(&token, &100);
+
+    harness.client.cancel_event();
+    assert_eq!(harness.client.get_bond().unwrap().state, bond::BondState::Slashed);
+    assert!(harness.client.try_purchase(&first, &token, &tier_sym).is_err());
+
+    // Refunds stay open past the cutoff; what revenue can't cover comes out of the bond
+    harness.warp_to(now + 200_000);
+    harness.client.refund(&first, &token, &first_id);
+    harness.client.refund(&second, &token, &second_id);
+    assert_eq!(token_client.balance(&first), 100);
+    assert_eq!(token_client.balance(&second), 100);
+    assert_eq!(harness.client.get_bond().unwrap().remaining, 200);
+    assert!(harness.client.try_release_bond().is_err());
+}
+
+#[test]
+fn test_bond_returned_after_completion() {
+    let e = Env::default();
+    let now = e.ledger().timestamp();
+    let harness = testutils::TicketHarness::setup(&e);
+    let token = harness.create_payment_token(&harness.admin, 300);
+    let token_client = soroban_sdk::token::Client::new(&e, &token);
+
+    harness.client.post_bond(&token, &300);
+    assert_eq!(token_client.balance(&harness.admin), 0);
+
+    harness.warp_to(now + 100_000 + bond::DEFAULT_BOND_HOLD_SECONDS - 1);
+    assert!(harness.client.try_release_bond().is_err());
+    harness.warp_to(now + 100_000 + bond::DEFAULT_BOND_HOLD_SECONDS);
+    harness.client.release_bond();
+    assert_eq!(token_client.balance(&harness.admin), 300);
+    assert_eq!(harness.client.get_bond().unwrap().state, bond::BondState::Released);
+    assert!(harness.client.try_release_bond().is_err());
+}
+
+#[test]
+fn test_abandoned_event_recovery_pays_pro_rata() {
+    let e = Env::default();
+    // Keep entries live across the abandonment period
+    e.ledger().with_mut(|li| {
+        li.min_persistent_entry_ttl = 1_000_000;
+        li.min_temp_entry_ttl = 1_000_000;
+        li.max_entry_ttl = 2_000_000;
+    });
+    let now = e.ledger().timestamp();
+    let harness = testutils::TicketHarness::setup(&e);
+    let tier_sym = harness.add_tier("GEN", 100, 10);
+    let token = harness.create_payment_token(&harness.admin, 0);
+    let first = harness.funded_buyer(&token, 100);
+    let second = harness.funded_buyer(&token, 100);
+    let outsider = Address::generate(&e);
+    let token_client = soroban_sdk::token::Client::new(&e, &token);
+
+    let first_id = harness.client.purchase(&first, &token, &tier_sym);
+    let second_id = harness.client.purchase(&second, &token, &tier_sym);
+    harness.client.set_reserve_coverage(&5_000);
+    harness.client.withdraw(&token, &100);
+    assert_eq!(harness.client.get_last_admin_activity(), e.ledger().sequence());
+
+    // The organizer has to be silent for the whole period after the event starts
+    harness.advance_ledgers(recovery::ABANDONMENT_LEDGERS);
+    assert!(harness.client.try_trigger_recovery(&first).is_err());
+    harness.warp_to(now + 100_001);
+    assert!(harness.client.try_trigger_recovery(&outsider).is_err());
+    assert!(harness.client.try_claim_recovery_refund(&first, &first_id).is_err());
+    harness.client.trigger_recovery(&first);
+    assert!(harness.client.is_recovery_active());
+    assert!(harness.client.try_withdraw(&token, &1).is_err());
+
+    // Half the escrow is left, so each holder gets half back
+    assert_eq!(harness.client.claim_recovery_refund(&first, &first_id), 50);
+    assert_eq!(harness.client.claim_recovery_refund(&second, &second_id), 50);
+    assert_eq!(token_client.balance(&first), 50);
+    assert_eq!(token_client.balance(&second), 50);
+    assert!(harness
+        .client
+        .try_claim_recovery_refund(&first, &first_id)
+        .is_err());
+    assert_eq!(harness.client.get_refund_liability(&token), 0);
+}
+
+#[test]
+fn test_purchase_and_batch_mint_return_token_ids() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let buyer = Address::generate(&e);
+    let client = create_contract(&e, &admin);
+    let token = create_payment_token(&e, &admin, &buyer, 1_000);
+
+    let tier_sym = Symbol::new(&e, "GEN");
+    client.add_tier(
+        &tier_sym,
+        &String::from_str(&e, "General"),
+        &100,
+        &10,
+        &PricingStrategy::Standard,
+    );
+
+    let minted = client.batch_mint(&admin, &tier_sym, &3);
+    assert_eq!(minted, soroban_sdk::vec![&e, 0u32, 1, 2]);
+
+    let token_id = client.purchase(&buyer, &token, &tier_sym);
+    assert_eq!(token_id, 3);
+    assert_eq!(client.owner_of(&token_id), buyer);
+}
+
+// ============================================================================
+// DISTRIBUTION MODE TESTS
+// ============================================================================
+
+#[test]
+fn test_mixed_distribution_reserves_lottery_supply() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user = Address::generate(&e);
+    let client = create_contract(&e, &admin);
+
+    let tier_sym = Symbol::new(&e, "GEN");
+    client.add_tier(
+        &tier_sym,
+        &String::from_str(&e, "General"),
+        &100,
+        &10,
+        &PricingStrategy::Standard,
+    );
+    client.set_distribution_mode(&tier_sym, &storage_types::DistributionMode::Mixed(4));
+
+    client.batch_mint(&user, &tier_sym, &6);
+    assert!(client.try_batch_mint(&user, &tier_sym, &1).is_err());
+    assert_eq!(
+        client.simulate_purchase(&user, &tier_sym, &1).error,
+        Some(PurchaseError::DirectAllocationExhausted)
+    );
+}
+
+#[test]
+fn test_lottery_only_tier_blocks_direct_sales() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let buyer = Address::generate(&e);
+    let client = create_contract(&e, &admin);
+    let token = create_payment_token(&e, &admin, &buyer, 1_000);
+
+    let tier_sym = Symbol::new(&e, "VIP");
+    client.add_tier(
+        &tier_sym,
+        &String::from_str(&e, "VIP"),
+        &100,
+        &10,
+        &PricingStrategy::Standard,
+    );
+
+    // A lottery cannot be attached to a direct-sale tier
+    assert!(client
+        .try_initialize_lottery(
+            &tier_sym,
+            &AllocationStrategyType::Lottery,
+            &10,
+            &(e.ledger().sequence() + 10),
+            &1,
+            &5,
+        )
+        .is_err());
+
+    client.set_distribution_mode(&tier_sym, &storage_types::DistributionMode::LotteryOnly);
+    assert!(client.try_purchase(&buyer, &token, &tier_sym).is_err());
+    assert!(client.try_batch_mint(&admin, &tier_sym, &1).is_err());
+    assert_eq!(
+        client.simulate_purchase(&buyer, &tier_sym, &1).error,
+        Some(PurchaseError::LotteryOnly)
+    );
+
+    // Nobody has won yet, so there is nothing to claim
+    assert!(client
+        .try_claim_lottery_ticket(&buyer, &token, &tier_sym)
+        .is_err());
+}
+
+// ============================================================================
+// SALES CHANNEL TESTS
+// ============================================================================
+
+#[test]
+fn test_channel_allocations_partition_supply() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let buyer = Address::generate(&e);
+    let client = create_contract(&e, &admin);
+
+    let tier_sym = Symbol::new(&e, "GEN");
+    client.add_tier(
+        &tier_sym,
+        &String::from_str(&e, "General"),
+        &100,
+        &10,
+        &PricingStrategy::Standard,
+    );
+
+    client.set_channel_allocation(&tier_sym, &channels::SalesChannel::Presale, &3);
+    client.set_channel_allocation(&tier_sym, &channels::SalesChannel::BoxOffice, &2);
+
+    // Box office mints draw on their own holdback first
+    client.batch_mint(&admin, &tier_sym, &2);
+    let box_office = client
+        .get_channel_allocations(&tier_sym)
+        .get(channels::SalesChannel::BoxOffice)
+        .unwrap();
+    assert_eq!(box_office.sold, 2);
+
+    // General sale only sees the unreserved 5
+    assert_eq!(client.simulate_purchase(&buyer, &tier_sym, &5).error, None);
+    assert_eq!(
+        client.simulate_purchase(&buyer, &tier_sym, &6).error,
+        Some(PurchaseError::ChannelAllocationExhausted)
+    );
+
+    // Releasing presale returns its unsold holdback to general sale
+    client.release_channel(&tier_sym, &channels::SalesChannel::Presale);
+    assert_eq!(client.simulate_purchase(&buyer, &tier_sym, &8).error, None);
+}
+
+#[test]
+fn test_named_allocations_draw_on_comp_reserve() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let tier_sym = harness.add_tier("VIP", 500, 10);
+    let sponsor = Address::generate(&e);
+    let artist = Address::generate(&e);
+    let sponsor_code = Symbol::new(&e, "SPONSOR");
+    let artist_code = Symbol::new(&e, "ARTIST");
+
+    // Nothing held back yet
+    let allocations = vec![&e, (sponsor.clone(), sponsor_code.clone())];
+    assert!(harness.client.try_allocate_named(&tier_sym, &allocations).is_err());
+
+    harness.client.set_channel_allocation(&tier_sym, &channels::SalesChannel::Comp, &2);
+    let allocations = vec![
+        &e,
+        (sponsor.clone(), sponsor_code.clone()),
+        (artist.clone(), artist_code.clone()),
+    ];
+    let token_ids = harness.client.allocate_named(&tier_sym, &allocations);
+    assert_eq!(harness.client.owner_of(&token_ids.get(0).unwrap()), sponsor);
+    assert_eq!(harness.client.owner_of(&token_ids.get(1).unwrap()), artist);
+    assert_eq!(
+        harness.client.get_allocation_reason(&token_ids.get(1).unwrap()),
+        Some(artist_code)
+    );
+    assert_eq!(harness.client.get_named_allocation_count(&tier_sym), 2);
+
+    let log = harness.client.get_admin_log(&0, &10);
+    let entry = log.get(log.len() - 1).unwrap();
+    assert_eq!(entry.function, Symbol::new(&e, "allocate_named"));
+
+    // The reserve is spent; batch mints still use the box office pool
+    let allocations = vec![&e, (sponsor.clone(), sponsor_code)];
+    assert!(harness.client.try_allocate_named(&tier_sym, &allocations).is_err());
+    let comped = harness.client.batch_mint(&sponsor, &tier_sym, &1);
+    assert_eq!(harness.client.get_allocation_reason(&comped.get(0).unwrap()), None);
+    assert_eq!(harness.client.get_named_allocation_count(&tier_sym), 2);
+}
+
+#[test]
+fn test_release_schedule_unlocks_supply_in_waves() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let buyer = Address::generate(&e);
+    let client = create_contract(&e, &admin);
+    let token = create_payment_token(&e, &admin, &buyer, 10_000);
+
+    let tier_sym = Symbol::new(&e, "GEN");
+    client.add_tier(
+        &tier_sym,
+        &String::from_str(&e, "General"),
+        &100,
+        &100,
+        &PricingStrategy::Standard,
+    );
+
+    let start = e.ledger().timestamp();
+    client.set_release_schedule(
+        &tier_sym,
+        &storage_types::ReleaseSchedule {
+            start_time: start,
+            wave_size: 2,
+            wave_interval: 3600,
+        },
+    );
+    assert_eq!(client.get_unlocked_supply(&tier_sym), Some(2));
+
+    client.purchase(&buyer, &token, &tier_sym);
+    client.purchase(&buyer, &token, &tier_sym);
+    assert!(client.try_purchase(&buyer, &token, &tier_sym).is_err());
+    assert_eq!(
+        client.simulate_purchase(&buyer, &tier_sym, &1).error,
+        Some(PurchaseError::SupplyNotReleased)
+    );
+
+    // Next wave
+    e.ledger().with_mut(|li| li.timestamp = start + 3600);
+    assert_eq!(client.get_unlocked_supply(&tier_sym), Some(4));
+    client.purchase(&buyer, &token, &tier_sym);
+
+    client.clear_release_schedule(&tier_sym);
+    assert_eq!(client.get_unlocked_supply(&tier_sym), Some(100));
+}
+
+// ============================================================================
+// REFUND AGENT TESTS
+// ============================================================================
+
+#[test]
+fn test_refund_agent_daily_limit() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let buyer = Address::generate(&e);
+    let agent = Address::generate(&e);
+    let client = create_contract(&e, &admin);
+    let token = create_payment_token(&e, &admin, &buyer, 1_000);
+
+    let tier_sym = Symbol::new(&e, "GEN");
+    client.add_tier(
+        &tier_sym,
+        &String::from_str(&e, "General"),
+        &100,
+        &100,
+        &PricingStrategy::Standard,
+    );
+    let first = client.purchase(&buyer, &token, &tier_sym);
+    let second = client.purchase(&buyer, &token, &tier_sym);
+
+    // Not yet an agent
+    assert!(client.try_agent_refund(&agent, &token, &first).is_err());
+
+    client.set_refund_agent(&agent, &150);
+    assert_eq!(client.agent_refund(&agent, &token, &first), 100);
+    assert_eq!(
+        soroban_sdk::token::Client::new(&e, &token).balance(&buyer),
+        900
+    );
+
+    // Second refund would take the agent past today's limit
+    assert!(client.try_agent_refund(&agent, &token, &second).is_err());
+
+    // Limit resets the next day
+    e.ledger().with_mut(|li| li.timestamp += 86_400);
+    client.agent_refund(&agent, &token, &second);
+    assert_eq!(client.balance(&buyer), 0);
+}
+
+#[test]
+fn test_agent_refund_counts_against_refund_limits() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let tier_sym = harness.add_tier("GEN", 100, 100);
+    let token = harness.create_payment_token(&harness.admin, 0);
+    let buyer = harness.funded_buyer(&token, 1_000);
+    let agent = Address::generate(&e);
+    let first = harness.client.purchase(&buyer, &token, &tier_sym);
+    let second = harness.client.purchase(&buyer, &token, &tier_sym);
+
+    harness.client.set_refund_agent(&agent, &1_000);
+    harness.client.set_refund_limits(&Some(RefundLimits {
+        max_count: 1,
+        max_value: 0,
+    }));
+    assert_eq!(harness.client.agent_refund(&agent, &token, &first), 100);
+    assert_eq!(harness.client.get_refund_stats(&buyer).count, 1);
+
+    // The agent cannot take the owner past their limit
+    assert_eq!(
+        harness.client.try_agent_refund(&agent, &token, &second),
+        Err(Ok(RefundError::CountLimitExceeded))
+    );
+    assert_eq!(
+        harness.client.try_refund(&buyer, &token, &second),
+        Err(Ok(RefundError::CountLimitExceeded))
+    );
+    assert_eq!(harness.client.balance(&buyer), 1);
+}
+
+// ============================================================================
+// DISCOUNT STACKING TESTS
+// ============================================================================
+
+#[test]
+fn test_discount_policy_combinations() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let buyer = Address::generate(&e);
+    let contract_id = e.register(SoulboundTicketContract, ());
+    let client = SoulboundTicketContractClient::new(&e, &contract_id);
+    // Event starts in 30 days, so TimeDecay tiers get the early-bird discount
+    client.initialize(&init_config(&e, &admin, 2_592_000, 2_592_000));
+    let token = create_payment_token(&e, &admin, &buyer, 10_000);
+
+    let tier_sym = Symbol::new(&e, "EARLY");
+    client.add_tier(
+        &tier_sym,
+        &String::from_str(&e, "Early"),
+        &1000,
+        &100,
+        &PricingStrategy::TimeDecay,
+    );
+    let promo = Symbol::new(&e, "SAVE20");
+    client.set_promo_code(&promo, &2000);
+    client.set_loyalty_discount(&buyer, &500);
+
+    // Public price only carries the early-bird discount
+    assert_eq!(client.get_ticket_price(&tier_sym), 900);
+
+    // Default policy stacks everything: 10% + 20% + 5%
+    let id = client.purchase_with_promo(&buyer, &token, &tier_sym, &promo);
+    assert_eq!(client.get_ticket(&id).unwrap().price_paid, 650);
+
+    // Stacked discounts are capped
+    client.set_discount_policy(&DiscountPolicy {
+        mode: discounts::StackingMode::Stack,
+        max_combined_bps: 2500,
+    });
+    let id = client.purchase_with_promo(&buyer, &token, &tier_sym, &promo);
+    assert_eq!(client.get_ticket(&id).unwrap().price_paid, 750);
+
+    // Best-of takes the promo alone
+    client.set_discount_policy(&DiscountPolicy {
+        mode: discounts::StackingMode::BestOf,
+        max_combined_bps: 10_000,
+    });
+    let id = client.purchase_with_promo(&buyer, &token, &tier_sym, &promo);
+    assert_eq!(client.get_ticket(&id).unwrap().price_paid, 800);
+
+    // Priority order: loyalty first, then promo
+    client.set_discount_policy(&DiscountPolicy {
+        mode: discounts::StackingMode::Priority(soroban_sdk::vec![
+            &e,
+            DiscountKind::Loyalty,
+            DiscountKind::Promo,
+        ]),
+        max_combined_bps: 10_000,
+    });
+    let id = client.purchase_with_promo(&buyer, &token, &tier_sym, &promo);
+    assert_eq!(client.get_ticket(&id).unwrap().price_paid, 950);
+
+    // Same policy applies to the preview path
+    assert_eq!(
+        client.simulate_purchase(&buyer, &tier_sym, &1).unit_price,
+        950
+    );
+
+    // Unknown promo codes are rejected
+    assert!(client
+        .try_purchase_with_promo(&buyer, &token, &tier_sym, &Symbol::new(&e, "BOGUS"))
+        .is_err());
+}
+
+// ============================================================================
+// RECEIPT TESTS
+// ============================================================================
+
+#[test]
+fn test_purchase_records_itemized_receipt() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let buyer = Address::generate(&e);
+    let client = create_contract(&e, &admin);
+    let token = create_payment_token(&e, &admin, &buyer, 1_000);
+
+    let tier_sym = Symbol::new(&e, "GEN");
+    client.add_tier(
+        &tier_sym,
+        &String::from_str(&e, "General"),
+        &200,
+        &100,
+        &PricingStrategy::Standard,
+    );
+    let promo = Symbol::new(&e, "SAVE10");
+    client.set_promo_code(&promo, &1000);
+    client.set_loyalty_discount(&buyer, &500);
+
+    let first = client.purchase_with_promo(&buyer, &token, &tier_sym, &promo);
+    let second = client.purchase(&buyer, &token, &tier_sym);
+
+    let receipt_ids = client.get_buyer_receipts(&buyer);
+    assert_eq!(receipt_ids.len(), 2);
+
+    let receipt = client.get_receipt(&receipt_ids.get(0).unwrap()).unwrap();
+    assert_eq!(receipt.buyer, buyer);
+    assert_eq!(receipt.token_ids, soroban_sdk::vec![&e, first]);
+    assert_eq!(receipt.base_price, 200);
+    assert_eq!(
+        receipt.discounts,
+        soroban_sdk::vec![
+            &e,
+            discounts::AppliedDiscount {
+                kind: DiscountKind::Promo,
+                amount: 20,
+            },
+            discounts::AppliedDiscount {
+                kind: DiscountKind::Loyalty,
+                amount: 10,
+            },
+        ]
+    );
+    assert_eq!(receipt.fees, 0);
+    assert_eq!(receipt.oracle_multiplier, 10_000);
+    assert_eq!(receipt.payment_token, token);
+    assert_eq!(receipt.total, 170);
+
+    let receipt = client.get_receipt(&receipt_ids.get(1).unwrap()).unwrap();
+    assert_eq!(receipt.token_ids, soroban_sdk::vec![&e, second]);
+    assert_eq!(receipt.total, 190);
+
+    assert!(client.get_receipt(&99).is_none());
+}
+
+#[test]
+fn test_receipt_token_is_transferable_when_enabled() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let general = harness.add_tier("GEN", 100, 10);
+    let corporate = harness.add_tier("CORP", 200, 10);
+    let token = harness.create_payment_token(&harness.admin, 0);
+    let buyer = harness.funded_buyer(&token, 1_000);
+    let accounts = Address::generate(&e);
+
+    harness.client.set_receipt_token_enabled(&corporate, &true);
+    assert!(!harness.client.is_receipt_token_enabled(&general));
+
+    harness.client.purchase(&buyer, &token, &general);
+    let ticket_id = harness.client.purchase(&buyer, &token, &corporate);
+    let receipt_ids = harness.client.get_buyer_receipts(&buyer);
+    assert!(harness
+        .client
+        .get_receipt_token(&receipt_ids.get(0).unwrap())
+        .is_none());
+
+    let receipt_id = receipt_ids.get(1).unwrap();
+    let receipt_token = harness.client.get_receipt_token(&receipt_id).unwrap();
+    assert_eq!(receipt_token.owner, buyer);
+    assert_eq!(receipt_token.total_paid, 200);
+
+    // The receipt moves to the accounting wallet; the ticket does not
+    harness
+        .client
+        .transfer_receipt_token(&buyer, &accounts, &receipt_id);
+    assert_eq!(
+        harness.client.get_receipt_token(&receipt_id).unwrap().owner,
+        accounts
+    );
+    assert_eq!(harness.client.owner_of(&ticket_id), buyer);
+    assert!(harness
+        .client
+        .try_transfer_receipt_token(&buyer, &accounts, &receipt_id)
+        .is_err());
+}
+
+// ============================================================================
+// INVITATION TESTS
+// ============================================================================
+
+#[test]
+fn test_invitation_claim_and_expiry() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let guest = Address::generate(&e);
+    let client = create_contract(&e, &admin);
+
+    let tier_sym = Symbol::new(&e, "GEN");
+    client.add_tier(
+        &tier_sym,
+        &String::from_str(&e, "General"),
+        &100,
+        &2,
+        &PricingStrategy::Standard,
+    );
+
+    let secret = Bytes::from_slice(&e, b"invite-secret-1");
+    let invite_hash = e.crypto().sha256(&secret).to_bytes();
+    let expiry = e.ledger().timestamp() + 3600;
+    client.create_invitation(&tier_sym, &invite_hash, &expiry);
+
+    let stale_secret = Bytes::from_slice(&e, b"invite-secret-2");
+    let stale_hash = e.crypto().sha256(&stale_secret).to_bytes();
+    client.create_invitation(&tier_sym, &stale_hash, &expiry);
+
+    // Both tickets are held for invitations
+    let buyer = Address::generate(&e);
+    let token = create_payment_token(&e, &admin, &buyer, 1_000);
+    assert!(client.try_purchase(&buyer, &token, &tier_sym).is_err());
+
+    // Wrong secret is rejected, the right one mints to the new wallet
+    assert!(client
+        .try_claim_invitation(&guest, &Bytes::from_slice(&e, b"guess"))
+        .is_err());
+    let token_id = client.claim_invitation(&guest, &secret);
+    assert_eq!(client.owner_of(&token_id), guest);
+    assert!(client.get_invitation(&invite_hash).is_none());
+    assert!(client.try_claim_invitation(&guest, &secret).is_err());
+
+    // Expired invitations cannot be claimed and release their ticket
+    assert!(client.try_expire_invitation(&stale_hash).is_err());
+    e.ledger().with_mut(|li| li.timestamp = expiry + 1);
+    assert!(client
+        .try_claim_invitation(&Address::generate(&e), &stale_secret)
+        .is_err());
+    client.expire_invitation(&stale_hash);
+    client.purchase(&buyer, &token, &tier_sym);
+}
+
+#[test]
+fn test_invitation_cannot_be_claimed_on_inactive_tier() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let tier_sym = harness.add_tier("GEN", 100, 10);
+    let secret = Bytes::from_slice(&e, b"invite-secret");
+    let invite_hash = e.crypto().sha256(&secret).to_bytes();
+    let expiry = e.ledger().timestamp() + 3600;
+    harness
+        .client
+        .create_invitation(&tier_sym, &invite_hash, &expiry);
+
+    harness.set_tier_active(&tier_sym, false);
+    let guest = Address::generate(&e);
+    assert!(harness
+        .client
+        .try_claim_invitation(&guest, &secret)
+        .is_err());
+    assert!(harness.client.get_invitation(&invite_hash).is_some());
+
+    harness.set_tier_active(&tier_sym, true);
+    let token_id = harness.client.claim_invitation(&guest, &secret);
+    assert_eq!(harness.client.owner_of(&token_id), guest);
+}
+
+#[test]
+fn test_offered_ticket_minted_only_on_acceptance() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let tier_sym = harness.add_tier("PROMO", 100, 2);
+    let fan = Address::generate(&e);
+    let busy = Address::generate(&e);
+    let token = harness.create_payment_token(&harness.admin, 0);
+    let buyer = harness.funded_buyer(&token, 1_000);
+
+    let accepted = harness.client.offer_ticket(&fan, &tier_sym);
+    let lapsed = harness.client.offer_ticket(&busy, &tier_sym);
+    assert_eq!(harness.client.get_offer(&accepted).unwrap().recipient, fan);
+
+    // Nothing is minted until the recipient accepts, but both tickets are held
+    assert_eq!(harness.client.balance(&fan), 0);
+    assert!(harness
+        .client
+        .try_purchase(&buyer, &token, &tier_sym)
+        .is_err());
+
+    let token_id = harness.client.accept_offer(&accepted);
+    assert_eq!(harness.client.owner_of(&token_id), fan);
+    assert_eq!(harness.client.get_offer(&accepted), None);
+    assert!(harness.client.try_accept_offer(&accepted).is_err());
+
+    // An offer left unanswered lapses and its ticket goes back on sale
+    assert!(harness.client.try_expire_offer(&lapsed).is_err());
+    harness.warp_time(offers::OFFER_ACCEPT_WINDOW + 1);
+    assert!(harness.client.try_accept_offer(&lapsed).is_err());
+    harness.client.expire_offer(&lapsed);
+    assert_eq!(harness.client.balance(&busy), 0);
+    harness.client.purchase(&buyer, &token, &tier_sym);
+}
+
+// ============================================================================
+// IDENTITY BINDING TESTS
+// ============================================================================
+
+#[test]
+fn test_identity_bound_purchase_and_rebind() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let buyer = Address::generate(&e);
+    let client = create_contract(&e, &admin);
+    let token = create_payment_token(&e, &admin, &buyer, 1_000);
+
+    let tier_sym = Symbol::new(&e, "IDCHECK");
+    client.add_tier(
+        &tier_sym,
+        &String::from_str(&e, "ID Checked"),
+        &100,
+        &10,
+        &PricingStrategy::Standard,
+    );
+    client.set_identity_required(&tier_sym, &true);
+
+    let salt = Bytes::from_slice(&e, b"salt-42");
+    let mut preimage = salt.clone();
+    preimage.extend_from_slice(b"Jane Doe");
+    let identity_hash = e.crypto().sha256(&preimage).to_bytes();
+
+    // Plain purchases are refused for ID-checked tiers
+    assert!(client.try_purchase(&buyer, &token, &tier_sym).is_err());
+
+    let token_id = client.purchase_with_identity(&buyer, &token, &tier_sym, &identity_hash);
+    let verify = |name: &str| client.verify_identity(&token_id, &String::from_str(&e, name), &salt);
+    assert_eq!(verify("Jane Doe"), Some(true));
+    assert_eq!(verify("Jane Do"), Some(false));
+
+    // Admin fixes a typo by rebinding
+    let mut fixed = salt.clone();
+    fixed.extend_from_slice(b"Jane Doe-Smith");
+    client.rebind_identity(&token_id, &e.crypto().sha256(&fixed).to_bytes());
+    assert_eq!(verify("Jane Doe-Smith"), Some(true));
+    assert_eq!(verify("Jane Doe"), Some(false));
+}
+
+// ============================================================================
+// EMERGENCY COUNCIL TESTS
+// ============================================================================
+
+#[test]
+fn test_council_can_halt_but_not_resume() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let member = Address::generate(&e);
+    let outsider = Address::generate(&e);
+    let buyer = Address::generate(&e);
+    let client = create_contract(&e, &admin);
+    let token = create_payment_token(&e, &admin, &buyer, 1_000);
+
+    let tier_sym = Symbol::new(&e, "GEN");
+    client.add_tier(
+        &tier_sym,
+        &String::from_str(&e, "General"),
+        &100,
+        &10,
+        &PricingStrategy::Standard,
+    );
+    client.set_council_member(&member, &true);
+    assert!(client.try_pause_all(&outsider).is_err());
+
+    // Veto a queued pricing change
+    client.set_timelock_delay(&100);
+    client.update_oracle_reference(&200_000_000);
+    let change_id = client.get_pending_changes().get(0).unwrap().id;
+    client.council_veto(&member, &change_id);
+    assert_eq!(client.get_pending_changes().len(), 0);
+
+    // Freeze pricing and pause sales
+    client.council_freeze(&member);
+    client.pause_all(&member);
+    assert!(client.is_paused());
+    assert!(client.try_purchase(&buyer, &token, &tier_sym).is_err());
+
+    // Only the owner lifts the halt
+    client.unpause_all();
+    client.emergency_freeze(&false);
+    client.purchase(&buyer, &token, &tier_sym);
+}
+
+// ============================================================================
+// ORACLE CACHE TESTS
+// ============================================================================
+
+#[test]
+fn test_oracle_multiplier_cached_until_update_frequency() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let client = create_contract(&e, &admin);
+
+    let oracle_id = e.register(MockAdjustableOracle, ());
+    let oracle_client = MockAdjustableOracleClient::new(&e, &oracle_id);
+    oracle_client.set_price(&110_000_000);
+
+    client.set_pricing_config(&PricingConfig {
+        pricing_mode: PricingMode::Dynamic,
+        oracle_address: oracle_id.clone(),
+        dex_pool_address: admin.clone(),
+        price_floor: 0,
+        price_ceiling: i128::MAX,
+        update_frequency: 3600,
+        last_update_time: e.ledger().timestamp(),
+        is_frozen: false,
+        oracle_pair: String::from_str(&e, "XLM/USD"),
+        oracle_reference_price: oracle::DIA_ORACLE_DECIMALS,
+        max_oracle_age_seconds: oracle::DEFAULT_STALENESS_SECONDS,
+    });
+
+    let tier_sym = Symbol::new(&e, "ORK");
+    client.add_tier(
+        &tier_sym,
+        &String::from_str(&e, "Oracle Tier"),
+        &100,
+        &100,
+        &PricingStrategy::Standard,
+    );
+    assert_eq!(client.get_ticket_price(&tier_sym), 110);
+
+    // Oracle moves, but the cached multiplier is still fresh
+    oracle_client.set_price(&120_000_000);
+    assert_eq!(client.get_ticket_price(&tier_sym), 110);
+
+    // Cache expires after `update_frequency`
+    e.ledger().with_mut(|li| li.timestamp += 3600);
+    assert_eq!(client.get_ticket_price(&tier_sym), 120);
+
+    // Admin can force a refresh at any time
+    oracle_client.set_price(&130_000_000);
+    assert_eq!(client.force_refresh_oracle(), 13_000);
+    assert_eq!(client.get_ticket_price(&tier_sym), 130);
+}
+
+// ============================================================================
+// ALLOCATION ARCHIVE TESTS
+// ============================================================================
+
+#[test]
+fn test_allocation_proofs_are_hash_chained() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let client = create_contract(&e, &admin);
+
+    let mut entries = soroban_sdk::Vec::new(&e);
+    for i in 0..3u32 {
+        entries.push_back(allocation::LotteryEntry {
+            participant: Address::generate(&e),
+            entry_time: e.ledger().timestamp(),
+            nonce: i,
+            commitment_hash: None,
+        });
+    }
+    let randomness = soroban_sdk::vec![&e, 7u128, 11u128];
+    let results = allocation::AllocationEngine::allocate_lottery(&e, &entries, &randomness, 2);
+
+    let tier_a = Symbol::new(&e, "LOTA");
+    let tier_b = Symbol::new(&e, "LOTB");
+    let (first, second) = e.as_contract(&client.address, || {
+        (
+            archive::ArchiveManager::seal(&e, &tier_a, 0, &entries, &randomness, &results),
+            archive::ArchiveManager::seal(&e, &tier_b, 0, &entries, &randomness, &results),
+        )
+    });
+
+    // Odd leaf is carried up: root = H(H(l0 || l1) || l2)
+    let leaf = |entry: allocation::LotteryEntry| e.crypto().sha256(&entry.to_xdr(&e)).to_bytes();
+    let pair = |left: BytesN<32>, right: BytesN<32>| {
+        let mut combined = Bytes::from(left);
+        combined.append(&Bytes::from(right));
+        e.crypto().sha256(&combined).to_bytes()
+    };
+    let expected_root = pair(
+        pair(leaf(entries.get(0).unwrap()), leaf(entries.get(1).unwrap())),
+        leaf(entries.get(2).unwrap()),
+    );
+    assert_eq!(first.entries_root, expected_root);
+    assert_eq!(first.entry_count, 3);
+    assert_eq!(first.winner_count, results.len());
+
+    // The second draw chains onto the first
+    assert_eq!(first.previous_hash, BytesN::from_array(&e, &[0u8; 32]));
+    assert_eq!(second.previous_hash, first.proof_hash);
+    assert_ne!(second.proof_hash, first.proof_hash);
+
+    assert_eq!(client.export_allocation_proof(&tier_a, &0), first);
+    assert!(client
+        .try_export_allocation_proof(&Symbol::new(&e, "NONE"), &0)
+        .is_err());
+}
+
+#[test]
+fn test_prune_lottery_entries_keeps_them_provable() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let client = create_contract(&e, &admin);
+    let tier_sym = Symbol::new(&e, "LOT");
+
+    let mut entries = soroban_sdk::Vec::new(&e);
+    for i in 0..3u32 {
+        entries.push_back(allocation::LotteryEntry {
+            participant: Address::generate(&e),
+            entry_time: e.ledger().timestamp(),
+            nonce: i,
+            commitment_hash: None,
+        });
+    }
+
+    // Pruning is only allowed once the draw is sealed
+    assert!(client
+        .try_prune_lottery_entries(&tier_sym, &0, &10)
+        .is_err());
+
+    e.as_contract(&client.address, || {
+        for (i, entry) in entries.iter().enumerate() {
+            e.storage().persistent().set(
+                &DataKey::Lottery(LotteryKey::Entry(tier_sym.clone(), 0, i as u32)),
+                &entry,
+            );
+        }
+        let randomness = soroban_sdk::vec![&e, 7u128];
+        let results = allocation::AllocationEngine::allocate_lottery(&e, &entries, &randomness, 1);
+        archive::ArchiveManager::seal(&e, &tier_sym, 0, &entries, &randomness, &results);
+    });
+
+    assert_eq!(client.prune_lottery_entries(&tier_sym, &0, &2), 2);
+    assert_eq!(client.prune_lottery_entries(&tier_sym, &0, &2), 1);
+    assert_eq!(client.prune_lottery_entries(&tier_sym, &0, &2), 0);
+    e.as_contract(&client.address, || {
+        for i in 0..3u32 {
+            assert!(!e
+                .storage()
+                .persistent()
+                .has(&DataKey::Lottery(LotteryKey::Entry(tier_sym.clone(), 0, i))));
+        }
+    });
+
+    // Entries remain provable against the sealed root
+    let leaf = |i: u32| {
+        e.crypto()
+            .sha256(&entries.get(i).unwrap().to_xdr(&e))
+            .to_bytes()
+    };
+    let mut first_pair = Bytes::from(leaf(0));
+    first_pair.append(&Bytes::from(leaf(1)));
+    let first_pair = e.crypto().sha256(&first_pair).to_bytes();
+
+    assert!(client.verify_lottery_entry(
+        &tier_sym,
+        &0,
+        &entries.get(0).unwrap(),
+        &0,
+        &soroban_sdk::vec![&e, leaf(1), leaf(2)],
+    ));
+    assert!(client.verify_lottery_entry(
+        &tier_sym,
+        &0,
+        &entries.get(2).unwrap(),
+        &2,
+        &soroban_sdk::vec![&e, first_pair],
+    ));
+    assert!(!client.verify_lottery_entry(
+        &tier_sym,
+        &0,
+        &entries.get(1).unwrap(),
+        &0,
+        &soroban_sdk::vec![&e, leaf(1), leaf(2)],
+    ));
+}
+
+// ============================================================================
+// TEST HARNESS
+// ============================================================================
+
+#[test]
+fn test_harness_fixtures() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+
+    let tier_sym = harness.add_tier("GEN", 100, 10);
+    let token = harness.create_payment_token(&harness.admin, 0);
+    let buyer = harness.funded_buyer(&token, 500);
+
+    let token_id = harness.client.purchase(&buyer, &token, &tier_sym);
+    assert_eq!(harness.client.owner_of(&token_id), buyer);
+
+    // Refunds close once the clock passes the cutoff
+    harness.warp_time(100_001);
+    assert!(harness
+        .client
+        .try_refund(&buyer, &token, &token_id)
+        .is_err());
+
+    let lottery_sym = harness.add_tier("LOT", 100, 10);
+    let entries = harness.lottery_entries(4);
+    let proof = harness.seal_lottery(&lottery_sym, &entries, &soroban_sdk::vec![&e, 3u128], 2);
+    assert_eq!(proof.entry_count, 4);
+    assert_eq!(
+        harness.client.export_allocation_proof(&lottery_sym, &0),
+        proof
+    );
+}
+
+// ============================================================================
+// RESOURCE BUDGETS (cargo test --features bench)
+// ============================================================================
+
+#[cfg(feature = "bench")]
+fn assert_within_limits(report: testutils::BudgetReport) {
+    assert!(
+        report.cpu_instructions <= bench::MAX_CPU_INSTRUCTIONS,
+        "{:?}",
+        report
+    );
+    assert!(
+        report.memory_bytes <= bench::MAX_MEMORY_BYTES,
+        "{:?}",
+        report
+    );
+}
+
+#[cfg(feature = "bench")]
+#[test]
+fn test_bench_lottery_allocation_1000_entries() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let tier_sym = harness.add_tier("LOT", 100, 100);
+
+    let (winners, report) = harness.measure(|| {
+        harness
+            .client
+            .bench_lottery_allocation(&tier_sym, &1000, &100)
+    });
+    assert_eq!(winners, 100);
+    assert_eq!(
+        harness
+            .client
+            .export_allocation_proof(&tier_sym, &0)
+            .entry_count,
+        1000
+    );
+    assert_within_limits(report);
+}
+
+#[cfg(feature = "bench")]
+#[test]
+fn test_bench_batch_mint_100_tickets() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let tier_sym = harness.add_tier("GEN", 100, 100);
+    let to = Address::generate(&e);
+
+    let (token_ids, report) = harness.measure(|| harness.client.batch_mint(&to, &tier_sym, &100));
+    assert_eq!(token_ids.len(), 100);
+    assert_within_limits(report);
+}
+
+#[cfg(feature = "bench")]
+#[test]
+fn test_bench_oracle_pricing() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+
+    let oracle_id = e.register(MockAdjustableOracle, ());
+    MockAdjustableOracleClient::new(&e, &oracle_id).set_price(&110_000_000);
+    harness.client.set_pricing_config(&PricingConfig {
+        pricing_mode: PricingMode::Dynamic,
+        oracle_address: oracle_id,
+        dex_pool_address: harness.admin.clone(),
+        price_floor: 0,
+        price_ceiling: i128::MAX,
+        update_frequency: 3600,
+        last_update_time: e.ledger().timestamp(),
+        is_frozen: false,
+        oracle_pair: String::from_str(&e, "XLM/USD"),
+        oracle_reference_price: oracle::DIA_ORACLE_DECIMALS,
+        max_oracle_age_seconds: oracle::DEFAULT_STALENESS_SECONDS,
+    });
+    let tier_sym = harness.add_tier("ORK", 100, 100);
+
+    let (price, report) = harness.measure(|| harness.client.bench_pricing(&tier_sym, &20));
+    assert_eq!(price, 110);
+    assert_within_limits(report);
+}
+
+// ============================================================================
+// SNAPSHOT / RESTORE
+// ============================================================================
+
+#[test]
+fn test_snapshot_restore_into_fresh_env() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let tier_sym = harness.add_tier("GEN", 100, 10);
+    let token = harness.create_payment_token(&harness.admin, 0);
+    let buyer = harness.funded_buyer(&token, 500);
+    let token_id = harness.client.purchase(&buyer, &token, &tier_sym);
+
+    let lottery_sym = harness.add_tier("LOT", 100, 10);
+    let entries = harness.lottery_entries(3);
+    let proof = harness.seal_lottery(&lottery_sym, &entries, &soroban_sdk::vec![&e, 7u128], 1);
+
+    let snapshot = harness.snapshot();
+    let restored = testutils::TicketHarness::restore(&snapshot);
+
+    // Values are compared through plain Rust types since the two Envs don't share objects
+    let client = &restored.client;
+    let ticket = client.get_ticket(&token_id).unwrap();
+    assert_eq!(ticket.price_paid, 100);
+    assert_eq!(ticket.purchase_time, e.ledger().timestamp());
+    assert_eq!(
+        client.get_event_info().start_time,
+        harness.client.get_event_info().start_time
+    );
+    let restored_proof = client.export_allocation_proof(&lottery_sym, &0);
+    assert_eq!(
+        restored_proof.proof_hash.to_array(),
+        proof.proof_hash.to_array()
+    );
+    assert_eq!(restored_proof.entry_count, 3);
+
+    // Restoring twice gives the same state, and the copy keeps working on its own
+    let again = testutils::TicketHarness::restore(&snapshot);
+    let tier_sym = Symbol::new(&again.env, "GEN");
+    assert_eq!(
+        again.client.get_ticket(&token_id).unwrap().price_paid,
+        ticket.price_paid
+    );
+    let next_id = again
+        .client
+        .batch_mint(&again.admin, &tier_sym, &1)
+        .get(0)
+        .unwrap();
+    assert_eq!(next_id, token_id + 1);
+    assert!(!client.ticket_exists(&next_id));
+    assert_eq!(client.get_ticket(&next_id), None);
+}
+
+// ============================================================================
+// PAYMENT TOKEN LOCK
+// ============================================================================
+
+#[test]
+fn test_refund_uses_the_token_the_ticket_was_paid_in() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let tier_sym = harness.add_tier("GEN", 100, 10);
+    let token_a = harness.create_payment_token(&harness.admin, 0);
+    let token_b = harness.create_payment_token(&harness.admin, 0);
+    let buyer = harness.funded_buyer(&token_a, 500);
+
+    // Treasury holds token B too, but the ticket was paid in token A
+    harness.fund(&token_b, &harness.client.address, 1_000);
+    let token_id = harness.client.purchase(&buyer, &token_a, &tier_sym);
+    assert_eq!(
+        harness.client.get_ticket(&token_id).unwrap().payment_token,
+        Some(token_a.clone())
+    );
+
+    assert!(harness
+        .client
+        .try_refund(&buyer, &token_b, &token_id)
+        .is_err());
+
+    harness.client.refund(&buyer, &token_a, &token_id);
+    assert_eq!(
+        soroban_sdk::token::Client::new(&e, &token_a).balance(&buyer),
+        500
+    );
+    assert_eq!(
+        soroban_sdk::token::Client::new(&e, &token_b).balance(&buyer),
+        0
+    );
+
+    // Comped tickets have no payment token and refund nothing
+    let comped = harness
+        .client
+        .batch_mint(&buyer, &tier_sym, &1)
+        .get(0)
+        .unwrap();
+    assert_eq!(
+        harness.client.get_ticket(&comped).unwrap().payment_token,
+        None
+    );
+    harness.client.refund(&buyer, &token_b, &comped);
+    assert_eq!(
+        soroban_sdk::token::Client::new(&e, &token_b).balance(&buyer),
+        0
+    );
+}
+
+// ============================================================================
+// PRICING MODE
+// ============================================================================
+
+#[test]
+fn test_fixed_pricing_mode_skips_oracle() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let oracle_id = e.register(MockAdjustableOracle, ());
+    MockAdjustableOracleClient::new(&e, &oracle_id).set_price(&200_000_000);
+
+    let mut config = PricingConfig {
+        pricing_mode: PricingMode::Dynamic,
+        oracle_address: harness.admin.clone(),
+        dex_pool_address: harness.admin.clone(),
+        price_floor: 0,
+        price_ceiling: i128::MAX,
+        update_frequency: 0,
+        last_update_time: e.ledger().timestamp(),
+        is_frozen: false,
+        oracle_pair: String::from_str(&e, "XLM/USD"),
+        oracle_reference_price: oracle::DIA_ORACLE_DECIMALS,
+        max_oracle_age_seconds: oracle::DEFAULT_STALENESS_SECONDS,
+    };
+
+    // Dynamic mode needs a real oracle
+    assert!(harness.client.try_set_pricing_config(&config).is_err());
+
+    // Fixed mode ignores the (2x) oracle entirely
+    config.pricing_mode = PricingMode::Fixed;
+    config.oracle_address = oracle_id.clone();
+    harness.client.set_pricing_config(&config);
+    let tier_sym = harness.add_tier("GEN", 100, 10);
+    assert_eq!(harness.client.get_ticket_price(&tier_sym), 100);
+    assert_eq!(harness.client.get_oracle_cache(), None);
+    assert!(harness.client.try_force_refresh_oracle().is_err());
+
+    config.pricing_mode = PricingMode::Dynamic;
+    harness.client.set_pricing_config(&config);
+    assert_eq!(harness.client.get_ticket_price(&tier_sym), 200);
+}
+
+// ============================================================================
+// PRICE-LOCK CREDITS
+// ============================================================================
+
+#[test]
+fn test_credit_redeem_within_value_cap() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let general = harness.add_tier("GEN", 100, 10);
+    let vip = harness.add_tier("VIP", 500, 10);
+    let token = harness.create_payment_token(&harness.admin, 0);
+    let buyer = harness.funded_buyer(&token, 1_000);
+
+    let deadline = e.ledger().timestamp() + 1_000;
+    harness.client.set_credit_offer(&Some(credits::CreditOffer {
+        price: 90,
+        value_cap: 150,
+        deadline,
+    }));
+    harness.client.purchase_credit(&buyer, &token);
+    assert!(harness.client.try_purchase_credit(&buyer, &token).is_err());
+    assert_eq!(harness.client.get_refund_liability(&token), 90);
+
+    // VIP is above the cap; general admission is within it
+    assert!(harness.client.try_redeem_credit(&buyer, &vip).is_err());
+    let token_id = harness.client.redeem_credit(&buyer, &general);
+    assert_eq!(harness.client.owner_of(&token_id), buyer);
+    assert_eq!(harness.client.get_ticket(&token_id).unwrap().price_paid, 90);
+    assert_eq!(harness.client.get_credit(&buyer), None);
+
+    // The ticket refunds what was paid for the credit
+    harness.client.refund(&buyer, &token, &token_id);
+    assert_eq!(
+        soroban_sdk::token::Client::new(&e, &token).balance(&buyer),
+        1_000
+    );
+}
+
+#[test]
+fn test_unredeemed_credit_refundable_after_deadline() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let tier_sym = harness.add_tier("GEN", 100, 10);
+    let token = harness.create_payment_token(&harness.admin, 0);
+    let buyer = harness.funded_buyer(&token, 1_000);
+
+    harness.client.set_credit_offer(&Some(credits::CreditOffer {
+        price: 100,
+        value_cap: 100,
+        deadline: e.ledger().timestamp() + 1_000,
+    }));
+    harness.client.purchase_credit(&buyer, &token);
+    assert!(harness.client.try_refund_credit(&buyer).is_err());
+
+    harness.warp_time(1_001);
+    assert!(harness.client.try_redeem_credit(&buyer, &tier_sym).is_err());
+    assert_eq!(harness.client.refund_credit(&buyer), 100);
+    assert_eq!(
+        soroban_sdk::token::Client::new(&e, &token).balance(&buyer),
+        1_000
+    );
+    assert_eq!(harness.client.get_refund_liability(&token), 0);
+}
+
+// ============================================================================
+// PURCHASE HISTORY
+// ============================================================================
+
+#[test]
+fn test_purchase_history_tracks_status() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let tier_sym = harness.add_tier("GEN", 100, 10);
+    let token = harness.create_payment_token(&harness.admin, 0);
+    let buyer = harness.funded_buyer(&token, 1_000);
+
+    let first = harness.client.purchase(&buyer, &token, &tier_sym);
+    let second = harness.client.purchase(&buyer, &token, &tier_sym);
+    let third = harness.client.purchase(&buyer, &token, &tier_sym);
+
+    harness.client.refund(&buyer, &token, &first);
+    harness.warp_to(harness.client.get_event_info().start_time);
+    harness.client.check_in(&second);
+    assert!(harness.client.try_check_in(&second).is_err());
+    assert!(harness.client.try_refund(&buyer, &token, &second).is_err());
+
+    let history = harness.client.get_purchase_history(&buyer, &0, &10);
+    assert_eq!(history.len(), 3);
+    assert_eq!(history.get(0).unwrap().status, TicketStatus::Refunded);
+    assert_eq!(history.get(1).unwrap().status, TicketStatus::CheckedIn);
+    assert_eq!(history.get(2).unwrap().status, TicketStatus::Active);
+    assert_eq!(history.get(2).unwrap().token_id, third);
+    assert_eq!(history.get(2).unwrap().price_paid, 100);
+
+    // Paging
+    let page = harness.client.get_purchase_history(&buyer, &1, &1);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().token_id, second);
+    assert_eq!(
+        harness.client.get_purchase_history(&buyer, &5, &10).len(),
+        0
+    );
+}
+
+// ============================================================================
+// ENTRY WINDOWS
+// ============================================================================
+
+#[test]
+fn test_entry_window_too_early_and_expired() {
+    let e = Env::default();
+    let now = e.ledger().timestamp();
+    let harness = testutils::TicketHarness::with_event_times(&e, now + 1_000, now + 500);
+    let general = harness.add_tier("GEN", 100, 10);
+    let vip = harness.add_tier("VIP", 100, 10);
+    let holder = Address::generate(&e);
+
+    // VIPs get in 300 seconds before doors open, until 2_000 seconds after
+    harness.client.set_tier_entry_window(
+        &vip,
+        &Some(EntryWindow {
+            valid_from: now + 700,
+            valid_until: now + 3_000,
+        }),
+    );
+    let ga_id = harness
+        .client
+        .batch_mint(&holder, &general, &1)
+        .get(0)
+        .unwrap();
+    let vip_id = harness.client.batch_mint(&holder, &vip, &1).get(0).unwrap();
+
+    assert_eq!(
+        harness.client.try_validate_entry(&vip_id),
+        Err(Ok(EntryError::TooEarly))
+    );
+    assert!(!harness.client.validate_ticket(&ga_id));
+
+    harness.warp_to(now + 700);
+    assert!(harness.client.validate_ticket(&vip_id));
+    assert_eq!(
+        harness.client.try_check_in(&ga_id),
+        Err(Ok(EntryError::TooEarly))
+    );
+    harness.client.check_in(&vip_id);
+
+    harness.warp_to(now + 3_001);
+    assert!(harness.client.validate_ticket(&ga_id));
+    assert_eq!(
+        harness.client.try_validate_entry(&vip_id),
+        Err(Ok(EntryError::Expired))
+    );
+}
+
+#[test]
+fn test_gate_supervisor_overrides_entry_window() {
+    let e = Env::default();
+    let now = e.ledger().timestamp();
+    let harness = testutils::TicketHarness::with_event_times(&e, now + 1_000, now + 500);
+    let general = harness.add_tier("GEN", 100, 10);
+    let late = harness.add_tier("LATE", 100, 10);
+    let holder = Address::generate(&e);
+    let supervisor = Address::generate(&e);
+
+    assert_eq!(harness.client.entry_window(&general), (now + 1_000, u64::MAX));
+    harness.client.set_tier_entry_window(
+        &late,
+        &Some(EntryWindow {
+            valid_from: now + 2_000,
+            valid_until: now + 4_000,
+        }),
+    );
+    assert_eq!(harness.client.entry_window(&late), (now + 2_000, now + 4_000));
+    let ticket_ids = harness.client.batch_mint(&holder, &late, &2);
+    let (first, second) = (ticket_ids.get(0).unwrap(), ticket_ids.get(1).unwrap());
+
+    // Windows changed after minting don't move tickets already issued
+    harness.client.set_tier_entry_window(&late, &None);
+    let ticket = harness.client.get_ticket(&first).unwrap();
+    assert_eq!(ticket.entry_window.valid_from, now + 2_000);
+
+    harness.warp_to(now + 1_500);
+    assert_eq!(
+        harness.client.try_check_in(&first),
+        Err(Ok(EntryError::TooEarly))
+    );
+    assert!(harness.client.try_check_in_override(&supervisor, &first).is_err());
+
+    harness.client.set_gate_supervisor(&supervisor, &true);
+    assert!(harness.client.is_gate_supervisor(&supervisor));
+    harness.client.check_in_override(&supervisor, &first);
+    assert_eq!(
+        harness.client.get_ticket(&first).unwrap().status,
+        TicketStatus::CheckedIn
+    );
+    assert_eq!(
+        harness.client.try_check_in_override(&supervisor, &first),
+        Err(Ok(EntryError::AlreadyCheckedIn))
+    );
+
+    harness.warp_to(now + 5_000);
+    harness.client.check_in_override(&supervisor, &second);
+}
+
+#[test]
+fn test_scan_in_tracks_gate_and_scanner_throughput() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let general = harness.add_tier("GEN", 100, 10);
+    let holder = Address::generate(&e);
+    let scanner = Address::generate(&e);
+    let other_scanner = Address::generate(&e);
+    let north = Symbol::new(&e, "north");
+    let south = Symbol::new(&e, "south");
+    let tickets = harness.client.batch_mint(&holder, &general, &3);
+    let first = tickets.get(0).unwrap();
+    let second = tickets.get(1).unwrap();
+    let third = tickets.get(2).unwrap();
+    harness.client.set_scanner(&scanner, &true);
+    harness.client.set_scanner(&other_scanner, &true);
+    assert_eq!(harness.client.get_config().roles.counts.scanners, 2);
+
+    harness.warp_to(harness.client.get_event_info().start_time);
+    assert!(harness.client.try_scan_in(&holder, &north, &first).is_err());
+    let opened = e.ledger().timestamp();
+    harness.client.scan_in(&scanner, &north, &first);
+    harness.warp_time(gate_stats::GATE_BUCKET_SECONDS);
+    harness.client.scan_in(&scanner, &north, &second);
+    harness.client.scan_in(&other_scanner, &south, &third);
+
+    // A rejected scan is not counted
+    assert_eq!(
+        harness.client.try_scan_in(&scanner, &north, &first),
+        Err(Ok(EntryError::AlreadyCheckedIn))
+    );
+
+    let stats = harness.client.get_gate_stats(&north);
+    assert_eq!(stats.check_ins, 2);
+    assert_eq!(stats.first_scan, opened);
+    assert_eq!(stats.last_scan, e.ledger().timestamp());
+    assert_eq!(stats.recent.len() as u64, gate_stats::RECENT_GATE_BUCKETS);
+    let last = stats.recent.len() - 1;
+    assert_eq!(stats.recent.get(last - 1), Some(1));
+    assert_eq!(stats.recent.get(last), Some(1));
+    assert_eq!(harness.client.get_gate_stats(&south).check_ins, 1);
+
+    let scanned = harness.client.get_scanner_stats(&scanner).unwrap();
+    assert_eq!(scanned.check_ins, 2);
+    assert_eq!(scanned.last_gate, north);
+    assert_eq!(harness.client.get_scanner_stats(&holder), None);
+}
+
+#[test]
+fn test_guest_slots_redeemed_at_check_in() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let vip = harness.add_tier("VIP", 500, 10);
+    let holder = Address::generate(&e);
+
+    harness.client.set_tier_guest_slots(&vip, &2);
+    let token_id = harness.client.batch_mint(&holder, &vip, &1).get(0).unwrap();
+    assert_eq!(harness.client.get_ticket(&token_id).unwrap().guest_slots, 2);
+    assert!(harness.client.try_set_tier_guest_slots(&vip, &11).is_err());
+
+    harness.warp_to(harness.client.get_event_info().start_time);
+    assert_eq!(
+        harness.client.try_check_in_guest(&token_id),
+        Err(Ok(EntryError::HolderNotCheckedIn))
+    );
+
+    harness.client.check_in(&token_id);
+    assert_eq!(harness.client.check_in_guest(&token_id), 1);
+    assert_eq!(harness.client.check_in_guest(&token_id), 0);
+    assert_eq!(
+        harness.client.try_check_in_guest(&token_id),
+        Err(Ok(EntryError::NoGuestSlots))
+    );
+    assert_eq!(harness.client.get_ticket(&token_id).unwrap().guest_slots, 0);
+}
+
+// ============================================================================
+// PURCHASE HOOKS
+// ============================================================================
+
+#[test]
+fn test_purchase_hook_modes() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let tier_sym = harness.add_tier("GEN", 100, 10);
+    let token = harness.create_payment_token(&harness.admin, 0);
+    let buyer = harness.funded_buyer(&token, 1_000);
+
+    let hook_id = e.register(MockPurchaseHook, ());
+    let hook = MockPurchaseHookClient::new(&e, &hook_id);
+    harness.client.set_purchase_hook(&Some(hooks::HookConfig {
+        address: hook_id.clone(),
+        mode: hooks::HookMode::Required,
+    }));
+
+    harness.client.purchase(&buyer, &token, &tier_sym);
+    assert_eq!(hook.calls(), 1);
+
+    // A failing required hook blocks the sale
+    hook.set_failing(&true);
+    assert!(harness
+        .client
+        .try_purchase(&buyer, &token, &tier_sym)
+        .is_err());
+    assert_eq!(harness.client.balance(&buyer), 1);
+
+    // Best-effort: the sale goes through and the hook's writes are rolled back
+    harness.client.set_purchase_hook(&Some(hooks::HookConfig {
+        address: hook_id,
+        mode: hooks::HookMode::BestEffort,
+    }));
+    harness.client.purchase(&buyer, &token, &tier_sym);
+    assert_eq!(harness.client.balance(&buyer), 2);
+    assert_eq!(hook.calls(), 1);
+}
+
+// ============================================================================
+// CHARGE PLANS
+// ============================================================================
+
+#[test]
+fn test_charge_plan_uses_allowance_and_can_be_revoked() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let tier_sym = harness.add_tier("SEASON", 100, 10);
+    let token = harness.create_payment_token(&harness.admin, 0);
+    let buyer = harness.funded_buyer(&token, 1_000);
+    let token_client = soroban_sdk::token::Client::new(&e, &token);
+
+    let plan_id = harness
+        .client
+        .create_charge_plan(&buyer, &token, &tier_sym, &100, &3);
+
+    // No allowance yet
+    assert!(harness.client.try_charge_plan(&plan_id).is_err());
+
+    token_client.approve(&buyer, &harness.client.address, &250, &1_000);
+    let token_id = harness.client.charge_plan(&plan_id);
+    assert_eq!(harness.client.owner_of(&token_id), buyer);
+    harness.client.charge_plan(&plan_id);
+    assert_eq!(token_client.balance(&buyer), 800);
+    assert_eq!(token_client.allowance(&buyer, &harness.client.address), 50);
+    assert_eq!(
+        harness
+            .client
+            .get_charge_plan(&plan_id)
+            .unwrap()
+            .charges_made,
+        2
+    );
+
+    token_client.approve(&buyer, &harness.client.address, &500, &1_000);
+    harness.client.revoke_charge_plan(&buyer, &plan_id);
+    assert!(harness.client.try_charge_plan(&plan_id).is_err());
+    assert_eq!(token_client.balance(&buyer), 800);
+}
+
+#[test]
+fn test_charge_plan_price_cap() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let tier_sym = harness.add_tier("SEASON", 150, 10);
+    let token = harness.create_payment_token(&harness.admin, 0);
+    let buyer = harness.funded_buyer(&token, 1_000);
+    soroban_sdk::token::Client::new(&e, &token).approve(
+        &buyer,
+        &harness.client.address,
+        &1_000,
+        &1_000,
+    );
+
+    let plan_id = harness
+        .client
+        .create_charge_plan(&buyer, &token, &tier_sym, &100, &3);
+    assert!(harness.client.try_charge_plan(&plan_id).is_err());
+    assert_eq!(
+        harness
+            .client
+            .get_charge_plan(&plan_id)
+            .unwrap()
+            .charges_made,
+        0
+    );
+}
+
+// ============================================================================
+// PRICE ROUNDING
+// ============================================================================
+
+#[test]
+fn test_rounding_rule_applied_per_payment_token() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let tier_sym = harness.add_tier("GEN", 1_234, 10);
+    let rounded = harness.create_payment_token(&harness.admin, 0);
+    let exact = harness.create_payment_token(&harness.admin, 0);
+    let buyer = harness.funded_buyer(&rounded, 10_000);
+
+    harness
+        .client
+        .set_rounding_rule(&rounded, &Some(RoundingRule { increment: 100 }));
+    assert_eq!(harness.client.get_ticket_price(&tier_sym), 1_234);
+    assert_eq!(
+        harness.client.get_ticket_price_in(&tier_sym, &rounded),
+        1_300
+    );
+    assert_eq!(harness.client.get_ticket_price_in(&tier_sym, &exact), 1_234);
+
+    let token_id = harness.client.purchase(&buyer, &rounded, &tier_sym);
+    assert_eq!(
+        harness.client.get_ticket(&token_id).unwrap().price_paid,
+        1_300
+    );
+    let receipt = harness.client.get_receipt(&0).unwrap();
+    assert_eq!(receipt.base_price, 1_234);
+    assert_eq!(receipt.rounding, 66);
+    assert_eq!(receipt.total, 1_300);
+}
+
+// ============================================================================
+// PRICING OVERFLOW
+// ============================================================================
+
+fn price_overflow() -> soroban_sdk::Error {
+    soroban_sdk::Error::from(PricingError::PriceOverflow)
+}
+
+#[test]
+fn test_mul_bps_is_exact_near_i128_max() {
+    assert_eq!(mul_bps(i128::MAX, 10_000), Some(i128::MAX));
+    assert_eq!(mul_bps(i128::MAX, 5_000), Some(i128::MAX / 2));
+    assert_eq!(mul_bps(12_345, 500), Some(617));
+    assert_eq!(mul_bps(i128::MAX, 10_001), None);
+    assert_eq!(mul_bps(i128::MAX / 2, i128::MAX), None);
+}
+
+#[test]
+fn test_demand_curve_for_tiny_supplies() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let holder = Address::generate(&e);
+
+    // Price of the last ticket of a supply-1..4 tier. Each tier climbs at most
+    // four fifths of the way up the curve, like any larger tier would.
+    for (symbol, max_supply, last_price) in [
+        ("S1", 1, 100),
+        ("S2", 2, 110),
+        ("S3", 3, 115),
+        ("S4", 4, 115),
+    ] {
+        let tier_sym = harness.add_tier(symbol, 100, max_supply);
+        assert_eq!(harness.client.get_ticket_price(&tier_sym), 100);
+        if max_supply > 1 {
+            harness
+                .client
+                .batch_mint(&holder, &tier_sym, &(max_supply - 1));
+        }
+        assert_eq!(harness.client.get_ticket_price(&tier_sym), last_price);
+    }
+}
+
+#[test]
+fn test_base_price_near_i128_max() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let holder = Address::generate(&e);
+    let buyer = Address::generate(&e);
+
+    // No markup: the full range is priceable
+    let flat = harness.add_tier("FLAT", i128::MAX, 10);
+    assert_eq!(harness.client.get_ticket_price(&flat), i128::MAX);
+
+    // +20% does not fit
+    let ab_b = harness.add_tier_with_strategy("ABB", i128::MAX, 10, PricingStrategy::AbTestB);
+    assert_eq!(
+        harness.client.try_get_ticket_price(&ab_b),
+        Err(Ok(price_overflow()))
+    );
+
+    // The first demand threshold pushes the price past i128::MAX
+    harness.client.batch_mint(&holder, &flat, &2);
+    assert_eq!(
+        harness.client.try_get_ticket_price(&flat),
+        Err(Ok(price_overflow()))
+    );
+    assert_eq!(
+        harness.client.simulate_purchase(&buyer, &flat, &1).error,
+        Some(PurchaseError::PriceOverflow)
+    );
+
+    // Each unit fits but the preview total does not
+    let half = harness.add_tier("HALF", i128::MAX / 2 + 1, 10);
+    let preview = harness.client.simulate_purchase(&buyer, &half, &2);
+    assert_eq!(preview.unit_price, i128::MAX / 2 + 1);
+    assert_eq!(preview.error, Some(PurchaseError::PriceOverflow));
+}
+
+#[test]
+fn test_rounding_past_i128_max_is_rejected() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let tier_sym = harness.add_tier("GEN", i128::MAX - 5, 10);
+    let token = harness.create_payment_token(&harness.admin, 0);
+
+    harness
+        .client
+        .set_rounding_rule(&token, &Some(RoundingRule { increment: 10 }));
+    assert_eq!(
+        harness.client.try_get_ticket_price_in(&tier_sym, &token),
+        Err(Ok(price_overflow()))
+    );
+}
+
+#[test]
+fn test_huge_oracle_multiplier_overflows() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let client = create_contract(&e, &admin);
+    let oracle_id = e.register(MockOracle, ());
+    let dex_id = e.register(MockDex, ());
+
+    // A 1e-8 reference turns MockOracle's $1.10 into a 110_000_000x multiplier
+    client.set_pricing_config(&PricingConfig {
+        pricing_mode: PricingMode::Dynamic,
+        oracle_address: oracle_id,
+        dex_pool_address: dex_id,
+        price_floor: 0,
+        price_ceiling: i128::MAX,
+        update_frequency: 0,
+        last_update_time: e.ledger().timestamp(),
+        is_frozen: false,
+        oracle_pair: String::from_str(&e, "XLM/USD"),
+        oracle_reference_price: 1,
+        max_oracle_age_seconds: oracle::DEFAULT_STALENESS_SECONDS,
+    });
+
+    let small = Symbol::new(&e, "SMALL");
+    let large = Symbol::new(&e, "LARGE");
+    for (tier_sym, base_price) in [(&small, 1_000), (&large, i128::MAX / 1_000)] {
+        client.add_tier(
+            tier_sym,
+            &String::from_str(&e, "Tier"),
+            &base_price,
+            &100,
+            &PricingStrategy::Standard,
+        );
+    }
+
+    assert_eq!(client.get_ticket_price(&small), 110_000_000_000);
+    assert_eq!(
+        client.try_get_ticket_price(&large),
+        Err(Ok(price_overflow()))
+    );
+}
+
+// ============================================================================
+// SALE LIMITS
+// ============================================================================
+
+#[test]
+fn test_sale_limits_per_transaction_and_ledger() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let tier_sym = harness.add_tier("DROP", 100, 10);
+    let token = harness.create_payment_token(&harness.admin, 0);
+    let whale = harness.funded_buyer(&token, 10_000);
+    let fan = harness.funded_buyer(&token, 10_000);
+    let late = harness.funded_buyer(&token, 10_000);
+
+    harness.client.set_sale_limits(
+        &tier_sym,
+        &Some(SaleLimits {
+            max_per_tx: 2,
+            max_per_ledger: 3,
+        }),
+    );
+
+    harness.client.purchase(&whale, &token, &tier_sym);
+    harness.client.purchase(&whale, &token, &tier_sym);
+    assert!(harness.client.try_purchase(&whale, &token, &tier_sym).is_err());
+    assert_eq!(
+        harness.client.simulate_purchase(&whale, &tier_sym, &1).error,
+        Some(PurchaseError::TxLimitExceeded)
+    );
+
+    harness.client.purchase(&fan, &token, &tier_sym);
+    assert!(harness.client.try_purchase(&late, &token, &tier_sym).is_err());
+    assert_eq!(
+        harness.client.simulate_purchase(&late, &tier_sym, &1).error,
+        Some(PurchaseError::LedgerLimitExceeded)
+    );
+
+    // Counters start over with the next ledger
+    harness.advance_ledgers(1);
+    assert_eq!(
+        harness.client.simulate_purchase(&whale, &tier_sym, &3).error,
+        Some(PurchaseError::TxLimitExceeded)
+    );
+    harness.client.purchase(&late, &token, &tier_sym);
+    harness.client.purchase(&whale, &token, &tier_sym);
+    assert_eq!(harness.client.balance(&whale), 3);
+
+    harness.client.set_sale_limits(&tier_sym, &None);
+    harness.client.purchase(&whale, &token, &tier_sym);
+    harness.client.purchase(&whale, &token, &tier_sym);
+    assert_eq!(harness.client.balance(&whale), 5);
+}
+
+// ============================================================================
+// LOTTERY SUBSCRIPTIONS
+// ============================================================================
+
+#[test]
+fn test_lottery_subscribers_notified_in_isolation() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let tier_sym = harness.add_tier("LOT", 100, 10);
+    harness
+        .client
+        .set_distribution_mode(&tier_sym, &storage_types::DistributionMode::LotteryOnly);
+    harness.client.initialize_lottery(
+        &tier_sym,
+        &AllocationStrategyType::FCFS,
+        &3,
+        &(e.ledger().sequence() + 10),
+        &1,
+        &5,
+    );
+    harness.store_lottery_entries(&tier_sym, &harness.lottery_entries(5));
+
+    let guild_id = e.register(MockLotterySubscriber, ());
+    let broken_id = e.register(MockLotterySubscriber, ());
+    let guild = MockLotterySubscriberClient::new(&e, &guild_id);
+    let broken = MockLotterySubscriberClient::new(&e, &broken_id);
+    broken.set_failing(&true);
+
+    harness.client.add_lottery_subscriber(&tier_sym, &broken_id);
+    harness.client.add_lottery_subscriber(&tier_sym, &guild_id);
+    assert!(harness
+        .client
+        .try_add_lottery_subscriber(&tier_sym, &guild_id)
+        .is_err());
+
+    harness.advance_ledgers(10);
+    harness.draw_randomness(&tier_sym, 3);
+    harness.client.execute_lottery_allocation(&tier_sym);
+
+    // The failing subscriber's write is rolled back; the draw and the guild are unaffected
+    assert_eq!(guild.winners(), Some(3));
+    assert_eq!(broken.winners(), None);
+    assert_eq!(harness.client.get_lottery_winners(&tier_sym).len(), 3);
+
+    harness.client.remove_lottery_subscriber(&tier_sym, &broken_id);
+    assert_eq!(
+        harness.client.get_lottery_subscribers(&tier_sym),
+        soroban_sdk::vec![&e, guild_id]
+    );
+}
+
+#[test]
+fn test_randomness_batch_stored_for_verification() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let tier_sym = harness.add_tier("LOT", 100, 10);
+    harness
+        .client
+        .set_distribution_mode(&tier_sym, &storage_types::DistributionMode::LotteryOnly);
+    harness.client.initialize_lottery(
+        &tier_sym,
+        &AllocationStrategyType::FCFS,
+        &3,
+        &(e.ledger().sequence() + 10),
+        &1,
+        &5,
+    );
+    let client = &harness.client;
+    let batch = |start: u32| client.get_randomness_batch(&tier_sym, &0, &0, &start, &10);
+    assert!(batch(0).is_empty());
+
+    harness.advance_ledgers(10);
+    let outputs = harness.draw_randomness(&tier_sym, 5);
+    assert_eq!(harness.client.get_randomness_draw_count(&tier_sym, &0), 1);
+    assert_eq!(batch(0), outputs);
+    assert_eq!(batch(3), outputs.slice(3..5));
+    assert!(batch(7).is_empty());
+
+    // Third parties can check the stored outputs against the recorded hash
+    let state = harness.client.get_vrf_state(&tier_sym, &0).unwrap();
+    assert_eq!(state.randomness_hash, vrf::VRFEngine::hash_randomness_batch(&e, &outputs));
+}
+
+#[test]
+fn test_lottery_rounds_keep_earlier_draws() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let tier_sym = harness.add_tier("LOT", 100, 10);
+    harness
+        .client
+        .set_distribution_mode(&tier_sym, &storage_types::DistributionMode::LotteryOnly);
+    harness.client.set_claim_window(&tier_sym, &100);
+
+    let mut waves = soroban_sdk::Vec::new(&e);
+    for round in 0..2u32 {
+        assert_eq!(harness.client.get_lottery_round(&tier_sym), round);
+        harness.client.initialize_lottery(
+            &tier_sym,
+            &AllocationStrategyType::FCFS,
+            &2,
+            &(e.ledger().sequence() + 10),
+            &1,
+            &5,
+        );
+        let entries = harness.lottery_entries(3);
+        harness.store_lottery_entries(&tier_sym, &entries);
+        assert!(harness.client.try_open_new_round(&tier_sym).is_err());
+
+        harness.advance_ledgers(10);
+        harness.draw_randomness(&tier_sym, 2);
+        harness.client.execute_lottery_allocation(&tier_sym);
+        assert!(harness
+            .client
+            .try_execute_lottery_allocation(&tier_sym)
+            .is_err());
+        if round == 0 {
+            // Winners keep the round open until they claim or their window closes
+            let winner = entries.get(0).unwrap().participant;
+            let token = harness.create_payment_token(&winner, 1_000);
+            harness
+                .client
+                .claim_lottery_ticket(&winner, &token, &tier_sym);
+            assert!(harness.client.try_open_new_round(&tier_sym).is_err());
+            harness.warp_time(101);
+            assert_eq!(harness.client.open_new_round(&tier_sym), 1);
+        }
+        waves.push_back(entries);
+    }
+
+    // Both waves keep their own entries, randomness, winners and proofs
+    for round in 0..2u32 {
+        let winners = harness.client.get_round_winners(&tier_sym, &round);
+        let entries = waves.get(round).unwrap();
+        assert_eq!(winners.len(), 2);
+        assert_eq!(
+            winners.get(0).unwrap().winner,
+            entries.get(0).unwrap().participant
+        );
+        assert!(harness.client.get_vrf_state(&tier_sym, &round).is_some());
+        let proof = harness.client.export_allocation_proof(&tier_sym, &round);
+        assert_eq!(proof.round, round);
+        assert_eq!(proof.entry_count, 3);
+    }
+    let first = harness.client.export_allocation_proof(&tier_sym, &0);
+    let second = harness.client.export_allocation_proof(&tier_sym, &1);
+    assert_eq!(second.previous_hash, first.proof_hash);
+    assert_eq!(
+        harness.client.get_lottery_winners(&tier_sym),
+        harness.client.get_round_winners(&tier_sym, &1)
+    );
+}
+
+#[test]
+fn test_unclaimed_winners_expire_and_are_backfilled() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let tier_sym = harness.add_tier("LOT", 100, 10);
+    harness
+        .client
+        .set_distribution_mode(&tier_sym, &storage_types::DistributionMode::LotteryOnly);
+    harness.client.set_claim_window(&tier_sym, &100);
+    harness.client.initialize_lottery(
+        &tier_sym,
+        &AllocationStrategyType::FCFS,
+        &2,
+        &(e.ledger().sequence() + 10),
+        &1,
+        &5,
+    );
+    let entries = harness.lottery_entries(4);
+    harness.store_lottery_entries(&tier_sym, &entries);
+    harness.advance_ledgers(10);
+    let main_draw = harness.draw_randomness(&tier_sym, 2);
+    harness.client.execute_lottery_allocation(&tier_sym);
+    let deadline = e.ledger().timestamp() + 100;
+    assert_eq!(
+        harness.client.get_claim_deadline(&tier_sym, &0, &1),
+        Some(deadline)
+    );
+
+    let claimer = entries.get(0).unwrap().participant;
+    let late = entries.get(1).unwrap().participant;
+    let token = harness.create_payment_token(&claimer, 1_000);
+    harness.fund(&token, &late, 1_000);
+    harness
+        .client
+        .claim_lottery_ticket(&claimer, &token, &tier_sym);
+    assert_eq!(harness.client.expire_unclaimed(&tier_sym), 0);
+    assert!(harness.client.try_backfill_draw(&tier_sym).is_err());
+
+    // Past the deadline the unclaimed result can no longer be claimed and is freed
+    harness.warp_time(101);
+    assert!(harness
+        .client
+        .try_claim_lottery_ticket(&late, &token, &tier_sym)
+        .is_err());
+    assert_eq!(harness.client.expire_unclaimed(&tier_sym), 1);
+    assert_eq!(harness.client.expire_unclaimed(&tier_sym), 0);
+    assert!(!harness.client.is_allocation_forfeited(&tier_sym, &0, &0));
+    assert!(harness.client.is_allocation_forfeited(&tier_sym, &0, &1));
+
+    // The backfill draws from entrants who never won, with fresh randomness
+    let target = harness.client.request_lottery_finalization(&tier_sym);
+    harness.advance_ledgers(target - e.ledger().sequence());
+    let backfilled = harness.client.backfill_draw(&tier_sym);
+    assert_eq!(backfilled.len(), 1);
+    let result = backfilled.get(0).unwrap();
+    assert_eq!(result.allocation_index, 2);
+    assert!(result.winner != claimer && result.winner != late);
+    assert_eq!(harness.client.get_round_winners(&tier_sym, &0).len(), 3);
+    assert_eq!(
+        harness.client.get_claim_deadline(&tier_sym, &0, &2),
+        Some(e.ledger().timestamp() + 100)
+    );
+    assert!(harness
+        .client
+        .try_request_lottery_finalization(&tier_sym)
+        .is_err());
+
+    // Both draws stay archived under the round
+    assert_eq!(harness.client.get_randomness_draw_count(&tier_sym, &0), 2);
+    let client = &harness.client;
+    assert_eq!(
+        client.get_randomness_batch(&tier_sym, &0, &0, &0, &10),
+        main_draw
+    );
+    assert_eq!(
+        client
+            .get_randomness_batch(&tier_sym, &0, &1, &0, &10)
+            .len(),
+        1
+    );
+
+    // Its transcript chains onto the main draw's
+    let proofs = harness.client.get_backfill_proofs(&tier_sym, &0);
+    assert_eq!(proofs.len(), 1);
+    let main = harness.client.export_allocation_proof(&tier_sym, &0);
+    assert_eq!(proofs.get(0).unwrap().previous_hash, main.proof_hash);
+    assert_eq!(proofs.get(0).unwrap().entry_count, 2);
+
+    harness.fund(&token, &result.winner, 1_000);
+    harness
+        .client
+        .claim_lottery_ticket(&result.winner, &token, &tier_sym);
+}
+
+#[test]
+fn test_did_win_reports_allocation_and_claims() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let tier_sym = harness.add_tier("LOT", 100, 10);
+    harness
+        .client
+        .set_distribution_mode(&tier_sym, &storage_types::DistributionMode::LotteryOnly);
+    harness.client.initialize_lottery(
+        &tier_sym,
+        &AllocationStrategyType::FCFS,
+        &2,
+        &(e.ledger().sequence() + 10),
+        &1,
+        &5,
+    );
+    let entries = harness.lottery_entries(3);
+    harness.store_lottery_entries(&tier_sym, &entries);
+    let winner = entries.get(1).unwrap().participant;
+    let loser = entries.get(2).unwrap().participant;
+    assert_eq!(harness.client.did_win(&tier_sym, &winner), None);
+
+    harness.advance_ledgers(10);
+    harness.draw_randomness(&tier_sym, 2);
+    harness.client.execute_lottery_allocation(&tier_sym);
+    assert_eq!(harness.client.did_win(&tier_sym, &loser), None);
+    let status = harness.client.did_win(&tier_sym, &winner).unwrap();
+    assert_eq!(status.round, 0);
+    assert_eq!(status.allocation_index, 1);
+    assert_eq!(status.claimed, 0);
+    assert!(!status.forfeited);
+
+    let token = harness.create_payment_token(&winner, 1_000);
+    let token_id = harness
+        .client
+        .claim_lottery_ticket(&winner, &token, &tier_sym);
+    let status = harness.client.did_win(&tier_sym, &winner).unwrap();
+    assert_eq!(status.claimed, 1);
+    assert_eq!(
+        harness.client.get_ticket(&token_id).unwrap().channel,
+        storage_types::PurchaseChannel::Lottery
+    );
+}
+
+#[test]
+fn test_lottery_subscriber_list_is_bounded() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let tier_sym = harness.add_tier("LOT", 100, 10);
+
+    for _ in 0..subscriptions::MAX_SUBSCRIBERS {
+        harness
+            .client
+            .add_lottery_subscriber(&tier_sym, &Address::generate(&e));
+    }
+    assert!(harness
+        .client
+        .try_add_lottery_subscriber(&tier_sym, &Address::generate(&e))
+        .is_err());
+}
+
+// ============================================================================
+// SNAPSHOT ELIGIBILITY
+// ============================================================================
+
+#[test]
+fn test_holder_snapshot_gates_purchases() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let tier_sym = harness.add_tier("GUILD", 100, 10);
+    let token = harness.create_payment_token(&harness.admin, 0);
+    let holder = harness.funded_buyer(&token, 1_000);
+    let small_holder = harness.funded_buyer(&token, 1_000);
+    let other_holder = harness.funded_buyer(&token, 1_000);
+    let guild_nft = Address::generate(&e);
+
+    let holdings = [
+        SnapshotHolding {
+            holder: holder.clone(),
+            balance: 5,
+        },
+        SnapshotHolding {
+            holder: small_holder.clone(),
+            balance: 1,
+        },
+        SnapshotHolding {
+            holder: other_holder.clone(),
+            balance: 10,
+        },
+    ];
+    let mut leaves = soroban_sdk::Vec::new(&e);
+    for holding in holdings.iter() {
+        leaves.push_back(eligibility::EligibilityManager::holding_leaf(&e, holding));
+    }
+    let snapshot = HolderSnapshot {
+        asset: guild_nft,
+        snapshot_ledger: e.ledger().sequence(),
+        min_balance: 2,
+        root: archive::ArchiveManager::merkle_root(&e, &leaves),
+        holder_count: 3,
+    };
+    harness
+        .client
+        .set_holder_snapshot(&tier_sym, &Some(snapshot.clone()));
+
+    assert!(harness.client.try_purchase(&holder, &token, &tier_sym).is_err());
+    assert_eq!(
+        harness.client.simulate_purchase(&holder, &tier_sym, &1).error,
+        Some(PurchaseError::NotEligible)
+    );
+
+    let proof = soroban_sdk::vec![&e, leaves.get(1).unwrap(), leaves.get(2).unwrap()];
+    harness
+        .client
+        .prove_holder_eligibility(&tier_sym, &holdings[0], &0, &proof);
+    assert!(harness.client.is_snapshot_eligible(&tier_sym, &holder));
+    harness.client.purchase(&holder, &token, &tier_sym);
+
+    // The same gate applies to lottery entries
+    let enter = |who: &Address| {
+        harness
+            .client
+            .try_register_lottery_entry(who, &tier_sym, &None)
+    };
+    assert_eq!(enter(&holder), Ok(Ok(())));
+    assert!(enter(&small_holder).is_err());
+    assert_eq!(harness.client.get_lottery_stats(&tier_sym).entry_count, 1);
+
+    // Below the threshold, or proven at the wrong position
+    let mut first_pair = Bytes::from(leaves.get(0).unwrap());
+    first_pair.append(&Bytes::from(leaves.get(1).unwrap()));
+    let first_pair = e.crypto().sha256(&first_pair).to_bytes();
+    let proof = soroban_sdk::vec![&e, leaves.get(0).unwrap(), leaves.get(2).unwrap()];
+    assert!(harness
+        .client
+        .try_prove_holder_eligibility(&tier_sym, &holdings[1], &1, &proof)
+        .is_err());
+    assert!(harness
+        .client
+        .try_prove_holder_eligibility(
+            &tier_sym,
+            &holdings[2],
+            &1,
+            &soroban_sdk::vec![&e, first_pair.clone()]
+        )
+        .is_err());
+    harness.client.prove_holder_eligibility(
+        &tier_sym,
+        &holdings[2],
+        &2,
+        &soroban_sdk::vec![&e, first_pair],
+    );
+    assert!(harness.client.is_snapshot_eligible(&tier_sym, &other_holder));
+
+    // A new snapshot voids earlier proofs
+    harness.client.set_holder_snapshot(
+        &tier_sym,
+        &Some(HolderSnapshot {
+            root: BytesN::from_array(&e, &[1u8; 32]),
+            ..snapshot
+        }),
+    );
+    assert!(!harness.client.is_snapshot_eligible(&tier_sym, &holder));
+    assert!(harness.client.try_purchase(&holder, &token, &tier_sym).is_err());
+
+    harness.client.set_holder_snapshot(&tier_sym, &None);
+    harness.client.purchase(&holder, &token, &tier_sym);
+}
+
+// ============================================================================
+// ANNOUNCEMENTS
+// ============================================================================
+
+#[test]
+fn test_announcements_keep_the_most_recent() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let uri = String::from_str(&e, "ipfs://notice");
+
+    assert!(harness.client.get_announcements().is_empty());
+    let total = announcements::MAX_ANNOUNCEMENTS + 3;
+    for i in 0..total {
+        let title_hash = BytesN::from_array(&e, &[i as u8; 32]);
+        assert_eq!(harness.client.post_announcement(&title_hash, &uri), i);
+    }
+
+    let kept = harness.client.get_announcements();
+    assert_eq!(kept.len(), announcements::MAX_ANNOUNCEMENTS);
+    assert_eq!(kept.first().unwrap().id, 3);
+    assert_eq!(kept.last().unwrap().id, total - 1);
+    assert_eq!(
+        kept.last().unwrap().title_hash,
+        BytesN::from_array(&e, &[(total - 1) as u8; 32])
+    );
+}
+
+// ============================================================================
+// CONTACT OPT-IN
+// ============================================================================
+
+#[test]
+fn test_contact_opt_in_and_revocation() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let tier_sym = harness.add_tier("GEN", 100, 10);
+    let token = harness.create_payment_token(&harness.admin, 0);
+    let buyer = harness.funded_buyer(&token, 1_000);
+    let first = harness.client.purchase(&buyer, &token, &tier_sym);
+    let second = harness.client.purchase(&buyer, &token, &tier_sym);
+    let contact_hash = BytesN::from_array(&e, &[7u8; 32]);
+
+    harness.client.register_contact(&first, &contact_hash);
+    harness.client.register_contact(&second, &contact_hash);
+    harness
+        .client
+        .register_contact(&second, &BytesN::from_array(&e, &[8u8; 32]));
+    assert_eq!(harness.client.get_contact(&first), Some(contact_hash));
+    assert_eq!(harness.client.get_contact_count(), 2);
+
+    harness.client.revoke_contact(&first);
+    assert_eq!(harness.client.get_contact(&first), None);
+    assert_eq!(harness.client.get_contact_count(), 1);
+
+    // Refunding the ticket drops its opt-in
+    harness.client.refund(&buyer, &token, &second);
+    assert_eq!(harness.client.get_contact(&second), None);
+    assert_eq!(harness.client.get_contact_count(), 0);
+}
+
+// ============================================================================
+// ACCESS GATING
+// ============================================================================
+
+#[test]
+fn test_has_access_follows_tier_mappings() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let general = harness.add_tier("GEN", 100, 10);
+    let vip = harness.add_tier("VIP", 500, 10);
+    let token = harness.create_payment_token(&harness.admin, 0);
+    let holder = harness.funded_buyer(&token, 1_000);
+    let stranger = Address::generate(&e);
+    let chat = Symbol::new(&e, "chat");
+    let merch = Symbol::new(&e, "merch");
+
+    let token_id = harness.client.purchase(&holder, &token, &general);
+    assert!(!harness.client.has_access(&holder, &chat));
+
+    harness
+        .client
+        .set_access_tiers(&chat, &soroban_sdk::vec![&e, general.clone(), vip.clone()]);
+    harness
+        .client
+        .set_access_tiers(&merch, &soroban_sdk::vec![&e, vip]);
+    assert!(harness.client.has_access(&holder, &chat));
+    assert!(!harness.client.has_access(&holder, &merch));
+    assert!(!harness.client.has_access(&stranger, &chat));
+    assert!(harness
+        .client
+        .try_set_access_tiers(&chat, &soroban_sdk::vec![&e, Symbol::new(&e, "NONE")])
+        .is_err());
+
+    // Refunded tickets no longer grant access
+    harness.client.refund(&holder, &token, &token_id);
+    assert!(!harness.client.has_access(&holder, &chat));
+}
+
+// ============================================================================
+// BULK TIER IMPORT
+// ============================================================================
+
+fn tier_input(e: &Env, symbol: &str, max_supply: u32) -> TierInput {
+    TierInput {
+        symbol: Symbol::new(e, symbol),
+        name: String::from_str(e, symbol),
+        base_price: 100,
+        max_supply,
+        strategy: PricingStrategy::Standard,
+    }
+}
+
+#[test]
+fn test_add_tiers_is_all_or_nothing() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    harness.add_tier("GEN", 100, 40);
+    harness.client.set_venue_capacity(&100);
+
+    harness.client.add_tiers(&soroban_sdk::vec![
+        &e,
+        tier_input(&e, "DAY1", 20),
+        tier_input(&e, "DAY2", 20),
+    ]);
+    assert_eq!(harness.client.get_total_tier_supply(), 80);
+    assert_eq!(harness.client.get_ticket_price(&Symbol::new(&e, "DAY2")), 100);
+
+    // Over capacity, duplicated or zero-supply: nothing from the batch is created
+    for batch in [
+        soroban_sdk::vec![&e, tier_input(&e, "VIP", 10), tier_input(&e, "VIP2", 11)],
+        soroban_sdk::vec![&e, tier_input(&e, "VIP", 10), tier_input(&e, "VIP", 10)],
+        soroban_sdk::vec![&e, tier_input(&e, "VIP", 10), tier_input(&e, "VIP2", 0)],
+    ] {
+        assert!(harness.client.try_add_tiers(&batch).is_err());
+        assert!(harness
+            .client
+            .try_get_ticket_price(&Symbol::new(&e, "VIP"))
+            .is_err());
+    }
+    assert_eq!(harness.client.get_total_tier_supply(), 80);
+
+    assert!(harness.client.try_set_venue_capacity(&79).is_err());
+    harness.client.set_venue_capacity(&0);
+    harness.client.add_tiers(&soroban_sdk::vec![
+        &e,
+        tier_input(&e, "VIP", 10),
+        tier_input(&e, "VIP2", 11),
+    ]);
+    assert_eq!(harness.client.get_total_tier_supply(), 101);
+}
+
+#[test]
+fn test_archived_tier_frees_symbol_until_restored() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let general = harness.add_tier("GEN", 100, 10);
+    let typo = harness.add_tier("GNE", 100, 30);
+    harness.client.set_venue_capacity(&40);
+    let token = harness.create_payment_token(&harness.admin, 0);
+    let buyer = harness.funded_buyer(&token, 100);
+    harness.client.purchase(&buyer, &token, &general);
+    assert!(harness.client.try_archive_tier(&general).is_err());
+
+    harness.client.archive_tier(&typo);
+    assert_eq!(harness.client.get_total_tier_supply(), 10);
+    assert_eq!(
+        harness.client.try_get_ticket_price(&typo),
+        Err(Ok(TierError::TierNotFound))
+    );
+    assert!(harness.client.get_archived_tier(&typo).is_some());
+
+    // Restoring puts the tier back as it was, capacity permitting
+    harness.add_tier("VIP", 100, 5);
+    assert_eq!(
+        harness.client.try_restore_tier(&typo),
+        Err(Ok(TierError::VenueCapacityExceeded))
+    );
+    harness.client.archive_tier(&Symbol::new(&e, "VIP"));
+    harness.client.restore_tier(&typo);
+    assert_eq!(harness.client.get_ticket_price(&typo), 100);
+    assert_eq!(harness.client.get_total_tier_supply(), 40);
+    assert_eq!(harness.client.get_archived_tier(&typo), None);
+
+    // Past the grace period the symbol can only be reused
+    harness.client.archive_tier(&typo);
+    harness.warp_time(tier_archive::TIER_RESTORE_GRACE + 1);
+    assert!(harness.client.try_restore_tier(&typo).is_err());
+    harness.add_tier("GNE", 50, 10);
+    assert_eq!(harness.client.get_ticket_price(&typo), 50);
+}
+
+#[test]
+fn test_add_tier_rejects_invalid_input_with_typed_errors() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    harness.add_tier("GEN", 100, 10);
+
+    let try_add = |symbol: &str, name: &str, base_price: i128, max_supply: u32| {
+        harness.client.try_add_tier(
+            &Symbol::new(&e, symbol),
+            &String::from_str(&e, name),
+            &base_price,
+            &max_supply,
+            &PricingStrategy::Standard,
+        )
+    };
+
+    assert_eq!(try_add("GEN", "General", 100, 10), Err(Ok(TierError::TierExists)));
+    assert_eq!(try_add("", "Empty", 100, 10), Err(Ok(TierError::InvalidSymbol)));
+    assert_eq!(
+        try_add("WAY_TOO_LONG_TIER", "Long", 100, 10),
+        Err(Ok(TierError::InvalidSymbol))
+    );
+    assert_eq!(try_add("VIP", "", 100, 10), Err(Ok(TierError::InvalidName)));
+    assert_eq!(try_add("VIP", " VIP", 100, 10), Err(Ok(TierError::InvalidName)));
+    assert_eq!(
+        try_add("VIP", "V\u{200B}IP", 100, 10),
+        Err(Ok(TierError::InvalidName))
+    );
+    assert_eq!(
+        try_add("VIP", "VIP", -1, 10),
+        Err(Ok(TierError::InvalidPrice))
+    );
+    assert_eq!(try_add("VIP", "VIP", 100, 0), Err(Ok(TierError::InvalidSupply)));
+
+    // Non-ASCII names are fine as long as every character is visible
+    assert_eq!(try_add("VIP", "Caf\u{e9} Lounge", 100, 10), Ok(Ok(())));
+}
+
+// ============================================================================
+// REFUND LIMITS
+// ============================================================================
+
+#[test]
+fn test_refund_limits_per_address() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let tier_sym = harness.add_tier("GEN", 100, 100);
+    let token = harness.create_payment_token(&harness.admin, 0);
+    let buyer = harness.funded_buyer(&token, 1_000);
+    let mut token_ids = std::vec::Vec::new();
+    for _ in 0..4 {
+        token_ids.push(harness.client.purchase(&buyer, &token, &tier_sym));
+    }
+
+    harness.client.set_refund_limits(&Some(RefundLimits {
+        max_count: 3,
+        max_value: 250,
+    }));
+    harness.client.refund(&buyer, &token, &token_ids[0]);
+    harness.client.refund(&buyer, &token, &token_ids[1]);
+    assert_eq!(
+        harness.client.try_refund(&buyer, &token, &token_ids[2]),
+        Err(Ok(RefundError::ValueLimitExceeded))
+    );
+
+    harness.client.set_refund_limits(&Some(RefundLimits {
+        max_count: 2,
+        max_value: 0,
+    }));
+    assert_eq!(
+        harness.client.try_refund(&buyer, &token, &token_ids[2]),
+        Err(Ok(RefundError::CountLimitExceeded))
+    );
+    assert_eq!(
+        harness.client.get_refund_stats(&buyer),
+        RefundStats {
+            count: 2,
+            value: 200,
+        }
+    );
+
+    harness.client.set_refund_limits(&None);
+    harness.client.refund(&buyer, &token, &token_ids[2]);
+    assert_eq!(harness.client.get_refund_stats(&buyer).count, 3);
+}
+
+// ============================================================================
+// REFUND MODES
+// ============================================================================
+
+#[test]
+fn test_refund_mode_restocking_fee() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let tier_sym = harness.add_tier("GEN", 100, 100);
+    let token = harness.create_payment_token(&harness.admin, 0);
+    let buyer = harness.funded_buyer(&token, 1_000);
+    let token_client = soroban_sdk::token::Client::new(&e, &token);
+
+    assert_eq!(harness.client.get_refund_mode(), RefundMode::PricePaid);
+    assert!(harness
+        .client
+        .try_set_refund_mode(&RefundMode::RestockingFee(10_001))
+        .is_err());
+    harness
+        .client
+        .set_refund_mode(&RefundMode::RestockingFee(1_000));
+
+    let token_id = harness.client.purchase(&buyer, &token, &tier_sym);
+    harness.client.refund(&buyer, &token, &token_id);
+    assert_eq!(token_client.balance(&buyer), 990);
+    assert_eq!(harness.client.get_refund_stats(&buyer).value, 90);
+
+    // The fee stays in the treasury as revenue, with no liability left against it
+    assert_eq!(token_client.balance(&harness.client.address), 10);
+    harness.client.withdraw(&token, &10);
+}
+
+#[test]
+fn test_refunded_inventory_is_resold_at_current_price() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let tier_sym = harness.add_tier("GEN", 100, 2);
+    let token = harness.create_payment_token(&harness.admin, 0);
+    let early = harness.funded_buyer(&token, 1_000);
+    let late = harness.funded_buyer(&token, 1_000);
+
+    let first = harness.client.purchase(&early, &token, &tier_sym);
+    harness.client.purchase(&early, &token, &tier_sym);
+    assert!(harness.client.try_purchase(&late, &token, &tier_sym).is_err());
+
+    // The refunded slot goes back on sale at the price the demand curve is at now
+    harness.client.refund(&early, &token, &first);
+    assert_eq!(harness.client.get_ticket_price(&tier_sym), 110);
+    let resold = harness.client.purchase(&late, &token, &tier_sym);
+    assert_eq!(harness.client.get_ticket(&resold).unwrap().price_paid, 110);
+
+    assert!(harness.client.try_purchase(&late, &token, &tier_sym).is_err());
+    assert_eq!(
+        harness.client.simulate_purchase(&late, &tier_sym, &1).error,
+        Some(PurchaseError::SoldOut)
+    );
+}
+
+// ============================================================================
+// BUNDLES
+// ============================================================================
+
+#[test]
+fn test_bundle_purchase_is_atomic_and_refunds_each_share() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let general = harness.add_tier("GEN", 100, 10);
+    let camping = harness.add_tier("CAMP", 50, 1);
+    let token = harness.create_payment_token(&harness.admin, 0);
+    let buyer = harness.funded_buyer(&token, 1_000);
+    let other = harness.funded_buyer(&token, 1_000);
+    let token_client = soroban_sdk::token::Client::new(&e, &token);
+
+    let bundle_id = harness.client.create_bundle(&crate::bundles::Bundle {
+        items: vec![
+            &e,
+            crate::bundles::BundleItem { tier_symbol: general.clone(), quantity: 1 },
+            crate::bundles::BundleItem { tier_symbol: camping.clone(), quantity: 1 },
+        ],
+        discount_bps: 1_000,
+        active: true,
+    });
+
+    // 10% off each ticket: 90 + 45
+    let token_ids = harness.client.purchase_bundle(&buyer, &token, &bundle_id);
+    assert_eq!(token_ids.len(), 2);
+    assert_eq!(token_client.balance(&buyer), 1_000 - 135);
+    let gen_id = token_ids.get(0).unwrap();
+    let gen_ticket = harness.client.get_ticket(&gen_id).unwrap();
+    assert_eq!(gen_ticket.price_paid, 90);
+    assert_eq!(gen_ticket.bundle_id, Some(bundle_id));
+    let camping_id = token_ids.get(1).unwrap();
+    let camping_ticket = harness.client.get_ticket(&camping_id).unwrap();
+    assert_eq!(camping_ticket.price_paid, 45);
+
+    // Camping is sold out, so no general ticket is sold either
+    assert!(harness.client.try_purchase_bundle(&other, &token, &bundle_id).is_err());
+    assert_eq!(token_client.balance(&other), 1_000);
+    assert_eq!(harness.client.balance(&other), 0);
+
+    // A constituent ticket refunds its discounted share
+    harness.client.refund(&buyer, &token, &token_ids.get(0).unwrap());
+    assert_eq!(token_client.balance(&buyer), 1_000 - 45);
+
+    harness.client.set_bundle_active(&bundle_id, &false);
+    assert!(harness.client.try_purchase_bundle(&other, &token, &bundle_id).is_err());
+}
+
+#[test]
+fn test_idempotent_purchases_return_original_tokens() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let general = harness.add_tier("GEN", 100, 10);
+    let token = harness.create_payment_token(&harness.admin, 0);
+    let buyer = harness.funded_buyer(&token, 1_000);
+    let other = harness.funded_buyer(&token, 1_000);
+    let token_client = soroban_sdk::token::Client::new(&e, &token);
+    let key = BytesN::from_array(&e, &[7; 32]);
+
+    let token_id = harness.client.purchase_idempotent(&buyer, &token, &general, &key);
+    assert_eq!(
+        harness.client.purchase_idempotent(&buyer, &token, &general, &key),
+        token_id
+    );
+    assert_eq!(token_client.balance(&buyer), 900);
+    assert_eq!(harness.client.balance(&buyer), 1);
+
+    // Keys belong to the buyer
+    assert_ne!(
+        harness.client.purchase_idempotent(&other, &token, &general, &key),
+        token_id
+    );
+    assert_eq!(token_client.balance(&other), 900);
+
+    let bundle_id = harness.client.create_bundle(&crate::bundles::Bundle {
+        items: vec![
+            &e,
+            crate::bundles::BundleItem { tier_symbol: general.clone(), quantity: 2 },
+        ],
+        discount_bps: 0,
+        active: true,
+    });
+    let bundle_key = BytesN::from_array(&e, &[8; 32]);
+    assert_eq!(harness.client.get_idempotent_purchase(&buyer, &bundle_key), None);
+    let token_ids = harness
+        .client
+        .purchase_bundle_idempotent(&buyer, &token, &bundle_id, &bundle_key);
+    assert_eq!(
+        harness
+            .client
+            .purchase_bundle_idempotent(&buyer, &token, &bundle_id, &bundle_key),
+        token_ids
+    );
+    assert_eq!(
+        harness.client.get_idempotent_purchase(&buyer, &bundle_key),
+        Some(token_ids)
+    );
+    assert_eq!(token_client.balance(&buyer), 700);
+    assert_eq!(harness.client.balance(&buyer), 3);
+}
+
+// ============================================================================
+// SALES STATS
+// ============================================================================
+
+#[test]
+fn test_sales_stats_count_issued_and_refunded_tickets() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let tier_sym = harness.add_tier("GEN", 100, 10);
+    let token = harness.create_payment_token(&harness.admin, 0);
+    let buyer = harness.funded_buyer(&token, 1_000);
+
+    let token_id = harness.client.purchase(&buyer, &token, &tier_sym);
+    harness.client.batch_mint(&buyer, &tier_sym, &2);
+    harness.client.refund(&buyer, &token, &token_id);
+
+    let stats = harness.client.get_sales_stats();
+    assert_eq!(stats.tickets_sold, 3);
+    assert_eq!(stats.tickets_refunded, 1);
+}
+
+#[test]
+fn test_supply_views() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let general = harness.add_tier("GEN", 100, 10);
+    let vip = harness.add_tier("VIP", 300, 5);
+    let token = harness.create_payment_token(&harness.admin, 0);
+    let buyer = harness.funded_buyer(&token, 1_000);
+
+    let token_id = harness.client.purchase(&buyer, &token, &general);
+    harness.client.purchase(&buyer, &token, &general);
+    harness.client.batch_mint(&buyer, &vip, &2);
+    harness.client.refund(&buyer, &token, &token_id);
+
+    assert_eq!(harness.client.total_supply(), 3);
+    assert_eq!(harness.client.tier_supply(&general), Some((1, 10, 9, 1)));
+    assert_eq!(harness.client.tier_supply(&vip), Some((2, 5, 3, 0)));
+    assert_eq!(harness.client.tier_supply(&Symbol::new(&e, "NONE")), None);
+}
+
+#[test]
+fn test_views_return_none_for_unknown_ids() {
+    let e = Env::default();
+    let harness = TicketHarness::setup(&e);
+    let missing = Symbol::new(&e, "NONE");
+
+    assert_eq!(harness.client.token_metadata(&404), None);
+    assert_eq!(harness.client.get_attributes(&404), None);
+    assert_eq!(harness.client.tier_supply(&missing), None);
+    assert_eq!(harness.client.get_unlocked_supply(&missing), None);
+    let name = String::from_str(&e, "Jane Doe");
+    let salt = Bytes::from_slice(&e, b"salt");
+    assert_eq!(harness.client.verify_identity(&404, &name, &salt), None);
+}
+
+// ============================================================================
+// FREEZE GRACE
+// ============================================================================
+
+#[test]
+fn test_freeze_grace_extends_refunds_for_recent_purchases() {
+    let e = Env::default();
+    let now = e.ledger().timestamp();
+    let harness = testutils::TicketHarness::with_event_times(&e, now + 100_000, now + 1_000);
+    let tier_sym = harness.add_tier("GEN", 100, 10);
+    let token = harness.create_payment_token(&harness.admin, 0);
+    let buyer = harness.funded_buyer(&token, 1_000);
+
+    harness.client.set_freeze_grace(&Some(freeze_grace::FreezeGrace {
+        lookback: 600,
+        window: 7_200,
+    }));
+
+    let early = harness.client.purchase(&buyer, &token, &tier_sym);
+    harness.warp_to(now + 900);
+    let recent = harness.client.purchase(&buyer, &token, &tier_sym);
+    let kept = harness.client.purchase(&buyer, &token, &tier_sym);
+    harness.warp_to(now + 950);
+    harness.client.emergency_freeze(&true);
+    assert_eq!(harness.client.get_last_freeze().unwrap().frozen_at, now + 950);
+
+    // Past the normal cutoff only the purchase made just before the freeze qualifies
+    harness.warp_to(now + 2_000);
+    assert!(harness.client.try_refund(&buyer, &token, &early).is_err());
+    harness.client.refund(&buyer, &token, &recent);
+
+    // The grace ends `window` seconds after the freeze
+    harness.warp_to(now + 950 + 7_201);
+    assert!(harness.client.try_refund(&buyer, &token, &kept).is_err());
+}
+
+#[test]
+fn test_freeze_grace_keeps_reserve_past_cutoff() {
+    let e = Env::default();
+    let now = e.ledger().timestamp();
+    let harness = testutils::TicketHarness::with_event_times(&e, now + 100_000, now + 1_000);
+    let tier_sym = harness.add_tier("GEN", 100, 10);
+    let token = harness.create_payment_token(&harness.admin, 0);
+    let buyer = harness.funded_buyer(&token, 1_000);
+    let token_client = soroban_sdk::token::Client::new(&e, &token);
+
+    harness.client.set_freeze_grace(&Some(freeze_grace::FreezeGrace {
+        lookback: 600,
+        window: 7_200,
+    }));
+    harness.warp_to(now + 900);
+    let recent = harness.client.purchase(&buyer, &token, &tier_sym);
+    harness.warp_to(now + 950);
+    harness.client.emergency_freeze(&true);
+
+    // Past the cutoff, the grace window still holds the refund reserve
+    harness.warp_to(now + 2_000);
+    let held = token_client.balance(&harness.client.address);
+    let reserve = harness.client.get_required_reserve(&token);
+    assert!(reserve > 0);
+    assert_eq!(harness.client.get_withdrawable(&token), held - reserve);
+    assert!(harness.client.try_withdraw(&token, &held).is_err());
+    harness.client.withdraw(&token, &(held - reserve));
+    harness.client.refund(&buyer, &token, &recent);
+
+    // Once the grace ends the reserve is released
+    harness.warp_to(now + 950 + 7_201);
+    assert_eq!(harness.client.get_required_reserve(&token), 0);
+}
+
+// ============================================================================
+// GROUP TICKETS
+// ============================================================================
+
+#[test]
+fn test_group_ticket_splits_to_companions_at_no_cost() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let tier_sym = harness.add_tier("GEN", 100, 10);
+    let token = harness.create_payment_token(&harness.admin, 0);
+    let buyer = harness.funded_buyer(&token, 1_000);
+    let token_client = soroban_sdk::token::Client::new(&e, &token);
+    let friends = soroban_sdk::vec![&e, Address::generate(&e), Address::generate(&e)];
+
+    let group = harness.client.purchase_group(&buyer, &token, &tier_sym, &3);
+    let paid = 1_000 - token_client.balance(&buyer);
+    assert_eq!(harness.client.get_ticket(&group).unwrap().price_paid, paid);
+    assert_eq!(harness.client.get_group_seats(&group), 2);
+    assert_eq!(harness.client.tier_supply(&tier_sym).unwrap().0, 3);
+
+    // One receipt covers every seat
+    let receipt_ids = harness.client.get_buyer_receipts(&buyer);
+    assert_eq!(receipt_ids.len(), 1);
+    let receipt = harness
+        .client
+        .get_receipt(&receipt_ids.get(0).unwrap())
+        .unwrap();
+    assert_eq!(receipt.token_ids, soroban_sdk::vec![&e, group]);
+    assert_eq!(receipt.total, paid);
+
+    // Only the holder may split, and never more seats than are left
+    assert!(harness
+        .client
+        .try_split_ticket(&friends.get(0).unwrap(), &group, &friends)
+        .is_err());
+    let mut too_many = friends.clone();
+    too_many.push_back(Address::generate(&e));
+    assert!(harness.client.try_split_ticket(&buyer, &group, &too_many).is_err());
+
+    let split = harness.client.split_ticket(&buyer, &group, &friends);
+    assert_eq!(token_client.balance(&buyer), 1_000 - paid);
+    assert_eq!(harness.client.get_group_seats(&group), 0);
+    assert_eq!(harness.client.tier_supply(&tier_sym).unwrap().0, 3);
+
+    // Companions are new tokens but not new sales
+    assert_eq!(harness.client.get_sales_stats().tickets_sold, 1);
+    assert_eq!(harness.client.get_split_count(), 2);
+    assert_eq!(harness.client.total_supply(), 3);
+
+    let mut total = harness.client.get_ticket(&group).unwrap().price_paid;
+    for (i, token_id) in split.iter().enumerate() {
+        assert_eq!(
+            harness.client.owner_of(&token_id),
+            friends.get(i as u32).unwrap()
+        );
+        total += harness.client.get_ticket(&token_id).unwrap().price_paid;
+    }
+    assert_eq!(total, paid);
+}
+
+#[test]
+fn test_split_companions_respect_wallet_cap_and_get_no_guest_slots() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let tier_sym = harness.add_tier("VIP", 100, 10);
+    harness.client.set_tier_guest_slots(&tier_sym, &2);
+    let token = harness.create_payment_token(&harness.admin, 0);
+    let buyer = harness.funded_buyer(&token, 1_000);
+    let friend = harness.funded_buyer(&token, 1_000);
+    harness.client.purchase(&friend, &token, &tier_sym);
+    harness.client.set_wallet_cap(&1);
+
+    let group = harness.client.purchase_group(&buyer, &token, &tier_sym, &3);
+    assert_eq!(harness.client.get_ticket(&group).unwrap().guest_slots, 2);
+
+    // The friend already holds as many tickets as the cap allows
+    assert!(harness
+        .client
+        .try_split_ticket(&buyer, &group, &soroban_sdk::vec![&e, friend.clone()])
+        .is_err());
+    // Two seats to one new wallet would leave it over the cap
+    let newcomer = Address::generate(&e);
+    assert!(harness
+        .client
+        .try_split_ticket(
+            &buyer,
+            &group,
+            &soroban_sdk::vec![&e, newcomer.clone(), newcomer.clone()]
+        )
+        .is_err());
+    assert_eq!(harness.client.get_group_seats(&group), 2);
+
+    let split = harness
+        .client
+        .split_ticket(&buyer, &group, &soroban_sdk::vec![&e, newcomer]);
+    let companion = harness.client.get_ticket(&split.get(0).unwrap()).unwrap();
+    assert_eq!(companion.guest_slots, 0);
+    assert_eq!(harness.client.get_ticket(&group).unwrap().guest_slots, 2);
+}
+
+#[test]
+fn test_refunding_group_ticket_returns_unsplit_seats() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let tier_sym = harness.add_tier("GEN", 100, 10);
+    let token = harness.create_payment_token(&harness.admin, 0);
+    let buyer = harness.funded_buyer(&token, 1_000);
+    let token_client = soroban_sdk::token::Client::new(&e, &token);
+
+    let group = harness.client.purchase_group(&buyer, &token, &tier_sym, &4);
+    harness
+        .client
+        .split_ticket(&buyer, &group, &soroban_sdk::vec![&e, Address::generate(&e)]);
+    assert_eq!(harness.client.get_group_seats(&group), 2);
+
+    harness.client.refund(&buyer, &token, &group);
+    assert_eq!(harness.client.tier_supply(&tier_sym).unwrap().0, 1);
+    assert_eq!(harness.client.get_group_seats(&group), 0);
+    assert_eq!(
+        token_client.balance(&buyer) + harness.client.get_refund_liability(&token),
+        1_000
+    );
+}
+
+#[test]
+fn test_failed_batch_purchases_charge_nothing() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let general = harness.add_tier("GEN", 100, 3);
+    let camping = harness.add_tier("CAMP", 50, 10);
+    let token = harness.create_payment_token(&harness.admin, 0);
+    let buyer = harness.funded_buyer(&token, 200);
+    let token_client = soroban_sdk::token::Client::new(&e, &token);
+
+    // The fourth seat runs past supply after three were counted
+    assert!(harness
+        .client
+        .try_purchase_group(&buyer, &token, &general, &4)
+        .is_err());
+    assert_eq!(token_client.balance(&buyer), 200);
+    assert_eq!(harness.client.balance(&buyer), 0);
+    assert_eq!(harness.client.tier_supply(&general).unwrap().0, 0);
+
+    // Every ticket mints but the payment can't be covered
+    let bundle_id = harness.client.create_bundle(&crate::bundles::Bundle {
+        items: vec![
+            &e,
+            crate::bundles::BundleItem { tier_symbol: general.clone(), quantity: 2 },
+            crate::bundles::BundleItem { tier_symbol: camping.clone(), quantity: 1 },
+        ],
+        discount_bps: 0,
+        active: true,
+    });
+    assert!(harness.client.try_purchase_bundle(&buyer, &token, &bundle_id).is_err());
+    assert_eq!(token_client.balance(&buyer), 200);
+    assert_eq!(harness.client.total_supply(), 0);
+    assert_eq!(harness.client.get_refund_liability(&token), 0);
+
+    harness.client.purchase_group(&buyer, &token, &general, &2);
+    assert_eq!(token_client.balance(&buyer), 0);
+    assert_eq!(harness.client.get_refund_liability(&token), 200);
+}
+
+// ============================================================================
+// CAPPED RESALE
+// ============================================================================
+
+#[test]
+fn test_resale_listing_enforces_cap_and_royalty() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let general = harness.add_tier("GEN", 100, 10);
+    let vip = harness.add_tier("VIP", 100, 10);
+    let token = harness.create_payment_token(&harness.admin, 0);
+    let seller = harness.funded_buyer(&token, 200);
+    let buyer = harness.funded_buyer(&token, 200);
+    let token_client = soroban_sdk::token::Client::new(&e, &token);
+
+    harness.client.set_resale_policy(
+        &general,
+        &Some(resale::ResalePolicy {
+            max_markup_bps: 2_000,
+            royalty_bps: 1_000,
+            cap_mode: resale::ResaleCapMode::Fixed,
+        }),
+    );
+    let token_id = harness.client.purchase(&seller, &token, &general);
+    let soulbound = harness.client.purchase(&seller, &token, &vip);
+    assert!(harness.client.try_list_for_resale(&soulbound, &100).is_err());
+
+    // At most 20% over face value
+    assert!(harness.client.try_list_for_resale(&token_id, &121).is_err());
+    harness.client.list_for_resale(&token_id, &120);
+    harness.client.delist(&token_id);
+    assert!(harness.client.try_buy_listed(&buyer, &token_id, &token).is_err());
+    harness.client.list_for_resale(&token_id, &120);
+    assert!(harness.client.try_buy_listed(&seller, &token_id, &token).is_err());
+
+    // 10% royalty to the organizer, the rest to the seller
+    harness.client.buy_listed(&buyer, &token_id, &token);
+    assert_eq!(harness.client.owner_of(&token_id), buyer);
+    assert_eq!(token_client.balance(&seller), 108);
+    assert_eq!(token_client.balance(&harness.admin), 12);
+    assert_eq!(token_client.balance(&buyer), 80);
+    assert_eq!(harness.client.get_listing(&token_id), None);
+    assert_eq!(
+        harness.client.get_purchase_history(&buyer, &0, &10).get(0).unwrap().token_id,
+        token_id
+    );
+
+    // The new holder may check in, after which the ticket can't be listed
+    harness.warp_to(e.ledger().timestamp() + 100_000);
+    harness.client.check_in(&token_id);
+    assert!(harness.client.try_list_for_resale(&token_id, &100).is_err());
+}
+
+#[test]
+fn test_ticket_records_purchase_channel() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let general = harness.add_tier("GEN", 100, 10);
+    let token = harness.create_payment_token(&harness.admin, 0);
+    let seller = harness.funded_buyer(&token, 200);
+    let buyer = harness.funded_buyer(&token, 200);
+    harness.client.set_resale_policy(
+        &general,
+        &Some(resale::ResalePolicy {
+            max_markup_bps: 0,
+            royalty_bps: 0,
+            cap_mode: resale::ResaleCapMode::Fixed,
+        }),
+    );
+
+    let comp = harness.client.batch_mint(&buyer, &general, &1);
+    let comp = comp.get(0).unwrap();
+    let ticket = harness.client.get_ticket(&comp).unwrap();
+    assert_eq!(ticket.channel, storage_types::PurchaseChannel::Comp);
+    let token_id = harness.client.purchase(&seller, &token, &general);
+    assert_eq!(
+        harness.client.get_ticket(&token_id).unwrap().channel,
+        storage_types::PurchaseChannel::DirectSale
+    );
+
+    // Resale keeps the rest of the ticket but records how the holder got it
+    harness.client.list_for_resale(&token_id, &100);
+    harness.client.buy_listed(&buyer, &token_id, &token);
+    let ticket = harness.client.get_ticket(&token_id).unwrap();
+    assert_eq!(ticket.channel, storage_types::PurchaseChannel::Resale);
+    assert_eq!(ticket.price_paid, 100);
+}
+
+#[test]
+fn test_indexed_resale_cap_locked_at_listing() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let oracle_id = e.register(MockAdjustableOracle, ());
+    let oracle_client = MockAdjustableOracleClient::new(&e, &oracle_id);
+    harness.client.set_pricing_config(&PricingConfig {
+        pricing_mode: PricingMode::Fixed,
+        oracle_address: oracle_id,
+        dex_pool_address: e.register(MockDex, ()),
+        price_floor: 0,
+        price_ceiling: i128::MAX,
+        update_frequency: 0,
+        last_update_time: e.ledger().timestamp(),
+        is_frozen: false,
+        oracle_pair: String::from_str(&e, "XLM/USD"),
+        oracle_reference_price: oracle::DIA_ORACLE_DECIMALS,
+        max_oracle_age_seconds: oracle::DEFAULT_STALENESS_SECONDS,
+    });
+
+    let general = harness.add_tier("GEN", 100, 10);
+    let token = harness.create_payment_token(&harness.admin, 0);
+    let seller = harness.funded_buyer(&token, 200);
+    let buyer = harness.funded_buyer(&token, 200);
+    let policy = resale::ResalePolicy {
+        max_markup_bps: 2_000,
+        royalty_bps: 0,
+        cap_mode: resale::ResaleCapMode::Indexed(resale::InflationIndex {
+            pair: String::from_str(&e, "CPI"),
+            base_index: 100_000_000,
+        }),
+    };
+    harness.client.set_resale_policy(&general, &Some(policy));
+    let first = harness.client.purchase(&seller, &token, &general);
+    let second = harness.client.purchase(&seller, &token, &general);
+
+    // Deflation leaves the plain markup cap
+    oracle_client.set_price(&90_000_000);
+    assert!(harness.client.try_list_for_resale(&first, &121).is_err());
+
+    // 10% inflation lifts the cap from 120 to 132
+    oracle_client.set_price(&110_000_000);
+    assert!(harness.client.try_list_for_resale(&first, &133).is_err());
+    harness.client.list_for_resale(&first, &132);
+    assert_eq!(harness.client.get_listing(&first).unwrap().price_cap, 132);
+
+    // A lower reading later doesn't undo the listing
+    oracle_client.set_price(&100_000_000);
+    assert!(harness.client.try_list_for_resale(&second, &132).is_err());
+    harness.client.buy_listed(&buyer, &first, &token);
+    assert_eq!(harness.client.owner_of(&first), buyer);
+}
+
+// ============================================================================
+// EVENT SERIES
+// ============================================================================
+
+#[test]
+fn test_series_benefit_claimed_once_per_source_ticket() {
+    let e = Env::default();
+    let first_event = testutils::TicketHarness::setup(&e);
+    let second_event = testutils::TicketHarness::setup(&e);
+    let day_one = first_event.add_tier("DAY1", 100, 10);
+    let day_two = second_event.add_tier("DAY2", 100, 10);
+    let token = second_event.create_payment_token(&second_event.admin, 0);
+    let holder = second_event.funded_buyer(&token, 1_000);
+    let stranger = second_event.funded_buyer(&token, 1_000);
+    let source_ids = first_event.client.batch_mint(&holder, &day_one, &2);
+    let source_id = source_ids.get(0).unwrap();
+
+    // Not linked yet
+    assert!(second_event
+        .client
+        .try_claim_series_benefit(&holder, &first_event.client.address, &source_id, &token)
+        .is_err());
+
+    second_event.client.set_series_benefit(
+        &first_event.client.address,
+        &Some(series::SeriesBenefit {
+            tier_symbol: day_two.clone(),
+            discount_bps: 2_000,
+            guaranteed: false,
+        }),
+    );
+    assert!(second_event
+        .client
+        .try_claim_series_benefit(&stranger, &first_event.client.address, &source_id, &token)
+        .is_err());
+
+    let token_id = second_event.client.claim_series_benefit(
+        &holder,
+        &first_event.client.address,
+        &source_id,
+        &token,
+    );
+    assert_eq!(second_event.client.owner_of(&token_id), holder);
+    let ticket = second_event.client.get_ticket(&token_id).unwrap();
+    assert_eq!(ticket.price_paid, 80);
+    let token_client = soroban_sdk::token::Client::new(&e, &token);
+    assert_eq!(token_client.balance(&holder), 920);
+    assert!(second_event
+        .client
+        .is_series_benefit_claimed(&first_event.client.address, &source_id));
+
+    // Each source ticket claims once; the holder's other ticket still can
+    assert!(second_event
+        .client
+        .try_claim_series_benefit(&holder, &first_event.client.address, &source_id, &token)
+        .is_err());
+    second_event.client.claim_series_benefit(
+        &holder,
+        &first_event.client.address,
+        &source_ids.get(1).unwrap(),
+        &token,
+    );
+    assert_eq!(token_client.balance(&holder), 840);
+}
+
+// ============================================================================
+// RETURNING ATTENDEES
+// ============================================================================
+
+#[test]
+fn test_free_tier_is_claimed_without_payment() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let free = harness.add_tier("FREE", 0, 10);
+    let paid = harness.add_tier("GEN", 100, 10);
+    let token = harness.create_payment_token(&harness.admin, 0);
+    let attendee = harness.funded_buyer(&token, 100);
+    assert!(harness
+        .client
+        .try_purchase(&attendee, &token, &free)
+        .is_err());
+    assert!(harness
+        .client
+        .try_claim_free_ticket(&attendee, &paid, &None)
+        .is_err());
+
+    // One ticket per address by default, with no token involved
+    let token_id = harness.client.claim_free_ticket(&attendee, &free, &None);
+    let ticket = harness.client.get_ticket(&token_id).unwrap();
+    assert_eq!(ticket.price_paid, 0);
+    assert_eq!(ticket.payment_token, None);
+    assert_eq!(harness.client.get_free_claims(&free, &attendee), 1);
+    assert!(harness
+        .client
+        .try_claim_free_ticket(&attendee, &free, &None)
+        .is_err());
+
+    // Registration questions make answers part of every claim
+    let questions = BytesN::from_array(&e, &[1; 32]);
+    let answers = BytesN::from_array(&e, &[2; 32]);
+    harness.client.set_free_claim_config(
+        &free,
+        &Some(free_claims::FreeClaimConfig {
+            max_per_address: 2,
+            questions_hash: Some(questions),
+        }),
+    );
+    assert!(harness
+        .client
+        .try_claim_free_ticket(&attendee, &free, &None)
+        .is_err());
+    let second = harness
+        .client
+        .claim_free_ticket(&attendee, &free, &Some(answers.clone()));
+    assert_eq!(
+        harness.client.get_registration_answers(&second),
+        Some(answers.clone())
+    );
+    assert_eq!(harness.client.get_registration_answers(&token_id), None);
+    assert!(harness
+        .client
+        .try_claim_free_ticket(&attendee, &free, &Some(answers))
+        .is_err());
+    assert_eq!(harness.client.balance(&attendee), 2);
+}
+
+#[test]
+fn test_returning_attendees_buy_at_grandfathered_price() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let tier_sym = harness.add_tier("GEN", 100, 10);
+    let token = harness.create_payment_token(&harness.admin, 0);
+    let attendee = harness.funded_buyer(&token, 1_000);
+    let late_attendee = harness.funded_buyer(&token, 1_000);
+    let newcomer = harness.funded_buyer(&token, 1_000);
+
+    let leaves = soroban_sdk::vec![
+        &e,
+        returning::ReturningManager::attendee_leaf(&e, &attendee),
+        returning::ReturningManager::attendee_leaf(&e, &late_attendee),
+    ];
+    let window_end = e.ledger().timestamp() + 1_000;
+    harness.client.set_returning_offer(
+        &tier_sym,
+        &Some(returning::ReturningOffer {
+            price: 70,
+            window_end,
+            root: archive::ArchiveManager::merkle_root(&e, &leaves),
+            attendee_count: 2,
+        }),
+    );
+
+    let proof = soroban_sdk::vec![&e, leaves.get(1).unwrap()];
+    assert!(harness
+        .client
+        .try_purchase_returning(&newcomer, &token, &tier_sym, &0, &proof)
+        .is_err());
+    let token_id = harness.client.purchase_returning(&attendee, &token, &tier_sym, &0, &proof);
+    assert_eq!(harness.client.get_ticket(&token_id).unwrap().price_paid, 70);
+    assert!(harness.client.has_returning_claim(&tier_sym, &attendee));
+
+    // One grandfathered ticket per attendee; regular purchases are unaffected
+    assert!(harness
+        .client
+        .try_purchase_returning(&attendee, &token, &tier_sym, &0, &proof)
+        .is_err());
+    let token_id = harness.client.purchase(&attendee, &token, &tier_sym);
+    assert!(harness.client.get_ticket(&token_id).unwrap().price_paid > 70);
+
+    // Closed once the window ends
+    harness.warp_to(window_end + 1);
+    let proof = soroban_sdk::vec![&e, leaves.get(0).unwrap()];
+    assert!(harness
+        .client
+        .try_purchase_returning(&late_attendee, &token, &tier_sym, &1, &proof)
+        .is_err());
+}
+
+// ============================================================================
+// LOYALTY POINTS
+// ============================================================================
+
+#[test]
+fn test_points_settle_capped_share_of_price() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let tier_sym = harness.add_tier("GEN", 100, 10);
+    let other_tier = harness.add_tier("VIP", 100, 10);
+    let token = harness.create_payment_token(&harness.admin, 0);
+    let points_token = harness.create_payment_token(&harness.admin, 0);
+    let buyer = harness.funded_buyer(&token, 1_000);
+    harness.fund(&points_token, &buyer, 1_000);
+    let token_client = soroban_sdk::token::Client::new(&e, &token);
+    let points_client = soroban_sdk::token::Client::new(&e, &points_token);
+
+    harness.client.set_points_policy(
+        &tier_sym,
+        &Some(points::PointsPolicy {
+            points_token: points_token.clone(),
+            points_per_unit: 10,
+            max_share_bps: 3_000,
+        }),
+    );
+    assert!(harness
+        .client
+        .try_purchase_with_points(&buyer, &token, &other_tier, &500)
+        .is_err());
+
+    // At most 30% of the price in points, whatever the buyer offers
+    let token_id = harness.client.purchase_with_points(&buyer, &token, &tier_sym, &500);
+    assert_eq!(harness.client.get_ticket(&token_id).unwrap().price_paid, 70);
+    assert_eq!(harness.client.get_points_paid(&token_id), 300);
+    assert_eq!(token_client.balance(&buyer), 930);
+    assert_eq!(points_client.balance(&buyer), 700);
+    assert_eq!(points_client.balance(&harness.admin), 300);
+
+    // Only whole units are settled in points
+    let token_id = harness.client.purchase_with_points(&buyer, &token, &tier_sym, &105);
+    assert_eq!(harness.client.get_ticket(&token_id).unwrap().price_paid, 90);
+    assert_eq!(points_client.balance(&buyer), 600);
+
+    // A points shortfall fails the whole purchase
+    let pointless = harness.funded_buyer(&token, 1_000);
+    assert!(harness
+        .client
+        .try_purchase_with_points(&pointless, &token, &tier_sym, &300)
+        .is_err());
+    assert_eq!(token_client.balance(&pointless), 1_000);
+    assert_eq!(harness.client.balance(&pointless), 0);
+}
+
+// ============================================================================
+// CUSTODIAL MIGRATIONS
+// ============================================================================
+
+#[test]
+fn test_migration_exemption_is_single_use() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let tier_sym = harness.add_tier("GEN", 100, 10);
+    let old_wallet = Address::generate(&e);
+    let new_wallet = Address::generate(&e);
+    let other = Address::generate(&e);
+    let token_ids = harness.client.batch_mint(&old_wallet, &tier_sym, &3);
+    let first_two = soroban_sdk::vec![&e, token_ids.get(0).unwrap(), token_ids.get(1).unwrap()];
+
+    // Still soulbound without an exemption
+    assert!(harness
+        .client
+        .try_migrate_tickets(&old_wallet, &new_wallet, &first_two)
+        .is_err());
+
+    harness.client.approve_migration(&old_wallet, &new_wallet);
+    assert!(harness
+        .client
+        .try_migrate_tickets(&old_wallet, &other, &first_two)
+        .is_err());
+    harness.client.migrate_tickets(&old_wallet, &new_wallet, &first_two);
+    assert_eq!(harness.client.balance(&new_wallet), 2);
+    assert_eq!(harness.client.owner_of(&token_ids.get(2).unwrap()), old_wallet);
+    assert_eq!(
+        harness.client.get_migration(&old_wallet, &new_wallet).unwrap().used_ledger,
+        Some(e.ledger().sequence())
+    );
+
+    // The exemption is spent
+    let rest = soroban_sdk::vec![&e, token_ids.get(2).unwrap()];
+    assert!(harness
+        .client
+        .try_migrate_tickets(&old_wallet, &new_wallet, &rest)
+        .is_err());
+    assert!(harness.client.try_revoke_migration(&old_wallet, &new_wallet).is_err());
+
+    harness.client.approve_migration(&new_wallet, &other);
+    harness.client.revoke_migration(&new_wallet, &other);
+    assert!(harness
+        .client
+        .try_migrate_tickets(&new_wallet, &other, &first_two)
+        .is_err());
+}
+
+// ============================================================================
+// ASSIGNED SEATING
+// ============================================================================
+
+#[test]
+fn test_seat_assignments_export_and_version() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let floor = harness.add_tier("FLOOR", 100, 10);
+    let balcony = harness.add_tier("BALC", 50, 10);
+    let token = harness.create_payment_token(&harness.admin, 0);
+    let buyer = harness.funded_buyer(&token, 1_000);
+    let other = harness.funded_buyer(&token, 1_000);
+
+    let first = harness.client.purchase(&buyer, &token, &floor);
+    let second = harness.client.purchase(&other, &token, &floor);
+    let upstairs = harness.client.purchase(&buyer, &token, &balcony);
+    assert_eq!(harness.client.seat_version(&floor), 0);
+
+    harness.client.assign_seat(&floor, &12, &first);
+    harness.client.assign_seat(&floor, &14, &second);
+    assert!(harness.client.try_assign_seat(&floor, &12, &second).is_err());
+    assert!(harness.client.try_assign_seat(&floor, &20, &upstairs).is_err());
+    assert_eq!(harness.client.seat_version(&floor), 2);
+    assert_eq!(
+        harness.client.export_seat_assignments(&floor, &0, &10),
+        vec![&e, (12, first, buyer.clone()), (14, second, other.clone())]
+    );
+    assert_eq!(
+        harness.client.export_seat_assignments(&floor, &1, &10),
+        vec![&e, (14, second, other.clone())]
+    );
+
+    // Moving a ticket frees its old seat
+    harness.client.assign_seat(&floor, &15, &first);
+    assert_eq!(harness.client.get_seat(&first), Some(15));
+    assert_eq!(harness.client.seat_version(&floor), 4);
+
+    // A refund gives the seat up
+    harness.client.refund(&other, &token, &second);
+    assert_eq!(harness.client.get_seat(&second), None);
+    assert_eq!(harness.client.seat_version(&floor), 5);
+    assert_eq!(
+        harness.client.export_seat_assignments(&floor, &0, &10),
+        vec![&e, (15, first, buyer.clone())]
+    );
+
+    harness.client.release_seat(&first);
+    assert_eq!(harness.client.export_seat_assignments(&floor, &0, &10).len(), 0);
+    assert!(harness.client.try_release_seat(&first).is_err());
+    assert_eq!(harness.client.seat_version(&balcony), 0);
+}
+
+// ============================================================================
+// LOTTERY STATS
+// ============================================================================
+
+#[test]
+fn test_lottery_stats_track_entries_and_window() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let tier_sym = harness.add_tier("LOT", 100, 10);
+
+    let stats = harness.client.get_lottery_stats(&tier_sym);
+    assert_eq!(stats.window, lottery_stats::LotteryWindow::NotInitialized);
+    assert_eq!(stats.win_probability_bps, 0);
+
+    harness
+        .client
+        .set_distribution_mode(&tier_sym, &storage_types::DistributionMode::LotteryOnly);
+    harness.client.initialize_lottery(
+        &tier_sym,
+        &AllocationStrategyType::Lottery,
+        &2,
+        &(e.ledger().sequence() + 10),
+        &1,
+        &5,
+    );
+
+    // Four entries from three addresses
+    let mut entries = harness.lottery_entries(3);
+    let mut repeat = entries.get(0).unwrap();
+    repeat.nonce = 3;
+    entries.push_back(repeat);
+    harness.store_lottery_entries(&tier_sym, &entries);
+
+    let stats = harness.client.get_lottery_stats(&tier_sym);
+    assert_eq!(stats.entry_count, 4);
+    assert_eq!(stats.allocations_available, 2);
+    assert_eq!(stats.unique_participants, 3);
+    assert_eq!(stats.win_probability_bps, 5_000);
+    assert_eq!(stats.window, lottery_stats::LotteryWindow::Open);
+
+    harness.advance_ledgers(10);
+    assert_eq!(
+        harness.client.get_lottery_stats(&tier_sym).window,
+        lottery_stats::LotteryWindow::AwaitingDraw
+    );
+}
+
+#[test]
+fn test_lottery_entry_policy_dedupes_winners() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let tier_sym = harness.add_tier("LOT", 100, 10);
+
+    assert_eq!(
+        harness.client.get_lottery_entry_policy(&tier_sym),
+        entry_policy::EntryPolicy::Unlimited
+    );
+    harness
+        .client
+        .set_lottery_entry_policy(&tier_sym, &entry_policy::EntryPolicy::WeightedByEntries);
+    assert_eq!(
+        harness.client.get_lottery_entry_policy(&tier_sym),
+        entry_policy::EntryPolicy::WeightedByEntries
+    );
+
+    // Two wins by the same address collapse into one; the next winner moves up
+    let repeat = Address::generate(&e);
+    let other = Address::generate(&e);
+    let result = |winner: &Address, allocation_index: u32| allocation::AllocationResult {
+        winner: winner.clone(),
+        allocation_index,
+        randomness_value: 0,
+        weight_applied: 1,
+    };
+    let results = soroban_sdk::vec![
+        &e,
+        result(&repeat, 0),
+        result(&repeat, 1),
+        result(&other, 2),
+    ];
+    let deduped = entry_policy::EntryPolicyManager::dedupe_winners(&e, &results, 2);
+    assert_eq!(
+        deduped,
+        soroban_sdk::vec![&e, result(&repeat, 0), result(&other, 1)]
+    );
+
+    // The policy is fixed once entries exist
+    harness.store_lottery_entries(&tier_sym, &harness.lottery_entries(2));
+    assert!(harness
+        .client
+        .try_set_lottery_entry_policy(&tier_sym, &entry_policy::EntryPolicy::OneEntryPerAddress)
+        .is_err());
+}
+
+#[test]
+fn test_one_entry_per_address_rejects_repeat_registration() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let tier_sym = harness.add_tier("LOT", 100, 10);
+    harness
+        .client
+        .set_lottery_entry_policy(&tier_sym, &entry_policy::EntryPolicy::OneEntryPerAddress);
+
+    let entrant = Address::generate(&e);
+    let other = Address::generate(&e);
+    let enter = |who: &Address| {
+        harness
+            .client
+            .try_register_lottery_entry(who, &tier_sym, &None)
+    };
+    assert_eq!(enter(&entrant), Ok(Ok(())));
+    assert_eq!(enter(&entrant), Err(Ok(RegistrationError::AlreadyEntered)));
+    assert_eq!(enter(&other), Ok(Ok(())));
+    assert_eq!(harness.client.get_lottery_stats(&tier_sym).entry_count, 2);
+}
+
+#[test]
+fn test_lottery_entries_capped_per_round() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let tier_sym = harness.add_tier("LOT", 100, 10);
+    assert_eq!(
+        harness.client.get_lottery_entry_cap(&tier_sym),
+        entry_caps::DEFAULT_MAX_LOTTERY_ENTRIES
+    );
+
+    let too_high = entry_caps::MAX_LOTTERY_ENTRY_CAP + 1;
+    assert!(harness
+        .client
+        .try_set_lottery_entry_cap(&tier_sym, &too_high)
+        .is_err());
+    harness.client.set_lottery_entry_cap(&tier_sym, &3);
+
+    harness.store_lottery_entries(&tier_sym, &harness.lottery_entries(3));
+    let entrant = Address::generate(&e);
+    let enter = || {
+        harness
+            .client
+            .try_register_lottery_entry(&entrant, &tier_sym, &None)
+    };
+    assert_eq!(enter(), Err(Ok(RegistrationError::EntryCapReached)));
+
+    // The cap cannot be lowered under the entries already in
+    assert!(harness
+        .client
+        .try_set_lottery_entry_cap(&tier_sym, &2)
+        .is_err());
+    harness.client.set_lottery_entry_cap(&tier_sym, &4);
+    assert_eq!(enter(), Ok(Ok(())));
+    assert_eq!(harness.client.get_lottery_stats(&tier_sym).entry_count, 4);
+}
+
+#[test]
+fn test_lottery_round_filled_to_cap_can_be_allocated() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let tier_sym = harness.add_tier("LOT", 100, 50);
+    harness.client.initialize_lottery(
+        &tier_sym,
+        &AllocationStrategyType::Lottery,
+        &5,
+        &(e.ledger().sequence() + 10),
+        &1,
+        &5,
+    );
+    let cap = harness.client.get_lottery_entry_cap(&tier_sym);
+    harness.store_lottery_entries(&tier_sym, &harness.lottery_entries(cap));
+    let late = Address::generate(&e);
+    assert_eq!(
+        harness
+            .client
+            .try_register_lottery_entry(&late, &tier_sym, &None),
+        Err(Ok(RegistrationError::EntryCapReached))
+    );
+
+    harness.advance_ledgers(10);
+    harness.draw_randomness(&tier_sym, 5);
+    // Runs within the default per-transaction budget
+    harness.measure(|| harness.client.execute_lottery_allocation(&tier_sym));
+    assert_eq!(harness.client.get_lottery_winners(&tier_sym).len(), 5);
+}
+
+// ============================================================================
+// ADMIN LOG
+// ============================================================================
+
+#[test]
+fn test_admin_log_records_privileged_calls() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    assert!(harness.client.get_admin_log(&0, &10).is_empty());
+
+    let tier_sym = harness.add_tier("GEN", 100, 10);
+    harness.advance_ledgers(5);
+    harness.client.set_wallet_cap(&2);
+
+    let log = harness.client.get_admin_log(&0, &10);
+    assert_eq!(log.len(), 2);
+    let first = log.get(0).unwrap();
+    assert_eq!(first.id, 0);
+    assert_eq!(first.function, Symbol::new(&e, "add_tier"));
+    assert_eq!(first.caller, harness.admin);
+    let second = log.get(1).unwrap();
+    assert_eq!(second.function, Symbol::new(&e, "set_wallet_cap"));
+    assert_eq!(second.ledger, first.ledger + 5);
+
+    // Same parameters hash the same; reads are not logged
+    harness.client.set_wallet_cap(&2);
+    harness.client.get_ticket_price(&tier_sym);
+    let log = harness.client.get_admin_log(&1, &10);
+    assert_eq!(log.len(), 2);
+    assert_eq!(log.get(0).unwrap().params_hash, log.get(1).unwrap().params_hash);
+    assert_ne!(first.params_hash, second.params_hash);
+}
+
+#[test]
+fn test_admin_log_keeps_most_recent_actions() {
+    let e = Env::default();
+    e.cost_estimate().budget().reset_unlimited();
+    let harness = testutils::TicketHarness::setup(&e);
+    for cap in 0..admin_log::MAX_ADMIN_LOG + 3 {
+        harness.client.set_wallet_cap(&cap);
+    }
+
+    // The three oldest were overwritten, so reading from 0 starts at id 3
+    let log = harness.client.get_admin_log(&0, &100);
+    assert_eq!(log.len(), admin_log::MAX_ADMIN_LOG_PAGE);
+    assert_eq!(log.get(0).unwrap().id, 3);
+
+    let tail = harness
+        .client
+        .get_admin_log(&(admin_log::MAX_ADMIN_LOG + 1), &10);
+    assert_eq!(tail.len(), 2);
+    assert_eq!(tail.get(1).unwrap().id, admin_log::MAX_ADMIN_LOG + 2);
+}
+
+// ============================================================================
+// CONFIG VIEW
+// ============================================================================
+
+#[test]
+fn test_get_config_summarises_contract_state() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+
+    let config = harness.client.get_config();
+    assert_eq!(config.schema_version, storage_types::SCHEMA_VERSION);
+    assert_eq!(config.event, harness.client.get_event_info());
+    assert_eq!(config.roles.admin, harness.admin);
+    assert_eq!(config.roles.counts, roles::RoleCounts::default());
+    assert!(!config.flags.paused);
+    assert_eq!(config.fees.wallet_cap, 0);
+
+    // Granting a role twice counts once; revoking takes it back off
+    let member = Address::generate(&e);
+    let relayer = Address::generate(&e);
+    harness.client.set_council_member(&member, &true);
+    harness.client.set_council_member(&member, &true);
+    harness.client.set_relayer(&relayer, &true);
+    harness.client.set_relayer(&relayer, &false);
+    harness.client.pause_all(&member);
+    harness.client.set_wallet_cap(&4);
+    harness.client.set_refund_mode(&RefundMode::RestockingFee(500));
+
+    let config = harness.client.get_config();
+    assert_eq!(
+        config.roles.counts,
+        roles::RoleCounts {
+            council: 1,
+            relayers: 0,
+            payment_processors: 0,
+            gate_supervisors: 0,
+            scanners: 0,
+        }
+    );
+    assert!(config.flags.paused);
+    assert_eq!(config.fees.wallet_cap, 4);
+    assert_eq!(config.fees.refund_mode, RefundMode::RestockingFee(500));
+}
+
+// ============================================================================
+// REFERENCE-UNIT ACCOUNTING
+// ============================================================================
+
+#[test]
+fn test_revenue_report_in_reference_unit() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let oracle_id = e.register(MockOracle, ());
+    let dex_id = e.register(MockDex, ());
+    harness.client.set_pricing_config(&PricingConfig {
+        pricing_mode: PricingMode::Fixed,
+        oracle_address: oracle_id,
+        dex_pool_address: dex_id,
+        price_floor: 0,
+        price_ceiling: i128::MAX,
+        update_frequency: 0,
+        last_update_time: e.ledger().timestamp(),
+        is_frozen: false,
+        oracle_pair: String::from_str(&e, "XLM/USD"),
+        oracle_reference_price: oracle::DIA_ORACLE_DECIMALS,
+        max_oracle_age_seconds: oracle::DEFAULT_STALENESS_SECONDS,
+    });
+
+    let priced = harness.create_payment_token(&harness.admin, 0);
+    let unpriced = harness.create_payment_token(&harness.admin, 0);
+    harness.client.set_reference_feed(
+        &priced,
+        &Some(accounting::ReferenceFeed {
+            pair: String::from_str(&e, "XLM/USD"),
+            decimals: 2,
+        }),
+    );
+
+    // MockOracle prices one whole token at $1.10, so 100 units at 2 decimals
+    // are worth $1.10 (110_000_000 at 8 decimals)
+    let gen = harness.add_tier("GEN", 100, 10);
+    let vip = harness.add_tier("VIP", 100, 10);
+    let buyer = harness.funded_buyer(&priced, 1_000);
+    harness.fund(&unpriced, &buyer, 1_000);
+    let token_id = harness.client.purchase(&buyer, &priced, &gen);
+    harness.client.purchase(&buyer, &unpriced, &vip);
+
+    let report = harness.client.get_revenue_report();
+    assert_eq!(report.gross_reference, 110_000_000);
+    assert_eq!(report.by_token.len(), 2);
+    let unpriced_revenue = report.by_token.get(1).unwrap();
+    assert_eq!(unpriced_revenue.token, unpriced);
+    assert_eq!(unpriced_revenue.gross, 100);
+    assert_eq!(unpriced_revenue.gross_reference, 0);
+
+    // Refunds are valued at the rate recorded at purchase
+    harness.client.refund(&buyer, &priced, &token_id);
+    let report = harness.client.get_revenue_report();
+    assert_eq!(report.refunded_reference, 110_000_000);
+    assert_eq!(report.net_reference, 0);
+    let priced_revenue = report.by_token.get(0).unwrap();
+    assert_eq!((priced_revenue.gross, priced_revenue.refunded), (100, 100));
+}
+
+// ============================================================================
+// SMART-WALLET BUYERS
+// ============================================================================
+
+#[test]
+fn test_policy_wallet_buys_and_refunds_with_allowance() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let tier_sym = harness.add_tier("SPONSOR", 100, 10);
+    let key = BytesN::from_array(&e, &[9u8; 32]);
+    let wallet = e.register(MockPolicyWallet, (key, harness.client.address.clone()));
+    let token = harness.create_payment_token(&wallet, 1_000);
+    let token_client = soroban_sdk::token::Client::new(&e, &token);
+    token_client.approve(&wallet, &harness.client.address, &500, &1_000);
+
+    // The wallet signs the purchase alone; payment comes from the allowance
+    let token_id = harness
+        .client
+        .purchase_with_allowance(&wallet, &token, &tier_sym, &150);
+    let auths = e.auths();
+    let (_, invocation) = auths
+        .iter()
+        .find(|(address, _)| *address == wallet)
+        .unwrap();
+    assert!(invocation.sub_invocations.is_empty());
+    assert_eq!(harness.client.owner_of(&token_id), wallet);
+    assert_eq!(token_client.balance(&wallet), 900);
+
+    // Above the signed cap the purchase fails
+    assert!(harness
+        .client
+        .try_purchase_with_allowance(&wallet, &token, &tier_sym, &50)
+        .is_err());
+
+    harness.client.refund(&wallet, &token, &token_id);
+    assert_eq!(token_client.balance(&wallet), 1_000);
+}
+
+#[test]
+fn test_policy_wallet_check_auth_accepts_only_allowed_calls() {
+    let e = Env::default();
+    let harness = testutils::TicketHarness::setup(&e);
+    let tier_sym = harness.add_tier("SPONSOR", 100, 10);
+    let key = BytesN::from_array(&e, &[9u8; 32]);
+    let wallet = e.register(
+        MockPolicyWallet,
+        (key.clone(), harness.client.address.clone()),
+    );
+    let token = harness.create_payment_token(&wallet, 1_000);
+    let payload = BytesN::from_array(&e, &[1u8; 32]);
+
+    let purchase = Context::Contract(ContractContext {
+        contract: harness.client.address.clone(),
+        fn_name: Symbol::new(&e, "purchase_with_allowance"),
+        args: (wallet.clone(), token.clone(), tier_sym, 150_i128).into_val(&e),
+    });
+    assert_eq!(
+        e.try_invoke_contract_check_auth::<MockWalletError>(
+            &wallet,
+            &payload,
+            key.clone().into_val(&e),
+            &soroban_sdk::vec![&e, purchase.clone()],
+        ),
+        Ok(())
+    );
+    assert_eq!(
+        e.try_invoke_contract_check_auth::<MockWalletError>(
+            &wallet,
+            &payload,
+            BytesN::from_array(&e, &[0u8; 32]).into_val(&e),
+            &soroban_sdk::vec![&e, purchase.clone()],
+        ),
+        Err(Ok(MockWalletError::BadSignature))
+    );
+
+    // A plain `purchase` would also need the wallet to sign the exact token
+    // transfer, which this policy refuses
+    let transfer = Context::Contract(ContractContext {
+        contract: token,
+        fn_name: Symbol::new(&e, "transfer"),
+        args: (wallet.clone(), harness.client.address.clone(), 100_i128).into_val(&e),
+    });
+    assert_eq!(
+        e.try_invoke_contract_check_auth::<MockWalletError>(
+            &wallet,
+            &payload,
+            key.into_val(&e),
+            &soroban_sdk::vec![&e, purchase, transfer],
+        ),
+        Err(Ok(MockWalletError::NotAllowed))
+    );
+}
@@ -0,0 +1,270 @@
+/// Reusable test harness
+/// Builders for a deployed contract, tiers, funded payment tokens, ledger time
+/// warping and lottery fixtures, for this crate's tests and downstream
+/// integration tests (enable the `testutils` feature)
+extern crate std;
+
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _, Snapshot},
+    token::StellarAssetClient,
+    Address, BytesN, Env, String, Symbol, Vec,
+};
+
+use crate::allocation::{AllocationEngine, LotteryEntry};
+use crate::archive::{AllocationProof, ArchiveManager};
+use crate::lottery_stats::LotteryStatsManager;
+use crate::rounds::RoundManager;
+use crate::storage_types::{DataKey, LotteryKey, PricingStrategy, TicketKey, Tier};
+use crate::vrf::RandomnessOutput;
+use crate::{InitConfig, SoulboundTicketContract, SoulboundTicketContractClient};
+
+/// CPU and memory consumed by one measured call
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct BudgetReport {
+    pub cpu_instructions: u64,
+    pub memory_bytes: u64,
+}
+
+/// Portable copy of a ledger holding a ticket contract. `state` covers every
+/// storage entry (tiers, tickets, configs, lottery state, token balances) and
+/// can be written to or read from JSON with `Snapshot::write_file`/`read_file`.
+#[derive(Clone)]
+pub struct ContractSnapshot {
+    pub state: Snapshot,
+    /// Strkey of the ticket contract within `state`
+    pub contract_id: std::string::String,
+}
+
+/// A deployed and initialized ticket contract with all auths mocked
+pub struct TicketHarness {
+    pub env: Env,
+    pub admin: Address,
+    pub client: SoulboundTicketContractClient<'static>,
+}
+
+impl TicketHarness {
+    /// Event starting in 100_000 seconds, refundable until it starts
+    pub fn setup(env: &Env) -> Self {
+        let start_time = env.ledger().timestamp() + 100_000;
+        Self::with_event_times(env, start_time, start_time)
+    }
+
+    pub fn with_event_times(env: &Env, start_time: u64, refund_cutoff_time: u64) -> Self {
+        Self::initialize(env, start_time, refund_cutoff_time, None)
+    }
+
+    /// Like `setup`, publishing `public_key` for signed lottery randomness
+    pub fn with_vrf_key(env: &Env, public_key: &BytesN<32>) -> Self {
+        let start_time = env.ledger().timestamp() + 100_000;
+        Self::initialize(env, start_time, start_time, Some(public_key.clone()))
+    }
+
+    fn initialize(
+        env: &Env,
+        start_time: u64,
+        refund_cutoff_time: u64,
+        vrf_public_key: Option<BytesN<32>>,
+    ) -> Self {
+        env.mock_all_auths();
+
+        let admin = Address::generate(env);
+        let contract_id = env.register(SoulboundTicketContract, ());
+        let client = SoulboundTicketContractClient::new(env, &contract_id);
+        client.initialize(&InitConfig {
+            admin: admin.clone(),
+            name: String::from_str(env, "EventTicket"),
+            symbol: String::from_str(env, "TKT"),
+            uri: String::from_str(env, "https://example.com"),
+            start_time,
+            refund_cutoff_time,
+            metadata: None,
+            pricing: None,
+            roles: None,
+            payout_schedule: None,
+            vrf_public_key,
+        });
+
+        Self {
+            env: env.clone(),
+            admin,
+            client,
+        }
+    }
+
+    /// Capture the full ledger state for `restore`
+    pub fn snapshot(&self) -> ContractSnapshot {
+        let strkey = self.client.address.to_string();
+        let mut buf = std::vec![0u8; strkey.len() as usize];
+        strkey.copy_into_slice(&mut buf);
+
+        ContractSnapshot {
+            state: self.env.to_snapshot(),
+            contract_id: std::string::String::from_utf8(buf).unwrap(),
+        }
+    }
+
+    /// Rebuild a harness in a fresh `Env` from a snapshot, e.g. one taken before
+    /// a migration or exported from a production ledger. The admin is read back
+    /// from the restored storage.
+    pub fn restore(snapshot: &ContractSnapshot) -> Self {
+        let env = Env::from_snapshot(snapshot.state.clone());
+        env.mock_all_auths();
+
+        let contract_id = Address::from_str(&env, &snapshot.contract_id);
+        env.register_at(&contract_id, SoulboundTicketContract, ());
+        let admin: Address = env.as_contract(&contract_id, || {
+            env.storage()
+                .instance()
+                .get(&DataKey::Admin)
+                .unwrap_or_else(|| panic!("Snapshot has no initialized contract"))
+        });
+
+        Self {
+            client: SoulboundTicketContractClient::new(&env, &contract_id),
+            env,
+            admin,
+        }
+    }
+
+    /// Add an active tier priced on the standard demand curve
+    pub fn add_tier(&self, symbol: &str, base_price: i128, max_supply: u32) -> Symbol {
+        self.add_tier_with_strategy(symbol, base_price, max_supply, PricingStrategy::Standard)
+    }
+
+    pub fn add_tier_with_strategy(
+        &self,
+        symbol: &str,
+        base_price: i128,
+        max_supply: u32,
+        strategy: PricingStrategy,
+    ) -> Symbol {
+        let tier_symbol = Symbol::new(&self.env, symbol);
+        self.client.add_tier(
+            &tier_symbol,
+            &String::from_str(&self.env, symbol),
+            &base_price,
+            &max_supply,
+            &strategy,
+        );
+        tier_symbol
+    }
+
+    /// Deploy a Stellar asset contract to pay with and mint `amount` to `holder`
+    pub fn create_payment_token(&self, holder: &Address, amount: i128) -> Address {
+        let token = self
+            .env
+            .register_stellar_asset_contract_v2(self.admin.clone());
+        self.fund(&token.address(), holder, amount);
+        token.address()
+    }
+
+    pub fn fund(&self, token: &Address, holder: &Address, amount: i128) {
+        StellarAssetClient::new(&self.env, token).mint(holder, &amount);
+    }
+
+    /// New address holding `amount` of `token`
+    pub fn funded_buyer(&self, token: &Address, amount: i128) -> Address {
+        let buyer = Address::generate(&self.env);
+        self.fund(token, &buyer, amount);
+        buyer
+    }
+
+    /// Move the ledger clock forward by `seconds`
+    pub fn warp_time(&self, seconds: u64) {
+        self.env.ledger().with_mut(|li| li.timestamp += seconds);
+    }
+
+    pub fn warp_to(&self, timestamp: u64) {
+        self.env.ledger().with_mut(|li| li.timestamp = timestamp);
+    }
+
+    pub fn advance_ledgers(&self, count: u32) {
+        self.env.ledger().with_mut(|li| li.sequence_number += count);
+    }
+
+    /// Request the tier's draw, move to its target ledger and generate
+    /// `batch_size` randomness outputs
+    pub fn draw_randomness(&self, tier_symbol: &Symbol, batch_size: u32) -> Vec<RandomnessOutput> {
+        let target = self.client.request_lottery_finalization(tier_symbol);
+        self.env.ledger().with_mut(|li| li.sequence_number = target);
+        self.client.generate_lottery_randomness(tier_symbol, &batch_size)
+    }
+
+    /// Run `f` against a fresh default budget and report what it consumed
+    pub fn measure<T>(&self, f: impl FnOnce() -> T) -> (T, BudgetReport) {
+        let budget = self.env.cost_estimate().budget();
+        budget.reset_default();
+        let result = f();
+        let report = BudgetReport {
+            cpu_instructions: budget.cpu_instruction_cost(),
+            memory_bytes: budget.memory_bytes_cost(),
+        };
+        budget.reset_unlimited();
+        (result, report)
+    }
+
+    /// `count` lottery entries from fresh addresses, entered at the current time
+    pub fn lottery_entries(&self, count: u32) -> Vec<LotteryEntry> {
+        let mut entries = Vec::new(&self.env);
+        for nonce in 0..count {
+            entries.push_back(LotteryEntry {
+                participant: Address::generate(&self.env),
+                entry_time: self.env.ledger().timestamp(),
+                nonce,
+                commitment_hash: None,
+            });
+        }
+        entries
+    }
+
+    /// Store `entries` as the registered entries of the tier's current round
+    pub fn store_lottery_entries(&self, tier_symbol: &Symbol, entries: &Vec<LotteryEntry>) {
+        let e = &self.env;
+        e.as_contract(&self.client.address, || {
+            let round = RoundManager::current(e, tier_symbol);
+            for (i, entry) in entries.iter().enumerate() {
+                e.storage().persistent().set(
+                    &DataKey::Lottery(LotteryKey::Entry(tier_symbol.clone(), round, i as u32)),
+                    &entry,
+                );
+                LotteryStatsManager::record_participant(e, tier_symbol, &entry.participant);
+            }
+            e.storage().persistent().set(
+                &DataKey::Lottery(LotteryKey::EntryCount(tier_symbol.clone(), round)),
+                &entries.len(),
+            );
+        });
+    }
+
+    /// Store `entries` for a tier, draw `winners` of them with `randomness`,
+    /// and seal the result as if the lottery had been executed
+    pub fn seal_lottery(
+        &self,
+        tier_symbol: &Symbol,
+        entries: &Vec<LotteryEntry>,
+        randomness: &Vec<u128>,
+        winners: u32,
+    ) -> AllocationProof {
+        self.store_lottery_entries(tier_symbol, entries);
+        let e = &self.env;
+        e.as_contract(&self.client.address, || {
+            let round = RoundManager::current(e, tier_symbol);
+            let results = AllocationEngine::allocate_lottery(e, entries, randomness, winners);
+            e.storage().persistent().set(
+                &DataKey::Lottery(LotteryKey::Results(tier_symbol.clone(), round)),
+                &results,
+            );
+            ArchiveManager::seal(e, tier_symbol, round, entries, randomness, &results)
+        })
+    }
+    /// Switch a tier's sales on or off in storage
+    pub fn set_tier_active(&self, tier_symbol: &Symbol, active: bool) {
+        let e = &self.env;
+        e.as_contract(&self.client.address, || {
+            let key = DataKey::Tickets(TicketKey::Tier(tier_symbol.clone()));
+            let mut tier: Tier = e.storage().persistent().get(&key).unwrap();
+            tier.active = active;
+            e.storage().persistent().set(&key, &tier);
+        });
+    }
+}
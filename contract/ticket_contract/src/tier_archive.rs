@@ -0,0 +1,58 @@
+/// Tier archiving
+/// Deleting a tier that has sold tickets would orphan them, but a tier created
+/// by mistake should not block its symbol or count against venue capacity
+/// forever. A tier nothing has been minted from can be archived: it leaves the
+/// catalog and its symbol can be reused. Until `TIER_RESTORE_GRACE` has passed
+/// it can be restored as it was, provided the symbol is still free. Other
+/// per-tier settings stay keyed by the symbol either way.
+
+use soroban_sdk::{contracttype, Env, Symbol};
+
+use crate::storage_types::{CatalogKey, DataKey, Tier};
+
+/// Seconds an archived tier can still be restored
+pub const TIER_RESTORE_GRACE: u64 = 7 * 24 * 60 * 60;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ArchivedTier {
+    pub tier: Tier,
+    pub archived_at: u64,
+}
+
+/// Archived tier records
+pub struct TierArchiveManager;
+
+impl TierArchiveManager {
+    pub fn get(e: &Env, tier_symbol: &Symbol) -> Option<ArchivedTier> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Catalog(CatalogKey::ArchivedTier(
+                tier_symbol.clone(),
+            )))
+    }
+
+    pub fn archive(e: &Env, tier_symbol: &Symbol, tier: Tier) {
+        e.storage().persistent().set(
+            &DataKey::Catalog(CatalogKey::ArchivedTier(tier_symbol.clone())),
+            &ArchivedTier {
+                tier,
+                archived_at: e.ledger().timestamp(),
+            },
+        );
+    }
+
+    /// Take the archived tier back out, if its grace period has not passed
+    pub fn take(e: &Env, tier_symbol: &Symbol) -> Tier {
+        let archived = Self::get(e, tier_symbol).unwrap_or_else(|| panic!("Tier not archived"));
+        if e.ledger().timestamp() > archived.archived_at.saturating_add(TIER_RESTORE_GRACE) {
+            panic!("Restore window closed");
+        }
+        e.storage()
+            .persistent()
+            .remove(&DataKey::Catalog(CatalogKey::ArchivedTier(
+                tier_symbol.clone(),
+            )));
+        archived.tier
+    }
+}
@@ -0,0 +1,173 @@
+/// Timelock for sensitive configuration changes
+/// Queues pricing, refund-policy, reserve and payout-address updates behind an
+/// effective-after ledger so buyers can inspect them before they take effect
+
+use soroban_sdk::{Env, Vec};
+
+use crate::refund_limits::RefundLimitManager;
+use crate::refund_policy::RefundPolicyManager;
+use crate::storage_types::{
+    ConfigChange, DataKey, EventInfo, GovernanceKey, PendingChange, PricingConfig, PricingKey,
+    TreasuryKey,
+};
+use crate::treasury::TreasuryManager;
+
+/// Timelock manager for queued configuration changes
+pub struct TimelockManager;
+
+impl TimelockManager {
+    /// Current delay in ledgers (0 = changes apply immediately)
+    pub fn get_delay(e: &Env) -> u32 {
+        e.storage()
+            .instance()
+            .get(&DataKey::Governance(GovernanceKey::TimelockDelay))
+            .unwrap_or(0)
+    }
+
+    /// All changes that are queued but not yet applied
+    pub fn get_pending(e: &Env) -> Vec<PendingChange> {
+        e.storage()
+            .instance()
+            .get(&DataKey::Governance(GovernanceKey::PendingChanges))
+            .unwrap_or_else(|| Vec::new(e))
+    }
+
+    /// Queue a change behind the configured delay, or apply it right away
+    /// when no delay is set. Returns the pending change id if queued.
+    pub fn submit(e: &Env, change: ConfigChange) -> Option<u32> {
+        let delay = Self::get_delay(e);
+        if delay == 0 {
+            Self::apply(e, &change);
+            return None;
+        }
+
+        let id: u32 = e
+            .storage()
+            .instance()
+            .get(&DataKey::Governance(GovernanceKey::NextChangeId))
+            .unwrap_or(0);
+        let queued_ledger = e.ledger().sequence();
+
+        let mut pending = Self::get_pending(e);
+        pending.push_back(PendingChange {
+            id,
+            change,
+            queued_ledger,
+            effective_ledger: queued_ledger.saturating_add(delay),
+        });
+
+        e.storage().instance().set(
+            &DataKey::Governance(GovernanceKey::PendingChanges),
+            &pending,
+        );
+        e.storage()
+            .instance()
+            .set(&DataKey::Governance(GovernanceKey::NextChangeId), &(id + 1));
+
+        Some(id)
+    }
+
+    /// Apply every queued change whose delay has elapsed.
+    /// Returns the number of changes applied.
+    pub fn apply_ready(e: &Env) -> u32 {
+        let now = e.ledger().sequence();
+        let mut remaining = Vec::new(e);
+        let mut applied = 0u32;
+
+        for pending in Self::get_pending(e).iter() {
+            if pending.effective_ledger <= now {
+                Self::apply(e, &pending.change);
+                applied += 1;
+            } else {
+                remaining.push_back(pending);
+            }
+        }
+
+        e.storage().instance().set(
+            &DataKey::Governance(GovernanceKey::PendingChanges),
+            &remaining,
+        );
+
+        applied
+    }
+
+    /// Drop a queued change before it takes effect.
+    /// Returns false if no pending change has this id.
+    pub fn cancel(e: &Env, id: u32) -> bool {
+        let mut pending = Self::get_pending(e);
+
+        for i in 0..pending.len() {
+            if pending.get_unchecked(i).id == id {
+                pending.remove(i);
+                e.storage().instance().set(
+                    &DataKey::Governance(GovernanceKey::PendingChanges),
+                    &pending,
+                );
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Write a change into live configuration
+    fn apply(e: &Env, change: &ConfigChange) {
+        match change {
+            ConfigChange::Pricing(config) => {
+                // A freeze or price update since queueing stays in effect
+                let mut config = config.clone();
+                if let Some(live) = Self::live_pricing(e) {
+                    config.is_frozen = live.is_frozen;
+                    config.last_update_time = live.last_update_time;
+                }
+                Self::set_pricing(e, &config);
+            }
+            ConfigChange::OracleReference(reference_price) => {
+                let mut config = Self::live_pricing(e).unwrap();
+                config.oracle_reference_price = *reference_price;
+                Self::set_pricing(e, &config);
+            }
+            ConfigChange::RefundCutoff(refund_cutoff_time) => {
+                let mut event_info: EventInfo =
+                    e.storage().instance().get(&DataKey::EventInfo).unwrap();
+                event_info.refund_cutoff_time = *refund_cutoff_time;
+                e.storage().instance().set(&DataKey::EventInfo, &event_info);
+            }
+            ConfigChange::TimelockDelay(delay) => {
+                e.storage()
+                    .instance()
+                    .set(&DataKey::Governance(GovernanceKey::TimelockDelay), delay);
+            }
+            ConfigChange::PayoutAddress(payout_address) => {
+                e.storage()
+                    .instance()
+                    .set(&DataKey::Treasury(TreasuryKey::PayoutAddress), payout_address);
+            }
+            ConfigChange::ReserveCoverage(coverage_bps) => {
+                TreasuryManager::set_coverage_bps(e, *coverage_bps);
+            }
+            ConfigChange::RefundMode(mode) => {
+                RefundPolicyManager::set_mode(e, mode);
+            }
+            ConfigChange::RefundLimits(limits) => {
+                RefundLimitManager::set_limits(e, limits.clone());
+            }
+        }
+    }
+
+    fn live_pricing(e: &Env) -> Option<PricingConfig> {
+        e.storage()
+            .instance()
+            .get(&DataKey::Pricing(PricingKey::Config))
+    }
+
+    fn set_pricing(e: &Env, config: &PricingConfig) {
+        e.storage()
+            .instance()
+            .set(&DataKey::Pricing(PricingKey::Config), config);
+        // Oracle source or reference may have changed
+        e.storage()
+            .instance()
+            .remove(&DataKey::Pricing(PricingKey::OracleCache));
+    }
+}
@@ -0,0 +1,108 @@
+/// Treasury and refund reserve accounting
+/// Ticket revenue is held by the contract; the organizer may withdraw it only
+/// while the remaining balance still covers outstanding refundable liability,
+/// and no faster than the payout schedule, if any, releases it. The organizer
+/// bond is held in the same balance but never counts as withdrawable.
+
+use soroban_sdk::{token, Address, Env};
+
+use crate::bond::BondManager;
+use crate::cancellation::CancellationManager;
+use crate::freeze_grace::FreezeGraceManager;
+use crate::payout_schedule::PayoutScheduleManager;
+use crate::storage_types::{DataKey, EventInfo, TreasuryKey};
+
+/// Default reserve coverage: 100% of refundable liability
+pub const DEFAULT_COVERAGE_BPS: u32 = 10_000;
+
+/// Least coverage the reserve keeps while refunds are open
+pub const MIN_COVERAGE_BPS: u32 = 5_000;
+
+/// Treasury manager for reserve tracking and invariant checks
+pub struct TreasuryManager;
+
+impl TreasuryManager {
+    /// Sum of refundable ticket prices paid in `token`
+    pub fn get_liability(e: &Env, token: &Address) -> i128 {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Treasury(TreasuryKey::Liability(token.clone())))
+            .unwrap_or(0)
+    }
+
+    pub fn add_liability(e: &Env, token: &Address, amount: i128) {
+        let liability = Self::get_liability(e, token) + amount;
+        e.storage().persistent().set(
+            &DataKey::Treasury(TreasuryKey::Liability(token.clone())),
+            &liability,
+        );
+    }
+
+    pub fn release_liability(e: &Env, token: &Address, amount: i128) {
+        let liability = (Self::get_liability(e, token) - amount).max(0);
+        e.storage().persistent().set(
+            &DataKey::Treasury(TreasuryKey::Liability(token.clone())),
+            &liability,
+        );
+    }
+
+    /// Share of liability (in bps) the treasury must keep while refunds are open
+    pub fn get_coverage_bps(e: &Env) -> u32 {
+        e.storage()
+            .instance()
+            .get(&DataKey::Treasury(TreasuryKey::CoverageBps))
+            .unwrap_or(DEFAULT_COVERAGE_BPS)
+    }
+
+    pub fn set_coverage_bps(e: &Env, coverage_bps: u32) {
+        e.storage()
+            .instance()
+            .set(&DataKey::Treasury(TreasuryKey::CoverageBps), &coverage_bps);
+    }
+
+    /// Where withdrawn revenue goes; the admin until a payout address is set
+    pub fn payout_address(e: &Env) -> Address {
+        e.storage()
+            .instance()
+            .get(&DataKey::Treasury(TreasuryKey::PayoutAddress))
+            .unwrap_or_else(|| e.storage().instance().get(&DataKey::Admin).unwrap())
+    }
+
+    /// Contract balance of `token`
+    pub fn balance(e: &Env, token: &Address) -> i128 {
+        token::Client::new(e, token).balance(&e.current_contract_address())
+    }
+
+    /// Minimum balance of `token` the treasury must hold right now
+    pub fn required_reserve(e: &Env, token: &Address) -> i128 {
+        if CancellationManager::is_cancelled(e) {
+            return Self::get_liability(e, token);
+        }
+        let event_info: EventInfo = e.storage().instance().get(&DataKey::EventInfo).unwrap();
+        if e.ledger().timestamp() > event_info.refund_cutoff_time {
+            // Tickets under a freeze's grace stay refundable past the cutoff.
+            // Liability is not tracked by purchase time, so all of it is kept.
+            if FreezeGraceManager::window_open(e) {
+                return Self::get_liability(e, token);
+            }
+            return 0;
+        }
+
+        let coverage_bps = Self::get_coverage_bps(e).max(MIN_COVERAGE_BPS);
+        Self::get_liability(e, token) * coverage_bps as i128 / 10_000
+    }
+
+    /// Most of `token` the organizer may withdraw right now
+    pub fn withdrawable(e: &Env, token: &Address) -> i128 {
+        let free = Self::balance(e, token)
+            - BondManager::locked(e, token)
+            - Self::required_reserve(e, token);
+        free.min(PayoutScheduleManager::remaining(e, token)).max(0)
+    }
+
+    /// Whether withdrawing `amount` of `token` keeps the reserve invariant and
+    /// stays within the payout schedule
+    pub fn can_withdraw(e: &Env, token: &Address, amount: i128) -> bool {
+        amount <= Self::withdrawable(e, token)
+    }
+}
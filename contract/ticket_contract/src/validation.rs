@@ -0,0 +1,71 @@
+/// Tier input validation
+/// Tier symbols and names are shown by wallets and marketplaces, so a tier is
+/// rejected before it is stored if its symbol is empty or overlong, or its name
+/// is empty, overlong, padded with whitespace or hides control, zero-width or
+/// bidi-override characters that make two names look alike.
+
+use soroban_sdk::{xdr::ToXdr, Env, String, Symbol};
+
+use crate::storage_types::{TierError, TierInput};
+
+pub const MAX_TIER_SYMBOL_LEN: u32 = 12;
+pub const MAX_TIER_NAME_LEN: u32 = 64;
+
+/// Checks shared by every API that creates a tier
+pub struct TierValidator;
+
+impl TierValidator {
+    pub fn validate(e: &Env, input: &TierInput) -> Result<(), TierError> {
+        let symbol_len = Self::symbol_len(e, &input.symbol);
+        if symbol_len == 0 || symbol_len > MAX_TIER_SYMBOL_LEN {
+            return Err(TierError::InvalidSymbol);
+        }
+        if !Self::is_valid_name(&input.name) {
+            return Err(TierError::InvalidName);
+        }
+        if input.base_price < 0 {
+            return Err(TierError::InvalidPrice);
+        }
+        if input.max_supply == 0 {
+            return Err(TierError::InvalidSupply);
+        }
+        Ok(())
+    }
+
+    /// Characters in `symbol`, read from its XDR encoding (tag, length, bytes)
+    fn symbol_len(e: &Env, symbol: &Symbol) -> u32 {
+        let xdr = symbol.clone().to_xdr(e);
+        let mut len = [0u8; 4];
+        for (i, byte) in xdr.slice(4..8).iter().enumerate() {
+            len[i] = byte;
+        }
+        u32::from_be_bytes(len)
+    }
+
+    fn is_valid_name(name: &String) -> bool {
+        let len = name.len();
+        if len == 0 || len > MAX_TIER_NAME_LEN {
+            return false;
+        }
+
+        let mut buf = [0u8; MAX_TIER_NAME_LEN as usize];
+        let buf = &mut buf[..len as usize];
+        name.copy_into_slice(buf);
+        let Ok(name) = core::str::from_utf8(buf) else {
+            return false;
+        };
+
+        name.trim() == name
+            && !name.chars().any(|c| {
+                c.is_control()
+                    || matches!(
+                        c,
+                        '\u{00AD}'
+                            | '\u{200B}'..='\u{200F}'
+                            | '\u{202A}'..='\u{202E}'
+                            | '\u{2060}'..='\u{2064}'
+                            | '\u{FEFF}'
+                    )
+            })
+    }
+}
@@ -1,8 +1,12 @@
 /// Verifiable Random Function (VRF) module for fair ticket allocation
 /// Implements cryptographic randomness using Soroban's native primitives
-/// for high-demand event ticket allocation with transparency and verifiability
+/// for high-demand event ticket allocation with transparency and verifiability.
+/// Every output is bound to a domain (contract, event id, tier and draw round)
+/// so randomness generated for one draw can never be replayed for another.
+/// With the `vrf-vectors` feature, `test_vectors()` publishes fixed inputs and
+/// the outputs the derivation must produce, for off-chain verifiers to match.
 
-use soroban_sdk::{contracttype, Address, Bytes, Env, Symbol, Vec};
+use soroban_sdk::{contracttype, xdr::ToXdr, Address, Bytes, BytesN, Env, Symbol, Vec};
 
 /// VRF Configuration parameters
 #[contracttype]
@@ -29,6 +33,19 @@ impl Default for VRFConfig {
     }
 }
 
+/// Domain separation inputs mixed into every output of a draw
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VRFDomain {
+    /// Ticket contract running the draw
+    pub contract: Address,
+    /// Organizer-assigned event id, zero until set
+    pub event_id: BytesN<32>,
+    pub tier_symbol: Symbol,
+    /// Draws generated for the tier before this one
+    pub round: u32,
+}
+
 /// VRF Proof structure for verifying randomness
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -41,6 +58,8 @@ pub struct VRFProof {
     pub ledger_sequence: u32,
     /// Hash of input used to generate randomness
     pub input_hash: Bytes,
+    /// Domain the output was generated in
+    pub domain: VRFDomain,
 }
 
 /// Random output with metadata for verification
@@ -73,72 +92,136 @@ impl VRFEngine {
     /// Uses Stellar's ledger hash as entropy source combined with commit-reveal scheme
     pub fn generate_vrf_randomness(
         e: &Env,
+        domain: &VRFDomain,
         input: Bytes,
         nonce: u32,
     ) -> (Bytes, VRFProof) {
+        let ledger_hash = e.ledger().hash().to_array::<32>().unwrap_or([0u8; 32]);
         let ledger_sequence = e.ledger().sequence();
-        let ledger_hash = e.ledger().hash();
-
-        // Combine input with ledger hash and nonce for entropy
-        let mut combined = Vec::new(e);
-        combined
-            .extend_from_array(&input.to_array::<32>().unwrap_or([0u8; 32]))
-            .unwrap();
-        combined
-            .extend_from_array(&ledger_hash.to_array::<32>().unwrap_or([0u8; 32]))
-            .unwrap();
-
-        // Add nonce bytes for batch differentiation
-        let nonce_bytes: [u8; 4] = nonce.to_le_bytes();
-        combined.extend_from_array(&nonce_bytes).unwrap();
+        Self::generate_vrf_randomness_at(e, domain, input, &ledger_hash, ledger_sequence, nonce)
+    }
 
-        // Generate output hash using SHA256
-        let output = soroban_sdk::crypto::sha256(&combined);
+    /// `generate_vrf_randomness` against a given ledger's hash and sequence
+    /// rather than the current ledger's, so a committed draw's outputs do not
+    /// depend on the ledger it is generated in
+    pub fn generate_vrf_randomness_at(
+        e: &Env,
+        domain: &VRFDomain,
+        input: Bytes,
+        ledger_hash: &[u8; 32],
+        ledger_sequence: u32,
+        nonce: u32,
+    ) -> (Bytes, VRFProof) {
+        let input_array = input.to_array::<32>().unwrap_or([0u8; 32]);
+
+        // Combine input with ledger hash, nonce and domain for entropy
+        let output = Bytes::from_array(
+            e,
+            &Self::derive_output(
+                e,
+                &input_array,
+                ledger_hash,
+                nonce,
+                &Self::domain_hash(e, domain),
+            ),
+        );
 
         // Create proof containing the input hash and ledger info
         let input_hash = soroban_sdk::crypto::sha256(&input);
         let proof = VRFProof {
             output: output.clone(),
-            proof: Self::generate_proof_bytes(e, &input, ledger_sequence, nonce),
+            proof: Bytes::from_array(
+                e,
+                &Self::derive_proof(e, &input_array, ledger_sequence, nonce),
+            ),
             ledger_sequence,
             input_hash,
+            domain: domain.clone(),
         };
 
         (output, proof)
     }
 
+    /// sha256 of the domain's XDR encoding
+    pub fn domain_hash(e: &Env, domain: &VRFDomain) -> [u8; 32] {
+        e.crypto().sha256(&domain.clone().to_xdr(e)).to_array()
+    }
+
+    /// Output of one draw: sha256(input || ledger_hash || nonce || domain_hash),
+    /// with the nonce as 4 little-endian bytes
+    pub fn derive_output(
+        e: &Env,
+        input: &[u8; 32],
+        ledger_hash: &[u8; 32],
+        nonce: u32,
+        domain_hash: &[u8; 32],
+    ) -> [u8; 32] {
+        let mut preimage = Bytes::from_array(e, input);
+        preimage.extend_from_array(ledger_hash);
+        preimage.extend_from_array(&nonce.to_le_bytes());
+        preimage.extend_from_array(domain_hash);
+        e.crypto().sha256(&preimage).to_array()
+    }
+
+    /// Proof bytes of one draw: sha256(input || ledger_sequence || nonce),
+    /// with the sequence and nonce as 4 little-endian bytes each
+    pub fn derive_proof(e: &Env, input: &[u8; 32], ledger_sequence: u32, nonce: u32) -> [u8; 32] {
+        let mut preimage = Bytes::from_array(e, input);
+        preimage.extend_from_array(&ledger_sequence.to_le_bytes());
+        preimage.extend_from_array(&nonce.to_le_bytes());
+        e.crypto().sha256(&preimage).to_array()
+    }
+
+    /// Random value of an output: its first 16 bytes as a little-endian u128
+    pub fn output_value(output: &[u8; 32]) -> u128 {
+        let mut value = [0u8; 16];
+        value.copy_from_slice(&output[..16]);
+        u128::from_le_bytes(value)
+    }
+
     /// Generate batch randomness for multiple selections
     pub fn generate_batch_randomness(
         e: &Env,
+        domain: &VRFDomain,
         batch_size: u32,
         seed: Bytes,
+    ) -> Vec<RandomnessOutput> {
+        let ledger_hash = e.ledger().hash().to_array::<32>().unwrap_or([0u8; 32]);
+        let ledger_sequence = e.ledger().sequence();
+        Self::generate_batch_randomness_at(
+            e,
+            domain,
+            batch_size,
+            seed,
+            &ledger_hash,
+            ledger_sequence,
+        )
+    }
+
+    /// `generate_batch_randomness` against a given ledger's hash and sequence
+    pub fn generate_batch_randomness_at(
+        e: &Env,
+        domain: &VRFDomain,
+        batch_size: u32,
+        seed: Bytes,
+        ledger_hash: &[u8; 32],
+        ledger_sequence: u32,
     ) -> Vec<RandomnessOutput> {
         let mut results = Vec::new(e);
 
         for i in 0..batch_size {
             let nonce = i;
-            let (output, proof) = Self::generate_vrf_randomness(e, seed.clone(), nonce);
+            let (output, proof) = Self::generate_vrf_randomness_at(
+                e,
+                domain,
+                seed.clone(),
+                ledger_hash,
+                ledger_sequence,
+                nonce,
+            );
 
             // Convert first 16 bytes of output to u128 for ticket selection
-            let output_array = output.to_array::<32>().unwrap_or([0u8; 32]);
-            let value = u128::from_le_bytes([
-                output_array[0],
-                output_array[1],
-                output_array[2],
-                output_array[3],
-                output_array[4],
-                output_array[5],
-                output_array[6],
-                output_array[7],
-                output_array[8],
-                output_array[9],
-                output_array[10],
-                output_array[11],
-                output_array[12],
-                output_array[13],
-                output_array[14],
-                output_array[15],
-            ]);
+            let value = Self::output_value(&output.to_array::<32>().unwrap_or([0u8; 32]));
 
             let randomness = RandomnessOutput {
                 value,
@@ -156,6 +239,7 @@ impl VRFEngine {
     pub fn verify_vrf_proof(
         e: &Env,
         proof: &VRFProof,
+        domain: &VRFDomain,
         original_input: Bytes,
         expected_ledger: u32,
     ) -> bool {
@@ -164,6 +248,11 @@ impl VRFEngine {
             return false;
         }
 
+        // Verify the proof was generated for this draw
+        if proof.domain != *domain {
+            return false;
+        }
+
         // Verify input hash
         let computed_input_hash = soroban_sdk::crypto::sha256(&original_input);
         if computed_input_hash != proof.input_hash {
@@ -174,33 +263,92 @@ impl VRFEngine {
         !proof.proof.is_empty() && proof.output.len() == 32
     }
 
-    /// Compute selection index for lottery from randomness
-    pub fn compute_selection_index(randomness_value: u128, pool_size: u32) -> u32 {
-        if pool_size == 0 {
-            return 0;
-        }
-        ((randomness_value % (pool_size as u128)) as u32)
+    /// Message the organizer signs for output `nonce` of a signed draw
+    /// committed to `target_ledger`: sha256(domain_hash || target_ledger || nonce),
+    /// with both numbers as 4 little-endian bytes
+    pub fn signing_message(
+        e: &Env,
+        domain: &VRFDomain,
+        target_ledger: u32,
+        nonce: u32,
+    ) -> BytesN<32> {
+        let mut preimage = Bytes::from_array(e, &Self::domain_hash(e, domain));
+        preimage.extend_from_array(&target_ledger.to_le_bytes());
+        preimage.extend_from_array(&nonce.to_le_bytes());
+        e.crypto().sha256(&preimage).to_bytes()
     }
 
-    /// Generate proof bytes for verifiability
-    fn generate_proof_bytes(
+    /// One output per signature, seeded by sha256 of the signature and mixed
+    /// with the given ledger's hash. Signature `i` must be `public_key`'s
+    /// signature over `signing_message(domain, ledger_sequence, i)`; an invalid
+    /// one traps. The signature is kept as the output's proof.
+    pub fn generate_signed_randomness(
         e: &Env,
-        input: &Bytes,
+        domain: &VRFDomain,
+        public_key: &BytesN<32>,
+        signatures: &Vec<BytesN<64>>,
+        ledger_hash: &[u8; 32],
         ledger_sequence: u32,
-        nonce: u32,
-    ) -> Bytes {
-        let mut proof_vec = Vec::new(e);
+    ) -> Vec<RandomnessOutput> {
+        let mut results = Vec::new(e);
+        for (nonce, signature) in signatures.iter().enumerate() {
+            let nonce = nonce as u32;
+            let message: Bytes = Self::signing_message(e, domain, ledger_sequence, nonce).into();
+            e.crypto().ed25519_verify(public_key, &message, &signature);
+
+            let signature = Bytes::from(signature);
+            let seed = Bytes::from(e.crypto().sha256(&signature).to_bytes());
+            let (output, mut proof) = Self::generate_vrf_randomness_at(
+                e,
+                domain,
+                seed,
+                ledger_hash,
+                ledger_sequence,
+                nonce,
+            );
+            proof.proof = signature;
+
+            results.push_back(RandomnessOutput {
+                value: Self::output_value(&output.to_array::<32>().unwrap_or([0u8; 32])),
+                proof,
+                batch_index: nonce,
+            });
+        }
+        results
+    }
 
-        // Combine input, ledger sequence, and nonce for proof
-        proof_vec.extend_from_array(&input.to_array::<32>().unwrap_or([0u8; 32]))
-            .unwrap();
-        proof_vec
-            .extend_from_array(&ledger_sequence.to_le_bytes())
-            .unwrap();
-        proof_vec.extend_from_array(&nonce.to_le_bytes()).unwrap();
+    /// Check that a signed output's proof is `public_key`'s signature for its
+    /// place in the draw and its target ledger, and seeds the output's input. Traps on a signature
+    /// that does not verify. The output itself also depends on the entropy of
+    /// `proof.ledger_sequence`, the draw's target ledger, which off-chain
+    /// verifiers recompute with `derive_output`.
+    pub fn verify_signed_output(
+        e: &Env,
+        output: &RandomnessOutput,
+        domain: &VRFDomain,
+        public_key: &BytesN<32>,
+    ) -> bool {
+        let proof = &output.proof;
+        if proof.domain != *domain {
+            return false;
+        }
+        let Ok(signature) = BytesN::<64>::try_from(proof.proof.clone()) else {
+            return false;
+        };
+        let message: Bytes =
+            Self::signing_message(e, domain, proof.ledger_sequence, output.batch_index).into();
+        e.crypto().ed25519_verify(public_key, &message, &signature);
 
-        // Hash to create proof
-        soroban_sdk::crypto::sha256(&proof_vec)
+        let seed = Bytes::from(e.crypto().sha256(&proof.proof).to_bytes());
+        soroban_sdk::crypto::sha256(&seed) == proof.input_hash
+    }
+
+    /// Compute selection index for lottery from randomness
+    pub fn compute_selection_index(randomness_value: u128, pool_size: u32) -> u32 {
+        if pool_size == 0 {
+            return 0;
+        }
+        ((randomness_value % (pool_size as u128)) as u32)
     }
 
     /// Compute hash of multiple random values for batch verification
@@ -234,6 +382,96 @@ impl VRFEngine {
     }
 }
 
+/// Fixed derivation inputs and the outputs `VRFEngine` must produce for them
+#[cfg(feature = "vrf-vectors")]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct VRFTestVector {
+    pub input: [u8; 32],
+    pub ledger_hash: [u8; 32],
+    pub ledger_sequence: u32,
+    pub nonce: u32,
+    pub domain_hash: [u8; 32],
+    /// `derive_output` of the inputs above
+    pub output: [u8; 32],
+    /// `derive_proof` of the input, ledger sequence and nonce
+    pub proof: [u8; 32],
+    /// sha256 of the input
+    pub input_hash: [u8; 32],
+    /// `output_value` of the output
+    pub value: u128,
+    pub pool_size: u32,
+    /// `compute_selection_index` of the value in a pool of `pool_size`
+    pub selection_index: u32,
+}
+
+/// Decode 64 hex digits at compile time
+#[cfg(feature = "vrf-vectors")]
+const fn hex32(digits: &str) -> [u8; 32] {
+    const fn nibble(digit: u8) -> u8 {
+        match digit {
+            b'0'..=b'9' => digit - b'0',
+            b'a'..=b'f' => digit - b'a' + 10,
+            _ => panic!("Invalid hex digit"),
+        }
+    }
+    let digits = digits.as_bytes();
+    let mut bytes = [0u8; 32];
+    let mut i = 0;
+    while i < 32 {
+        bytes[i] = (nibble(digits[2 * i]) << 4) | nibble(digits[2 * i + 1]);
+        i += 1;
+    }
+    bytes
+}
+
+/// Reference vectors for the output, proof and selection derivations. Any
+/// change to them breaks verification of past draws, so it must come with new
+/// vectors and a new scheme version for off-chain verifiers.
+#[cfg(feature = "vrf-vectors")]
+pub fn test_vectors() -> [VRFTestVector; 3] {
+    [
+        VRFTestVector {
+            input: [0x00; 32],
+            ledger_hash: [0x00; 32],
+            ledger_sequence: 0,
+            nonce: 0,
+            domain_hash: [0x00; 32],
+            output: hex32("cd00e292c5970d3c5e2f0ffa5171e555bc46bfc4faddfb4a418b6840b86e79a3"),
+            proof: hex32("2c34ce1df23b838c5abf2a7f6437cca3d3067ed509ff25f11df6b11b582b51eb"),
+            input_hash: hex32("66687aadf862bd776c8fc18b8e9f8e20089714856ee233b3902a591d0d5f2925"),
+            value: 114_175_714_029_478_311_050_102_741_012_316_291_277,
+            pool_size: 100,
+            selection_index: 77,
+        },
+        VRFTestVector {
+            input: [0x11; 32],
+            ledger_hash: [0x22; 32],
+            ledger_sequence: 1_000,
+            nonce: 1,
+            domain_hash: [0x33; 32],
+            output: hex32("4f939b18a4d0450688e9736dc5a6fa146cc26e1e0618ab9823897f8872cf6a47"),
+            proof: hex32("ba2538e9b5fc3b0e5f462b45a177403bb1a222a2c09972815af8945d150fd14a"),
+            input_hash: hex32("02d449a31fbb267c8f352e9968a79e3e5fc95c1bbeaa502fd6454ebde5a4bedc"),
+            value: 27_886_016_652_148_241_212_955_640_698_536_432_463,
+            pool_size: 7,
+            selection_index: 5,
+        },
+        VRFTestVector {
+            input: [0xab; 32],
+            ledger_hash: [0xcd; 32],
+            ledger_sequence: u32::MAX,
+            nonce: 42,
+            domain_hash: [0xef; 32],
+            output: hex32("7deaa2c8c504b45a9c586f06eccef8244f4e7dd30cd1dfc3a3728f713aa91c8e"),
+            proof: hex32("623c72d94a031e25bc3724f3e639dcdf213a83f5190a7738beec96cf3d37e0b3"),
+            input_hash: hex32("9a2db2e23f1504cd056606553ac049c5e718e8f9ce9233876df1a7a1821af885"),
+            value: 49_144_094_345_389_833_564_287_338_954_828_737_149,
+            pool_size: 1_000,
+            selection_index: 149,
+        },
+    ]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,49 @@
+/// Will-call pickup
+/// Agents (travel desks, corporate buyers) can buy tickets for attendees whose
+/// wallets they do not know. Such a ticket is held by the contract in the
+/// `HeldForPickup` state, not valid for entry, bound to the hash of a pickup
+/// code. At the venue, staff release it to the attendee's wallet once they
+/// present the code, after which it is an ordinary soulbound ticket.
+
+use soroban_sdk::{contracttype, Address, Bytes, BytesN, Env};
+
+use crate::storage_types::{DataKey, VenueKey};
+
+/// Ticket waiting at will-call
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PickupHold {
+    /// Who bought and paid for the ticket
+    pub agent: Address,
+    /// sha256 of the pickup code given to the attendee
+    pub code_hash: BytesN<32>,
+}
+
+/// Pickup holds per token id
+pub struct WillCallManager;
+
+impl WillCallManager {
+    pub fn get(e: &Env, token_id: u32) -> Option<PickupHold> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Venue(VenueKey::PickupHold(token_id)))
+    }
+
+    pub fn hold(e: &Env, token_id: u32, hold: &PickupHold) {
+        e.storage()
+            .persistent()
+            .set(&DataKey::Venue(VenueKey::PickupHold(token_id)), hold);
+    }
+
+    /// Check `code_preimage` against the hold and clear it
+    pub fn release(e: &Env, token_id: u32, code_preimage: &Bytes) -> PickupHold {
+        let hold = Self::get(e, token_id).unwrap_or_else(|| panic!("Ticket not held for pickup"));
+        if e.crypto().sha256(code_preimage).to_bytes() != hold.code_hash {
+            panic!("Invalid pickup code");
+        }
+        e.storage()
+            .persistent()
+            .remove(&DataKey::Venue(VenueKey::PickupHold(token_id)));
+        hold
+    }
+}
@@ -0,0 +1,67 @@
+/// Winner lookup index
+/// Results are stored as one vector per round, so finding an address in them
+/// means reading and scanning every result. Whenever results are stored, each
+/// winner's first allocation index is also recorded under their address, so
+/// `did_win` answers with a single read plus the claim status.
+
+use soroban_sdk::{contracttype, Address, Env, Symbol, Vec};
+
+use crate::allocation::AllocationResult;
+use crate::backfill::BackfillManager;
+use crate::rounds::RoundManager;
+use crate::storage_types::{DataKey, LotteryKey};
+
+/// Where an address stands in a tier's current lottery round
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WinnerStatus {
+    pub round: u32,
+    /// First allocation the address won in the round
+    pub allocation_index: u32,
+    /// Lottery tickets the address has claimed in the round
+    pub claimed: u32,
+    /// Whether the allocation at `allocation_index` expired unclaimed
+    pub forfeited: bool,
+}
+
+/// Address to allocation index lookups
+pub struct WinnerIndexManager;
+
+impl WinnerIndexManager {
+    /// Index the winners of `results`, keeping an address's earliest allocation
+    pub fn record(e: &Env, tier_symbol: &Symbol, round: u32, results: &Vec<AllocationResult>) {
+        for result in results.iter() {
+            let key = DataKey::Lottery(LotteryKey::WinnerIndex(
+                tier_symbol.clone(),
+                round,
+                result.winner.clone(),
+            ));
+            if !e.storage().persistent().has(&key) {
+                e.storage().persistent().set(&key, &result.allocation_index);
+            }
+        }
+    }
+
+    /// `address`'s first allocation index in the round, if they won
+    pub fn lookup(e: &Env, tier_symbol: &Symbol, round: u32, address: &Address) -> Option<u32> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Lottery(LotteryKey::WinnerIndex(
+                tier_symbol.clone(),
+                round,
+                address.clone(),
+            )))
+    }
+
+    /// Status of `address` in the tier's current round, `None` if they did not win
+    pub fn status(e: &Env, tier_symbol: &Symbol, address: &Address) -> Option<WinnerStatus> {
+        let round = RoundManager::current(e, tier_symbol);
+        let allocation_index = Self::lookup(e, tier_symbol, round, address)?;
+        Some(WinnerStatus {
+            round,
+            allocation_index,
+            claimed: BackfillManager::claimed(e, tier_symbol, round, address),
+            forfeited: BackfillManager::is_forfeited(e, tier_symbol, round, allocation_index),
+        })
+    }
+}